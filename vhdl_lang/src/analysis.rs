@@ -8,9 +8,12 @@
 mod analyze;
 mod assignment;
 mod association;
+mod case_coverage;
 mod concurrent;
 mod declarative;
+mod design_interface;
 mod design_unit;
+mod elaboration;
 mod expression;
 mod literals;
 mod lock;
@@ -24,12 +27,18 @@ mod semantic;
 mod sequential;
 mod standard;
 mod static_expression;
+mod static_value;
 mod subprogram;
 mod target;
 mod types;
 
 #[cfg(test)]
 pub(crate) mod tests;
+pub(crate) use elaboration::default_entity;
 pub(crate) use root::{Library, LockedUnit};
 
-pub use self::root::{DesignRoot, EntHierarchy};
+pub use self::design_interface::{DesignInterface, DesignInterfaceKind, InterfaceElement};
+pub use self::elaboration::{AssociatedPort, GenericValue, Instance, InstanceTree};
+pub use self::root::{
+    DesignRoot, EntHierarchy, InlayHint, InlayHintKind, SemanticToken, SemanticTokenKind,
+};
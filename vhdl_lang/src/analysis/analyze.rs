@@ -4,14 +4,16 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+use super::association::AssociatedFormal;
 use super::root::*;
 pub(crate) use super::scope::Scope;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::*;
-use crate::syntax::TokenAccess;
-use fnv::FnvHashSet;
+use crate::syntax::{TokenAccess, TokenId};
+use crate::TokenSpan;
+use fnv::{FnvHashMap, FnvHashSet};
 use std::cell::RefCell;
 use std::ops::Deref;
 
@@ -159,6 +161,21 @@ pub(super) struct AnalyzeContext<'a, 't> {
     uses: RefCell<FnvHashSet<UnitId>>,
     missing_unit: RefCell<FnvHashSet<(Symbol, Symbol, Option<Symbol>)>>,
     uses_library_all: RefCell<FnvHashSet<Symbol>>,
+    // The type of each expression checked against a target type
+    expression_types: RefCell<FnvHashMap<TokenSpan, EntityId>>,
+    // The formal of each positional actual and the subprogram of each call
+    // that were resolved unambiguously
+    positional_formals: RefCell<FnvHashMap<TokenSpan, EntityId>>,
+    resolved_calls: RefCell<Vec<(SrcPos, EntityId)>>,
+    // The formal associated with each actual
+    associated_formals: RefCell<FnvHashMap<TokenSpan, AssociatedFormal>>,
+    // The declarations that use an incomplete type as part of a circular type dependency
+    circular_type_uses: RefCell<Vec<SrcPos>>,
+    // The uses of 'driving and 'driving_value with their prefix signal
+    // within the statement part of the process that is being analyzed
+    driving_attributes: RefCell<Option<Vec<(SrcPos, SignalAttribute, EntityId)>>>,
+    // The sensitivity list of the process whose statement part is being analyzed
+    sensitivity_list: RefCell<Option<SrcPos>>,
     pub ctx: &'t dyn TokenAccess,
 }
 
@@ -186,10 +203,97 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             uses: RefCell::new(FnvHashSet::default()),
             missing_unit: RefCell::new(FnvHashSet::default()),
             uses_library_all: RefCell::new(FnvHashSet::default()),
+            expression_types: RefCell::new(FnvHashMap::default()),
+            positional_formals: RefCell::new(FnvHashMap::default()),
+            resolved_calls: RefCell::new(Vec::new()),
+            associated_formals: RefCell::new(FnvHashMap::default()),
+            circular_type_uses: RefCell::new(Vec::new()),
+            driving_attributes: RefCell::new(None),
+            sensitivity_list: RefCell::new(None),
             ctx,
         }
     }
 
+    pub fn record_expression_type(&self, span: TokenSpan, typ: TypeEnt<'a>) {
+        self.expression_types.borrow_mut().insert(span, typ.id());
+    }
+
+    pub fn take_expression_types(&self) -> FnvHashMap<TokenSpan, EntityId> {
+        self.expression_types.take()
+    }
+
+    pub fn record_positional_formal(&self, actual: TokenSpan, formal: InterfaceEnt<'a>) {
+        self.positional_formals
+            .borrow_mut()
+            .insert(actual, formal.id());
+    }
+
+    pub fn take_positional_formals(&self) -> FnvHashMap<TokenSpan, EntityId> {
+        self.positional_formals.take()
+    }
+
+    pub fn record_resolved_call(&self, call_pos: &SrcPos, subpgm: OverloadedEnt<'a>) {
+        self.resolved_calls
+            .borrow_mut()
+            .push((call_pos.clone(), subpgm.id()));
+    }
+
+    pub fn take_resolved_calls(&self) -> Vec<(SrcPos, EntityId)> {
+        self.resolved_calls.take()
+    }
+
+    pub fn record_associated_formal(&self, actual: TokenSpan, formal: AssociatedFormal) {
+        self.associated_formals.borrow_mut().insert(actual, formal);
+    }
+
+    pub fn take_associated_formals(&self) -> FnvHashMap<TokenSpan, AssociatedFormal> {
+        self.associated_formals.take()
+    }
+
+    pub fn record_circular_type_use(&self, pos: SrcPos) {
+        self.circular_type_uses.borrow_mut().push(pos);
+    }
+
+    /// Remove the uses of incomplete types before their full declaration that are
+    /// already reported as part of a circular type dependency
+    pub fn remove_circular_type_uses(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let uses = self.circular_type_uses.take();
+        diagnostics.retain(|diagnostic| {
+            diagnostic.code != ErrorCode::IncompleteTypeBeforeFullDeclaration
+                || !uses.iter().any(|pos| {
+                    pos.source() == diagnostic.pos.source()
+                        && pos.start() <= diagnostic.pos.start()
+                        && diagnostic.pos.end() <= pos.end()
+                })
+        });
+    }
+
+    /// Start recording the uses of 'driving and 'driving_value within a process
+    /// that has the given sensitivity list, if any
+    pub fn begin_process(&self, sensitivity_list: Option<SrcPos>) {
+        self.driving_attributes.replace(Some(Vec::new()));
+        self.sensitivity_list.replace(sensitivity_list);
+    }
+
+    /// The sensitivity list of the process whose statement part is being analyzed
+    pub fn process_sensitivity_list(&self) -> Option<SrcPos> {
+        self.sensitivity_list.borrow().clone()
+    }
+
+    pub fn record_driving_attribute(&self, pos: SrcPos, attr: SignalAttribute, signal: EntRef) {
+        if let Some(ref mut uses) = *self.driving_attributes.borrow_mut() {
+            if !uses.iter().any(|(prev, ..)| prev == &pos) {
+                uses.push((pos, attr, signal.id()));
+            }
+        }
+    }
+
+    /// Stop recording and take the uses of 'driving and 'driving_value within the process
+    pub fn end_process(&self) -> Vec<(SrcPos, SignalAttribute, EntityId)> {
+        self.sensitivity_list.take();
+        self.driving_attributes.take().unwrap_or_default()
+    }
+
     pub fn work_library_name(&self) -> &Symbol {
         self.current_unit.library_name()
     }
@@ -202,6 +306,31 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         &self.current_unit
     }
 
+    /// Some checks are not reported within third party libraries, see [DesignRoot::set_third_party]
+    pub fn is_third_party(&self) -> bool {
+        self.root.is_third_party(self.work_library_name())
+    }
+
+    /// When an unresolved identifier names a known library,
+    /// suggest to add a library clause at the start of the design unit
+    pub fn suggest_library_clause(
+        &self,
+        designator: &Designator,
+        diagnostic: Diagnostic,
+    ) -> Diagnostic {
+        let Designator::Identifier(ref name) = designator else {
+            return diagnostic;
+        };
+        if diagnostic.code != ErrorCode::Unresolved || self.root.get_lib(name).is_none() {
+            return diagnostic;
+        }
+        let start = self.ctx.get_pos(TokenId::new(0)).start();
+        diagnostic.fix(
+            SrcPos::new(self.source.clone(), crate::data::Range::new(start, start)),
+            format!("library {name};\n"),
+        )
+    }
+
     fn make_use_of(&self, use_pos: Option<&SrcPos>, unit_id: &UnitId) -> FatalResult {
         // Check local cache before taking lock
         if self.uses.borrow_mut().insert(unit_id.clone()) {
@@ -288,10 +417,10 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         if let Some(std_library) = self.get_library(&self.std_sym) {
             scope.make_potentially_visible(None, std_library);
 
-            let standard_region = self
-                .standard_package_region()
-                .expect("Expected standard package");
-            scope.make_all_potentially_visible(None, standard_region);
+            // Units are not analyzed when the std library lacks the standard package
+            if let Some(standard_region) = self.standard_package_region() {
+                scope.make_all_potentially_visible(None, standard_region);
+            }
         }
 
         Ok(())
@@ -412,6 +541,21 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         );
     }
 
+    /// Returns the analyzed design unit of the architecture denoted by `architecture_name`,
+    /// or `None` when the entity has no such architecture.
+    pub(super) fn get_architecture_unit(
+        &self,
+        pos: &SrcPos,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+        architecture_name: &Symbol,
+    ) -> FatalResult<Option<UnitReadGuard<'a>>> {
+        match self.get_secondary_unit(library_name, entity_name, architecture_name) {
+            Some(unit) => Ok(Some(self.get_analysis(Some(pos), unit)?)),
+            None => Ok(None),
+        }
+    }
+
     pub fn lookup_in_library(
         &self,
         diagnostics: &mut dyn DiagnosticHandler,
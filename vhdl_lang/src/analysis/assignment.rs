@@ -5,12 +5,14 @@
 //! Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::analyze::*;
+use super::expression::ExpressionType;
 use super::scope::*;
-use super::target::AssignmentType;
+use super::target::{AssignmentType, TargetType};
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
 use crate::data::*;
-use crate::named_entity::*;
+use crate::named_entity::StaticValue;
 
 impl<'a, 't> AnalyzeContext<'a, 't> {
     // @TODO maybe make generic function for expression/waveform.
@@ -26,7 +28,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let ttyp = as_fatal(self.resolve_target(scope, target, assignment_type, diagnostics))?;
         match rhs {
             AssignmentRightHand::Simple(expr) => {
-                self.analyze_expression_for_target(scope, ttyp, expr, diagnostics)?;
+                self.analyze_expression_for_target(scope, target, &ttyp, expr, diagnostics)?;
             }
             AssignmentRightHand::Conditional(conditionals) => {
                 let Conditionals {
@@ -35,23 +37,32 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 } = conditionals;
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
-                    self.analyze_expression_for_target(scope, ttyp, item, diagnostics)?;
+                    self.analyze_expression_for_target(scope, target, &ttyp, item, diagnostics)?;
                     self.boolean_expr(scope, condition, diagnostics)?;
                 }
                 if let Some(expr) = else_item {
-                    self.analyze_expression_for_target(scope, ttyp, expr, diagnostics)?;
+                    self.analyze_expression_for_target(scope, target, &ttyp, expr, diagnostics)?;
                 }
             }
             AssignmentRightHand::Selected(selection) => {
                 let Selection {
+                    is_matching,
                     expression,
                     alternatives,
                 } = selection;
-                let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                let ctyp =
+                    self.case_expression_type(scope, *is_matching, expression, diagnostics)?;
                 for Alternative { choices, item } in alternatives.iter_mut() {
-                    self.analyze_expression_for_target(scope, ttyp, item, diagnostics)?;
+                    self.analyze_expression_for_target(scope, target, &ttyp, item, diagnostics)?;
                     self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                 }
+                if !*is_matching {
+                    let choices: Vec<_> = alternatives
+                        .iter()
+                        .map(|alternative| alternative.choices.as_slice())
+                        .collect();
+                    self.check_duplicate_choices(ctyp, &choices, diagnostics);
+                }
             }
         }
         Ok(())
@@ -68,7 +79,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let ttyp = as_fatal(self.resolve_target(scope, target, assignment_type, diagnostics))?;
         match rhs {
             AssignmentRightHand::Simple(wavf) => {
-                self.analyze_waveform(scope, ttyp, wavf, diagnostics)?;
+                self.analyze_waveform(scope, target, &ttyp, wavf, diagnostics)?;
             }
             AssignmentRightHand::Conditional(conditionals) => {
                 let Conditionals {
@@ -77,23 +88,106 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 } = conditionals;
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
-                    self.analyze_waveform(scope, ttyp, item, diagnostics)?;
+                    self.analyze_waveform(scope, target, &ttyp, item, diagnostics)?;
                     self.boolean_expr(scope, condition, diagnostics)?;
                 }
                 if let Some(wavf) = else_item {
-                    self.analyze_waveform(scope, ttyp, wavf, diagnostics)?;
+                    self.analyze_waveform(scope, target, &ttyp, wavf, diagnostics)?;
                 }
             }
             AssignmentRightHand::Selected(selection) => {
                 let Selection {
+                    is_matching,
                     expression,
                     alternatives,
                 } = selection;
-                let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                let ctyp =
+                    self.case_expression_type(scope, *is_matching, expression, diagnostics)?;
                 for Alternative { choices, item } in alternatives.iter_mut() {
-                    self.analyze_waveform(scope, ttyp, item, diagnostics)?;
+                    self.analyze_waveform(scope, target, &ttyp, item, diagnostics)?;
                     self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                 }
+                if !*is_matching {
+                    let choices: Vec<_> = alternatives
+                        .iter()
+                        .map(|alternative| alternative.choices.as_slice())
+                        .collect();
+                    self.check_duplicate_choices(ctyp, &choices, diagnostics);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// LRM 10.5.2.1 The pulse rejection limit is a time expression that is neither negative
+    /// nor greater than the delay of the first element of any of the waveforms.
+    ///
+    /// The limit is only compared when it and the delay are static,
+    /// the waveforms must already have been analyzed.
+    pub fn analyze_delay_mechanism(
+        &self,
+        scope: &Scope<'a>,
+        delay_mechanism: &mut Option<DelayMechanism>,
+        rhs: &AssignmentRightHand<Waveform>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let Some(DelayMechanism::Inertial {
+            reject: Some(ref mut reject),
+        }) = delay_mechanism
+        else {
+            return Ok(());
+        };
+        self.expr_with_ttyp(scope, self.time(), reject, diagnostics)?;
+
+        let Some(StaticValue::Physical(limit)) = self.static_value(self.time(), &reject.item)
+        else {
+            return Ok(());
+        };
+        if limit < 0 {
+            diagnostics.add(
+                reject.pos(self.ctx),
+                "Pulse rejection limit must not be negative",
+                ErrorCode::InvalidRejectTime,
+            );
+            return Ok(());
+        }
+
+        let waveforms: Vec<&Waveform> = match rhs {
+            AssignmentRightHand::Simple(wavf) => vec![wavf],
+            AssignmentRightHand::Conditional(conditionals) => conditionals
+                .conditionals
+                .iter()
+                .map(|conditional| &conditional.item)
+                .chain(conditionals.else_item.iter())
+                .collect(),
+            AssignmentRightHand::Selected(selection) => selection
+                .alternatives
+                .iter()
+                .map(|alternative| &alternative.item)
+                .collect(),
+        };
+        for wavf in waveforms {
+            let Waveform::Elements(ref elems) = wavf else {
+                continue;
+            };
+            let Some(first) = elems.first() else {
+                continue;
+            };
+            // Without an after clause the delay is zero
+            let delay = match first.after {
+                Some(ref after) => match self.static_value(self.time(), &after.item) {
+                    Some(StaticValue::Physical(delay)) => delay,
+                    _ => continue,
+                },
+                None => 0,
+            };
+            if limit > delay {
+                diagnostics.add(
+                    reject.pos(self.ctx),
+                    "Pulse rejection limit must not be greater than the delay of the first waveform element",
+                    ErrorCode::InvalidRejectTime,
+                );
+                break;
             }
         }
         Ok(())
@@ -102,7 +196,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     fn analyze_waveform(
         &self,
         scope: &Scope<'a>,
-        ttyp: Option<TypeEnt<'a>>,
+        target: &mut WithTokenSpan<Target>,
+        ttyp: &Option<TargetType<'a>>,
         wavf: &mut Waveform,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
@@ -110,7 +205,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             Waveform::Elements(ref mut elems) => {
                 for elem in elems.iter_mut() {
                     let WaveformElement { value, after } = elem;
-                    self.analyze_expression_for_target(scope, ttyp, value, diagnostics)?;
+                    self.analyze_expression_for_target(scope, target, ttyp, value, diagnostics)?;
                     if let Some(expr) = after {
                         self.expr_with_ttyp(scope, self.time(), expr, diagnostics)?;
                     }
@@ -124,14 +219,34 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     pub fn analyze_expression_for_target(
         &self,
         scope: &Scope<'a>,
-        ttyp: Option<TypeEnt<'a>>,
+        target: &mut WithTokenSpan<Target>,
+        ttyp: &Option<TargetType<'a>>,
         expr: &mut WithTokenSpan<Expression>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
-        if let Some(ttyp) = ttyp {
-            self.expr_with_ttyp(scope, ttyp, expr, diagnostics)?;
-        } else {
-            self.expr_unknown_ttyp(scope, expr, diagnostics)?;
+        match ttyp {
+            Some(TargetType::Name(ttyp)) => {
+                self.expr_with_subtype(scope, ttyp, expr, diagnostics)?;
+            }
+            // The type of an aggregate target is determined by the right hand side
+            Some(TargetType::Aggregate(elements)) => {
+                if matches!(expr.item, Expression::Aggregate(_)) {
+                    self.expr_without_ttyp(scope, expr, diagnostics)?;
+                } else if let Some(ExpressionType::Unambiguous(typ)) =
+                    as_fatal(self.expr_type(scope, expr, diagnostics))?
+                {
+                    self.check_aggregate_target(
+                        target,
+                        elements,
+                        typ,
+                        &expr.pos(self.ctx),
+                        diagnostics,
+                    );
+                }
+            }
+            None => {
+                self.expr_unknown_ttyp(scope, expr, diagnostics)?;
+            }
         }
         Ok(())
     }
@@ -84,6 +84,15 @@ impl<'a> ResolvedFormal<'a> {
     }
 }
 
+/// The formal that an actual is associated with as a whole
+#[derive(Clone, Copy)]
+pub(crate) struct AssociatedFormal {
+    pub formal: EntityId,
+    /// The static length of an array formal.
+    /// An unconstrained formal takes the length of its actual
+    pub length: Option<i64>,
+}
+
 impl<'a, 't> AnalyzeContext<'a, 't> {
     fn resolve_formal(
         &self,
@@ -422,12 +431,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
 
         for (idx, formal) in formal_region.iter().enumerate() {
-            if !(associated.contains_key(&idx)
-                // Default may be unconnected
-                || formal.has_default()
-                // Output ports are allowed to be unconnected
-                || (formal_region.typ == InterfaceType::Port && formal.is_out_or_inout_signal()))
-            {
+            if !(associated.contains_key(&idx) || may_be_unassociated(formal_region, formal)) {
                 let diagnostic = Diagnostic::new(
                     error_pos,
                     format!("No association of {}", formal.describe()),
@@ -460,6 +464,18 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         self.check_missing_and_duplicates(error_pos, &resolved_pairs, formal_region, diagnostics)
     }
 
+    /// True when every actual has a formal, formals without actuals are not checked
+    pub(super) fn has_formal_for_every_actual(
+        &self,
+        formal_region: &FormalRegion<'a>,
+        scope: &Scope<'a>,
+        elems: &mut [AssociationElement],
+    ) -> EvalResult<bool> {
+        let resolved_pairs =
+            self.combine_formal_with_actuals(formal_region, scope, elems, &mut NullDiagnostics)?;
+        Ok(resolved_pairs.iter().all(|(_, formal)| formal.is_some()))
+    }
+
     pub fn check_association<'e>(
         &self,
         error_pos: &SrcPos, // The position of the instance/call-site
@@ -484,6 +500,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 .map(|(_, resolved_formal)| resolved_formal)
                 .collect_vec();
 
+            for (resolved_formal, assoc) in resolved_formals.iter().zip(elems.iter()) {
+                if let (None, Some(resolved_formal)) = (&assoc.formal, resolved_formal) {
+                    self.record_positional_formal(assoc.actual.span, resolved_formal.iface);
+                }
+            }
+
             for (resolved_formal, actual) in resolved_formals
                 .iter()
                 .zip(elems.iter_mut().map(|assoc| &mut assoc.actual))
@@ -500,24 +522,188 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                     diagnostics,
                                 )?;
                             }
-                            self.expr_pos_with_ttyp(
-                                scope,
-                                resolved_formal.type_mark,
-                                actual.span,
-                                expr,
-                                diagnostics,
-                            )?;
+                            if resolved_formal.iface.mode()
+                                == Some(&InterfaceMode::Simple(Mode::Out))
+                            {
+                                // The actual of a formal of mode out is written and not read
+                                self.expr_pos_with_ttyp(
+                                    scope,
+                                    resolved_formal.type_mark,
+                                    actual.span,
+                                    expr,
+                                    &mut AllowOutPortRead(diagnostics),
+                                )?;
+                            } else {
+                                self.expr_pos_with_ttyp(
+                                    scope,
+                                    resolved_formal.type_mark,
+                                    actual.span,
+                                    expr,
+                                    diagnostics,
+                                )?;
+                            }
+                            if formal_region.typ == InterfaceType::Generic {
+                                self.check_globally_static_generic(
+                                    resolved_formal.iface,
+                                    actual.span,
+                                    expr,
+                                    diagnostics,
+                                );
+                            }
+                            if !(resolved_formal.is_partial || resolved_formal.is_converted) {
+                                self.record_associated_formal(
+                                    actual.span,
+                                    AssociatedFormal {
+                                        formal: resolved_formal.iface.id(),
+                                        length: self.associated_length(resolved_formal.iface, expr),
+                                    },
+                                );
+                            }
                         } else {
                             self.expr_pos_unknown_ttyp(scope, actual.span, expr, diagnostics)?;
                         }
                     }
-                    ActualPart::Open => {}
+                    ActualPart::Open => {
+                        if let Some(resolved_formal) = resolved_formal {
+                            if !resolved_formal.is_partial
+                                && !may_be_unassociated(formal_region, resolved_formal.iface)
+                            {
+                                let formal =
+                                    match ObjectEnt::from_any(resolved_formal.iface.inner()) {
+                                        Some(object) => object.describe_class(),
+                                        None => resolved_formal.iface.describe(),
+                                    };
+                                diagnostics.add(
+                                    actual.pos(self.ctx),
+                                    format!(
+                                        "{} without a default value cannot be left open",
+                                        capitalize(&formal)
+                                    ),
+                                    ErrorCode::Unassociated,
+                                );
+                            }
+                        }
+                    }
                 }
             }
         }
         Ok(())
     }
 
+    /// LRM 6.5.6.2: The actual of a generic constant must be a globally static expression
+    fn check_globally_static_generic(
+        &self,
+        formal: InterfaceEnt<'a>,
+        span: TokenSpan,
+        expr: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if formal.interface_class() != InterfaceClass::Constant {
+            return;
+        }
+        if let Some((span, ent)) = self.find_non_static(span, expr) {
+            let mut diagnostic = Diagnostic::new(
+                span.pos(self.ctx),
+                format!(
+                    "{} is not globally static and cannot be the actual of {}",
+                    capitalize(&ent.describe()),
+                    formal.describe()
+                ),
+                ErrorCode::NonStaticGenericActual,
+            );
+            if let Some(decl_pos) = formal.decl_pos() {
+                diagnostic.add_related(decl_pos, "Defined here");
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Find a reference within an expression that prevents it from being globally static,
+    /// such as a signal, a variable or a call to an impure function
+    fn find_non_static(
+        &self,
+        span: TokenSpan,
+        expr: &Expression,
+    ) -> Option<(TokenSpan, EntRef<'a>)> {
+        match expr {
+            Expression::Binary(_, left, right) => self
+                .find_non_static(left.span, &left.item)
+                .or_else(|| self.find_non_static(right.span, &right.item)),
+            Expression::Unary(_, inner) => self.find_non_static(inner.span, &inner.item),
+            Expression::Aggregate(assocs) => assocs.iter().find_map(|assoc| match assoc {
+                ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr) => {
+                    self.find_non_static(expr.span, &expr.item)
+                }
+            }),
+            Expression::Qualified(qexpr) => self.find_non_static(qexpr.expr.span, &qexpr.expr.item),
+            Expression::Name(name) => self.find_non_static_name(span, name),
+            _ => None,
+        }
+    }
+
+    fn find_non_static_name(
+        &self,
+        span: TokenSpan,
+        name: &Name,
+    ) -> Option<(TokenSpan, EntRef<'a>)> {
+        match name {
+            Name::Designator(designator) => {
+                let ent = self.arena.get(designator.reference.get()?);
+                is_non_static(ent).then_some((span, ent))
+            }
+            Name::Selected(prefix, suffix) => suffix
+                .item
+                .reference
+                .get()
+                .map(|id| self.arena.get(id))
+                .filter(|ent| is_non_static(ent))
+                .map(|ent| (suffix.token.into(), ent))
+                .or_else(|| self.find_non_static_name(prefix.span, &prefix.item)),
+            Name::SelectedAll(prefix) | Name::Slice(prefix, _) => {
+                self.find_non_static_name(prefix.span, &prefix.item)
+            }
+            Name::CallOrIndexed(fcall) => self
+                .find_non_static_name(fcall.name.span, &fcall.name.item)
+                .or_else(|| {
+                    fcall
+                        .parameters
+                        .iter()
+                        .find_map(|assoc| match assoc.actual.item {
+                            ActualPart::Expression(ref expr) => {
+                                self.find_non_static(assoc.actual.span, expr)
+                            }
+                            ActualPart::Open => None,
+                        })
+                }),
+            // Attributes of the subtype of an object, such as 'length, are static
+            // while the value of a signal attribute is not
+            Name::Attribute(attr) => match attr.attr.item {
+                AttributeDesignator::Signal(_) => {
+                    self.find_non_static_name(attr.name.span, &attr.name.item)
+                }
+                _ => attr
+                    .expr
+                    .as_ref()
+                    .and_then(|expr| self.find_non_static(expr.span, &expr.item)),
+            },
+            Name::External(_) => None,
+        }
+    }
+
+    /// The length of an array formal when associated with `actual`
+    fn associated_length(&self, formal: InterfaceEnt<'a>, actual: &Expression) -> Option<i64> {
+        let AnyEntKind::Object(object) = formal.kind() else {
+            return None;
+        };
+        if !matches!(object.subtype.base().kind(), Type::Array { .. }) {
+            return None;
+        }
+        object
+            .subtype
+            .static_length()
+            .or_else(|| self.static_length(formal.type_mark(), actual))
+    }
+
     // LRM 4.2.2.1: In a subprogram, the interface mode must match the mode of the actual designator
     // when the interface mode is signal, variable or file. Furthermore, they must be a single name.
     fn check_parameter_interface(
@@ -530,9 +716,18 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     ) -> FatalResult {
         match resolved_formal.iface.interface_class() {
             InterfaceClass::Signal => {
-                let Some(name) =
-                    as_fatal(self.expression_as_name(expr, scope, actual_pos, diagnostics))?
-                else {
+                let is_name = matches!(expr, Expression::Name(_));
+                let name = as_fatal(self.expression_as_name(expr, scope, actual_pos, diagnostics))?;
+                if self.check_writable_actual(
+                    resolved_formal,
+                    is_name,
+                    name.as_ref(),
+                    actual_pos,
+                    diagnostics,
+                ) {
+                    return Ok(());
+                }
+                let Some(name) = name else {
                     diagnostics.add(
                         actual_pos.pos(self.ctx),
                         "Expression must be a name denoting a signal",
@@ -552,9 +747,18 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
             }
             InterfaceClass::Variable => {
-                let Some(name) =
-                    as_fatal(self.expression_as_name(expr, scope, actual_pos, diagnostics))?
-                else {
+                let is_name = matches!(expr, Expression::Name(_));
+                let name = as_fatal(self.expression_as_name(expr, scope, actual_pos, diagnostics))?;
+                if self.check_writable_actual(
+                    resolved_formal,
+                    is_name,
+                    name.as_ref(),
+                    actual_pos,
+                    diagnostics,
+                ) {
+                    return Ok(());
+                }
+                let Some(name) = name else {
                     diagnostics.add(
                         actual_pos.pos(self.ctx),
                         "Expression must be a name denoting a variable or shared variable",
@@ -600,6 +804,41 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// LRM 4.2.2.1: The actual of a formal of mode out or inout must be a name denoting an object
+    /// that may be written to. Returns true when a diagnostic was emitted.
+    fn check_writable_actual(
+        &self,
+        resolved_formal: &ResolvedFormal<'a>,
+        is_name: bool,
+        name: Option<&ResolvedName>,
+        actual_pos: TokenSpan,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> bool {
+        let Some(mode) = resolved_formal.iface.mode() else {
+            return false;
+        };
+        if !resolved_formal.iface.is_out_or_inout() {
+            return false;
+        }
+        let is_writable = match name {
+            Some(ResolvedName::ObjectName(ObjectName { base, .. })) => base.can_be_assigned_to(),
+            Some(_) => false,
+            // The name could not be resolved and has already been reported
+            None => is_name,
+        };
+        if !is_writable {
+            diagnostics.push(
+                Diagnostic::new(
+                    actual_pos.pos(self.ctx),
+                    format!("actual for {mode} parameter must be a writable object"),
+                    ErrorCode::InterfaceModeMismatch,
+                )
+                .opt_related(resolved_formal.iface.decl_pos(), "Defined here"),
+            );
+        }
+        !is_writable
+    }
+
     fn expression_as_name(
         &self,
         expr: &mut Expression,
@@ -617,6 +856,13 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     }
 }
 
+/// A formal with a default value may be unassociated or left open.
+/// Output ports are also allowed to be unconnected.
+fn may_be_unassociated(formal_region: &FormalRegion, formal: InterfaceEnt) -> bool {
+    formal.has_default()
+        || (formal_region.typ == InterfaceType::Port && formal.is_out_or_inout_signal())
+}
+
 fn to_formal_conversion_argument(
     parameters: &mut [AssociationElement],
 ) -> Option<(TokenSpan, &mut Box<Name>)> {
@@ -663,3 +909,26 @@ impl Diagnostic {
         )
     }
 }
+
+/// Drops the diagnostics of reading ports of mode out
+struct AllowOutPortRead<'d>(&'d mut dyn DiagnosticHandler);
+
+impl DiagnosticHandler for AllowOutPortRead<'_> {
+    fn push(&mut self, diagnostic: Diagnostic) {
+        if diagnostic.code != ErrorCode::ReadOutPort {
+            self.0.push(diagnostic);
+        }
+    }
+}
+
+fn is_non_static(ent: EntRef<'_>) -> bool {
+    match ent.kind() {
+        AnyEntKind::Object(object) => object.class != ObjectClass::Constant,
+        AnyEntKind::ObjectAlias { base_object, .. } => base_object.class() != ObjectClass::Constant,
+        AnyEntKind::File(_) | AnyEntKind::InterfaceFile(_) => true,
+        AnyEntKind::Overloaded(overloaded) => {
+            overloaded.signature().return_type().is_some() && !overloaded.signature().is_pure()
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,373 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use super::analyze::*;
+use super::static_value::subtype_range;
+use crate::analysis::static_expression::bit_string_to_string;
+use crate::ast;
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::*;
+use crate::named_entity::*;
+
+/// The maximum number of missing values listed in a diagnostic
+const MAX_LISTED_MISSING: usize = 5;
+
+/// The values covered by a single choice as an inclusive range of positions.
+/// Enumeration literals are represented by their position within the type.
+enum CoveredValues {
+    Range(i64, i64),
+    /// The value of a string or bit string literal choice of a character array type
+    String(Latin1String),
+    Unknown,
+}
+
+/// The type whose values are covered by the choices
+enum Coverage<'a> {
+    /// Enumeration literals in order of declaration
+    Enum(TypeEnt<'a>, Vec<&'a Designator>),
+    /// Missing values are only detected when the case expression names an object
+    /// whose subtype has a statically known range
+    Integer(TypeEnt<'a>),
+    /// One-dimensional arrays of an enumeration type such as `bit_vector`,
+    /// only duplicates of string and bit string literals are detected
+    Array(TypeEnt<'a>),
+}
+
+impl<'a> Coverage<'a> {
+    fn typ(&self) -> TypeEnt<'a> {
+        match self {
+            Coverage::Enum(typ, _) | Coverage::Integer(typ) | Coverage::Array(typ) => *typ,
+        }
+    }
+
+    fn describe_value(&self, value: i64) -> String {
+        match self {
+            Coverage::Enum(_, literals) => match literals[value as usize] {
+                literal @ Designator::Character(_) => literal.to_string(),
+                literal => format!("'{literal}'"),
+            },
+            Coverage::Integer(_) | Coverage::Array(_) => value.to_string(),
+        }
+    }
+}
+
+/// The statically known values covered by the choices of all alternatives
+struct CoveredChoices {
+    /// Inclusive ranges of positions together with the position of their choice
+    ranges: Vec<(i64, i64, SrcPos)>,
+    has_others: bool,
+    /// False when the value of any choice is not statically known
+    is_known: bool,
+}
+
+impl<'a, 't> AnalyzeContext<'a, 't> {
+    /// LRM 10.9: Each value of the subtype of the case expression shall be represented
+    /// once and only once in the set of choices, unless the choice `others` is used.
+    ///
+    /// The check is skipped for choices whose value is not statically known.
+    pub fn check_case_coverage(
+        &self,
+        ctyp: Option<TypeEnt<'a>>,
+        expression: &WithTokenSpan<Expression>,
+        alternatives: &[&[WithTokenSpan<Choice>]],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(coverage) = ctyp.and_then(|typ| self.case_coverage(typ)) else {
+            return;
+        };
+
+        let covered = self.covered_choices(&coverage, alternatives, diagnostics);
+        if covered.has_others || !covered.is_known {
+            return;
+        }
+
+        let missing = match coverage {
+            Coverage::Enum(_, ref literals) => (0..literals.len() as i64)
+                .filter(|value| {
+                    !covered
+                        .ranges
+                        .iter()
+                        .any(|(low, high, _)| low <= value && value <= high)
+                })
+                .map(|value| coverage.describe_value(value))
+                .collect(),
+            Coverage::Integer(_) => match self.case_expression_bounds(&expression.item) {
+                Some((low, high)) => missing_ranges(&covered.ranges, low, high)
+                    .into_iter()
+                    .map(|(low, high)| {
+                        if low == high {
+                            low.to_string()
+                        } else {
+                            format!("{low} to {high}")
+                        }
+                    })
+                    .collect(),
+                None => Vec::new(),
+            },
+            Coverage::Array(_) => Vec::new(),
+        };
+
+        if !missing.is_empty() {
+            let mut listed = missing
+                .iter()
+                .take(MAX_LISTED_MISSING)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if missing.len() > MAX_LISTED_MISSING {
+                listed.push_str(&format!(" and {} more", missing.len() - MAX_LISTED_MISSING));
+            }
+
+            diagnostics.add(
+                expression.pos(self.ctx),
+                format!(
+                    "Missing choices for {listed} of {}. Add the missing choices or a choice 'others'",
+                    coverage.typ().describe()
+                ),
+                ErrorCode::MissingChoice,
+            );
+        }
+    }
+
+    /// The bounds of an integer case expression that is the name of an object
+    /// whose subtype has a statically known range
+    fn case_expression_bounds(&self, expr: &Expression) -> Option<(i64, i64)> {
+        let Expression::Name(name) = expr else {
+            return None;
+        };
+        let AnyEntKind::Object(object) = self.arena.get(name.get_suffix_reference()?).kind() else {
+            return None;
+        };
+        let range = subtype_range(self, &object.subtype)?;
+        match (range.left, range.direction, range.right) {
+            (StaticValue::Integer(left), Direction::Ascending, StaticValue::Integer(right)) => {
+                Some((left, right))
+            }
+            (StaticValue::Integer(left), Direction::Descending, StaticValue::Integer(right)) => {
+                Some((right, left))
+            }
+            _ => None,
+        }
+    }
+
+    /// Reports choices that cover a value which is already covered by an earlier choice,
+    /// for selected assignments and case generate statements that need not cover all values.
+    pub fn check_duplicate_choices(
+        &self,
+        ctyp: Option<TypeEnt<'a>>,
+        alternatives: &[&[WithTokenSpan<Choice>]],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(coverage) = ctyp.and_then(|typ| self.case_coverage(typ)) {
+            self.covered_choices(&coverage, alternatives, diagnostics);
+        }
+    }
+
+    fn covered_choices(
+        &self,
+        coverage: &Coverage<'a>,
+        alternatives: &[&[WithTokenSpan<Choice>]],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> CoveredChoices {
+        let mut covered = CoveredChoices {
+            ranges: Vec::new(),
+            has_others: false,
+            is_known: true,
+        };
+        // The index of the alternative of each covered range
+        let mut covered_by: Vec<usize> = Vec::new();
+        // The string values together with the position and alternative of their choice
+        let mut covered_strings: Vec<(Latin1String, SrcPos, usize)> = Vec::new();
+
+        for (idx, choice) in alternatives
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, choices)| choices.iter().map(move |choice| (idx, choice)))
+        {
+            let values = match &choice.item {
+                Choice::Others => {
+                    covered.has_others = true;
+                    continue;
+                }
+                Choice::Expression(expr) => match coverage {
+                    Coverage::Array(_) => match choice_string(expr) {
+                        Some(value) => CoveredValues::String(value),
+                        None => CoveredValues::Unknown,
+                    },
+                    _ => match self.choice_value(coverage, expr) {
+                        Some(value) => CoveredValues::Range(value, value),
+                        None => CoveredValues::Unknown,
+                    },
+                },
+                Choice::DiscreteRange(drange) => self.choice_range(coverage, drange),
+            };
+
+            let (low, high) = match values {
+                CoveredValues::Range(low, high) => (low, high),
+                CoveredValues::String(value) => {
+                    let pos = choice.pos(self.ctx);
+                    if let Some((_, prev_pos, prev_idx)) = covered_strings
+                        .iter()
+                        .find(|(prev_value, ..)| *prev_value == value)
+                    {
+                        diagnostics.push(duplicate_choice(
+                            format!("\"{value}\""),
+                            *prev_idx == idx,
+                            &pos,
+                            prev_pos,
+                        ));
+                    }
+                    covered_strings.push((value, pos, idx));
+                    continue;
+                }
+                CoveredValues::Unknown => {
+                    covered.is_known = false;
+                    continue;
+                }
+            };
+
+            if low > high {
+                // A null range does not cover any value
+                continue;
+            }
+
+            let pos = choice.pos(self.ctx);
+            if let Some((prev_idx, (prev_low, _, prev_pos))) = covered
+                .ranges
+                .iter()
+                .enumerate()
+                .find(|(_, (prev_low, prev_high, _))| low <= *prev_high && *prev_low <= high)
+            {
+                diagnostics.push(duplicate_choice(
+                    coverage.describe_value(low.max(*prev_low)),
+                    covered_by[prev_idx] == idx,
+                    &pos,
+                    prev_pos,
+                ));
+            }
+            covered.ranges.push((low, high, pos));
+            covered_by.push(idx);
+        }
+
+        covered
+    }
+
+    fn case_coverage(&self, typ: TypeEnt<'a>) -> Option<Coverage<'a>> {
+        match typ.kind() {
+            // Subtypes do not track their constraints so the values to cover are not known
+            Type::Enum(_) => Some(Coverage::Enum(typ, self.enum_literals(typ.base()))),
+            _ if matches!(
+                typ.base_type().kind(),
+                Type::Integer | Type::Universal(UniversalType::Integer)
+            ) =>
+            {
+                Some(Coverage::Integer(typ))
+            }
+            _ => match typ.base_type().kind() {
+                Type::Array { indexes, elem_type }
+                    if indexes.len() == 1
+                        && matches!(elem_type.base_type().kind(), Type::Enum(_)) =>
+                {
+                    Some(Coverage::Array(typ))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// The statically known position of a choice expression
+    fn choice_value(&self, coverage: &Coverage<'a>, expr: &Expression) -> Option<i64> {
+        match (coverage, self.static_value(coverage.typ(), expr)?) {
+            (Coverage::Enum(..), StaticValue::Enum(position)) => i64::try_from(position).ok(),
+            (Coverage::Integer(_), StaticValue::Integer(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn choice_range(&self, coverage: &Coverage<'a>, drange: &DiscreteRange) -> CoveredValues {
+        match drange {
+            DiscreteRange::Range(ast::Range::Range(constraint)) => {
+                let left = self.choice_value(coverage, &constraint.left_expr.item);
+                let right = self.choice_value(coverage, &constraint.right_expr.item);
+                match (left, right, constraint.direction) {
+                    (Some(left), Some(right), Direction::Ascending) => {
+                        CoveredValues::Range(left, right)
+                    }
+                    (Some(left), Some(right), Direction::Descending) => {
+                        CoveredValues::Range(right, left)
+                    }
+                    _ => CoveredValues::Unknown,
+                }
+            }
+            DiscreteRange::Discrete(type_mark, None) => match coverage {
+                Coverage::Enum(typ, literals) if type_mark.item.attr.is_none() => {
+                    // Only the full type is known, subtypes do not track their constraints
+                    match type_mark.item.name.item.get_suffix_reference() {
+                        Some(id) if id == typ.id() => {
+                            CoveredValues::Range(0, literals.len() as i64 - 1)
+                        }
+                        _ => CoveredValues::Unknown,
+                    }
+                }
+                _ => CoveredValues::Unknown,
+            },
+            _ => CoveredValues::Unknown,
+        }
+    }
+}
+
+/// The inclusive ranges of the values from `low` to `high` that are not covered
+fn missing_ranges(covered: &[(i64, i64, SrcPos)], low: i64, high: i64) -> Vec<(i64, i64)> {
+    let mut covered: Vec<_> = covered.iter().map(|(low, high, _)| (*low, *high)).collect();
+    covered.sort_unstable();
+
+    let mut missing = Vec::new();
+    // The lowest value that is not yet known to be covered
+    let mut next = low;
+    for (covered_low, covered_high) in covered {
+        if next > high || covered_low > high {
+            break;
+        }
+        if covered_low > next {
+            missing.push((next, covered_low - 1));
+        }
+        if covered_high >= high {
+            return missing;
+        }
+        next = next.max(covered_high + 1);
+    }
+    if next <= high {
+        missing.push((next, high));
+    }
+    missing
+}
+
+/// The value of a string or bit string literal choice
+fn choice_string(expr: &Expression) -> Option<Latin1String> {
+    match expr {
+        Expression::Literal(Literal::String(value)) => Some(value.clone()),
+        Expression::Literal(Literal::BitString(bit_string)) => {
+            bit_string_to_string(bit_string).ok()
+        }
+        _ => None,
+    }
+}
+
+fn duplicate_choice(
+    value: String,
+    same_alternative: bool,
+    pos: &SrcPos,
+    prev_pos: &SrcPos,
+) -> Diagnostic {
+    let message = if same_alternative {
+        format!("Choice {value} is already covered")
+    } else {
+        format!("Choice {value} is already covered by a previous alternative")
+    };
+    Diagnostic::new(pos, message, ErrorCode::DuplicateChoice)
+        .related(prev_pos, "Previously covered here")
+}
@@ -6,11 +6,13 @@
 
 use super::*;
 use crate::analysis::names::ResolvedName;
+use crate::ast::search::sequential_driven_signals;
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::*;
+use crate::syntax::Kind;
 use crate::{HasTokenSpan, TokenSpan};
 use analyze::*;
 use target::AssignmentType;
@@ -88,17 +90,43 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     self.boolean_expr(scope, guard_condition, diagnostics)?;
                 }
                 let nested = scope.nested();
-                if let Some(ref mut list) = block.header.generic_clause {
-                    self.analyze_interface_list(&nested, parent, list, diagnostics)?;
-                }
-                if let Some(ref mut list) = block.header.generic_map {
-                    self.analyze_assoc_elems(scope, &mut list.list.items[..], diagnostics)?;
-                }
-                if let Some(ref mut list) = block.header.port_clause {
-                    self.analyze_interface_list(&nested, parent, list, diagnostics)?;
+                let generics = match block.header.generic_clause {
+                    Some(ref mut list) => self.analyze_formal_list(
+                        &nested,
+                        parent,
+                        InterfaceType::Generic,
+                        list,
+                        diagnostics,
+                    )?,
+                    None => FormalRegion::new(InterfaceType::Generic),
+                };
+                if let Some(ref mut map) = block.header.generic_map {
+                    self.check_association(
+                        self.ctx.get_pos(map.start),
+                        &generics,
+                        scope,
+                        &mut map.list.items[..],
+                        diagnostics,
+                    )?;
                 }
-                if let Some(ref mut list) = block.header.port_map {
-                    self.analyze_assoc_elems(scope, &mut list.list.items[..], diagnostics)?;
+                let ports = match block.header.port_clause {
+                    Some(ref mut list) => self.analyze_formal_list(
+                        &nested,
+                        parent,
+                        InterfaceType::Port,
+                        list,
+                        diagnostics,
+                    )?,
+                    None => FormalRegion::new(InterfaceType::Port),
+                };
+                if let Some(ref mut map) = block.header.port_map {
+                    self.check_association(
+                        self.ctx.get_pos(map.start),
+                        &ports,
+                        scope,
+                        &mut map.list.items[..],
+                        diagnostics,
+                    )?;
                 }
 
                 self.define_labels_for_concurrent_part(
@@ -109,6 +137,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 )?;
                 self.analyze_declarative_part(&nested, parent, &mut block.decl, diagnostics)?;
                 self.analyze_concurrent_part(&nested, parent, &mut block.statements, diagnostics)?;
+                nested.close(diagnostics);
             }
             ConcurrentStatement::Process(ref mut process) => {
                 let ProcessStatement {
@@ -130,7 +159,15 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 let nested = scope.nested();
                 self.define_labels_for_sequential_part(&nested, parent, statements, diagnostics)?;
                 self.analyze_declarative_part(&nested, parent, decl, diagnostics)?;
-                self.analyze_sequential_part(&nested, parent, statements, diagnostics)?;
+                self.begin_process(
+                    sensitivity_list
+                        .as_ref()
+                        .map(|_| self.sensitivity_list_pos(statement.statement.span)),
+                );
+                let result = self.analyze_sequential_part(&nested, parent, statements, diagnostics);
+                self.check_driving_attributes(statements, self.end_process(), diagnostics);
+                result?;
+                nested.close(diagnostics);
             }
             ConcurrentStatement::ForGenerate(ref mut gen) => {
                 let ForGenerateStatement {
@@ -175,6 +212,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 let CaseGenerateStatement {
                     sels:
                         Selection {
+                            is_matching: _,
                             ref mut expression,
                             ref mut alternatives,
                         },
@@ -192,13 +230,22 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     let nested = scope.nested();
                     self.analyze_generate_body(&nested, parent, item, src_span, diagnostics)?;
                 }
+                let choices: Vec<_> = alternatives
+                    .iter()
+                    .map(|alternative| alternative.choices.as_slice())
+                    .collect();
+                self.check_duplicate_choices(ctyp, &choices, diagnostics);
             }
             ConcurrentStatement::Instance(ref mut instance) => {
                 self.analyze_instance(scope, instance, diagnostics)?;
             }
             ConcurrentStatement::Assignment(ref mut assign) => {
-                // @TODO more delaymechanism
-                let ConcurrentSignalAssignment { target, rhs, .. } = assign;
+                let ConcurrentSignalAssignment {
+                    target,
+                    delay_mechanism,
+                    rhs,
+                    ..
+                } = assign;
                 self.analyze_waveform_assignment(
                     scope,
                     target,
@@ -206,6 +253,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     rhs,
                     diagnostics,
                 )?;
+                self.analyze_delay_mechanism(scope, delay_mechanism, rhs, diagnostics)?;
             }
             ConcurrentStatement::ProcedureCall(ref mut pcall) => {
                 let ConcurrentProcedureCall { call, .. } = pcall;
@@ -226,7 +274,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
                 }
                 if let Some(expr) = severity {
-                    self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
+                    self.severity_expr(scope, expr, diagnostics)?;
                 }
             }
         };
@@ -269,6 +317,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             self.analyze_declarative_part(scope, parent, decl, diagnostics)?;
         }
         self.analyze_concurrent_part(scope, inner_parent, statements, diagnostics)?;
+        scope.close(diagnostics);
 
         Ok(())
     }
@@ -446,6 +495,29 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         self.analyze_assoc_elems(scope, aspect.list.items.as_mut_slice(), diagnostics)
     }
 
+    /// The position of the parenthesized sensitivity list of a process statement
+    fn sensitivity_list_pos(&self, span: TokenSpan) -> SrcPos {
+        let tokens = self.ctx.get_token_slice(span.start_token, span.end_token);
+        let mut depth = 0;
+        let mut start = None;
+        for (idx, token) in tokens.iter().enumerate() {
+            match token.kind {
+                Kind::LeftPar => {
+                    start.get_or_insert(idx);
+                    depth += 1;
+                }
+                Kind::RightPar => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return tokens[start.unwrap_or(idx)].pos.combine(&token.pos);
+                    }
+                }
+                _ => {}
+            }
+        }
+        span.pos(self.ctx)
+    }
+
     pub fn sensitivity_list_check(
         &self,
         scope: &Scope<'a>,
@@ -486,4 +558,34 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
         Ok(())
     }
+
+    /// LRM 16.2.4: It is an error if 'driving or 'driving_value is used within a process
+    /// on a signal that the process does not drive
+    fn check_driving_attributes(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        uses: Vec<(SrcPos, SignalAttribute, EntityId)>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if uses.is_empty() {
+            return;
+        }
+        let driven: Vec<EntityId> = sequential_driven_signals(statements)
+            .into_iter()
+            .map(|id| self.arena.get(id).as_actual().id())
+            .collect();
+
+        for (pos, attr, signal) in uses {
+            if !driven.contains(&signal) {
+                diagnostics.add(
+                    pos,
+                    format!(
+                        "'{attr} requires that {} is driven by the process",
+                        self.arena.get(signal).describe()
+                    ),
+                    ErrorCode::MissingDriver,
+                );
+            }
+        }
+    }
 }
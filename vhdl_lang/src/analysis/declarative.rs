@@ -6,12 +6,14 @@
 
 use super::names::*;
 use super::*;
+use crate::ast::search::{Search, SearchResult, SearchState, Searcher};
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::{Signature, *};
-use crate::{ast, named_entity, HasTokenSpan};
+use crate::standard::VHDLStandard;
+use crate::{ast, named_entity, HasTokenSpan, TokenAccess, TokenId};
 use analyze::*;
 use fnv::FnvHashMap;
 use itertools::Itertools;
@@ -41,6 +43,8 @@ impl Declaration {
                     | SubprogramBody(_)
                     | Use(_)
                     | Package(_)
+                    | PackageDeclaration(_)
+                    | PackageBody(_)
                     | Configuration(_)
                     | View(_)
             ),
@@ -61,6 +65,8 @@ impl Declaration {
                     | SubprogramBody(_)
                     | Use(_)
                     | Package(_)
+                    | PackageDeclaration(_)
+                    | PackageBody(_)
                     | View(_)
             ),
             // LRM: package_body_declarative_item
@@ -86,6 +92,8 @@ impl Declaration {
                     | SubprogramBody(_)
                     | Use(_)
                     | Package(_)
+                    | PackageDeclaration(_)
+                    | PackageBody(_)
             ),
             // LRM: package_declarative_item
             AnyEntKind::Design(Design::Package(..)) => matches!(
@@ -100,6 +108,7 @@ impl Declaration {
                     | SubprogramInstantiation(_)
                     | Use(_)
                     | Package(_)
+                    | PackageDeclaration(_)
                     | View(_)
             ),
             _ => {
@@ -113,6 +122,17 @@ impl Declaration {
     }
 }
 
+/// LRM 4.7: A variable declared in a package must be a shared variable
+fn is_non_shared_variable_in_package(decl: &Declaration, parent: EntRef) -> bool {
+    matches!(
+        decl,
+        Declaration::Object(ObjectDeclaration {
+            class: ObjectClass::Variable,
+            ..
+        })
+    ) && matches!(parent.kind(), AnyEntKind::Design(Design::Package(..)))
+}
+
 impl<'a, 't> AnalyzeContext<'a, 't> {
     pub fn analyze_declarative_part(
         &self,
@@ -126,8 +146,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         for i in 0..declarations.len() {
             // Handle incomplete types
 
+            let (preceding, following) = declarations.split_at_mut(i);
             let (WithTokenSpan { item: decl, span }, remaining) =
-                declarations[i..].split_first_mut().unwrap();
+                following.split_first_mut().unwrap();
 
             if !decl.is_allowed_in_context(parent.kind()) {
                 diagnostics.add(
@@ -135,6 +156,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     format!("{} declaration not allowed here", decl.describe()),
                     ErrorCode::DeclarationNotAllowed,
                 )
+            } else if is_non_shared_variable_in_package(decl, parent) {
+                diagnostics.add(
+                    span.pos(self.ctx),
+                    "Variables declared in a package must be shared",
+                    ErrorCode::NonSharedVariableInPackage,
+                )
             }
 
             match decl {
@@ -150,18 +177,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                         self.ctx.get_pos(full_decl.ident.tree.token),
                                         full_decl.span,
                                     ),
-                                    None => {
-                                        let error = Diagnostic::new(
-                                            type_decl.ident.pos(self.ctx),
-                                            format!(
-                                                "Missing full type declaration of incomplete type '{}'",
-                                                type_decl.ident.name()
-                                            ),
-                                            ErrorCode::MissingFullTypeDeclaration,
-                                        ).related(type_decl.ident.pos(self.ctx), "The full type declaration shall occur immediately within the same declarative part");
-                                        diagnostics.push(error);
-                                        (type_decl.ident.pos(self.ctx), type_decl.span)
-                                    }
+                                    // Reported when the declarative region is closed
+                                    None => (type_decl.ident.pos(self.ctx), type_decl.span),
                                 };
 
                                 let designator =
@@ -171,7 +188,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                 let ent = self.arena.explicit(
                                     designator,
                                     parent,
-                                    AnyEntKind::Type(Type::Incomplete),
+                                    AnyEntKind::Type(Type::Incomplete(
+                                        type_decl.ident.pos(self.ctx).clone(),
+                                    )),
                                     Some(decl_pos),
                                     span,
                                     Some(self.source()),
@@ -213,6 +232,16 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         }
                     }
                 },
+                Declaration::Attribute(Attribute::Specification(attr_spec)) => {
+                    self.attribute_specification(
+                        scope,
+                        parent,
+                        attr_spec,
+                        preceding,
+                        remaining,
+                        diagnostics,
+                    )?;
+                }
                 _ => {
                     self.analyze_declaration(scope, parent, &mut declarations[i], diagnostics)?;
                 }
@@ -221,6 +250,30 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// Since VHDL-2002, shared variables must be of a protected type
+    fn check_shared_variable_type(
+        &self,
+        subtype_indication: &SubtypeIndication,
+        subtype: Subtype<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.standard < VHDLStandard::VHDL2008 {
+            return;
+        }
+        // The actual of a generic type may be a protected type
+        if matches!(subtype.base().kind(), Type::Protected(..) | Type::Interface) {
+            return;
+        }
+        diagnostics.add(
+            subtype_indication.type_mark.pos(self.ctx),
+            format!(
+                "Shared variable must be of a protected type, got {}",
+                subtype.type_mark().describe()
+            ),
+            ErrorCode::SharedVariableNotProtected,
+        );
+    }
+
     fn analyze_alias_declaration(
         &self,
         scope: &Scope<'a>,
@@ -228,7 +281,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         alias: &mut AliasDeclaration,
         src_span: TokenSpan,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> EvalResult<EntRef<'a>> {
+    ) -> EvalResult<Vec<EntRef<'a>>> {
         let AliasDeclaration {
             designator,
             name,
@@ -273,6 +326,26 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         }
                     }
                 }
+                ResolvedName::Design(design)
+                    if matches!(
+                        design.0.as_actual().kind(),
+                        AnyEntKind::Design(
+                            Design::Package(..)
+                                | Design::PackageInstance(_)
+                                | Design::InterfacePackageInstance(_)
+                        )
+                    ) =>
+                {
+                    if let Some(ref signature) = signature {
+                        diagnostics.push(Diagnostic::should_not_have_signature(
+                            "Alias",
+                            signature.pos(self.ctx),
+                        ));
+                    }
+                    AnyEntKind::Design(Design::Alias(
+                        DesignEnt::from_any(design.0.as_actual()).unwrap(),
+                    ))
+                }
                 ResolvedName::Library(_)
                 | ResolvedName::Design(_)
                 | ResolvedName::Expression(_) => {
@@ -303,22 +376,65 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         // TODO: Uninstantiated subprogram in aliases
                         let signature_key =
                             self.resolve_signature(scope, signature, diagnostics)?;
-                        if let Some(ent) = overloaded.get(&SubprogramKey::Normal(signature_key)) {
-                            if let Some(reference) = name.item.suffix_reference_mut() {
-                                reference.set_unique_reference(&ent);
+                        match overloaded
+                            .with_signature(&SubprogramKey::Normal(signature_key))
+                            .as_slice()
+                        {
+                            [] => {
+                                diagnostics.push(Diagnostic::no_overloaded_with_signature(
+                                    des.pos(self.ctx),
+                                    &des.item,
+                                    &overloaded,
+                                ));
+                                return Err(EvalError::Unknown);
+                            }
+                            [ent] => {
+                                if let Some(reference) = name.item.suffix_reference_mut() {
+                                    reference.set_unique_reference(ent);
+                                }
+                                AnyEntKind::Overloaded(Overloaded::Alias(*ent))
+                            }
+                            homographs => {
+                                diagnostics.push(
+                                    Diagnostic::ambiguous_homographs(des.pos(self.ctx), homographs)
+                                        .unwrap(),
+                                );
+                                return Err(EvalError::Unknown);
                             }
-                            AnyEntKind::Overloaded(Overloaded::Alias(ent))
-                        } else {
-                            diagnostics.push(Diagnostic::no_overloaded_with_signature(
-                                des.pos(self.ctx),
-                                &des.item,
-                                &overloaded,
-                            ));
-                            return Err(EvalError::Unknown);
                         }
-                    } else {
+                    } else if overloaded
+                        .entities()
+                        .any(|ent| matches!(ent.kind(), Overloaded::EnumLiteral(_)))
+                    {
                         diagnostics.push(Diagnostic::signature_required(name.pos(self.ctx)));
                         return Err(EvalError::Unknown);
+                    } else {
+                        // Without a signature all overloaded subprograms are aliased,
+                        // as commonly accepted by vendor tools
+                        diagnostics.push(Diagnostic::new(
+                            name.pos(self.ctx),
+                            "Signature required for alias of subprogram and enum literals",
+                            ErrorCode::SubprogramAliasWithoutSignature,
+                        ));
+                        if let Some(ent) = overloaded.as_unique() {
+                            if let Some(reference) = name.item.suffix_reference_mut() {
+                                reference.set_unique_reference(ent);
+                            }
+                        }
+                        return Ok(overloaded
+                            .sorted_entities()
+                            .into_iter()
+                            .map(|ent| {
+                                designator.define(
+                                    self.ctx,
+                                    self.arena,
+                                    parent,
+                                    AnyEntKind::Overloaded(Overloaded::Alias(ent)),
+                                    src_span,
+                                    Some(self.source()),
+                                )
+                            })
+                            .collect());
                     }
                 }
                 ResolvedName::Final(ent) => {
@@ -332,14 +448,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
         };
 
-        Ok(designator.define(
+        Ok(vec![designator.define(
             self.ctx,
             self.arena,
             parent,
             kind,
             src_span,
             Some(self.source()),
-        ))
+        )])
     }
 
     pub(crate) fn analyze_declaration(
@@ -352,13 +468,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let src_span = decl.span();
         match &mut decl.item {
             Declaration::Alias(alias) => {
-                if let Some(ent) = as_fatal(self.analyze_alias_declaration(
+                let ents = as_fatal(self.analyze_alias_declaration(
                     scope,
                     parent,
                     alias,
                     decl.span,
                     diagnostics,
-                ))? {
+                ))?;
+                for ent in ents.into_iter().flatten() {
                     scope.add(ent, diagnostics);
 
                     for implicit in ent.as_actual().implicits.iter() {
@@ -393,19 +510,27 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 
                 if let Some(ref mut expr) = object_decl.expression {
                     if let Ok(ref subtype) = subtype {
-                        self.expr_pos_with_ttyp(
-                            scope,
-                            subtype.type_mark(),
-                            expr.span,
-                            &mut expr.item,
-                            diagnostics,
-                        )?;
+                        self.expr_with_subtype(scope, subtype, expr, diagnostics)?;
                     } else {
                         self.expr_unknown_ttyp(scope, expr, diagnostics)?;
                     }
                 }
 
                 if let Some(subtype) = as_fatal(subtype)? {
+                    self.check_object_type(
+                        object_decl.class,
+                        &subtype,
+                        object_decl.subtype_indication.type_mark.pos(self.ctx),
+                        diagnostics,
+                    );
+                    if object_decl.class == ObjectClass::SharedVariable {
+                        self.check_shared_variable_type(
+                            &object_decl.subtype_indication,
+                            subtype,
+                            diagnostics,
+                        );
+                    }
+
                     let kind = if object_decl.class == ObjectClass::Constant
                         && object_decl.expression.is_none()
                     {
@@ -442,6 +567,15 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     );
                     object_decl.ident.decl.set(object_ent.id());
 
+                    if let Some(ref expr) = object_decl.expression {
+                        if object_decl.class == ObjectClass::Constant {
+                            if let Some(value) = self.static_value(subtype.type_mark(), &expr.item)
+                            {
+                                self.arena.set_static_value(object_ent.id(), value);
+                            }
+                        }
+                    }
+
                     scope.add(object_ent, diagnostics);
                 }
             }
@@ -526,7 +660,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     }
                 }
                 Attribute::Specification(ref mut attr_spec) => {
-                    self.attribute_specification(scope, parent, attr_spec, diagnostics)?;
+                    self.attribute_specification(scope, parent, attr_spec, &[], &[], diagnostics)?;
                 }
             },
             Declaration::SubprogramBody(ref mut body) => {
@@ -606,6 +740,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     scope.add(ent, diagnostics);
                 }
             }
+            Declaration::PackageDeclaration(ref mut package) => {
+                self.analyze_nested_package(scope, parent, package, src_span, diagnostics)?;
+            }
+            Declaration::PackageBody(ref mut body) => {
+                self.analyze_nested_package_body(scope, parent, body, src_span, diagnostics)?;
+            }
             Declaration::Configuration(..) => {}
             Declaration::View(view) => {
                 if let Some(view) = as_fatal(self.analyze_view_declaration(
@@ -624,6 +764,102 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// A package declared within a declarative part is visible in the enclosing region
+    /// while its own declarations are analyzed. The region is not closed since the
+    /// package body may follow later in the enclosing declarative part or its body.
+    fn analyze_nested_package(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        package: &mut PackageDeclaration,
+        src_span: TokenSpan,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let ent = self.arena.define(
+            self.ctx,
+            &mut package.ident,
+            parent,
+            AnyEntKind::Design(Design::Package(Visibility::default(), Region::default())),
+            src_span,
+            Some(self.source()),
+        );
+        scope.add(ent, diagnostics);
+
+        let package_scope = scope.nested().in_package_declaration();
+        if let Some(ref mut list) = package.generic_clause {
+            self.analyze_interface_list(&package_scope, ent, list, diagnostics)?;
+        }
+        self.analyze_declarative_part(&package_scope, ent, &mut package.decl, diagnostics)?;
+
+        let region = package_scope.into_region();
+        let kind = if package.generic_clause.is_some() {
+            AnyEntKind::Design(Design::UninstPackage(Visibility::default(), region))
+        } else {
+            AnyEntKind::Design(Design::Package(Visibility::default(), region))
+        };
+        unsafe {
+            ent.set_kind(kind);
+        }
+        Ok(())
+    }
+
+    /// The body of a package declared earlier in the same declarative region
+    fn analyze_nested_package_body(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        body: &mut PackageBody,
+        src_span: TokenSpan,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let ident_pos = body.ident.pos(self.ctx);
+        let Some(primary) = scope
+            .lookup_immediate(&body.ident.tree.item.clone().into())
+            .and_then(|visible| visible.into_non_overloaded().ok())
+        else {
+            diagnostics.add(
+                ident_pos,
+                format!("No declaration of package '{}'", &body.ident),
+                ErrorCode::Unresolved,
+            );
+            return Ok(());
+        };
+
+        let region = match primary.kind() {
+            AnyEntKind::Design(Design::Package(_, region) | Design::UninstPackage(_, region)) => {
+                region
+            }
+            _ => {
+                let mut diagnostic = Diagnostic::new(
+                    ident_pos,
+                    format!("'{}' is not a package", &body.ident),
+                    ErrorCode::MismatchedKinds,
+                );
+                if let Some(pos) = primary.decl_pos() {
+                    diagnostic.add_related(pos, format!("Found {}", primary.describe()));
+                }
+                diagnostics.push(diagnostic);
+                return Ok(());
+            }
+        };
+
+        let ent = self.arena.alloc(
+            body.ident.tree.name().clone().into(),
+            Some(parent),
+            Related::DeclaredBy(primary),
+            AnyEntKind::Design(Design::PackageBody),
+            Some(ident_pos.clone()),
+            src_span,
+            Some(self.source()),
+        );
+        body.ident.decl.set(ent.id());
+
+        let body_scope = Scope::extend(region, Some(scope));
+        self.analyze_declarative_part(&body_scope, ent, &mut body.decl, diagnostics)?;
+        body_scope.close(diagnostics);
+        Ok(())
+    }
+
     /// Analyzes a mode view declaration.
     /// * Checks that the type of the view declaration is a record type
     /// * Checks that all elements are associated in the view
@@ -705,6 +941,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         scope: &Scope<'a>,
         parent: EntRef<'a>,
         attr_spec: &mut AttributeSpecification,
+        preceding: &[WithTokenSpan<Declaration>],
+        following: &[WithTokenSpan<Declaration>],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         let AttributeSpecification {
@@ -774,13 +1012,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 Ok(NamedEntities::Overloaded(overloaded)) => {
                     if let Some(signature) = signature {
                         match as_fatal(self.resolve_signature(scope, signature, diagnostics))? {
-                            Some(signature_key) => {
-                                if let Some(ent) =
-                                    overloaded.get(&SubprogramKey::Normal(signature_key))
-                                {
-                                    designator.set_unique_reference(&ent);
-                                    ent.into()
-                                } else {
+                            Some(signature_key) => match overloaded
+                                .with_signature(&SubprogramKey::Normal(signature_key))
+                                .as_slice()
+                            {
+                                [] => {
                                     diagnostics.push(Diagnostic::no_overloaded_with_signature(
                                         designator.pos(self.ctx),
                                         &designator.item.item,
@@ -788,7 +1024,21 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                     ));
                                     return Ok(());
                                 }
-                            }
+                                [ent] => {
+                                    designator.set_unique_reference(ent);
+                                    (*ent).into()
+                                }
+                                homographs => {
+                                    diagnostics.push(
+                                        Diagnostic::ambiguous_homographs(
+                                            designator.pos(self.ctx),
+                                            homographs,
+                                        )
+                                        .unwrap(),
+                                    );
+                                    return Ok(());
+                                }
+                            },
                             None => {
                                 return Ok(());
                             }
@@ -802,7 +1052,23 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     }
                 }
                 Err(err) => {
-                    diagnostics.push(err);
+                    // LRM 7.2: The specification must follow the declaration of the named entity
+                    if let Some(decl_pos) = find_later_declaration(&designator.item.item, following)
+                    {
+                        diagnostics.push(
+                            Diagnostic::new(
+                                designator.pos(self.ctx),
+                                format!(
+                                    "Attribute specification of '{}' must come after its declaration",
+                                    designator.item.item
+                                ),
+                                ErrorCode::MisplacedAttributeSpec,
+                            )
+                            .related(self.ctx.get_pos(decl_pos), "Declared here"),
+                        );
+                    } else {
+                        diagnostics.push(err);
+                    }
                     return Ok(());
                 }
             };
@@ -863,6 +1129,30 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 
             if let Err(diagnostic) = res {
                 diagnostics.push(diagnostic);
+                return Ok(());
+            }
+
+            // LRM 7.2: The specification must precede any use of the attribute on the named entity
+            let mut searcher = FindAttributeUse::new(self.arena, ent, attr_ent.into());
+            if preceding
+                .iter()
+                .any(|decl| decl.search(self.ctx, &mut searcher) == SearchResult::Found)
+            {
+                if let Some(use_pos) = searcher.result {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            designator.pos(self.ctx),
+                            format!(
+                                "Attribute '{}' of {} is specified after its use",
+                                attr_ent.name(),
+                                ent.describe()
+                            ),
+                            ErrorCode::MisplacedAttributeSpec,
+                        )
+                        .related(use_pos, "Used here")
+                        .opt_related(ent.decl_pos(), "Declared here"),
+                    );
+                }
             }
         }
 
@@ -965,7 +1255,15 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             ModeIndication::Simple(mode) => {
                 let (subtype, class) =
                     self.analyze_simple_mode_indication(scope, mode, diagnostics)?;
-                Ok(self.arena.define(
+                if class == ObjectClass::Signal {
+                    self.check_object_type(
+                        class,
+                        &subtype,
+                        mode.subtype_indication.type_mark.pos(self.ctx),
+                        diagnostics,
+                    );
+                }
+                let ent = self.arena.define(
                     self.ctx,
                     &mut object_decl.ident,
                     parent,
@@ -980,7 +1278,15 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     }),
                     span,
                     Some(self.source()),
-                ))
+                );
+                if let (InterfaceType::Generic, Some(expr)) =
+                    (object_decl.list_type, &mode.expression)
+                {
+                    if let Some(value) = self.static_value(subtype.type_mark(), &expr.item) {
+                        self.arena.set_static_value(ent.id(), value);
+                    }
+                }
+                Ok(ent)
             }
             ModeIndication::View(view) => {
                 let resolved =
@@ -1028,13 +1334,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 
         if let Some(ref mut expression) = mode.expression {
             if let Ok(ref subtype) = subtype {
-                self.expr_pos_with_ttyp(
-                    scope,
-                    subtype.type_mark(),
-                    expression.span,
-                    &mut expression.item,
-                    diagnostics,
-                )?;
+                self.expr_with_subtype(scope, subtype, expression, diagnostics)?;
             } else {
                 self.expr_unknown_ttyp(scope, expression, diagnostics)?
             }
@@ -1067,17 +1367,38 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         declarations: &mut [InterfaceDeclaration],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult<FormalRegion<'a>> {
-        let mut params = FormalRegion::new(InterfaceType::Parameter);
+        self.analyze_formal_list(
+            scope,
+            parent,
+            InterfaceType::Parameter,
+            declarations,
+            diagnostics,
+        )
+    }
+
+    /// Analyze an interface list and return the interface objects that can be associated.
+    /// Generic types, subprograms and packages are added to the scope only.
+    pub fn analyze_formal_list(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        typ: InterfaceType,
+        declarations: &mut [InterfaceDeclaration],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<FormalRegion<'a>> {
+        let mut formals = FormalRegion::new(typ);
 
         for decl in declarations.iter_mut() {
             if let Some(ent) =
                 as_fatal(self.analyze_interface_declaration(scope, parent, decl, diagnostics))?
             {
                 scope.add(ent, diagnostics);
-                params.add(ent);
+                if InterfaceEnt::from_any(ent).is_some() {
+                    formals.add(ent);
+                }
             }
         }
-        Ok(params)
+        Ok(formals)
     }
 
     pub(crate) fn analyze_array_index(
@@ -1096,7 +1417,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 }
 
 impl Diagnostic {
-    fn no_overloaded_with_signature(
+    pub(crate) fn no_overloaded_with_signature(
         pos: &SrcPos,
         des: &Designator,
         overloaded: &OverloadedName,
@@ -1182,6 +1503,8 @@ fn get_entity_class(ent: EntRef) -> Option<EntityClass> {
             Design::PackageInstance(_) => None,
             Design::InterfacePackageInstance(_) => None,
             Design::Context(_) => None,
+            // Alias is never the direct target of attribute
+            Design::Alias(_) => None,
         },
         AnyEntKind::View(_) => None,
     }
@@ -1208,6 +1531,74 @@ fn find_full_type_definition<'a>(
     None
 }
 
+/// The identifier of a declaration of `designator` among the later declarations of a declarative part
+fn find_later_declaration(
+    designator: &Designator,
+    decls: &[WithTokenSpan<Declaration>],
+) -> Option<TokenId> {
+    decls.iter().find_map(|decl| {
+        let (name, token) = match &decl.item {
+            Declaration::Object(ObjectDeclaration { ident, .. })
+            | Declaration::File(FileDeclaration { ident, .. })
+            | Declaration::Type(TypeDeclaration { ident, .. })
+            | Declaration::Component(ComponentDeclaration { ident, .. }) => (
+                Designator::Identifier(ident.tree.item.clone()),
+                ident.tree.token,
+            ),
+            Declaration::Alias(alias) => (
+                alias.designator.tree.item.clone(),
+                alias.designator.tree.token,
+            ),
+            Declaration::SubprogramDeclaration(SubprogramDeclaration { specification, .. })
+            | Declaration::SubprogramBody(SubprogramBody { specification, .. }) => {
+                let designator = specification.subpgm_designator();
+                (designator.item.clone().into_designator(), designator.token)
+            }
+            _ => return None,
+        };
+        (name == *designator).then_some(token)
+    })
+}
+
+/// Search for the first use of a user defined attribute of a named entity
+struct FindAttributeUse<'a> {
+    arena: &'a Arena,
+    prefix: EntityId,
+    attr: EntityId,
+    // The reference preceding the attribute designator is the prefix of the attribute name
+    previous: Option<EntityId>,
+    result: Option<SrcPos>,
+}
+
+impl<'a> FindAttributeUse<'a> {
+    fn new(arena: &'a Arena, prefix: EntRef, attr: EntRef) -> FindAttributeUse<'a> {
+        FindAttributeUse {
+            arena,
+            prefix: prefix.id(),
+            attr: attr.id(),
+            previous: None,
+            result: None,
+        }
+    }
+}
+
+impl Searcher for FindAttributeUse<'_> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        let id = reference.get();
+        if id == Some(self.attr) && self.previous == Some(self.prefix) {
+            self.result = Some(pos.clone());
+            return SearchState::Finished(SearchResult::Found);
+        }
+        self.previous = id.map(|id| self.arena.get(id).as_actual().id());
+        SearchState::NotFinished
+    }
+}
+
 const UNASSOCIATED_DISPLAY_THRESHOLD: usize = 3;
 
 /// Pretty formats a hash set with unassociated record elements.
@@ -0,0 +1,393 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! The generics and ports of entities and components for tooling such as
+//! schematic viewers and instantiation template generators
+
+use super::root::DesignRoot;
+use crate::ast::search::{FoundDeclaration, Search, SearchResult, SearchState, Searcher};
+use crate::ast::*;
+use crate::data::{Position, Source, SrcPos};
+use crate::formatting::FormattingOptions;
+use crate::named_entity::*;
+use crate::syntax::TokenAccess;
+use crate::TokenSpan;
+use fnv::FnvHashMap;
+use std::fmt::Write;
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DesignInterfaceKind {
+    Entity,
+    Component,
+}
+
+/// The generics and ports of an entity or a component
+#[derive(PartialEq, Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DesignInterface {
+    pub kind: DesignInterfaceKind,
+    /// The library the entity or component is declared in
+    pub library: String,
+    pub name: String,
+    /// Generics in the order of declaration
+    pub generics: Vec<InterfaceElement>,
+    /// Ports in the order of declaration
+    pub ports: Vec<InterfaceElement>,
+}
+
+#[derive(PartialEq, Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterfaceElement {
+    pub name: String,
+    /// The mode of a port such as `in` or `view bus_view`, `None` for generics
+    pub mode: Option<String>,
+    /// The subtype indication as written in the declaration
+    pub subtype: String,
+    /// The default expression as written in the declaration
+    pub default: Option<String>,
+}
+
+impl DesignInterface {
+    /// An instantiation statement with the given label that associates every generic
+    /// and port with an actual of the same name, ready to be filled in
+    pub fn instantiation_template(&self, label: &str, options: &FormattingOptions) -> String {
+        let unit = match self.kind {
+            DesignInterfaceKind::Entity => format!(
+                "{} {}.{}",
+                options.keyword_case.apply("entity"),
+                self.library,
+                self.name
+            ),
+            DesignInterfaceKind::Component => {
+                format!("{} {}", options.keyword_case.apply("component"), self.name)
+            }
+        };
+        self.instantiation(label, &unit, "", &FnvHashMap::default(), options)
+    }
+
+    /// Generics and ports are associated by name in the order of declaration.
+    /// The keys of `actuals` are lower case formal names.
+    fn instantiation(
+        &self,
+        label: &str,
+        unit: &str,
+        margin: &str,
+        actuals: &FnvHashMap<String, Actual>,
+        options: &FormattingOptions,
+    ) -> String {
+        let indent = " ".repeat(options.indent_width);
+        let mut result = format!("{label}: {unit}");
+        for (aspect, elements) in [("generic", &self.generics), ("port", &self.ports)] {
+            if elements.is_empty() {
+                continue;
+            }
+            write!(
+                result,
+                "\n{margin}{indent}{} {} (",
+                options.keyword_case.apply(aspect),
+                options.keyword_case.apply("map")
+            )
+            .unwrap();
+            let associations: Vec<String> = elements
+                .iter()
+                .flat_map(
+                    |element| match actuals.get(&element.name.to_ascii_lowercase()) {
+                        Some(Actual::Whole(actual)) => {
+                            vec![format!("{} => {actual}", element.name)]
+                        }
+                        Some(Actual::Partial(associations)) => associations
+                            .iter()
+                            .map(|(formal, actual)| format!("{formal} => {actual}"))
+                            .collect(),
+                        None => vec![format!("{0} => {0}", element.name)],
+                    },
+                )
+                .collect();
+            for (i, association) in associations.iter().enumerate() {
+                let separator = if i + 1 < associations.len() { "," } else { "" };
+                write!(result, "\n{margin}{indent}{indent}{association}{separator}").unwrap();
+            }
+            write!(result, "\n{margin}{indent})").unwrap();
+        }
+        result.push(';');
+        result
+    }
+}
+
+/// The subtype indication and default expression of interface object declarations
+#[derive(Default)]
+struct FindInterfaceDeclarations {
+    declarations: FnvHashMap<EntityId, (String, Option<String>)>,
+}
+
+impl Searcher for FindInterfaceDeclarations {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::InterfaceObject(object) = decl {
+            if let Some(id) = object.ident.decl.get() {
+                let declaration = match object.mode {
+                    ModeIndication::Simple(ref mode) => (
+                        mode.subtype_indication.to_string(),
+                        mode.expression.as_ref().map(|expr| expr.to_string()),
+                    ),
+                    ModeIndication::View(ref view) => (
+                        view.subtype_indication
+                            .as_ref()
+                            .map(|subtype| subtype.to_string())
+                            .unwrap_or_default(),
+                        None,
+                    ),
+                };
+                self.declarations.insert(id, declaration);
+            }
+        }
+        SearchState::NotFinished
+    }
+}
+
+/// The actual of a formal within an existing instantiation
+enum Actual {
+    /// The actual of the formal as a whole
+    Whole(String),
+    /// The associations of elements or slices of the formal as written
+    Partial(Vec<(String, String)>),
+}
+
+/// The instantiation statement at the cursor
+struct FindInstantiation {
+    cursor: Position,
+    result: Option<Instantiation>,
+}
+
+struct Instantiation {
+    pos: SrcPos,
+    label: Option<String>,
+    /// `entity` or `component`
+    keyword: &'static str,
+    /// The instantiated unit as written, such as `work.fifo(rtl)`
+    unit: String,
+    ent: EntityId,
+    /// The actuals of named associations by lower case formal name
+    actuals: FnvHashMap<String, Actual>,
+    /// Positional actuals, their formal is known from the analysis
+    positional: Vec<(TokenSpan, String)>,
+    /// False when a formal is neither a name of a generic or port, nor an element
+    /// or slice of one, such as a formal with a conversion function
+    is_supported: bool,
+}
+
+impl Searcher for FindInstantiation {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        let FoundDeclaration::ConcurrentStatement(statement) = decl else {
+            return SearchState::NotFinished;
+        };
+        let start = match statement.label.tree {
+            Some(ref label) => label.token,
+            None => statement.statement.span.start_token,
+        };
+        let pos = ctx.get_span(start, statement.statement.span.end_token);
+        if !pos.range().contains(self.cursor) {
+            return SearchState::NotFinished;
+        }
+        let mut actuals = FnvHashMap::default();
+        let mut positional = Vec::new();
+        let mut is_supported = true;
+        let (keyword, unit, name) = match statement.statement.item {
+            ConcurrentStatement::Instance(ref instance) => {
+                for map_aspect in [&instance.generic_map, &instance.port_map]
+                    .into_iter()
+                    .flatten()
+                {
+                    for element in map_aspect.list.items.iter() {
+                        let actual = element.actual.item.to_string();
+                        let Some(ref formal) = element.formal else {
+                            positional.push((element.actual.span, actual));
+                            continue;
+                        };
+                        match formal.item {
+                            Name::Designator(ref designator) => {
+                                actuals.insert(
+                                    designator.item.to_string().to_ascii_lowercase(),
+                                    Actual::Whole(actual),
+                                );
+                            }
+                            Name::CallOrIndexed(_) | Name::Slice(..) | Name::Selected(..) => {
+                                let prefix = formal.item.prefix().unwrap().to_string();
+                                match actuals
+                                    .entry(prefix.to_ascii_lowercase())
+                                    .or_insert_with(|| Actual::Partial(Vec::new()))
+                                {
+                                    Actual::Partial(associations) => {
+                                        associations.push((formal.item.to_string(), actual))
+                                    }
+                                    Actual::Whole(_) => is_supported = false,
+                                }
+                            }
+                            _ => is_supported = false,
+                        }
+                    }
+                }
+                match instance.unit {
+                    InstantiatedUnit::Component(ref name) => {
+                        ("component", name.item.to_string(), name)
+                    }
+                    InstantiatedUnit::Entity(ref name, ref architecture) => {
+                        let mut unit = name.item.to_string();
+                        if let Some(architecture) = architecture {
+                            write!(unit, "({})", architecture.item).unwrap();
+                        }
+                        ("entity", unit, name)
+                    }
+                    InstantiatedUnit::Configuration(_) => return SearchState::NotFinished,
+                }
+            }
+            // A component instantiation without map aspects is parsed as a procedure call
+            ConcurrentStatement::ProcedureCall(ref pcall)
+                if pcall.call.item.parameters.is_empty() =>
+            {
+                let name = &pcall.call.item.name;
+                ("component", name.item.to_string(), name)
+            }
+            _ => return SearchState::NotFinished,
+        };
+        let Some(ent) = name.item.get_suffix_reference() else {
+            return SearchState::NotFinished;
+        };
+
+        self.result = Some(Instantiation {
+            pos,
+            label: statement
+                .label
+                .tree
+                .as_ref()
+                .map(|label| label.item.name_utf8()),
+            keyword,
+            unit,
+            ent,
+            actuals,
+            positional,
+            is_supported,
+        });
+        SearchState::Finished(SearchResult::Found)
+    }
+}
+
+impl DesignRoot {
+    /// The generics and ports of an entity or a component.
+    ///
+    /// Returns `None` when `ent` is neither an entity nor a component.
+    pub fn interface_of(&self, ent: EntRef) -> Option<DesignInterface> {
+        let (kind, region) = match ent.kind() {
+            AnyEntKind::Design(Design::Entity(_, region)) => (DesignInterfaceKind::Entity, region),
+            AnyEntKind::Component(region) => (DesignInterfaceKind::Component, region),
+            _ => return None,
+        };
+
+        let mut searcher = FindInterfaceDeclarations::default();
+        if let Some(pos) = ent.decl_pos() {
+            let _ = self.search_source(&pos.source, &mut searcher);
+        }
+        let element = |iface: &InterfaceEnt| {
+            let (subtype, default) = searcher
+                .declarations
+                .get(&iface.id())
+                .cloned()
+                .unwrap_or_else(|| (iface.type_mark().designator().to_string(), None));
+            InterfaceElement {
+                name: iface.designator().to_string(),
+                mode: iface
+                    .mode()
+                    .filter(|_| iface.is_signal())
+                    .map(|mode| mode.to_string()),
+                subtype,
+                default,
+            }
+        };
+
+        let (ports, generics) = region.ports_and_generics();
+        Some(DesignInterface {
+            kind,
+            library: ent
+                .library_name()
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+            name: ent.designator().to_string(),
+            generics: generics.iter().map(element).collect(),
+            ports: ports.iter().map(element).collect(),
+        })
+    }
+
+    /// The instantiation statement at the cursor with a named association for every generic
+    /// and port of the instantiated entity or component. Existing associations keep their
+    /// actual, positional actuals are associated by name with the formal they belong to,
+    /// and the other actuals are placeholders with the name of the formal.
+    ///
+    /// Returns the position of the statement together with the text that replaces it,
+    /// or `None` when the cursor is not within the instantiation of an entity or component,
+    /// or when an existing association cannot be kept, such as one with a conversion function.
+    pub fn instantiation_template_at(
+        &self,
+        source: &Source,
+        cursor: Position,
+        options: &FormattingOptions,
+    ) -> Option<(SrcPos, String)> {
+        let mut searcher = FindInstantiation {
+            cursor,
+            result: None,
+        };
+        let unit = self.units_by_source(source).find(|unit| {
+            unit.unit
+                .expect_analyzed()
+                .search(&unit.tokens, &mut searcher)
+                == SearchResult::Found
+        })?;
+        let mut instantiation = searcher.result?;
+        if !instantiation.is_supported {
+            return None;
+        }
+        let interface = self.interface_of(self.get_ent(instantiation.ent))?;
+
+        let data = unit.unit.expect_analyzed();
+        for (span, actual) in instantiation.positional {
+            let formal = data.result().positional_formals.get(&span)?;
+            instantiation.actuals.insert(
+                self.get_ent(*formal)
+                    .designator()
+                    .to_string()
+                    .to_ascii_lowercase(),
+                Actual::Whole(actual),
+            );
+        }
+        let is_formal = |name: &String| {
+            interface
+                .generics
+                .iter()
+                .chain(interface.ports.iter())
+                .any(|element| element.name.eq_ignore_ascii_case(name))
+        };
+        if !instantiation.actuals.keys().all(is_formal) {
+            return None;
+        }
+
+        let margin: String = source
+            .contents()
+            .get_line(instantiation.pos.start().line as usize)
+            .unwrap_or_default()
+            .chars()
+            .take_while(|chr| chr.is_whitespace())
+            .collect();
+        let label = instantiation
+            .label
+            .unwrap_or_else(|| format!("{}_inst", interface.name));
+        let unit = format!(
+            "{} {}",
+            options.keyword_case.apply(instantiation.keyword),
+            instantiation.unit
+        );
+        let text = interface.instantiation(&label, &unit, &margin, &instantiation.actuals, options);
+        Some((instantiation.pos, text))
+    }
+}
@@ -78,6 +78,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             diagnostics,
         )?;
         self.analyze_declarative_part(&primary_scope, ent, &mut unit.decl, diagnostics)?;
+        self.check_entity_statements(&unit.statements, diagnostics);
         self.analyze_concurrent_part(&primary_scope, ent, &mut unit.statements, diagnostics)?;
 
         let region = primary_scope.into_region();
@@ -89,6 +90,23 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// LRM 3.2.4: The statements of an entity must be passive
+    fn check_entity_statements(
+        &self,
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for statement in statements.iter() {
+            if let ConcurrentStatement::Assignment(_) = statement.statement.item {
+                diagnostics.add(
+                    statement.statement.span.pos(self.ctx),
+                    "Signal assignment is not allowed in the statement part of an entity",
+                    ErrorCode::NonPassiveEntityStatement,
+                );
+            }
+        }
+    }
+
     fn analyze_configuration(
         &self,
         unit: &mut ConfigurationDeclaration,
@@ -118,6 +136,16 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     );
                 }
             }
+
+            for ConfigurationDeclarativeItem::Use(ref mut use_clause) in unit.decl.iter_mut() {
+                self.analyze_use_clause(&root_region, use_clause, diagnostics)?;
+            }
+            self.analyze_architecture_configuration(
+                &root_region,
+                named_entity,
+                &mut unit.block_config,
+                diagnostics,
+            )?;
         };
 
         self.arena.define(
@@ -132,6 +160,249 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// Resolve the architecture of an entity denoted by a block configuration
+    /// and check the configuration items against the statements of the architecture
+    fn analyze_architecture_configuration(
+        &self,
+        scope: &Scope<'a>,
+        entity: DesignEnt<'a>,
+        block_config: &mut BlockConfiguration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let (Designator::Identifier(entity_name), Some(library_name)) =
+            (entity.designator(), entity.library_name())
+        else {
+            return Ok(());
+        };
+        let pos = block_config.block_spec.pos(self.ctx);
+        let Name::Designator(ref mut designator) = block_config.block_spec.item else {
+            return Ok(());
+        };
+        let Designator::Identifier(ref architecture_name) = designator.item else {
+            return Ok(());
+        };
+        let architecture_name = architecture_name.clone();
+
+        let Some(arch) = as_fatal(self.get_architecture(
+            diagnostics,
+            library_name,
+            &pos,
+            entity_name,
+            &architecture_name,
+        ))?
+        else {
+            return Ok(());
+        };
+        designator.set_unique_reference(arch.into());
+
+        let Some(data) =
+            self.get_architecture_unit(&pos, library_name, entity_name, &architecture_name)?
+        else {
+            return Ok(());
+        };
+        let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref body)) = *data else {
+            return Ok(());
+        };
+        let statements: Vec<_> = body.statements.iter().collect();
+        self.analyze_configuration_items(
+            scope,
+            &arch.describe(),
+            &statements,
+            block_config,
+            diagnostics,
+        )
+    }
+
+    /// Check the items of a block configuration against the statements of the configured block
+    fn analyze_configuration_items(
+        &self,
+        scope: &Scope<'a>,
+        block: &str,
+        statements: &[&LabeledConcurrentStatement],
+        block_config: &mut BlockConfiguration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let scope = scope.nested();
+        for use_clause in block_config.use_clauses.iter_mut() {
+            self.analyze_use_clause(&scope, use_clause, diagnostics)?;
+        }
+
+        for item in block_config.items.iter_mut() {
+            match item {
+                ConfigurationItem::Block(ref mut inner) => {
+                    let Some(label) = inner
+                        .block_spec
+                        .item
+                        .prefix()
+                        .and_then(|designator| designator.as_identifier())
+                        .cloned()
+                    else {
+                        continue;
+                    };
+                    let nested = find_labeled(statements, &label)
+                        .and_then(|statement| nested_statements(&statement.statement.item));
+                    let Some((kind, nested)) = nested else {
+                        diagnostics.add(
+                            inner.block_spec.suffix_pos().pos(self.ctx),
+                            format!("No block or generate statement '{label}' within {block}"),
+                            ErrorCode::Unresolved,
+                        );
+                        continue;
+                    };
+                    self.analyze_configuration_items(
+                        &scope,
+                        &format!("{kind} '{label}'"),
+                        &nested,
+                        inner,
+                        diagnostics,
+                    )?;
+                }
+                ConfigurationItem::Component(ref mut component) => {
+                    if let InstantiationList::Labels(ref labels) = component.spec.instantiation_list
+                    {
+                        for label in labels {
+                            self.check_configured_instance(
+                                block,
+                                statements,
+                                label,
+                                &component.spec.component_name.item,
+                                diagnostics,
+                            );
+                        }
+                    }
+
+                    let Some(entity_aspect) = component
+                        .bind_ind
+                        .as_mut()
+                        .and_then(|bind_ind| bind_ind.entity_aspect.as_mut())
+                    else {
+                        continue;
+                    };
+                    let entity = self.analyze_entity_aspect(&scope, entity_aspect, diagnostics)?;
+                    if let (Some(entity), Some(ref mut inner)) =
+                        (entity, component.block_config.as_mut())
+                    {
+                        self.analyze_architecture_configuration(
+                            &scope,
+                            entity,
+                            inner,
+                            diagnostics,
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_configured_instance(
+        &self,
+        block: &str,
+        statements: &[&LabeledConcurrentStatement],
+        label: &Ident,
+        component_name: &Name,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(statement) = find_labeled(statements, &label.item) else {
+            diagnostics.add(
+                label.pos(self.ctx),
+                format!("No instance '{}' within {block}", label.item),
+                ErrorCode::Unresolved,
+            );
+            return;
+        };
+
+        let is_instance_of_component = match statement.statement.item {
+            ConcurrentStatement::Instance(InstantiationStatement {
+                unit: InstantiatedUnit::Component(ref name),
+                ..
+            }) => suffix_designator(&name.item)
+                .is_some_and(|designator| suffix_designator(component_name) == Some(designator)),
+            _ => false,
+        };
+        if !is_instance_of_component {
+            let component = suffix_designator(component_name)
+                .map(|designator| format!(" '{designator}'"))
+                .unwrap_or_default();
+            diagnostics.add(
+                label.pos(self.ctx),
+                format!(
+                    "'{}' is not an instance of component{component}",
+                    label.item
+                ),
+                ErrorCode::MismatchedKinds,
+            );
+        }
+    }
+
+    /// Resolve the entity or configuration of a binding indication.
+    /// Returns the entity when it is bound directly.
+    fn analyze_entity_aspect(
+        &self,
+        scope: &Scope<'a>,
+        entity_aspect: &mut EntityAspect,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<DesignEnt<'a>>> {
+        match entity_aspect {
+            EntityAspect::Entity(ref mut entity_name, ref architecture_name) => {
+                let Some(resolved) = as_fatal(self.name_resolve(
+                    scope,
+                    entity_name.span,
+                    &mut entity_name.item,
+                    diagnostics,
+                ))?
+                else {
+                    return Ok(None);
+                };
+                match resolved {
+                    ResolvedName::Design(ent) if matches!(ent.kind(), Design::Entity(..)) => {
+                        if let (
+                            Some(architecture_name),
+                            Designator::Identifier(entity_ident),
+                            Some(library_name),
+                        ) = (architecture_name, ent.designator(), ent.library_name())
+                        {
+                            as_fatal(self.get_architecture(
+                                diagnostics,
+                                library_name,
+                                architecture_name.pos(self.ctx),
+                                entity_ident,
+                                &architecture_name.item,
+                            ))?;
+                        }
+                        Ok(Some(ent))
+                    }
+                    other => {
+                        diagnostics.push(
+                            other.kind_error(entity_name.suffix_pos().pos(self.ctx), "entity"),
+                        );
+                        Ok(None)
+                    }
+                }
+            }
+            EntityAspect::Configuration(ref mut config_name) => {
+                let Some(resolved) = as_fatal(self.name_resolve(
+                    scope,
+                    config_name.span,
+                    &mut config_name.item,
+                    diagnostics,
+                ))?
+                else {
+                    return Ok(None);
+                };
+                if !matches!(resolved, ResolvedName::Design(ent) if matches!(ent.kind(), Design::Configuration))
+                {
+                    diagnostics.push(
+                        resolved
+                            .kind_error(config_name.suffix_pos().pos(self.ctx), "configuration"),
+                    );
+                }
+                Ok(None)
+            }
+            EntityAspect::Open => Ok(None),
+        }
+    }
+
     fn analyze_package(
         &self,
         unit: &mut PackageDeclaration,
@@ -222,7 +493,19 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         self.add_implicit_context_clause(&root_scope)?;
         let scope = root_scope.nested();
         let src_span = unit.span();
-        self.analyze_context_clause(&scope, &mut unit.items, diagnostics)?;
+        for item in unit.items.iter_mut() {
+            // LRM 13.3: The library logical name work is neither defined
+            // nor referred to within a context declaration
+            if let Some(pos) = self.work_in_context_item(item) {
+                diagnostics.add(
+                    pos,
+                    "The library logical name 'work' is not allowed in a context declaration",
+                    ErrorCode::IllegalContextItem,
+                );
+                continue;
+            }
+            self.analyze_context_clause(&scope, std::slice::from_mut(item), diagnostics)?;
+        }
 
         self.arena.define(
             self.ctx,
@@ -236,6 +519,37 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    fn work_in_context_item(&self, item: &ContextItem) -> Option<SrcPos> {
+        match item {
+            ContextItem::Library(LibraryClause { name_list, .. }) => name_list
+                .items
+                .iter()
+                .find(|name| name.item.item == self.work_sym)
+                .map(|name| name.item.pos(self.ctx).clone()),
+            ContextItem::Use(UseClause { name_list, .. })
+            | ContextItem::Context(ContextReference { name_list, .. }) => name_list
+                .items
+                .iter()
+                .find_map(|name| self.work_prefix(name)),
+        }
+    }
+
+    /// The position of the library logical name work when it is the prefix of a selected name
+    fn work_prefix(&self, name: &WithTokenSpan<Name>) -> Option<SrcPos> {
+        match name.item {
+            Name::Selected(ref prefix, _) | Name::SelectedAll(ref prefix) => match prefix.item {
+                Name::Designator(ref des) => match des.item {
+                    Designator::Identifier(ref sym) if *sym == self.work_sym => {
+                        Some(prefix.pos(self.ctx))
+                    }
+                    _ => None,
+                },
+                _ => self.work_prefix(prefix),
+            },
+            _ => None,
+        }
+    }
+
     fn analyze_architecture(
         &self,
         unit: &mut ArchitectureBody,
@@ -514,6 +828,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             Name::Designator(designator) => {
                 let visible = scope
                     .lookup(self.ctx, name.span, designator.designator())
+                    .map_err(|err| self.suggest_library_clause(designator.designator(), err))
                     .into_eval_result(diagnostics)?;
                 designator.set_reference(&visible);
                 Ok(UsedNames::Single(visible))
@@ -660,26 +975,36 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 UsedNames::Single(visible) => {
                     visible.make_potentially_visible_in(Some(&name.pos(self.ctx)), scope);
                 }
-                UsedNames::AllWithin(visibility_pos, named_entity) => match named_entity.kind() {
-                    AnyEntKind::Library => {
-                        let library_name = named_entity.designator().expect_identifier();
-                        self.use_all_in_library(&name.pos(self.ctx), library_name, scope)?;
-                    }
-                    AnyEntKind::Design(design) => match design {
-                        Design::UninstPackage(..) => {
-                            diagnostics.push(Diagnostic::invalid_selected_name_prefix(
-                                named_entity,
-                                &visibility_pos,
-                            ));
-                        }
-                        Design::Package(_, ref primary_region)
-                        | Design::PackageInstance(ref primary_region)
-                        | Design::InterfacePackageInstance(ref primary_region) => {
-                            scope.make_all_potentially_visible(
-                                Some(&name.pos(self.ctx)),
-                                primary_region,
-                            );
+                UsedNames::AllWithin(visibility_pos, named_entity) => {
+                    match named_entity.actual_kind() {
+                        AnyEntKind::Library => {
+                            let library_name = named_entity.designator().expect_identifier();
+                            self.use_all_in_library(&name.pos(self.ctx), library_name, scope)?;
                         }
+                        AnyEntKind::Design(design) => match design {
+                            Design::UninstPackage(..) => {
+                                diagnostics.push(Diagnostic::invalid_selected_name_prefix(
+                                    named_entity,
+                                    &visibility_pos,
+                                ));
+                            }
+                            Design::Package(_, ref primary_region)
+                            | Design::PackageInstance(ref primary_region)
+                            | Design::InterfacePackageInstance(ref primary_region) => {
+                                scope.make_all_potentially_visible(
+                                    Some(&name.pos(self.ctx)),
+                                    primary_region,
+                                );
+                            }
+                            _ => {
+                                diagnostics.add(
+                                    visibility_pos,
+                                    "Invalid prefix for selected name",
+                                    ErrorCode::MismatchedKinds,
+                                );
+                            }
+                        },
+
                         _ => {
                             diagnostics.add(
                                 visibility_pos,
@@ -687,16 +1012,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                 ErrorCode::MismatchedKinds,
                             );
                         }
-                    },
-
-                    _ => {
-                        diagnostics.add(
-                            visibility_pos,
-                            "Invalid prefix for selected name",
-                            ErrorCode::MismatchedKinds,
-                        );
                     }
-                },
+                }
             }
         }
 
@@ -737,3 +1054,57 @@ pub enum UsedNames<'a> {
     /// @TODO add pos for where declaration was made visible into VisibleDeclaration
     AllWithin(SrcPos, EntRef<'a>),
 }
+
+fn find_labeled<'s>(
+    statements: &[&'s LabeledConcurrentStatement],
+    label: &Symbol,
+) -> Option<&'s LabeledConcurrentStatement> {
+    statements.iter().copied().find(|statement| {
+        statement
+            .label
+            .tree
+            .as_ref()
+            .is_some_and(|ident| ident.item == *label)
+    })
+}
+
+/// The kind and the statements of a block or generate statement that can be configured
+/// by a block configuration. The statements of all alternatives of a generate statement
+/// are considered.
+fn nested_statements(
+    statement: &ConcurrentStatement,
+) -> Option<(&'static str, Vec<&LabeledConcurrentStatement>)> {
+    match statement {
+        ConcurrentStatement::Block(block) => Some(("block", block.statements.iter().collect())),
+        ConcurrentStatement::ForGenerate(gen) => {
+            Some(("generate", gen.body.statements.iter().collect()))
+        }
+        ConcurrentStatement::IfGenerate(gen) => Some((
+            "generate",
+            gen.conds
+                .conditionals
+                .iter()
+                .map(|cond| &cond.item)
+                .chain(gen.conds.else_item.iter())
+                .flat_map(|body| body.statements.iter())
+                .collect(),
+        )),
+        ConcurrentStatement::CaseGenerate(gen) => Some((
+            "generate",
+            gen.sels
+                .alternatives
+                .iter()
+                .flat_map(|alternative| alternative.item.statements.iter())
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+fn suffix_designator(name: &Name) -> Option<&Designator> {
+    match name {
+        Name::Designator(designator) => Some(&designator.item),
+        Name::Selected(_, suffix) => Some(&suffix.item.item),
+        _ => None,
+    }
+}
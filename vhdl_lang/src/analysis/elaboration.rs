@@ -0,0 +1,686 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Best-effort elaboration of the static structure of a design hierarchy
+
+use super::root::{DesignRoot, Library, LockedUnit};
+use super::static_value::{evaluate, named_value, StaticScope};
+use crate::ast;
+use crate::ast::*;
+use crate::data::*;
+use crate::named_entity::*;
+use fnv::FnvHashMap;
+use std::cell::Cell;
+
+/// Recursive instantiations are not elaborated beyond this depth
+const MAX_DEPTH: usize = 64;
+
+/// The architectures of instances beyond this number are not elaborated
+const MAX_INSTANCES: usize = 10000;
+
+/// For generate statements with more iterations are elaborated as if their range was not static
+const MAX_ITERATIONS: i64 = 4096;
+
+/// The instances of a design hierarchy below a top level design entity
+#[derive(Debug, Clone)]
+pub struct InstanceTree<'a> {
+    pub top: Instance<'a>,
+}
+
+impl<'a> InstanceTree<'a> {
+    /// Find an instance by its path below the top level, such as `u_mid.gen(1).u_leaf`
+    pub fn find(&self, path: &str) -> Option<&Instance<'a>> {
+        self.top.find(path)
+    }
+}
+
+/// A design entity within an elaborated hierarchy
+#[derive(Debug, Clone)]
+pub struct Instance<'a> {
+    /// The path of the instance relative to its parent instance, such as `u_fifo`.
+    /// Labels of enclosing block and generate statements are part of the path,
+    /// for example `gen(2).u_fifo`. The top level instance is named after its entity.
+    pub label: String,
+    /// The label of the instantiation statement, `None` for the top level
+    pub pos: Option<SrcPos>,
+    /// The component through which the design entity is instantiated
+    pub component: Option<EntRef<'a>>,
+    /// `None` when no entity is bound to a component
+    pub entity: Option<EntRef<'a>>,
+    /// `None` when the entity has no architecture
+    pub architecture: Option<EntRef<'a>>,
+    /// The generics of the entity in the order of declaration
+    pub generics: Vec<GenericValue<'a>>,
+    /// The ports of the component or entity associated by the port map
+    /// in the order of association
+    pub ports: Vec<AssociatedPort<'a>>,
+    pub children: Vec<Instance<'a>>,
+}
+
+impl<'a> Instance<'a> {
+    pub fn find(&self, path: &str) -> Option<&Instance<'a>> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| {
+            let rest = strip_label(path, &child.label)?;
+            match rest.strip_prefix('.') {
+                Some(rest) => child.find(rest),
+                None if rest.is_empty() => Some(child),
+                None => None,
+            }
+        })
+    }
+
+    /// The value of a generic of the entity
+    pub fn generic(&self, name: &str) -> Option<StaticValue> {
+        self.generics
+            .iter()
+            .find(|generic| match generic.generic.designator() {
+                Designator::Identifier(sym) => sym.name_utf8().eq_ignore_ascii_case(name),
+                _ => false,
+            })?
+            .value
+    }
+
+    /// The length of an array port associated by the port map
+    pub fn port_length(&self, name: &str) -> Option<i64> {
+        self.ports
+            .iter()
+            .find(|port| match port.port.designator() {
+                Designator::Identifier(sym) => sym.name_utf8().eq_ignore_ascii_case(name),
+                _ => false,
+            })?
+            .length
+    }
+}
+
+/// Labels are case insensitive
+fn strip_label<'p>(path: &'p str, label: &str) -> Option<&'p str> {
+    let prefix = path.get(..label.len())?;
+    if prefix.eq_ignore_ascii_case(label) {
+        Some(&path[label.len()..])
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GenericValue<'a> {
+    pub generic: EntRef<'a>,
+    /// `None` when the value of the generic is not static
+    pub value: Option<StaticValue>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AssociatedPort<'a> {
+    pub port: EntRef<'a>,
+    /// The length of an array port, which an unconstrained port takes from its actual.
+    /// `None` when the length is not static
+    pub length: Option<i64>,
+}
+
+/// The values of generics and generate parameters within an instance
+type Env = FnvHashMap<EntityId, StaticValue>;
+
+struct ElaborationScope<'r, 'a> {
+    root: &'a DesignRoot,
+    env: &'r Env,
+}
+
+impl<'a> StaticScope<'a> for ElaborationScope<'_, 'a> {
+    fn lookup(&self, id: EntityId) -> EntRef<'a> {
+        self.root.get_ent(id)
+    }
+
+    fn recorded_value(&self, id: EntityId) -> Option<StaticValue> {
+        self.root.static_value(id)
+    }
+
+    fn recorded_range(&self, id: EntityId) -> Option<StaticRange> {
+        self.root.static_range(id)
+    }
+
+    fn value_of(&self, ent: EntRef<'a>) -> Option<StaticValue> {
+        self.env
+            .get(&ent.id())
+            .copied()
+            .or_else(|| named_value(self, ent))
+    }
+}
+
+/// The outcome of evaluating the condition of a generate alternative
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Selected {
+    Yes,
+    No,
+    Unknown,
+}
+
+impl From<Option<StaticValue>> for Selected {
+    /// The position of TRUE and '1' is one
+    fn from(value: Option<StaticValue>) -> Selected {
+        match value {
+            Some(StaticValue::Enum(1)) => Selected::Yes,
+            Some(StaticValue::Enum(0)) => Selected::No,
+            _ => Selected::Unknown,
+        }
+    }
+}
+
+struct Elaborator<'a> {
+    root: &'a DesignRoot,
+    /// The number of elaborated instances
+    elaborated: Cell<usize>,
+}
+
+impl<'a> Elaborator<'a> {
+    fn scope<'r>(&self, env: &'r Env) -> ElaborationScope<'r, 'a> {
+        ElaborationScope {
+            root: self.root,
+            env,
+        }
+    }
+
+    fn get_ent(&self, id: Option<EntityId>) -> Option<EntRef<'a>> {
+        id.map(|id| self.root.get_ent(id))
+    }
+
+    fn library_of(&self, ent: EntRef<'a>) -> Option<&'a Library> {
+        self.root.get_lib(ent.library_name()?)
+    }
+
+    fn architectures(&self, entity: EntRef<'a>) -> Vec<&'a LockedUnit> {
+        let (Some(library), Designator::Identifier(name)) =
+            (self.library_of(entity), entity.designator())
+        else {
+            return Vec::new();
+        };
+        library
+            .secondary_units(name)
+            .filter(|unit| unit.kind() == AnyKind::Secondary(SecondaryKind::Architecture))
+            .collect()
+    }
+
+    fn unit_of(&self, ent: EntRef<'a>) -> Option<&'a LockedUnit> {
+        let library = self.library_of(ent)?;
+        match ent.kind() {
+            AnyEntKind::Design(Design::Architecture(entity)) => self
+                .architectures(entity.0)
+                .into_iter()
+                .find(|unit| unit.unit.expect_analyzed().ent_id() == Some(ent.id())),
+            _ => {
+                let Designator::Identifier(name) = ent.designator() else {
+                    return None;
+                };
+                library.primary_unit(name)
+            }
+        }
+    }
+
+    /// The LRM binds the most recently analyzed architecture by default,
+    /// which is approximated by the architecture that appears last
+    fn default_architecture(&self, entity: EntRef<'a>) -> Option<EntRef<'a>> {
+        let unit = self
+            .architectures(entity)
+            .into_iter()
+            .max_by_key(|unit| (unit.pos().source().file_name(), unit.pos().start()))?;
+        self.get_ent(unit.unit.expect_analyzed().ent_id())
+    }
+
+    fn architecture_named(&self, entity: EntRef<'a>, name: &Symbol) -> Option<EntRef<'a>> {
+        let unit = self
+            .architectures(entity)
+            .into_iter()
+            .find(|unit| unit.unit_id().secondary_name() == Some(name))?;
+        self.get_ent(unit.unit.expect_analyzed().ent_id())
+    }
+
+    /// The entity and architecture selected by a configuration declaration
+    fn configured(&self, config: EntRef<'a>) -> (Option<EntRef<'a>>, Option<EntRef<'a>>) {
+        let Some(unit) = self.unit_of(config) else {
+            return (None, None);
+        };
+        let data = unit.unit.expect_analyzed();
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(config)) = data.data() else {
+            return (None, None);
+        };
+        let entity = self
+            .get_ent(config.entity_name.item.get_suffix_reference())
+            .filter(|ent| matches!(ent.kind(), AnyEntKind::Design(Design::Entity(..))));
+        let Some(entity) = entity else {
+            return (None, None);
+        };
+        let architecture = match &config.block_config.block_spec.item {
+            Name::Designator(designator) => match &designator.item {
+                Designator::Identifier(name) => self.architecture_named(entity, name),
+                _ => None,
+            },
+            _ => None,
+        };
+        (Some(entity), architecture)
+    }
+
+    fn generics_of(&self, ent: EntRef<'a>) -> Vec<InterfaceEnt<'a>> {
+        match ent.kind() {
+            AnyEntKind::Design(Design::Entity(_, region)) | AnyEntKind::Component(region) => {
+                region.ports_and_generics().1
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The values of the generics associated by a generic map.
+    /// Unassociated generics take the value of their default expression.
+    fn associate(
+        &self,
+        generics: &[InterfaceEnt<'a>],
+        generic_map: Option<&MapAspect>,
+        env: &Env,
+    ) -> Vec<GenericValue<'a>> {
+        let mut values: Vec<GenericValue<'a>> = generics
+            .iter()
+            .map(|generic| GenericValue {
+                generic: generic.inner(),
+                value: self.root.static_value(generic.id()),
+            })
+            .collect();
+
+        let Some(generic_map) = generic_map else {
+            return values;
+        };
+
+        for (idx, element) in generic_map.list.items.iter().enumerate() {
+            let idx = match &element.formal {
+                Some(formal) => {
+                    let Some(id) = formal.item.get_suffix_reference() else {
+                        continue;
+                    };
+                    let Some(idx) = generics.iter().position(|generic| generic.id() == id) else {
+                        continue;
+                    };
+                    idx
+                }
+                None => idx,
+            };
+            let Some(generic) = generics.get(idx) else {
+                continue;
+            };
+            if let ActualPart::Expression(expr) = &element.actual.item {
+                values[idx].value = evaluate(&self.scope(env), Some(generic.type_mark()), expr);
+            }
+        }
+        values
+    }
+
+    /// Elaborate the architecture of an instance.
+    /// `path` contains the architectures of the enclosing instances.
+    fn elaborate(&self, mut instance: Instance<'a>, path: &[EntityId]) -> Instance<'a> {
+        if path.len() >= MAX_DEPTH || self.elaborated.get() >= MAX_INSTANCES {
+            return instance;
+        }
+        let Some(architecture) = instance.architecture else {
+            return instance;
+        };
+        // A recursive instantiation with non-static generics cannot be known to terminate
+        if path.contains(&architecture.id())
+            && instance
+                .generics
+                .iter()
+                .any(|generic| generic.value.is_none())
+        {
+            return instance;
+        }
+        self.elaborated.set(self.elaborated.get() + 1);
+
+        if let Some(unit) = self.unit_of(architecture) {
+            let mut path = path.to_vec();
+            path.push(architecture.id());
+            let env: Env = instance
+                .generics
+                .iter()
+                .filter_map(|generic| Some((generic.generic.id(), generic.value?)))
+                .collect();
+            let data = unit.unit.expect_analyzed();
+            if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = data.data() {
+                self.statements(
+                    unit,
+                    &arch.statements,
+                    &env,
+                    "",
+                    &path,
+                    &mut instance.children,
+                );
+            }
+        }
+        instance
+    }
+
+    fn instantiation(
+        &self,
+        unit: &LockedUnit,
+        label: String,
+        pos: Option<SrcPos>,
+        statement: &InstantiationStatement,
+        env: &Env,
+        path: &[EntityId],
+    ) -> Instance<'a> {
+        let generic_map = statement.generic_map.as_ref();
+        let (component, entity, architecture, generics) = match &statement.unit {
+            InstantiatedUnit::Entity(name, architecture_name) => {
+                let entity = self.get_ent(name.item.get_suffix_reference());
+                let architecture = match architecture_name {
+                    Some(name) => self.get_ent(name.reference.get()),
+                    None => entity.and_then(|entity| self.default_architecture(entity)),
+                };
+                let generics = entity
+                    .map(|entity| self.associate(&self.generics_of(entity), generic_map, env))
+                    .unwrap_or_default();
+                (None, entity, architecture, generics)
+            }
+            InstantiatedUnit::Component(name) => {
+                let component = self.get_ent(name.item.get_suffix_reference());
+                let entity = component.and_then(|component| {
+                    self.root
+                        .get_lib(unit.unit_id().library_name())
+                        .and_then(|library| default_entity(self.root, library, component))
+                });
+                let architecture = entity.and_then(|entity| self.default_architecture(entity));
+                let component_generics = component
+                    .map(|component| self.associate(&self.generics_of(component), generic_map, env))
+                    .unwrap_or_default();
+                let generics = match entity {
+                    Some(entity) => self.bind_generics(entity, &component_generics),
+                    None => component_generics,
+                };
+                (component, entity, architecture, generics)
+            }
+            InstantiatedUnit::Configuration(name) => {
+                let (entity, architecture) = match self.get_ent(name.item.get_suffix_reference()) {
+                    Some(config) => self.configured(config),
+                    None => (None, None),
+                };
+                let architecture = architecture
+                    .or_else(|| entity.and_then(|entity| self.default_architecture(entity)));
+                let generics = entity
+                    .map(|entity| self.associate(&self.generics_of(entity), generic_map, env))
+                    .unwrap_or_default();
+                (None, entity, architecture, generics)
+            }
+        };
+
+        let instance = Instance {
+            label,
+            pos,
+            component,
+            entity,
+            architecture,
+            generics,
+            ports: self.associated_ports(unit, statement.port_map.as_ref()),
+            children: Vec::new(),
+        };
+        self.elaborate(instance, path)
+    }
+
+    /// The ports associated by a port map as recorded by the analysis of the instantiation
+    fn associated_ports(
+        &self,
+        unit: &LockedUnit,
+        port_map: Option<&MapAspect>,
+    ) -> Vec<AssociatedPort<'a>> {
+        let Some(port_map) = port_map else {
+            return Vec::new();
+        };
+        let data = unit.unit.expect_analyzed();
+        port_map
+            .list
+            .items
+            .iter()
+            .filter_map(|element| {
+                let associated = data.result().associated_formals.get(&element.actual.span)?;
+                Some(AssociatedPort {
+                    port: self.root.get_ent(associated.formal),
+                    length: associated.length,
+                })
+            })
+            .collect()
+    }
+
+    /// Generics of a component are associated with the generics of the same name
+    /// of the entity that it is bound to by default
+    fn bind_generics(
+        &self,
+        entity: EntRef<'a>,
+        component_generics: &[GenericValue<'a>],
+    ) -> Vec<GenericValue<'a>> {
+        let mut generics = self.associate(&self.generics_of(entity), None, &Env::default());
+        for generic in generics.iter_mut() {
+            if let Some(component_generic) = component_generics
+                .iter()
+                .find(|value| value.generic.designator() == generic.generic.designator())
+            {
+                generic.value = component_generic.value;
+            }
+        }
+        generics
+    }
+
+    fn statements(
+        &self,
+        unit: &LockedUnit,
+        statements: &[LabeledConcurrentStatement],
+        env: &Env,
+        prefix: &str,
+        path: &[EntityId],
+        instances: &mut Vec<Instance<'a>>,
+    ) {
+        for statement in statements {
+            let Some(ident) = statement.label.tree.as_ref() else {
+                continue;
+            };
+            let label = format!("{prefix}{}", ident.item.name_utf8());
+
+            match &statement.statement.item {
+                ConcurrentStatement::Instance(instance) => {
+                    let pos = ident.pos(&unit.tokens).clone();
+                    instances.push(self.instantiation(unit, label, Some(pos), instance, env, path));
+                }
+                ConcurrentStatement::Block(block) => {
+                    let prefix = format!("{label}.");
+                    self.statements(unit, &block.statements, env, &prefix, path, instances);
+                }
+                ConcurrentStatement::ForGenerate(generate) => {
+                    let index = generate.index_name.decl.get();
+                    match (index, self.iterations(&generate.discrete_range, env)) {
+                        (Some(index), Some(iterations)) => {
+                            for value in iterations {
+                                let mut env = env.clone();
+                                env.insert(index, StaticValue::Integer(value));
+                                let prefix = format!("{label}({value}).");
+                                self.generate_body(
+                                    unit,
+                                    &generate.body,
+                                    &env,
+                                    &prefix,
+                                    path,
+                                    instances,
+                                );
+                            }
+                        }
+                        _ => {
+                            let prefix = format!("{label}.");
+                            self.generate_body(unit, &generate.body, env, &prefix, path, instances);
+                        }
+                    }
+                }
+                ConcurrentStatement::IfGenerate(generate) => {
+                    let prefix = format!("{label}.");
+                    let mut selected = false;
+                    for conditional in generate.conds.conditionals.iter() {
+                        let condition: Selected =
+                            evaluate(&self.scope(env), None, &conditional.condition.item).into();
+                        if condition != Selected::No {
+                            self.generate_body(
+                                unit,
+                                &conditional.item,
+                                env,
+                                &prefix,
+                                path,
+                                instances,
+                            );
+                        }
+                        if condition == Selected::Yes {
+                            selected = true;
+                            break;
+                        }
+                    }
+                    if let (false, Some(else_item)) = (selected, &generate.conds.else_item) {
+                        self.generate_body(unit, else_item, env, &prefix, path, instances);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(generate) => {
+                    let prefix = format!("{label}.");
+                    let scope = self.scope(env);
+                    let value = evaluate(&scope, None, &generate.sels.expression.item);
+                    for alternative in generate.sels.alternatives.iter() {
+                        let choice = alternative
+                            .choices
+                            .iter()
+                            .map(|choice| match (&choice.item, value) {
+                                (Choice::Others, _) => Selected::Yes,
+                                (Choice::Expression(expr), Some(value)) => {
+                                    match evaluate(&scope, None, expr) {
+                                        Some(choice) if choice == value => Selected::Yes,
+                                        Some(_) => Selected::No,
+                                        None => Selected::Unknown,
+                                    }
+                                }
+                                _ => Selected::Unknown,
+                            })
+                            .fold(Selected::No, |selected, choice| match (selected, choice) {
+                                (Selected::Yes, _) | (_, Selected::Yes) => Selected::Yes,
+                                (Selected::Unknown, _) | (_, Selected::Unknown) => {
+                                    Selected::Unknown
+                                }
+                                _ => Selected::No,
+                            });
+                        if choice != Selected::No {
+                            self.generate_body(
+                                unit,
+                                &alternative.item,
+                                env,
+                                &prefix,
+                                path,
+                                instances,
+                            );
+                        }
+                        if choice == Selected::Yes {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn generate_body(
+        &self,
+        unit: &LockedUnit,
+        body: &GenerateBody,
+        env: &Env,
+        prefix: &str,
+        path: &[EntityId],
+        instances: &mut Vec<Instance<'a>>,
+    ) {
+        self.statements(unit, &body.statements, env, prefix, path, instances);
+    }
+
+    /// The values of an integer for generate parameter in the order of iteration
+    fn iterations(&self, drange: &DiscreteRange, env: &Env) -> Option<Vec<i64>> {
+        let (DiscreteRange::Range(ast::Range::Range(constraint))
+        | DiscreteRange::Discrete(_, Some(ast::Range::Range(constraint)))) = drange
+        else {
+            return None;
+        };
+        let scope = self.scope(env);
+        let (Some(StaticValue::Integer(left)), Some(StaticValue::Integer(right))) = (
+            evaluate(&scope, None, &constraint.left_expr.item),
+            evaluate(&scope, None, &constraint.right_expr.item),
+        ) else {
+            return None;
+        };
+        let (low, high) = match constraint.direction {
+            Direction::Ascending => (left, right),
+            Direction::Descending => (right, left),
+        };
+        if high.checked_sub(low)? >= MAX_ITERATIONS {
+            return None;
+        }
+        let values = low..=high;
+        Some(match constraint.direction {
+            Direction::Ascending => values.collect(),
+            Direction::Descending => values.rev().collect(),
+        })
+    }
+}
+
+/// Find the entity that a component is bound to by default,
+/// that is the entity with the same name in the library of the component
+pub(crate) fn default_entity<'a>(
+    root: &'a DesignRoot,
+    library: &Library,
+    component: EntRef<'a>,
+) -> Option<EntRef<'a>> {
+    let Designator::Identifier(name) = component.designator() else {
+        return None;
+    };
+    let unit = library.primary_unit(name)?;
+    let ent = root.get_ent(unit.unit.expect_analyzed().ent_id()?);
+    if matches!(ent.kind(), AnyEntKind::Design(Design::Entity(..))) {
+        Some(ent)
+    } else {
+        None
+    }
+}
+
+impl DesignRoot {
+    /// Elaborate the design hierarchy below a top level entity or architecture.
+    ///
+    /// Instances are bound to entities and architectures using direct instantiations,
+    /// configurations and the default binding of components. The values of generics are
+    /// propagated when they are static. For generate statements with static ranges and
+    /// if or case generate statements with static conditions are elaborated accordingly,
+    /// otherwise their statements are elaborated once. The architectures of recursive
+    /// instances whose generics are not static are not elaborated.
+    ///
+    /// Returns `None` when `top` is not an entity or an architecture.
+    pub fn elaborate<'a>(&'a self, top: EntRef<'a>) -> Option<InstanceTree<'a>> {
+        let elaborator = Elaborator {
+            root: self,
+            elaborated: Cell::new(0),
+        };
+        let (entity, architecture) = match top.kind() {
+            AnyEntKind::Design(Design::Entity(..)) => (top, elaborator.default_architecture(top)),
+            AnyEntKind::Design(Design::Architecture(entity)) => (entity.0, Some(top)),
+            _ => return None,
+        };
+        let generics = elaborator.associate(&elaborator.generics_of(entity), None, &Env::default());
+        let top = Instance {
+            label: entity.designator().to_string(),
+            pos: None,
+            component: None,
+            entity: Some(entity),
+            architecture,
+            generics,
+            ports: Vec::new(),
+            children: Vec::new(),
+        };
+        Some(InstanceTree {
+            top: elaborator.elaborate(top, &[]),
+        })
+    }
+}
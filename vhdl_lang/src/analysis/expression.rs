@@ -230,10 +230,16 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     ) -> EvalResult<TypeEnt<'a>> {
         match self.expr_type(scope, expr, diagnostics)? {
             ExpressionType::Unambiguous(typ) => Ok(typ),
-            ExpressionType::Ambiguous(_)
-            | ExpressionType::String
-            | ExpressionType::Null
-            | ExpressionType::Aggregate => {
+            ExpressionType::Aggregate => {
+                if let Expression::Aggregate(ref mut assocs) = expr.item {
+                    self.analyze_aggregate(scope, assocs, diagnostics)?;
+                }
+                bail!(
+                    diagnostics,
+                    Diagnostic::untyped_aggregate(&expr.pos(self.ctx))
+                );
+            }
+            ExpressionType::Ambiguous(_) | ExpressionType::String | ExpressionType::Null => {
                 diagnostics.add(
                     expr.pos(self.ctx),
                     "Ambiguous expression. You can use a qualified expression type'(expr) to disambiguate.",
@@ -562,7 +568,73 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
                 Literal::Null => Ok(ExpressionType::Null),
             },
+            Expression::Conditional(ref mut conditionals) => {
+                let Conditionals {
+                    conditionals,
+                    else_item,
+                } = conditionals.as_mut();
+                let mut items = Vec::with_capacity(conditionals.len() + 1);
+                for Conditional { condition, item } in conditionals.iter_mut() {
+                    self.boolean_expr(scope, condition, diagnostics)?;
+                    items.push(item);
+                }
+                items.extend(else_item.as_mut());
+                self.alternatives_type(scope, items, diagnostics)
+            }
+            Expression::Selected(ref mut selection) => {
+                let Selection {
+                    is_matching,
+                    expression,
+                    alternatives,
+                } = selection.as_mut();
+                let ctyp =
+                    self.case_expression_type(scope, *is_matching, expression, diagnostics)?;
+                let mut items = Vec::with_capacity(alternatives.len());
+                for Alternative { choices, item } in alternatives.iter_mut() {
+                    self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
+                    items.push(item);
+                }
+                self.alternatives_type(scope, items, diagnostics)
+            }
+        }
+    }
+
+    /// The type of a conditional or selected expression is given by its alternatives.
+    /// The first alternative with an unambiguous type determines the type of the others.
+    fn alternatives_type(
+        &self,
+        scope: &Scope<'a>,
+        mut items: Vec<&mut WithTokenSpan<Expression>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<ExpressionType<'a>> {
+        let mut types = Vec::with_capacity(items.len());
+        for item in items.iter_mut() {
+            types.push(as_fatal(self.expr_type(scope, item, diagnostics))?);
+        }
+
+        let Some(typ) = types.iter().find_map(|typ| match typ {
+            Some(ExpressionType::Unambiguous(typ)) => Some(*typ),
+            _ => None,
+        }) else {
+            return types.into_iter().flatten().next().ok_or(EvalError::Unknown);
+        };
+
+        for (item, item_type) in items.into_iter().zip(types) {
+            match item_type {
+                Some(ExpressionType::Unambiguous(item_type))
+                    if !self.can_be_target_type(item_type, typ.base()) =>
+                {
+                    diagnostics.push(Diagnostic::type_mismatch(
+                        &item.pos(self.ctx),
+                        &item_type.describe(),
+                        typ,
+                    ));
+                }
+                Some(ExpressionType::Unambiguous(_)) | None => {}
+                Some(_) => self.expr_with_ttyp(scope, typ, item, diagnostics)?,
+            }
         }
+        Ok(ExpressionType::Unambiguous(typ))
     }
 
     // Fallback for analyzing an expression without a known target type
@@ -577,6 +649,24 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// Analyze an expression in a context that does not provide a target type.
+    /// Unlike other expressions, the type of an aggregate is only ever determined by the context,
+    /// so an aggregate is illegal here unless it is qualified.
+    pub fn expr_without_ttyp(
+        &self,
+        scope: &Scope<'a>,
+        expr: &mut WithTokenSpan<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        if let Expression::Aggregate(ref mut assocs) = expr.item {
+            self.analyze_aggregate(scope, assocs, diagnostics)?;
+            diagnostics.push(Diagnostic::untyped_aggregate(&expr.pos(self.ctx)));
+            Ok(())
+        } else {
+            self.expr_unknown_ttyp(scope, expr, diagnostics)
+        }
+    }
+
     fn analyze_qualified_expression(
         &self,
         scope: &Scope<'a>,
@@ -603,18 +693,50 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
     }
 
-    pub fn analyze_allocation(
+    /// Analyze an allocator and check that the allocated type
+    /// matches the designated subtype of the target access type
+    pub fn analyze_allocation_with_ttyp(
         &self,
         scope: &Scope<'a>,
+        target_type: TypeEnt<'a>,
         alloc: &mut WithTokenSpan<Allocator>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
-        match &mut alloc.item {
+        let alloc_type = match &mut alloc.item {
             Allocator::Qualified(ref mut qexpr) => {
-                as_fatal(self.analyze_qualified_expression(scope, qexpr, diagnostics))?;
+                as_fatal(self.analyze_qualified_expression(scope, qexpr, diagnostics))?
             }
             Allocator::Subtype(ref mut subtype) => {
-                self.analyze_subtype_indication(scope, subtype, diagnostics)?;
+                as_fatal(self.resolve_subtype_indication(scope, subtype, diagnostics))?
+                    .map(|subtype| subtype.type_mark())
+            }
+        };
+
+        let Some(alloc_type) = alloc_type else {
+            return Ok(());
+        };
+
+        match target_type.base().accessed_type() {
+            Some(designated_type) => {
+                if !self.can_be_target_type(alloc_type, designated_type.base()) {
+                    diagnostics.add(
+                        alloc.pos(self.ctx),
+                        format!(
+                            "{} does not match {} designated by {}",
+                            alloc_type.describe(),
+                            designated_type.describe(),
+                            target_type.describe()
+                        ),
+                        ErrorCode::TypeMismatch,
+                    );
+                }
+            }
+            None => {
+                diagnostics.push(Diagnostic::type_mismatch(
+                    &alloc.pos(self.ctx),
+                    &format!("allocator of {}", alloc_type.describe()),
+                    target_type,
+                ));
             }
         }
         Ok(())
@@ -630,6 +752,26 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         self.expr_pos_with_ttyp(scope, target_type, expr.span, &mut expr.item, diagnostics)
     }
 
+    /// Analyze an expression with the type mark of a subtype and check its value
+    /// against the innermost constraint of the subtype, which implies the outer ones
+    pub fn expr_with_subtype(
+        &self,
+        scope: &Scope<'a>,
+        subtype: &Subtype<'a>,
+        expr: &mut WithTokenSpan<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        self.expr_pos_without_constraint_check(
+            scope,
+            subtype.type_mark(),
+            expr.span,
+            &mut expr.item,
+            diagnostics,
+        )?;
+        self.check_subtype_constraint(subtype, expr.span, &expr.item, diagnostics);
+        Ok(())
+    }
+
     fn implicit_bool_types(&self, scope: &Scope<'a>, span: TokenSpan) -> FnvHashSet<BaseType<'a>> {
         if let Ok(NamedEntities::Overloaded(overloaded)) = scope.lookup(
             self.ctx,
@@ -722,6 +864,41 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// The severity expression of an assert or report statement
+    pub fn severity_expr(
+        &self,
+        scope: &Scope<'a>,
+        expr: &mut WithTokenSpan<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let mut expr_diagnostics = Vec::new();
+        self.expr_with_ttyp(scope, self.severity_level(), expr, &mut expr_diagnostics)?;
+
+        for mut diagnostic in expr_diagnostics {
+            // A misspelled severity level is more helpful than just a missing declaration
+            if let Expression::Name(ref name) = expr.item {
+                if let Name::Designator(ref designator) = name.as_ref() {
+                    let is_misspelled_level = diagnostic.code == ErrorCode::Unresolved
+                        && diagnostic.pos == expr.pos(self.ctx)
+                        && diagnostic.fixes.iter().any(|fix| {
+                            matches!(
+                                fix.replacement.to_lowercase().as_str(),
+                                "note" | "warning" | "error" | "failure"
+                            )
+                        });
+                    if is_misspelled_level {
+                        diagnostic.message = format!(
+                            "Unknown severity level '{}'. Expected one of note, warning, error or failure",
+                            designator.item
+                        );
+                    }
+                }
+            }
+            diagnostics.push(diagnostic);
+        }
+        Ok(())
+    }
+
     /// Returns true if the name actually matches the target type
     /// None if it was uncertain
     pub fn expr_pos_with_ttyp(
@@ -732,6 +909,20 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         expr: &mut Expression,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
+        self.expr_pos_without_constraint_check(scope, target_type, span, expr, diagnostics)?;
+        self.check_subtype_constraint(&Subtype::new(target_type), span, expr, diagnostics);
+        Ok(())
+    }
+
+    fn expr_pos_without_constraint_check(
+        &self,
+        scope: &Scope<'a>,
+        target_type: TypeEnt<'a>,
+        span: TokenSpan,
+        expr: &mut Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        self.record_expression_type(span, target_type);
         let target_base = target_type.base_type();
         match expr {
             Expression::Literal(ref mut lit) => {
@@ -877,7 +1068,40 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
             },
             Expression::New(ref mut alloc) => {
-                self.analyze_allocation(scope, alloc, diagnostics)?;
+                self.analyze_allocation_with_ttyp(scope, target_type, alloc, diagnostics)?;
+            }
+            Expression::Conditional(ref mut conditionals) => {
+                let Conditionals {
+                    conditionals,
+                    else_item,
+                } = conditionals.as_mut();
+                for Conditional { condition, item } in conditionals.iter_mut() {
+                    self.expr_with_ttyp(scope, target_type, item, diagnostics)?;
+                    self.boolean_expr(scope, condition, diagnostics)?;
+                }
+                if let Some(item) = else_item {
+                    self.expr_with_ttyp(scope, target_type, item, diagnostics)?;
+                }
+            }
+            Expression::Selected(ref mut selection) => {
+                let Selection {
+                    is_matching,
+                    expression,
+                    alternatives,
+                } = selection.as_mut();
+                let ctyp =
+                    self.case_expression_type(scope, *is_matching, expression, diagnostics)?;
+                for Alternative { choices, item } in alternatives.iter_mut() {
+                    self.expr_with_ttyp(scope, target_type, item, diagnostics)?;
+                    self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
+                }
+                if !*is_matching {
+                    let choices: Vec<_> = alternatives
+                        .iter()
+                        .map(|alternative| alternative.choices.as_slice())
+                        .collect();
+                    self.check_duplicate_choices(ctyp, &choices, diagnostics);
+                }
             }
         }
 
@@ -929,6 +1153,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         for (idx, assoc) in assocs.iter_mut().enumerate() {
             match assoc {
                 ElementAssociation::Named(ref mut choices, ref mut actual_expr) => {
+                    let mut named_elem = None;
                     let typ = if choices.len() == 1 {
                         let choice = choices.first_mut().unwrap();
                         let choice_span = choice.span;
@@ -939,6 +1164,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                 {
                                     if let Some(elem) = elems.lookup(&simple_name.item) {
                                         simple_name.set_unique_reference(&elem);
+                                        named_elem = Some(elem);
                                         associated.associate(
                                             self.ctx,
                                             &elem,
@@ -1055,7 +1281,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         None
                     };
 
-                    if let Some(typ) = typ {
+                    if let Some(elem) = named_elem {
+                        self.expr_with_subtype(scope, elem.subtype(), actual_expr, diagnostics)?;
+                    } else if let Some(typ) = typ {
                         self.expr_pos_with_ttyp(
                             scope,
                             typ.into(),
@@ -1069,7 +1297,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
                 ElementAssociation::Positional(ref mut expr) => {
                     if let Some(elem) = elems.nth(idx) {
-                        self.expr_with_ttyp(scope, elem.type_mark(), expr, diagnostics)?;
+                        self.expr_with_subtype(scope, elem.subtype(), expr, diagnostics)?;
                         associated.associate(self.ctx, elem, expr.span, diagnostics);
                     } else {
                         self.expr_unknown_ttyp(scope, expr, diagnostics)?;
@@ -1234,11 +1462,23 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 }
 
 impl Diagnostic {
+    fn untyped_aggregate(pos: &SrcPos) -> Diagnostic {
+        Diagnostic::new(
+            pos,
+            "Type of aggregate cannot be determined from the context. You can use a qualified expression type'(expr) to specify the type.",
+            ErrorCode::UntypedAggregate,
+        )
+    }
+
     fn ambiguous_op<'a>(
         pos: &SrcPos,
         op: Operator,
         candidates: impl IntoIterator<Item = OverloadedEnt<'a>>,
     ) -> Diagnostic {
+        let candidates: Vec<_> = candidates.into_iter().collect();
+        if let Some(diag) = Diagnostic::ambiguous_homographs(pos, &candidates) {
+            return diag;
+        }
         let mut diag = Diagnostic::new(
             pos,
             format!(
@@ -1470,7 +1710,7 @@ mod test {
             vec![Diagnostic::new(
                 code.s1("x'subtype"),
                 "integer type 'INTEGER' cannot be used in an expression",
-                ErrorCode::MismatchedKinds,
+                ErrorCode::TypeUsedAsValue,
             )],
         );
     }
@@ -19,6 +19,7 @@ use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::*;
+use crate::standard::VHDLStandard;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ObjectBase<'a> {
@@ -100,6 +101,16 @@ impl<'a> ObjectBase<'a> {
         }
     }
 
+    /// The declaration of the object itself, looking through aliases
+    pub fn decl_pos(&self) -> Option<&'a SrcPos> {
+        match self {
+            ObjectBase::Object(obj) => obj.ent.decl_pos(),
+            ObjectBase::ObjectAlias(obj, _) => obj.ent.decl_pos(),
+            ObjectBase::DeferredConstant(ent) => ent.decl_pos(),
+            ObjectBase::ExternalName(_) => None,
+        }
+    }
+
     pub fn is_port(&self) -> bool {
         match self {
             ObjectBase::Object(obj) => obj.kind().is_port(),
@@ -127,6 +138,15 @@ impl<'a> ObjectName<'a> {
         }
     }
 
+    /// The subtype of the object, including its range constraint, when the name
+    /// denotes the entire object
+    pub fn subtype(&self) -> Subtype<'a> {
+        match (self.type_mark, self.base) {
+            (None, ObjectBase::Object(obj)) => obj.object().subtype,
+            _ => Subtype::new(self.type_mark()),
+        }
+    }
+
     fn with_suffix(self, type_mark: TypeEnt<'a>) -> Self {
         ObjectName {
             base: self.base,
@@ -393,18 +413,10 @@ impl<'a> ResolvedName<'a> {
             },
             ResolvedName::Type(typ) => Some((*typ).into()),
             ResolvedName::Design(des) => Some((*des).into()),
+            ResolvedName::Overloaded(_, overloaded) => overloaded.as_unique(),
+            ResolvedName::Final(ent) => Some(ent.as_actual()),
             ResolvedName::Library(..) => None,
-            ResolvedName::Overloaded(_, _) => None,
             ResolvedName::Expression(_) => None,
-            ResolvedName::Final(_) => None,
-        }
-    }
-
-    /// Convenience function that returns `Some(name)` when self is an object name, else `None`
-    fn as_object_name(&self) -> Option<ObjectName<'a>> {
-        match self {
-            ResolvedName::ObjectName(oname) => Some(*oname),
-            _ => None,
         }
     }
 }
@@ -506,13 +518,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         name: ResolvedName<'a>,
     ) -> Result<Option<DisambiguatedType<'a>>, Diagnostic> {
         match name {
-            ResolvedName::Library(_) | ResolvedName::Design(_) | ResolvedName::Type(_) => {
-                Err(Diagnostic::new(
-                    pos.pos(self.ctx),
-                    format!("{} cannot be used in an expression", name.describe_type()),
-                    ErrorCode::MismatchedKinds,
-                ))
-            }
+            ResolvedName::Library(_) | ResolvedName::Design(_) => Err(Diagnostic::new(
+                pos.pos(self.ctx),
+                format!("{} cannot be used in an expression", name.describe_type()),
+                ErrorCode::MismatchedKinds,
+            )),
+            ResolvedName::Type(_) => Err(Diagnostic::type_used_as_value(&pos.pos(self.ctx), &name)),
             ResolvedName::Final(ent) => match ent.actual_kind() {
                 AnyEntKind::LoopParameter(typ) => {
                     Ok(typ.map(|typ| DisambiguatedType::Unambiguous(typ.into())))
@@ -556,13 +567,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         suffix_ref: Option<&mut Reference>,
     ) -> Result<Option<TypeEnt<'a>>, Diagnostic> {
         match name {
-            ResolvedName::Library(_) | ResolvedName::Design(_) | ResolvedName::Type(_) => {
-                Err(Diagnostic::new(
-                    span.pos(self.ctx),
-                    format!("{} cannot be used in an expression", name.describe_type()),
-                    ErrorCode::MismatchedKinds,
-                ))
-            }
+            ResolvedName::Library(_) | ResolvedName::Design(_) => Err(Diagnostic::new(
+                span.pos(self.ctx),
+                format!("{} cannot be used in an expression", name.describe_type()),
+                ErrorCode::MismatchedKinds,
+            )),
+            ResolvedName::Type(_) => Err(Diagnostic::type_used_as_value(&span.pos(self.ctx), name)),
             ResolvedName::Final(ent) => match ent.actual_kind() {
                 AnyEntKind::LoopParameter(typ) => Ok(typ.map(|typ| typ.into())),
                 AnyEntKind::PhysicalLiteral(typ) => Ok(Some(*typ)),
@@ -1039,6 +1049,29 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             AttributeDesignator::SimpleName
             | AttributeDesignator::InstanceName
             | AttributeDesignator::PathName => {
+                let is_valid_prefix = match prefix {
+                    ResolvedName::Expression(_) => false,
+                    ResolvedName::Library(_) => attr.attr.item == AttributeDesignator::SimpleName,
+                    // The path of local ports and generics of a component is not defined
+                    ResolvedName::ObjectName(ObjectName {
+                        base: ObjectBase::Object(obj),
+                        ..
+                    }) => {
+                        attr.attr.item == AttributeDesignator::SimpleName
+                            || !is_component_interface(obj)
+                    }
+                    _ => true,
+                };
+
+                if !is_valid_prefix {
+                    diagnostics.push(Diagnostic::cannot_be_prefix_of_attribute(
+                        &name_pos.pos(self.ctx),
+                        prefix,
+                        attr,
+                    ));
+                    return Err(EvalError::Unknown);
+                }
+
                 check_no_attr_argument(self.ctx, attr, diagnostics);
                 Ok(AttrResolveResult::Value(self.string().base()))
             }
@@ -1088,22 +1121,60 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         check_no_sattr_argument(self.ctx, sattr, expr, diagnostics);
                         Ok(AttrResolveResult::Value(typ.base()))
                     }
-                    SignalAttribute::Driving => {
+                    SignalAttribute::Driving | SignalAttribute::DrivingValue => {
                         check_no_sattr_argument(self.ctx, sattr, expr, diagnostics);
-                        Ok(AttrResolveResult::Value(self.boolean().base()))
-                    }
-                    SignalAttribute::DrivingValue => {
-                        check_no_sattr_argument(self.ctx, sattr, expr, diagnostics);
-                        Ok(AttrResolveResult::Value(typ.base()))
+                        if let Some(signal) = prefix.as_actual_entity() {
+                            self.record_driving_attribute(name_pos.pos(self.ctx), sattr, signal);
+                        }
+                        if sattr == SignalAttribute::Driving {
+                            Ok(AttrResolveResult::Value(self.boolean().base()))
+                        } else {
+                            Ok(AttrResolveResult::Value(typ.base()))
+                        }
                     }
                 }
             }
 
             AttributeDesignator::Ident(ref mut sym) => {
+                if let ResolvedName::Overloaded(ref des, ref overloaded) = prefix {
+                    if overloaded.as_unique().is_none() {
+                        diagnostics.add(
+                            name_pos.pos(self.ctx),
+                            format!(
+                                "Signature required for the prefix {} of a user defined attribute",
+                                des.item.describe()
+                            ),
+                            ErrorCode::SignatureRequired,
+                        );
+                        return Err(EvalError::Unknown);
+                    }
+                }
+
                 if let Some(actual) = prefix.as_actual_entity() {
                     if let Some(attr) = actual.get_attribute(&sym.item) {
                         sym.set_unique_reference(attr.into());
                         Ok(AttrResolveResult::Value(attr.typ().base()))
+                    } else if let Some(decl) = scope
+                        .lookup(
+                            self.ctx,
+                            attr.attr.token,
+                            &Designator::Identifier(sym.item.clone()),
+                        )
+                        .ok()
+                        .and_then(|ents| ents.into_non_overloaded().ok())
+                        .and_then(AttributeEnt::from_any)
+                    {
+                        sym.set_unique_reference(decl.into());
+                        diagnostics.add(
+                            attr.attr.pos(self.ctx),
+                            format!(
+                                "Attribute '{}' is not specified for {}",
+                                decl.name(),
+                                actual.describe()
+                            ),
+                            ErrorCode::Unresolved,
+                        );
+                        Err(EvalError::Unknown)
                     } else {
                         diagnostics.add(
                             attr.attr.pos(self.ctx),
@@ -1152,6 +1223,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     /// variable x: std_logic_vector(2 downto 0);
     /// x'subtype -> std_logic_vector(2 downto 0)
     /// x'element -> std_logic
+    /// f(x)'element -> element type of the return type of f
     /// ```
     fn resolve_type_attribute_suffix(
         &self,
@@ -1160,23 +1232,27 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         pos: TokenSpan,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<TypeEnt<'a>> {
-        // all type attribute suffixes require that the prefix be an object type
-        let Some(obj) = prefix.as_object_name() else {
-            diagnostics.add(
-                pos.pos(self.ctx),
-                format!(
-                    "The {} attribute can only be used on objects, not {}",
-                    suffix,
-                    prefix.describe()
-                ),
-                ErrorCode::IllegalAttribute,
-            );
-            return Err(EvalError::Unknown);
+        // all type attribute suffixes require that the prefix be an object or a function result
+        let type_mark = match prefix {
+            ResolvedName::ObjectName(obj) => obj.type_mark(),
+            ResolvedName::Expression(DisambiguatedType::Unambiguous(typ)) => *typ,
+            _ => {
+                diagnostics.add(
+                    pos.pos(self.ctx),
+                    format!(
+                        "The {} attribute can only be used on objects or function results, not {}",
+                        suffix,
+                        prefix.describe()
+                    ),
+                    ErrorCode::IllegalAttribute,
+                );
+                return Err(EvalError::Unknown);
+            }
         };
         match suffix {
-            TypeAttribute::Subtype => Ok(obj.type_mark()),
+            TypeAttribute::Subtype => Ok(type_mark),
             TypeAttribute::Element => {
-                if let Some((elem_type, _)) = obj.type_mark().array_type() {
+                if let Some((elem_type, _)) = type_mark.array_type() {
                     Ok(elem_type)
                 } else {
                     diagnostics.add(
@@ -1215,6 +1291,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             SplitName::Designator(designator) => {
                 let name = scope
                     .lookup(self.ctx, span, designator.designator())
+                    .map_err(|err| self.suggest_library_clause(designator.designator(), err))
                     .into_eval_result(diagnostics)?;
                 return Ok(match name {
                     NamedEntities::Single(ent) => {
@@ -1254,7 +1331,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         };
 
         // Any other suffix must collapse overloaded
-        if !matches!(suffix, Suffix::CallOrIndexed(_)) {
+        // except for attributes of the subprogram itself
+        if !matches!(suffix, Suffix::CallOrIndexed(_)) && !is_entity_attribute(&suffix) {
             if let ResolvedName::Overloaded(ref des, ref overloaded) = resolved {
                 let disambiguated = self
                     .disambiguate_no_actuals(
@@ -1307,6 +1385,42 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
 
         if let Suffix::Attribute(ref mut attr) = suffix {
+            if let ResolvedName::Overloaded(ref des, ref overloaded) = resolved {
+                if let Some(ref mut signature) = attr.signature {
+                    let key = self.resolve_signature(scope, signature, diagnostics)?;
+                    if let Some(ent) = overloaded.get(&SubprogramKey::Normal(key)) {
+                        resolved =
+                            ResolvedName::Overloaded(des.clone(), OverloadedName::single(ent));
+                    } else {
+                        bail!(
+                            diagnostics,
+                            Diagnostic::no_overloaded_with_signature(
+                                des.pos(self.ctx),
+                                &des.item,
+                                overloaded,
+                            )
+                        );
+                    }
+                }
+            } else if let Some(ref signature) = attr.signature {
+                bail!(
+                    diagnostics,
+                    Diagnostic::new(
+                        signature.pos(self.ctx),
+                        format!(
+                            "{} is not a subprogram or enum literal and may not have a signature",
+                            resolved.describe()
+                        ),
+                        ErrorCode::IllegalSignature,
+                    )
+                );
+            }
+
+            if let ResolvedName::Overloaded(_, ref overloaded) = resolved {
+                if let Some(ent) = overloaded.as_unique() {
+                    prefix.set_unique_reference(ent);
+                }
+            }
             let typ =
                 self.attribute_suffix(span, prefix.span, scope, &resolved, attr, diagnostics)?;
             return match typ {
@@ -1643,6 +1757,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     ) -> EvalResult<DisambiguatedType<'a>> {
         let resolved =
             self.name_resolve_with_suffixes(scope, span, name, None, false, diagnostics)?;
+        self.check_deferred_constant_value(scope, span, &resolved, diagnostics);
+        self.check_out_port_read(span, &resolved, diagnostics);
         match self.name_to_type(span, name.suffix_reference_mut(), resolved) {
             Ok(Some(typ)) => Ok(typ),
             Ok(None) => Err(EvalError::Unknown),
@@ -1653,6 +1769,85 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
     }
 
+    /// Before VHDL-2008, ports of mode out cannot be read
+    fn check_out_port_read(
+        &self,
+        span: TokenSpan,
+        resolved: &ResolvedName<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.standard >= VHDLStandard::VHDL2008 {
+            return;
+        }
+        let ResolvedName::ObjectName(object_name) = resolved else {
+            return;
+        };
+        if object_name.base.is_port()
+            && object_name.base.mode() == Some(&InterfaceMode::Simple(Mode::Out))
+        {
+            diagnostics.add(
+                span.pos(self.ctx),
+                format!(
+                    "{} cannot be read before VHDL-2008",
+                    object_name.base.describe_class()
+                ),
+                ErrorCode::ReadOutPort,
+            );
+        }
+    }
+
+    /// The value of a deferred constant is not known before its full declaration,
+    /// so it cannot be used in an expression directly within the package declaration
+    /// or within the package body before the full declaration.
+    /// Default expressions of interface declarations are nested regions and are thus allowed.
+    fn check_deferred_constant_value(
+        &self,
+        scope: &Scope<'a>,
+        span: TokenSpan,
+        resolved: &ResolvedName<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let ResolvedName::ObjectName(ObjectName {
+            base: ObjectBase::DeferredConstant(ent),
+            ..
+        }) = resolved
+        else {
+            return;
+        };
+
+        if !scope.is_package_region() {
+            return;
+        }
+
+        // Deferred constants of other packages have already been fully declared
+        let is_immediate = matches!(
+            scope.lookup_immediate(ent.designator()),
+            Some(NamedEntities::Single(immediate)) if immediate.id() == ent.id()
+        );
+        if !is_immediate {
+            return;
+        }
+
+        let mut diag = Diagnostic::new(
+            span.pos(self.ctx),
+            format!(
+                "Deferred constant '{}' cannot be used before its full declaration",
+                ent.designator()
+            ),
+            ErrorCode::DeferredConstantBeforeFullDeclaration,
+        );
+        if let Some(decl_pos) = ent.decl_pos() {
+            diag.add_related(
+                decl_pos,
+                format!(
+                    "Full declaration of '{}' must appear in the package body",
+                    ent.designator()
+                ),
+            );
+        }
+        diagnostics.push(diag);
+    }
+
     /// Analyze a name that is part of an expression that must be unambiguous
     pub fn expression_name_with_ttyp(
         &self,
@@ -1670,6 +1865,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             false,
             diagnostics,
         ))? {
+            self.check_deferred_constant_value(scope, span, &resolved, diagnostics);
+            self.check_out_port_read(span, &resolved, diagnostics);
             // @TODO target_type already used above, functions could probably be simplified
             match self.name_to_unambiguous_type(span, &resolved, ttyp, name.suffix_reference_mut())
             {
@@ -1845,6 +2042,8 @@ impl Declaration {
             Declaration::SubprogramBody(_) => "subprogram body",
             Declaration::Use(_) => "use",
             Declaration::Package(_) => "package instantiation",
+            Declaration::PackageDeclaration(_) => "package",
+            Declaration::PackageBody(_) => "package body",
             Declaration::Configuration(_) => "configuration",
             Declaration::View(_) => "view",
         }
@@ -1852,6 +2051,17 @@ impl Declaration {
 }
 
 impl Diagnostic {
+    fn type_used_as_value(pos: &SrcPos, resolved: &ResolvedName) -> Diagnostic {
+        Diagnostic::new(
+            pos,
+            format!(
+                "{} cannot be used in an expression",
+                resolved.describe_type()
+            ),
+            ErrorCode::TypeUsedAsValue,
+        )
+    }
+
     fn cannot_be_prefix(prefix_pos: &SrcPos, resolved: ResolvedName, suffix: Suffix) -> Diagnostic {
         let suffix_desc = match suffix {
             Suffix::Selected(_) => "selected",
@@ -1941,6 +2151,10 @@ impl Diagnostic {
         call_name: &WithToken<Designator>,
         candidates: impl IntoIterator<Item = OverloadedEnt<'a>>,
     ) -> Diagnostic {
+        let candidates: Vec<_> = candidates.into_iter().collect();
+        if let Some(diag) = Diagnostic::ambiguous_homographs(call_name.pos(ctx), &candidates) {
+            return diag;
+        }
         let mut diag = Diagnostic::new(
             call_name.pos(ctx),
             format!("Ambiguous call to {}", call_name.item.describe()),
@@ -1951,6 +2165,35 @@ impl Diagnostic {
     }
 }
 
+/// Attributes whose prefix denotes a named entity rather than a value,
+/// that is 'simple_name, 'instance_name, 'path_name and user defined attributes
+fn is_entity_attribute(suffix: &Suffix) -> bool {
+    matches!(
+        suffix,
+        Suffix::Attribute(AttributeSuffix {
+            attr: WithToken {
+                item: AttributeDesignator::SimpleName
+                    | AttributeDesignator::InstanceName
+                    | AttributeDesignator::PathName
+                    | AttributeDesignator::Ident(_),
+                ..
+            },
+            ..
+        })
+    )
+}
+
+/// A port or generic declared by a component declaration
+fn is_component_interface(obj: &ObjectEnt) -> bool {
+    matches!(
+        obj.object().iface,
+        Some(ObjectInterface::Port(_) | ObjectInterface::Generic)
+    ) && matches!(
+        obj.parent.map(|parent| parent.kind()),
+        Some(AnyEntKind::Component(_))
+    )
+}
+
 fn check_no_attr_argument(
     ctx: &dyn TokenAccess,
     suffix: &AttributeSuffix,
@@ -2132,7 +2375,7 @@ type my_type is array(natural range<>) of integer;
             diagnostics,
             vec![Diagnostic::new(
                 code.s1("my_type'subtype"),
-                "The subtype attribute can only be used on objects, not array type 'my_type'",
+                "The subtype attribute can only be used on objects or function results, not array type 'my_type'",
                 ErrorCode::IllegalAttribute,
             )],
         )
@@ -2156,7 +2399,7 @@ variable x: integer;
             diagnostics,
             vec![Diagnostic::new(
                 code.s1("x'subtype'subtype"),
-                "The subtype attribute can only be used on objects, not integer type 'INTEGER'",
+                "The subtype attribute can only be used on objects or function results, not integer type 'INTEGER'",
                 ErrorCode::IllegalAttribute,
             )],
         )
@@ -2676,6 +2919,31 @@ constant c0 : arr_t := (others => 0);
         );
     }
 
+    #[test]
+    fn length_attribute_of_function_result() {
+        let test = TestSetup::new();
+
+        test.declarative_part(
+            "
+function fun(arg : natural) return integer_vector;
+        ",
+        );
+
+        let code = test.snippet("fun(3)'length");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                test.ctx(&code.tokenize()).universal_integer().into()
+            )))
+        );
+
+        let code = test.snippet("fun(3)'element");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Type(test.lookup_type("integer")))
+        );
+    }
+
     #[test]
     fn ascending_descending() {
         let test = TestSetup::new();
@@ -2840,10 +3108,12 @@ constant c0 : arr_t := (others => 0);
             "
 impure function pop return integer is
 begin
+  return 0;
 end function;
 
 impure function pop return boolean is
 begin
+  return false;
 end function;
 
 type enum_t is (alpha, beta);
@@ -3068,6 +3338,52 @@ signal thesig : integer;
         );
     }
 
+    #[test]
+    fn name_attributes_of_subprograms() {
+        let test = TestSetup::new();
+        test.declarative_part(
+            "
+function fun return integer;
+procedure proc;
+        ",
+        );
+
+        for snippet in ["fun'simple_name", "proc'instance_name", "proc'path_name"] {
+            let code = test.snippet(snippet);
+            assert_eq!(
+                test.name_resolve(&code, None, &mut NoDiagnostics),
+                Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                    test.ctx(&code.tokenize()).string()
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn name_attributes_on_expression() {
+        let test = TestSetup::new();
+        test.declarative_part(
+            "
+function fun(arg : natural) return integer;
+        ",
+        );
+
+        let code = test.snippet("fun(0)'path_name");
+        let mut diagnostics = Vec::new();
+        assert_eq!(
+            test.name_resolve(&code, None, &mut diagnostics),
+            Err(EvalError::Unknown)
+        );
+        check_diagnostics(
+            diagnostics,
+            vec![Diagnostic::new(
+                code.s1("fun(0)'path_name"),
+                "Expression of integer type 'INTEGER' cannot be the the prefix of 'path_name attribute",
+                ErrorCode::CannotBePrefixed,
+            )],
+        )
+    }
+
     #[test]
     fn integer_type_conversion() {
         let test = TestSetup::new();
@@ -197,6 +197,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         self.check_association(error_pos, ent.formals(), scope, assocs, diagnostics)?;
+        self.record_resolved_call(error_pos, ent);
         Ok(())
     }
 
@@ -237,6 +238,30 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(result)
     }
 
+    /// Candidates that have a formal for every actual
+    /// but where formals without a default value are left unassociated
+    fn disambiguate_by_partial_assoc_formals(
+        &self,
+        scope: &Scope<'a>,
+        candidates: &[OverloadedEnt<'a>],
+        assocs: &mut [AssociationElement],
+    ) -> EvalResult<Vec<OverloadedEnt<'a>>> {
+        let mut result = Vec::new();
+        for ent in candidates.iter() {
+            if as_fatal(self.has_formal_for_every_actual(ent.formals(), scope, assocs))?
+                == Some(true)
+            {
+                result.push(*ent);
+            }
+
+            for elem in assocs.iter_mut() {
+                clear_references(elem, self.ctx);
+            }
+        }
+
+        Ok(result)
+    }
+
     fn actual_types(
         &self,
         scope: &Scope<'a>,
@@ -315,6 +340,16 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             self.check_call(scope, call_pos, ent, assocs, diagnostics)?;
             return Ok(Disambiguated::Unambiguous(ent));
         } else if ok_formals.is_empty() {
+            // A unique candidate that only misses associations is checked
+            // to report the formals that are not associated
+            if let [ent] = self
+                .disambiguate_by_partial_assoc_formals(scope, &ok_kind, assocs)?
+                .as_slice()
+            {
+                self.check_call(scope, call_pos, *ent, assocs, diagnostics)?;
+                return Ok(Disambiguated::Unambiguous(*ent));
+            }
+
             // No candidate matched actual/formal profile
             diagnostics.push(Diagnostic::ambiguous(self.ctx, call_name, ok_kind));
             return Err(EvalError::Unknown);
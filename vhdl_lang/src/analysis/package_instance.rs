@@ -300,6 +300,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         unsafe {
             inst.set_kind(kind);
         }
+        if let Some(value) = self.arena.static_value(uninst.id()) {
+            self.arena.set_static_value(inst.id(), value);
+        }
+        if let Some(range) = self.arena.static_range(uninst.id()) {
+            self.arena.set_static_range(inst.id(), range);
+        }
 
         for implicit_uninst in uninst.implicits.iter() {
             unsafe {
@@ -443,6 +449,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let Signature {
             formals,
             return_type,
+            pure,
         } = signature;
 
         let FormalRegion {
@@ -470,6 +477,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 entities: inst_entities,
             },
             return_type: return_type.map(|typ| self.map_type_ent(mapping, typ)),
+            pure: *pure,
         })
     }
 
@@ -554,7 +562,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             Type::File => Type::File,
             Type::Alias(typ) => Type::Alias(self.map_type_ent(mapping, *typ)),
             Type::Universal(utyp) => Type::Universal(*utyp),
-            Type::Incomplete => Type::Incomplete,
+            Type::Incomplete(pos) => Type::Incomplete(pos.clone()),
             Type::Interface => Type::Interface,
         })
     }
@@ -592,10 +600,22 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         mapping: &FnvHashMap<EntityId, TypeEnt<'a>>,
         subtype: Subtype<'a>,
     ) -> Subtype<'a> {
-        let Subtype { type_mark } = subtype;
+        let Subtype {
+            type_mark,
+            range,
+            range_constrained,
+            length,
+            index_range,
+            resolved,
+        } = subtype;
 
         Subtype {
             type_mark: self.map_type_ent(mapping, type_mark),
+            range,
+            range_constrained,
+            length,
+            index_range,
+            resolved,
         }
     }
 }
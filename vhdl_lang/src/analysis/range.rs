@@ -134,12 +134,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
             } else {
                 // This should never happen
+                let mut diagnostic = Diagnostic::internal(
+                    attr.name.pos(self.ctx),
+                    format!("{} has no indexes", typ.describe()),
+                );
                 if let Some(decl_pos) = typ.decl_pos() {
-                    // To debug if it ever happens
-                    eprintln!("{}", decl_pos.show("Array with no indexes"));
-                    eprintln!("{}", attr.name.pos(self.ctx).show("Used here"));
-                    panic!("Internal error")
+                    diagnostic.add_related(decl_pos, "Array declared here");
                 }
+                diagnostics.push(diagnostic);
                 Err(EvalError::Unknown)
             }
         } else {
@@ -183,7 +185,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                     l.base().describe(),
                                     r.base().describe()
                                 ),
-                                ErrorCode::TypeMismatch,
+                                ErrorCode::RangeBoundTypeMismatch,
                             );
                             return Err(EvalError::Unknown);
                         }
@@ -231,7 +233,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     diagnostics.add(
                         constraint.span().pos(self.ctx),
                         "Range type of left and right side does not match",
-                        ErrorCode::TypeMismatch,
+                        ErrorCode::RangeBoundTypeMismatch,
                     );
                     Err(EvalError::Unknown)
                 } else {
@@ -540,7 +542,7 @@ function f1 return integer;
             vec![Diagnostic::new(
                 code.s1("0 to false"),
                 "Range type mismatch, left is type universal_integer, right is type 'BOOLEAN'",
-                ErrorCode::TypeMismatch,
+                ErrorCode::RangeBoundTypeMismatch,
             )],
         );
     }
@@ -568,7 +570,7 @@ function f1 return integer;
             vec![Diagnostic::new(
                 code.s1("f1 to false"),
                 "Range type of left and right side does not match",
-                ErrorCode::TypeMismatch,
+                ErrorCode::RangeBoundTypeMismatch,
             )],
         );
     }
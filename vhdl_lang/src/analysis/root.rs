@@ -5,6 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::analyze::*;
+use super::association::AssociatedFormal;
 use super::lock::*;
 use super::standard::StandardTypes;
 use super::standard::UniversalTypes;
@@ -14,6 +15,7 @@ use crate::ast::search::*;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::{Symbols, Token, TokenAccess};
 use crate::{HasTokenSpan, TokenSpan};
 use fnv::{FnvHashMap, FnvHashSet};
@@ -21,6 +23,7 @@ use parking_lot::RwLock;
 use std::collections::hash_map::Entry;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::Arc;
 
 /// A design unit with design unit data
@@ -28,6 +31,15 @@ pub(crate) struct AnalysisData {
     pub diagnostics: Vec<Diagnostic>,
     pub has_circular_dependency: bool,
     pub arena: FinalArena,
+    /// The type of expressions that were checked against a target type
+    pub expression_types: FnvHashMap<TokenSpan, EntityId>,
+    /// The formal of each positional actual of a resolved association
+    pub positional_formals: FnvHashMap<TokenSpan, EntityId>,
+    /// The subprogram of each unambiguously resolved call,
+    /// calls that are analyzed several times occur more than once
+    pub resolved_calls: Vec<(SrcPos, EntityId)>,
+    /// The formal associated with each actual
+    pub associated_formals: FnvHashMap<TokenSpan, AssociatedFormal>,
 }
 
 pub(super) type UnitReadGuard<'a> = ReadGuard<'a, AnyDesignUnit, AnalysisData>;
@@ -238,12 +250,16 @@ impl Library {
     }
 
     /// Iterate over units in the order they appear in the file.
+    /// Files are visited in the order of their file names.
     /// Ensures diagnostics do not have to be sorted later.
     fn sorted_unit_ids(&self) -> Vec<UnitId> {
         // @TODO insert sort when adding instead
         let mut result = Vec::new();
 
-        for unit_ids in self.units_by_source.values() {
+        let mut sources: Vec<_> = self.units_by_source.iter().collect();
+        sources.sort_by_key(|(source, _)| *source);
+
+        for (_, unit_ids) in sources {
             let mut unit_ids: Vec<UnitId> = unit_ids.clone().into_iter().collect();
             unit_ids.sort_by_key(|unit_id| self.units.get(unit_id.key()).unwrap().pos().start());
             result.append(&mut unit_ids);
@@ -291,6 +307,12 @@ impl Library {
 /// dependencies between design units.
 pub struct DesignRoot {
     pub(super) symbols: Arc<Symbols>,
+    // The VHDL standard that the design is analyzed against
+    pub(super) standard: VHDLStandard,
+    // Report violations of the standard that vendor tools accept as warnings
+    pub(super) lenient: bool,
+    // Libraries configured as third party, such as vendor libraries
+    third_party: FnvHashSet<Symbol>,
     pub(super) standard_pkg_id: Option<EntityId>,
     pub(super) standard_arena: Option<FinalArena>,
     pub(super) universal: Option<UniversalTypes>,
@@ -312,11 +334,24 @@ pub struct DesignRoot {
     // Tracks which units have a "use library.all;" clause.
     // library name  =>  set(affected)
     users_of_library_all: RwLock<FnvHashMap<Symbol, FnvHashSet<UnitId>>>,
+
+    // Called before each unit is analyzed by analyze_streaming, allows tests to block
+    // the analysis of a unit
+    #[cfg(test)]
+    #[allow(clippy::type_complexity)]
+    pub(crate) before_streamed_analysis: Option<Box<dyn Fn(&UnitId) + Send + Sync>>,
 }
 
 impl DesignRoot {
     pub fn new(symbols: Arc<Symbols>) -> DesignRoot {
+        DesignRoot::with_standard(symbols, VHDLStandard::default())
+    }
+
+    pub fn with_standard(symbols: Arc<Symbols>, standard: VHDLStandard) -> DesignRoot {
         DesignRoot {
+            standard,
+            lenient: false,
+            third_party: FnvHashSet::default(),
             universal: None,
             standard_pkg_id: None,
             standard_arena: None,
@@ -328,9 +363,38 @@ impl DesignRoot {
             users_of: RwLock::new(FnvHashMap::default()),
             missing_unit: RwLock::new(FnvHashMap::default()),
             users_of_library_all: RwLock::new(FnvHashMap::default()),
+            #[cfg(test)]
+            before_streamed_analysis: None,
         }
     }
 
+    /// Report violations of the standard that vendor tools commonly accept, such as reading
+    /// ports of mode out before VHDL-2008, as [ErrorCode::NonStandard] warnings,
+    /// see [ErrorCode::is_non_standard].
+    /// Only design units that are analyzed afterwards are affected.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Do not report impurity of pure functions and unreachable statements within a library,
+    /// which vendor libraries commonly contain. Used for libraries configured with
+    /// `is_third_party`. Only design units that are analyzed afterwards are affected.
+    pub fn set_third_party(&mut self, library_name: Symbol) {
+        self.third_party.insert(library_name);
+    }
+
+    pub fn is_third_party(&self, library_name: &Symbol) -> bool {
+        self.third_party.contains(library_name)
+    }
+
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
     /// Create library if it does not exist or return existing
     fn get_or_create_library(&mut self, name: Symbol) -> &mut Library {
         match self.libraries.entry(name) {
@@ -365,6 +429,12 @@ impl DesignRoot {
         self.libraries.values()
     }
 
+    fn sorted_libraries(&self) -> Vec<&Library> {
+        let mut libraries: Vec<_> = self.libraries.values().collect();
+        libraries.sort_by_key(|library| library.name().name_utf8());
+        libraries
+    }
+
     pub fn get_lib(&self, sym: &Symbol) -> Option<&Library> {
         self.libraries.get(sym)
     }
@@ -389,6 +459,20 @@ impl DesignRoot {
         None
     }
 
+    /// A sorted textual dump of the declarations of an analyzed primary unit,
+    /// see [`Region::dump`](crate::named_entity::Region::dump)
+    pub fn dump_primary_unit(&self, library_name: &Symbol, ident: &Symbol) -> Option<String> {
+        let units = self.get_library_units(library_name)?;
+        let unit = units.get(&UnitKey::Primary(ident.clone()))?;
+        let data = self.get_analysis(unit);
+
+        if let AnyDesignUnit::Primary(primary) = data.deref() {
+            let design = DesignEnt::from_any(self.arenas.get(primary.ent_id()?))?;
+            return design.kind().region().map(|region| region.dump());
+        }
+        None
+    }
+
     /// Get a named entity corresponding to the library
     pub(super) fn get_library_arena(
         &self,
@@ -513,6 +597,20 @@ impl DesignRoot {
         }
     }
 
+    /// The name of `ent` qualified by the names of the enclosing declarative regions
+    /// up to and including the library, such as `lib.pkg.fun[natural return natural]`.
+    /// Subprograms are followed by their signature to distinguish overloads.
+    pub fn full_name(&self, ent: EntRef) -> String {
+        let mut name = ent.designator().to_string();
+        if let Some(signature) = ent.signature() {
+            name.push_str(&signature.describe());
+        }
+        match ent.parent {
+            Some(parent) => format!("{}.{name}", self.full_name(parent)),
+            None => name,
+        }
+    }
+
     /// Search for all references to the declaration at decl_pos
     pub fn find_all_references(&self, ent: EntRef) -> Vec<SrcPos> {
         let mut searcher = FindAllReferences::new(self, ent);
@@ -520,6 +618,141 @@ impl DesignRoot {
         searcher.references
     }
 
+    /// Find all concurrent statements that drive the signal `ent`.
+    ///
+    /// Each driver is reported at the label of the process or concurrent signal assignment
+    /// when it has one, or at the first token of the statement otherwise.
+    pub fn drivers_of(&self, ent: EntRef) -> Vec<SrcPos> {
+        let mut searcher = FindAllDrivers::default();
+        let _ = self.search(&mut searcher);
+        searcher
+            .drivers
+            .remove(&ent.id())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|driver| driver.pos)
+            .collect()
+    }
+
+    /// Find the signals that are implicitly part of the sensitivity list of
+    /// each `process(all)` within the source.
+    ///
+    /// Each process is reported at its label when it has one,
+    /// or at its first token otherwise.
+    pub fn implied_sensitivity(&self, source: &Source) -> Vec<(SrcPos, Vec<EntRef<'_>>)> {
+        let mut searcher = FindImpliedSensitivity::default();
+        let _ = self.search_source(source, &mut searcher);
+        searcher
+            .processes
+            .into_iter()
+            .map(|(pos, ids)| {
+                let signals = ids
+                    .into_iter()
+                    .map(|id| self.get_ent(id))
+                    .filter(|ent| {
+                        matches!(ent.kind(), AnyEntKind::Object(obj) if obj.class == ObjectClass::Signal)
+                    })
+                    .collect();
+                (pos, signals)
+            })
+            .collect()
+    }
+
+    /// Find the type of the innermost expression at the cursor position.
+    ///
+    /// Only expressions that were checked against a known target type are recorded,
+    /// `None` is returned when the type could not be determined.
+    pub fn expression_type(&self, source: &Source, cursor: Position) -> Option<TypeEnt<'_>> {
+        let mut innermost: Option<(SrcPos, EntityId)> = None;
+        for unit in self.units_by_source(source) {
+            let data = unit.unit.expect_analyzed();
+            for (span, id) in data.result().expression_types.iter() {
+                let pos = span.pos(&unit.tokens);
+                if !pos.contains(cursor) {
+                    continue;
+                }
+                if innermost
+                    .as_ref()
+                    .is_none_or(|(prev, _)| prev.start() <= pos.start() && pos.end() <= prev.end())
+                {
+                    innermost = Some((pos, *id));
+                }
+            }
+        }
+        innermost.and_then(|(_, id)| TypeEnt::from_any(self.get_ent(id)))
+    }
+
+    /// Hints for the source derived from resolved associations and calls, sorted by position.
+    ///
+    /// Positional actuals are hinted with the formal they are associated with and
+    /// function calls with the return type of the called function.
+    /// Calls that could not be resolved unambiguously have no hints.
+    pub fn inlay_hints(&self, source: &Source) -> Vec<InlayHint<'_>> {
+        let mut hints = Vec::new();
+        for unit in self.units_by_source(source) {
+            let data = unit.unit.expect_analyzed();
+            for (span, id) in data.result().positional_formals.iter() {
+                hints.push(InlayHint {
+                    pos: span.pos(&unit.tokens).pos_at_beginning(),
+                    kind: InlayHintKind::Formal(self.get_ent(*id)),
+                });
+            }
+            for (pos, id) in data.result().resolved_calls.iter() {
+                let Some(subpgm) = OverloadedEnt::from_any(self.get_ent(*id)) else {
+                    continue;
+                };
+                if let Some(return_type) = subpgm.return_type() {
+                    hints.push(InlayHint {
+                        pos: pos.pos_at_end(),
+                        kind: InlayHintKind::ReturnType(return_type.into()),
+                    });
+                }
+            }
+        }
+        hints.sort_by_key(|hint| {
+            (
+                hint.pos.start(),
+                matches!(hint.kind, InlayHintKind::ReturnType(_)),
+            )
+        });
+        hints.dedup();
+        hints
+    }
+
+    /// Classify every name within the source by the named entity it denotes, sorted by position.
+    ///
+    /// Both declarations and references are included, names that could not be resolved
+    /// and operator symbols are not.
+    pub fn semantic_tokens(&self, source: &Source) -> Vec<SemanticToken<'_>> {
+        let mut searcher = FindNamedEntities::new(self);
+        let _ = self.search_source(source, &mut searcher);
+
+        let mut tokens: Vec<SemanticToken<'_>> = searcher
+            .names
+            .into_iter()
+            .filter(|(pos, ..)| pos.source() == source)
+            .filter_map(|(pos, id, is_declaration)| {
+                let ent = self.get_ent(id);
+                if matches!(ent.designator(), Designator::OperatorSymbol(_)) {
+                    return None;
+                }
+                let (kind, is_readonly) = SemanticTokenKind::classify(ent);
+                Some(SemanticToken {
+                    pos,
+                    kind,
+                    is_declaration,
+                    is_readonly,
+                    ent,
+                })
+            })
+            .collect();
+
+        // A declaration may also be searched as a reference to itself
+        tokens.sort_by_key(|token| (token.pos.start(), !token.is_declaration));
+        tokens.dedup_by(|next, prev| next.pos == prev.pos);
+        tokens
+    }
+
     pub fn public_symbols<'a>(&'a self) -> Box<dyn Iterator<Item = EntRef<'a>> + 'a> {
         Box::new(self.libraries.values().flat_map(|library| {
             std::iter::once(self.arenas.get(library.id)).chain(library.units.values().flat_map(
@@ -686,7 +919,7 @@ impl DesignRoot {
         NotFound
     }
 
-    fn units_by_source<'a>(
+    pub(crate) fn units_by_source<'a>(
         &'a self,
         source: &'a Source,
     ) -> impl Iterator<Item = &'a LockedUnit> + 'a {
@@ -733,47 +966,84 @@ impl DesignRoot {
         &self,
         arena_id: ArenaId,
         unit_id: &UnitId,
-        source: Source,
+        pos: &SrcPos,
         unit: &mut UnitWriteGuard,
         ctx: &dyn TokenAccess,
     ) {
         // All units reference the standard arena
         // @TODO keep the same ArenaId when re-using unit
         let arena = Arena::new(arena_id);
-        let context = AnalyzeContext::new(self, unit_id, source, &arena, ctx);
+        let context = AnalyzeContext::new(self, unit_id, pos.source().clone(), &arena, ctx);
 
         let mut diagnostics = Vec::new();
         let mut has_circular_dependency = false;
 
-        let result = match unit.deref_mut() {
-            AnyDesignUnit::Primary(unit) => {
-                if let Err(err) = context.analyze_primary_unit(unit, &mut diagnostics) {
-                    has_circular_dependency = true;
-                    err.push_into(&mut diagnostics);
-                };
-
-                AnalysisData {
-                    arena: arena.finalize(),
-                    diagnostics,
-                    has_circular_dependency,
+        // A broken invariant of the analysis must not take down the whole program,
+        // such as a language server that analyzes incomplete code while it is typed.
+        // The panic is turned into a diagnostic on the unit instead.
+        let has_std_library = self.libraries.contains_key(&self.symbol_utf8("std"));
+        let analyzed = if has_std_library && self.standard_pkg_id.is_none() {
+            // Every expression depends on the types declared by the standard package,
+            // such as when a library named std replaces the one that is installed
+            diagnostics.push(Diagnostic::new(
+                pos,
+                format!(
+                    "{} cannot be analyzed without the standard package 'std.standard'",
+                    capitalize(&unit_id.describe())
+                ),
+                ErrorCode::Unresolved,
+            ));
+            Ok(Ok(()))
+        } else {
+            panic::catch_unwind(AssertUnwindSafe(|| match unit.deref_mut() {
+                AnyDesignUnit::Primary(unit) => {
+                    context.analyze_primary_unit(unit, &mut diagnostics)
+                }
+                AnyDesignUnit::Secondary(unit) => {
+                    context.analyze_secondary_unit(unit, &mut diagnostics)
                 }
+            }))
+        };
+        match analyzed {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                has_circular_dependency = true;
+                err.push_into(&mut diagnostics);
             }
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("unknown error");
+                diagnostics.push(Diagnostic::internal(
+                    pos,
+                    format!(
+                        "Analysis of {} was aborted by an internal error: {message}",
+                        unit_id.describe()
+                    ),
+                ));
+            }
+        }
+        context.remove_circular_type_uses(&mut diagnostics);
 
-            AnyDesignUnit::Secondary(unit) => {
-                let mut diagnostics = Vec::new();
-
-                if let Err(err) = context.analyze_secondary_unit(unit, &mut diagnostics) {
-                    has_circular_dependency = true;
-                    err.push_into(&mut diagnostics);
-                };
+        let mut result = AnalysisData {
+            expression_types: context.take_expression_types(),
+            positional_formals: context.take_positional_formals(),
+            resolved_calls: context.take_resolved_calls(),
+            associated_formals: context.take_associated_formals(),
+            arena: arena.finalize(),
+            diagnostics,
+            has_circular_dependency,
+        };
 
-                AnalysisData {
-                    arena: arena.finalize(),
-                    diagnostics,
-                    has_circular_dependency,
+        if self.lenient {
+            for diagnostic in result.diagnostics.iter_mut() {
+                if diagnostic.code.is_non_standard() {
+                    diagnostic.code = ErrorCode::NonStandard;
                 }
             }
-        };
+        }
 
         unit.finish(result);
     }
@@ -784,7 +1054,7 @@ impl DesignRoot {
                 self.analyze_unit(
                     locked_unit.arena_id,
                     locked_unit.unit_id(),
-                    locked_unit.pos().source().clone(),
+                    locked_unit.pos(),
                     &mut unit,
                     &locked_unit.tokens,
                 );
@@ -886,6 +1156,23 @@ impl DesignRoot {
         }
     }
 
+    /// Returns the design units that need to be re-analyzed when the given sources change.
+    /// These are the units defined in the sources and all units which transitively depend on them.
+    /// All other units keep their cached analysis result.
+    ///
+    /// This assumes that the changed sources still define the same design units.
+    pub fn affected_by_sources(&self, sources: &[Source]) -> FnvHashSet<UnitId> {
+        let mut affected = FnvHashSet::default();
+        for library in self.libraries.values() {
+            for source in sources {
+                if let Some(unit_ids) = library.units_by_source.get(source) {
+                    affected.extend(unit_ids.iter().cloned());
+                }
+            }
+        }
+        get_all_affected(&self.users_of.read(), affected)
+    }
+
     /// Resets the analysis state of all design units which need to be re-analyzed
     /// because another design unit has been added or removed.
     fn reset(&mut self) {
@@ -990,13 +1277,14 @@ impl DesignRoot {
         self.standard_pkg_id = None;
         self.standard_arena = None;
 
-        let std_package = if let Some(AnyPrimaryUnit::Package(pkg)) = unit.as_primary_mut() {
-            assert!(pkg.context_clause.is_empty());
-            assert!(pkg.generic_clause.is_none());
-            pkg
-        } else {
-            panic!("Expected standard package is primary unit");
+        // Units that cannot be the standard package are analyzed like any other unit,
+        // which reports that the standard package is missing
+        let Some(AnyPrimaryUnit::Package(std_package)) = unit.as_primary_mut() else {
+            return;
         };
+        if !std_package.context_clause.is_empty() || std_package.generic_clause.is_some() {
+            return;
+        }
 
         let standard_pkg = {
             let (lib_arena, id) = self.get_library_arena(&std_lib_name).unwrap();
@@ -1088,6 +1376,10 @@ impl DesignRoot {
             standard_pkg.set_kind(kind);
         }
 
+        let expression_types = context.take_expression_types();
+        let positional_formals = context.take_positional_formals();
+        let resolved_calls = context.take_resolved_calls();
+        let associated_formals = context.take_associated_formals();
         self.standard_pkg_id = Some(standard_pkg.id());
         let arena = arena.finalize();
         self.standard_arena = Some(arena.clone());
@@ -1096,6 +1388,10 @@ impl DesignRoot {
             arena,
             diagnostics,
             has_circular_dependency: false,
+            expression_types,
+            positional_formals,
+            resolved_calls,
+            associated_formals,
         };
 
         unit.finish(result);
@@ -1133,6 +1429,21 @@ impl DesignRoot {
 
     // Returns the units that where re-analyzed
     pub fn analyze(&mut self, diagnostics: &mut dyn DiagnosticHandler) -> Vec<UnitId> {
+        self.analyze_units(diagnostics, false)
+    }
+
+    /// Analyze like [DesignRoot::analyze] but emit the diagnostics of each unit as soon as
+    /// the unit is analyzed, instead of after all units are analyzed.
+    /// The order of the diagnostics of different units is not deterministic.
+    pub fn analyze_streaming(&mut self, diagnostics: &mut dyn DiagnosticHandler) -> Vec<UnitId> {
+        self.analyze_units(diagnostics, true)
+    }
+
+    fn analyze_units(
+        &mut self,
+        diagnostics: &mut dyn DiagnosticHandler,
+        streaming: bool,
+    ) -> Vec<UnitId> {
         self.reset();
 
         let mut units = Vec::default();
@@ -1164,9 +1475,37 @@ impl DesignRoot {
 
         use rayon::prelude::*;
 
-        units.par_iter().for_each(|id| {
-            self.get_analysis(self.get_unit(id).unwrap());
-        });
+        // Units are analyzed in parallel. A unit that depends on another unit which is
+        // not yet analyzed will analyze it on demand, or wait for the thread that currently
+        // analyzes it, through the analysis lock of that unit.
+        // Thus, units only wait for the units that they actually depend on.
+        if streaming {
+            let root = &*self;
+            let units = &units;
+            let (sender, receiver) = std::sync::mpsc::channel();
+            std::thread::scope(|scope| {
+                scope.spawn(move || {
+                    units.par_iter().for_each_with(sender, |sender, id| {
+                        #[cfg(test)]
+                        if let Some(ref before_analysis) = root.before_streamed_analysis {
+                            before_analysis(id);
+                        }
+                        root.get_analysis(root.get_unit(id).unwrap());
+                        // Only fails when the receiving thread panicked
+                        let _ = sender.send(id);
+                    });
+                });
+
+                for id in receiver {
+                    let unit = root.get_unit(id).unwrap();
+                    diagnostics.append(unit.unit.expect_analyzed().result().diagnostics.clone());
+                }
+            });
+        } else {
+            units.par_iter().for_each(|id| {
+                self.get_analysis(self.get_unit(id).unwrap());
+            });
+        }
 
         for library in self.libraries.values() {
             self.arenas.link(&library.arena);
@@ -1177,9 +1516,18 @@ impl DesignRoot {
             }
         }
 
-        // Emit diagnostics sorted within a file
-        for library in self.libraries.values() {
+        // Emit diagnostics sorted within a file and independent of the order in which
+        // units were analyzed
+        let streamed: FnvHashSet<&UnitId> = if streaming {
+            units.iter().collect()
+        } else {
+            FnvHashSet::default()
+        };
+        for library in self.sorted_libraries() {
             for unit_id in library.sorted_unit_ids() {
+                if streamed.contains(&unit_id) {
+                    continue;
+                }
                 let unit = library.units.get(unit_id.key()).unwrap();
                 diagnostics.append(unit.unit.expect_analyzed().result().diagnostics.clone());
             }
@@ -1193,6 +1541,17 @@ impl DesignRoot {
         self.arenas.get(id)
     }
 
+    /// The value of a constant, the default value of a generic or the value of a physical unit
+    /// as a multiple of its primary unit, when it is known during analysis
+    pub fn static_value(&self, id: EntityId) -> Option<StaticValue> {
+        self.arenas.static_value(id)
+    }
+
+    /// The range of an integer type declaration when its bounds are known during analysis
+    pub fn static_range(&self, id: EntityId) -> Option<StaticRange> {
+        self.arenas.static_range(id)
+    }
+
     /// Returns a reference to the symbols that were used to analyze and parse the design root.
     pub fn symbols(&self) -> &Symbols {
         self.symbols.as_ref()
@@ -1235,6 +1594,108 @@ fn get_all_affected(
     all_affected
 }
 
+/// A hint displayed inline with the source code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlayHint<'a> {
+    /// The position the hint is displayed at
+    pub pos: SrcPos,
+    pub kind: InlayHintKind<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InlayHintKind<'a> {
+    /// The formal a positional actual is associated with, displayed before the actual
+    Formal(EntRef<'a>),
+    /// The return type of a function call, displayed after the call
+    ReturnType(EntRef<'a>),
+}
+
+/// A name within the source classified by the named entity it denotes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticToken<'a> {
+    pub pos: SrcPos,
+    pub kind: SemanticTokenKind,
+    /// The name is the declaration of the named entity and not a reference to it
+    pub is_declaration: bool,
+    /// The named entity cannot be assigned, such as a constant or a port of mode in
+    pub is_readonly: bool,
+    pub ent: EntRef<'a>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SemanticTokenKind {
+    Library,
+    /// An entity, architecture, package, configuration or context
+    DesignUnit,
+    Component,
+    Type,
+    Signal,
+    Variable,
+    Constant,
+    File,
+    Port,
+    Generic,
+    Parameter,
+    RecordElement,
+    Function,
+    Procedure,
+    EnumLiteral,
+    PhysicalUnit,
+    Attribute,
+    View,
+    Label,
+}
+
+impl SemanticTokenKind {
+    /// The kind of a named entity and whether it is read-only.
+    /// Aliases are classified as the object or subprogram they denote
+    fn classify(ent: EntRef<'_>) -> (SemanticTokenKind, bool) {
+        use SemanticTokenKind::*;
+
+        match ent.kind() {
+            AnyEntKind::Library => (Library, false),
+            AnyEntKind::Design(_) => (DesignUnit, false),
+            AnyEntKind::Component(_) => (Component, false),
+            AnyEntKind::Type(_) => (Type, false),
+            AnyEntKind::Object(object) => match object.iface {
+                Some(ObjectInterface::Generic) => (Generic, true),
+                Some(ObjectInterface::Port(ref mode)) => {
+                    (Port, matches!(mode, InterfaceMode::Simple(Mode::In)))
+                }
+                Some(ObjectInterface::Parameter(ref mode)) => {
+                    (Parameter, matches!(mode, InterfaceMode::Simple(Mode::In)))
+                }
+                None => match object.class {
+                    ObjectClass::Signal => (Signal, false),
+                    ObjectClass::Constant => (Constant, true),
+                    ObjectClass::Variable | ObjectClass::SharedVariable => (Variable, false),
+                },
+            },
+            AnyEntKind::ObjectAlias { base_object, .. } => {
+                SemanticTokenKind::classify(base_object.ent)
+            }
+            AnyEntKind::ExternalAlias { class, .. } => match class {
+                ExternalObjectClass::Constant => (Constant, true),
+                ExternalObjectClass::Signal => (Signal, false),
+                ExternalObjectClass::Variable => (Variable, false),
+            },
+            AnyEntKind::DeferredConstant(_) | AnyEntKind::LoopParameter(_) => (Constant, true),
+            AnyEntKind::File(_) | AnyEntKind::InterfaceFile(_) => (File, false),
+            AnyEntKind::ElementDeclaration(_) => (RecordElement, false),
+            AnyEntKind::Overloaded(overloaded) => match overloaded {
+                Overloaded::Alias(aliased) => SemanticTokenKind::classify((*aliased).into()),
+                Overloaded::EnumLiteral(_) => (EnumLiteral, true),
+                _ if overloaded.signature().return_type().is_some() => (Function, false),
+                _ => (Procedure, false),
+            },
+            AnyEntKind::PhysicalLiteral(_) => (PhysicalUnit, true),
+            AnyEntKind::Attribute(_) => (Attribute, false),
+            AnyEntKind::View(_) => (View, false),
+            AnyEntKind::Concurrent(_) | AnyEntKind::Sequential(_) => (Label, false),
+        }
+    }
+}
+
 pub struct EntHierarchy<'a> {
     pub ent: EntRef<'a>,
     pub children: Vec<EntHierarchy<'a>>,
@@ -1466,6 +1927,56 @@ end configuration;
         assert_eq!(library.duplicates.len(), 1);
     }
 
+    #[test]
+    fn diagnostics_order_does_not_depend_on_add_order() {
+        let mut builder = LibraryBuilder::new();
+        let code1 = builder.code(
+            "lib1",
+            "
+package pkg1 is
+  constant c0 : natural := missing0;
+  constant c1 : natural := missing1;
+end package;
+",
+        );
+        let code2 = builder.code(
+            "lib2",
+            "
+package pkg2 is
+  constant c0 : natural := missing2;
+end package;
+",
+        );
+        let code3 = builder.code(
+            "lib2",
+            "
+package pkg3 is
+  constant c0 : natural := missing3;
+end package;
+",
+        );
+
+        let analyze = |files: &[(&str, &Code)]| {
+            let mut root = DesignRoot::new(builder.symbols());
+            crate::analysis::tests::add_standard_library(builder.symbols(), &mut root);
+            for (library_name, code) in files {
+                root.add_design_file(code.symbol(library_name), code.design_file());
+            }
+            let mut diagnostics = Vec::new();
+            root.analyze(&mut diagnostics);
+            diagnostics
+        };
+
+        let diagnostics = analyze(&[("lib1", &code1), ("lib2", &code2), ("lib2", &code3)]);
+        assert_eq!(diagnostics.len(), 4);
+        for _ in 0..4 {
+            assert_eq!(
+                analyze(&[("lib2", &code3), ("lib2", &code2), ("lib1", &code1)]),
+                diagnostics
+            );
+        }
+    }
+
     #[test]
     pub fn rejects_illegal_raw_id() {
         let mut builder = LibraryBuilder::new();
@@ -1478,4 +1989,63 @@ end configuration;
         assert_eq!(root.entity_id_from_raw(ent.id.to_raw()), Some(ent.id));
         assert_eq!(root.entity_id_from_raw(0xFFFF << 32), None);
     }
+
+    #[test]
+    fn dump_primary_unit_lists_sorted_declarations() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+package pkg is
+  type state_t is (idle, busy);
+  subtype byte_t is natural range 0 to 255;
+  type rec_t is record
+    field : byte_t;
+  end record;
+  constant width : natural := 8;
+  constant deferred : state_t;
+  signal state : state_t;
+  function next_state(s : state_t; n : natural) return state_t;
+  procedure reset(signal s : out state_t);
+  alias current is state;
+end package;
+
+package body pkg is
+  constant deferred : state_t := idle;
+
+  function next_state(s : state_t; n : natural) return state_t is
+  begin
+    return s;
+  end function;
+
+  procedure reset(signal s : out state_t) is
+  begin
+    s <= idle;
+  end procedure;
+end package body;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        assert_eq!(
+            root.dump_primary_unit(&root.symbol_utf8("libname"), &root.symbol_utf8("pkg"))
+                .unwrap(),
+            "\
+constant width : NATURAL
+deferred constant deferred : state_t
+enum literal busy [return state_t]
+enum literal idle [return state_t]
+function next_state [state_t, NATURAL return state_t]
+object alias current : state_t
+procedure reset [state_t]
+record type rec_t
+signal state : state_t
+subtype byte_t : NATURAL
+type state_t"
+        );
+        assert_eq!(
+            root.dump_primary_unit(&root.symbol_utf8("libname"), &root.symbol_utf8("missing")),
+            None
+        );
+    }
 }
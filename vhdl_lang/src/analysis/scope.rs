@@ -10,11 +10,28 @@ use crate::named_entity::*;
 
 use crate::data::error_codes::ErrorCode;
 use crate::{TokenAccess, TokenSpan};
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use std::cell::RefCell;
-use std::collections::hash_map::Entry;
 use std::rc::Rc;
 
+/// The maximum number of replacements that are suggested for an unresolved identifier
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, chr_a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, chr_b) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(chr_a != *chr_b);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
 #[derive(Default, Clone)]
 pub(crate) struct Scope<'a>(Rc<RefCell<ScopeInner<'a>>>);
 
@@ -131,6 +148,44 @@ impl<'a> ScopeInner<'a> {
         visible.into_unambiguous(ctx, span, designator)
     }
 
+    /// Add the designators that are declared in or visible from the region
+    fn designators_into(&self, designators: &mut FnvHashSet<Designator>) {
+        designators.extend(self.region.entities.keys().cloned());
+        designators.extend(self.region.visibility.designators().cloned());
+        if let Some(ref parent) = self.parent {
+            parent.0.borrow().designators_into(designators);
+        }
+    }
+
+    /// The visible identifiers that are closest to a misspelled identifier
+    fn similar_identifiers(&self, ident: &Symbol) -> Vec<Symbol> {
+        let name = ident.name_utf8().to_lowercase();
+        // Short identifiers are similar to too many unrelated identifiers
+        let max_distance = name.chars().count() / 3;
+        if max_distance == 0 {
+            return Vec::new();
+        }
+
+        let mut designators = FnvHashSet::default();
+        self.designators_into(&mut designators);
+        let mut similar: Vec<_> = designators
+            .into_iter()
+            .filter_map(|designator| match designator {
+                Designator::Identifier(symbol) => {
+                    let distance = edit_distance(&name, &symbol.name_utf8().to_lowercase());
+                    (distance <= max_distance).then_some((distance, symbol))
+                }
+                _ => None,
+            })
+            .collect();
+        similar.sort_by_key(|(distance, symbol)| (*distance, symbol.name_utf8()));
+        similar
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, symbol)| symbol)
+            .collect()
+    }
+
     /// Lookup a designator from within the region itself
     /// Thus all parent regions and visibility is relevant
     fn lookup_uncached(
@@ -162,22 +217,30 @@ impl<'a> ScopeInner<'a> {
 
         match result {
             Some(visible) => Ok(visible),
-            None => Err(Diagnostic::new(
-                span.pos(ctx),
-                match designator {
-                    Designator::Identifier(ident) => {
-                        format!("No declaration of '{ident}'")
-                    }
-                    Designator::OperatorSymbol(operator) => {
-                        format!("No declaration of operator '{operator}'")
+            None => {
+                let mut diagnostic = Diagnostic::new(
+                    span.pos(ctx),
+                    match designator {
+                        Designator::Identifier(ident) => {
+                            format!("No declaration of '{ident}'")
+                        }
+                        Designator::OperatorSymbol(operator) => {
+                            format!("No declaration of operator '{operator}'")
+                        }
+                        Designator::Character(chr) => {
+                            format!("No declaration of '{chr}'")
+                        }
+                        Designator::Anonymous(_) => "No declaration of <anonymous>".to_owned(),
+                    },
+                    ErrorCode::Unresolved,
+                );
+                if let Designator::Identifier(ident) = designator {
+                    for similar in self.similar_identifiers(ident) {
+                        diagnostic = diagnostic.fix(span.pos(ctx), similar.name_utf8());
                     }
-                    Designator::Character(chr) => {
-                        format!("No declaration of '{chr}'")
-                    }
-                    Designator::Anonymous(_) => "No declaration of <anonymous>".to_owned(),
-                },
-                ErrorCode::Unresolved,
-            )),
+                }
+                Err(diagnostic)
+            }
         }
     }
 
@@ -192,11 +255,7 @@ impl<'a> ScopeInner<'a> {
         }
 
         let ents = self.lookup_uncached(ctx, span, designator)?;
-        if let Entry::Vacant(vacant) = self.cache.entry(designator.clone()) {
-            Ok(vacant.insert(ents).clone())
-        } else {
-            unreachable!("Cache miss cannot be followed by occupied entry")
-        }
+        Ok(self.cache.entry(designator.clone()).or_insert(ents).clone())
     }
 }
 
@@ -298,10 +357,10 @@ impl<'a> Scope<'a> {
     }
 
     fn into_inner(self) -> ScopeInner<'a> {
-        if let Ok(cell) = Rc::try_unwrap(self.0) {
-            cell.into_inner()
-        } else {
-            panic!("Expect no child regions");
+        match Rc::try_unwrap(self.0) {
+            Ok(cell) => cell.into_inner(),
+            // A nested scope that is still referenced is left with an empty region
+            Err(shared) => std::mem::take(&mut *shared.borrow_mut()),
         }
     }
 
@@ -313,6 +372,14 @@ impl<'a> Scope<'a> {
         self.into_inner().into_visibility()
     }
 
+    /// True if the immediate region is a package declaration or package body
+    pub fn is_package_region(&self) -> bool {
+        matches!(
+            self.0.as_ref().borrow().region.kind,
+            RegionKind::PackageDeclaration | RegionKind::PackageBody
+        )
+    }
+
     pub fn lookup_immediate(&self, designator: &Designator) -> Option<NamedEntities<'a>> {
         let inner = self.0.as_ref().borrow();
         let names = inner.lookup_immediate(designator)?;
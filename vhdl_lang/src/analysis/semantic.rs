@@ -20,6 +20,34 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         scope: &Scope<'a>,
         type_mark: &mut WithTokenSpan<TypeMark>,
         diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<TypeEnt<'a>> {
+        let typ = self.resolve_type_mark_allow_incomplete(scope, type_mark, diagnostics)?;
+        if let Type::Incomplete(decl_pos) = typ.kind() {
+            diagnostics.push(
+                Diagnostic::new(
+                    type_mark.pos(self.ctx),
+                    format!(
+                        "Incomplete type '{}' cannot be used before its full declaration",
+                        typ.designator()
+                    ),
+                    ErrorCode::IncompleteTypeBeforeFullDeclaration,
+                )
+                .related(
+                    decl_pos,
+                    format!("Incomplete type '{}' declared here", typ.designator()),
+                ),
+            );
+        }
+        Ok(typ)
+    }
+
+    /// LRM 5.4.2: Prior to its full type declaration, an incomplete type may only be used as the
+    /// type mark of the designated subtype of an access type
+    pub fn resolve_type_mark_allow_incomplete(
+        &self,
+        scope: &Scope<'a>,
+        type_mark: &mut WithTokenSpan<TypeMark>,
+        diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<TypeEnt<'a>> {
         let name = self.name_resolve(
             scope,
@@ -82,6 +110,42 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
     }
 
+    /// The type of the expression of a case statement or a selected assignment
+    ///
+    /// LRM 10.9: The expression of a matching case statement or selection shall be of type
+    /// BIT or STD_ULOGIC or of a one-dimensional array type whose element type is BIT or STD_ULOGIC
+    pub fn case_expression_type(
+        &self,
+        scope: &Scope<'a>,
+        is_matching: bool,
+        expression: &mut WithTokenSpan<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<TypeEnt<'a>>> {
+        let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+        if let Some(typ) = ctyp {
+            if is_matching && !self.is_matching_case_type(typ) {
+                diagnostics.add(
+                    expression.pos(self.ctx),
+                    format!(
+                        "{} cannot be the expression of a matching case. Expected 'BIT', 'STD_ULOGIC' or a one-dimensional array of these",
+                        typ.describe()
+                    ),
+                    ErrorCode::TypeMismatch,
+                );
+            }
+        }
+        Ok(ctyp)
+    }
+
+    fn is_matching_case_type(&self, typ: TypeEnt<'a>) -> bool {
+        match typ.base_type().kind() {
+            Type::Array {
+                indexes, elem_type, ..
+            } if indexes.len() == 1 => self.has_matching_op(elem_type.base_type()),
+            _ => self.has_matching_op(typ.base_type()),
+        }
+    }
+
     pub fn choice_with_ttyp(
         &self,
         scope: &Scope<'a>,
@@ -148,6 +212,16 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             };
 
         match resolved {
+            ResolvedName::Overloaded(_, ref names)
+                if !names.entities().any(|ent| ent.is_procedure()) =>
+            {
+                // Only functions are visible, a function can never be called as a procedure
+                diagnostics.push(Diagnostic::invalid_procedure_call(
+                    &name.pos(self.ctx),
+                    names.sorted_entities(),
+                ));
+                self.analyze_assoc_elems(scope, parameters, diagnostics)?;
+            }
             ResolvedName::Overloaded(ref des, names) => {
                 match as_fatal(self.disambiguate(
                     scope,
@@ -165,20 +239,10 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         name.set_unique_reference(&ent);
 
                         if !ent.is_procedure() {
-                            let mut diagnostic = Diagnostic::new(
+                            diagnostics.push(Diagnostic::invalid_procedure_call(
                                 &name.pos(self.ctx),
-                                "Invalid procedure call",
-                                ErrorCode::InvalidCall,
-                            );
-                            for ent in names.sorted_entities() {
-                                if let Some(decl_pos) = ent.decl_pos() {
-                                    diagnostic.add_related(
-                                        decl_pos,
-                                        format!("{} is not a procedure", ent.describe()),
-                                    );
-                                }
-                            }
-                            diagnostics.push(diagnostic);
+                                names.sorted_entities(),
+                            ));
                         } else if ent.is_uninst_subprogram_body() {
                             diagnostics.add(
                                 &name.pos(self.ctx),
@@ -232,6 +296,63 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 }
 
 impl Diagnostic {
+    fn invalid_procedure_call<'a>(
+        pos: &SrcPos,
+        candidates: impl IntoIterator<Item = OverloadedEnt<'a>>,
+    ) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(pos, "Invalid procedure call", ErrorCode::InvalidCall);
+        for ent in candidates {
+            if let Some(decl_pos) = ent.decl_pos() {
+                diagnostic.add_related(decl_pos, format!("{} is not a procedure", ent.describe()));
+            }
+        }
+        diagnostic
+    }
+
+    /// Candidates that all share the same signature are homographs made visible
+    /// from different declarative regions, they are ambiguous at every call
+    pub fn ambiguous_homographs<'a>(
+        pos: &SrcPos,
+        candidates: &[OverloadedEnt<'a>],
+    ) -> Option<Diagnostic> {
+        let (first, rest) = candidates.split_first()?;
+        if rest.is_empty()
+            || rest
+                .iter()
+                .any(|ent| ent.subprogram_key() != first.subprogram_key())
+        {
+            return None;
+        }
+
+        let mut candidates = candidates.to_vec();
+        candidates.sort_by(|x, y| x.decl_pos().cmp(&y.decl_pos()));
+        let sources: Vec<_> = candidates
+            .iter()
+            .map(|ent| match ent.parent {
+                Some(parent) => parent.describe(),
+                None => "an unknown region".to_owned(),
+            })
+            .collect();
+        let (last, init) = sources.split_last()?;
+
+        let mut diag = Diagnostic::new(
+            pos,
+            format!(
+                "Ambiguous use of {}, it is made visible from {}{} and {last}",
+                first.describe(),
+                if init.len() == 1 { "both " } else { "" },
+                init.join(", ")
+            ),
+            ErrorCode::AmbiguousCall,
+        );
+        for (ent, source) in candidates.iter().zip(sources.iter()) {
+            if let Some(decl_pos) = ent.decl_pos() {
+                diag.add_related(decl_pos, format!("Declared in {source}"));
+            }
+        }
+        Some(diag)
+    }
+
     pub fn add_subprogram_candidates<'a>(
         &mut self,
         prefix: &str,
@@ -312,7 +433,7 @@ impl Diagnostic {
                 suffix,
                 named_entity.describe(),
             ),
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )
     }
 }
@@ -5,11 +5,12 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::*;
-use crate::HasTokenSpan;
+use crate::{HasTokenSpan, TokenSpan};
 use analyze::*;
 use target::AssignmentType;
 
@@ -117,7 +118,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 let ReturnStatement { ref mut expression } = ret;
 
                 match SequentialRoot::from(parent) {
-                    SequentialRoot::Function(ttyp) => {
+                    SequentialRoot::Function(_, ttyp) => {
                         if let Some(ref mut expression) = expression {
                             self.expr_with_ttyp(scope, ttyp, expression, diagnostics)?;
                         } else {
@@ -147,6 +148,23 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
             }
             SequentialStatement::Wait(ref mut wait_stmt) => {
+                self.check_not_in_function(
+                    parent,
+                    statement_span,
+                    "Wait statement",
+                    ErrorCode::WaitInFunction,
+                    diagnostics,
+                );
+                if let Some(sensitivity_list) = self.process_sensitivity_list() {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            statement_span.pos(self.ctx),
+                            "Wait statement is not allowed in a process with a sensitivity list",
+                            ErrorCode::WaitInSensitizedProcess,
+                        )
+                        .related(sensitivity_list, "Sensitivity list of the process"),
+                    );
+                }
                 let WaitStatement {
                     sensitivity_clause,
                     condition_clause,
@@ -171,14 +189,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
                 }
                 if let Some(expr) = severity {
-                    self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
+                    self.severity_expr(scope, expr, diagnostics)?;
                 }
             }
             SequentialStatement::Report(ref mut report_stmt) => {
                 let ReportStatement { report, severity } = report_stmt;
                 self.expr_with_ttyp(scope, self.string(), report, diagnostics)?;
                 if let Some(expr) = severity {
-                    self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
+                    self.severity_expr(scope, expr, diagnostics)?;
                 }
             }
             SequentialStatement::Exit(ref mut exit_stmt) => {
@@ -239,17 +257,25 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
             SequentialStatement::Case(ref mut case_stmt) => {
                 let CaseStatement {
-                    is_matching: _,
+                    is_matching,
                     expression,
                     alternatives,
                     end_label_pos: _,
                 } = case_stmt;
-                let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                let ctyp =
+                    self.case_expression_type(scope, *is_matching, expression, diagnostics)?;
                 for alternative in alternatives.iter_mut() {
                     let Alternative { choices, item } = alternative;
                     self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                     self.analyze_sequential_part(scope, parent, item, diagnostics)?;
                 }
+                if !*is_matching {
+                    let choices: Vec<_> = alternatives
+                        .iter()
+                        .map(|alternative| alternative.choices.as_slice())
+                        .collect();
+                    self.check_case_coverage(ctyp, expression, &choices, diagnostics);
+                }
             }
             SequentialStatement::Loop(ref mut loop_stmt) => {
                 let LoopStatement {
@@ -287,8 +313,19 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 self.analyze_procedure_call(scope, pcall, diagnostics)?;
             }
             SequentialStatement::SignalAssignment(ref mut assign) => {
-                // @TODO more
-                let SignalAssignment { target, rhs, .. } = assign;
+                self.check_not_in_function(
+                    parent,
+                    statement_span,
+                    "Signal assignment",
+                    ErrorCode::IllegalStatementInFunction,
+                    diagnostics,
+                );
+                self.check_passive(parent, statement_span, diagnostics);
+                let SignalAssignment {
+                    target,
+                    delay_mechanism,
+                    rhs,
+                } = assign;
                 self.analyze_waveform_assignment(
                     scope,
                     target,
@@ -296,6 +333,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     rhs,
                     diagnostics,
                 )?;
+                self.analyze_delay_mechanism(scope, delay_mechanism, rhs, diagnostics)?;
             }
             SequentialStatement::VariableAssignment(ref mut assign) => {
                 let VariableAssignment { target, rhs } = assign;
@@ -308,6 +346,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 )?;
             }
             SequentialStatement::SignalForceAssignment(ref mut assign) => {
+                self.check_not_in_function(
+                    parent,
+                    statement_span,
+                    "Signal assignment",
+                    ErrorCode::IllegalStatementInFunction,
+                    diagnostics,
+                );
+                self.check_passive(parent, statement_span, diagnostics);
                 let SignalForceAssignment {
                     target,
                     force_mode: _,
@@ -322,6 +368,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 )?;
             }
             SequentialStatement::SignalReleaseAssignment(ref mut assign) => {
+                self.check_not_in_function(
+                    parent,
+                    statement_span,
+                    "Signal assignment",
+                    ErrorCode::IllegalStatementInFunction,
+                    diagnostics,
+                );
+                self.check_passive(parent, statement_span, diagnostics);
                 let SignalReleaseAssignment {
                     target,
                     force_mode: _,
@@ -334,6 +388,52 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// Functions cannot suspend or drive signals
+    fn check_not_in_function(
+        &self,
+        parent: EntRef<'a>,
+        statement_span: TokenSpan,
+        what: &str,
+        code: ErrorCode,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let SequentialRoot::Function(function, _) = SequentialRoot::from(parent) {
+            diagnostics.push(
+                Diagnostic::new(
+                    statement_span.pos(self.ctx),
+                    format!("{what} is not allowed in a function"),
+                    code,
+                )
+                .related(
+                    self.ctx.get_pos(function.src_span.start_token),
+                    format!("In function '{}'", function.designator()),
+                ),
+            );
+        }
+    }
+
+    /// Processes in the statement part of an entity must be passive
+    fn check_passive(
+        &self,
+        parent: EntRef<'a>,
+        statement_span: TokenSpan,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(entity) = find_entity_of_process(parent) {
+            diagnostics.push(
+                Diagnostic::new(
+                    statement_span.pos(self.ctx),
+                    "Signal assignment is not allowed in a passive process",
+                    ErrorCode::NonPassiveEntityStatement,
+                )
+                .opt_related(
+                    entity.decl_pos(),
+                    format!("In the statement part of entity '{}'", entity.designator()),
+                ),
+            );
+        }
+    }
+
     fn check_loop_label(
         &self,
         scope: &Scope<'a>,
@@ -395,14 +495,161 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             self.analyze_sequential_statement(scope, parent, statement, diagnostics)?;
         }
 
+        if !self.is_third_party() {
+            self.check_unreachable(statements, diagnostics);
+        }
         Ok(())
     }
+
+    /// Statements that follow a statement which never continues with the next one
+    /// can never be executed
+    fn check_unreachable(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(idx) = statements
+            .iter()
+            .position(|statement| never_continues(&statement.statement.item))
+        else {
+            return;
+        };
+        if let Some(unreachable) = statements.get(idx + 1) {
+            diagnostics.push(
+                Diagnostic::new(
+                    unreachable.statement.span.pos(self.ctx),
+                    "Unreachable statement",
+                    ErrorCode::UnreachableCode,
+                )
+                .related(
+                    statements[idx].statement.span.pos(self.ctx),
+                    "Execution never continues after this statement",
+                ),
+            );
+        }
+    }
+
+    /// True if the statements cannot complete other than by a return statement.
+    /// A loop without an iteration scheme or with a statically true condition that contains
+    /// no exit statement never completes, and neither does a report or a failing assertion
+    /// of severity `failure`, which ends the simulation
+    pub(super) fn always_returns(&self, statements: &[LabeledSequentialStatement]) -> bool {
+        list_ends_every_path(statements, &|statement| match statement {
+            SequentialStatement::Return(_) => true,
+            SequentialStatement::Loop(loop_stmt) => {
+                let is_endless = match loop_stmt.iteration_scheme {
+                    None => true,
+                    Some(IterationScheme::While(ref condition)) => {
+                        self.is_static_boolean(&condition.item, true)
+                    }
+                    Some(IterationScheme::For(..)) => false,
+                };
+                is_endless && !contains_exit(&loop_stmt.statements)
+            }
+            SequentialStatement::Report(report) => self.is_failure(&report.severity),
+            SequentialStatement::Assert(assert) => {
+                self.is_static_boolean(&assert.condition.item, false)
+                    && self.is_failure(&assert.severity)
+            }
+            _ => false,
+        })
+    }
+
+    fn is_static_boolean(&self, expr: &Expression, value: bool) -> bool {
+        self.static_value(self.boolean(), expr) == Some(StaticValue::Enum(value as usize))
+    }
+
+    /// True if the severity expression is statically `failure`
+    fn is_failure(&self, severity: &Option<WithTokenSpan<Expression>>) -> bool {
+        severity.as_ref().is_some_and(|severity| {
+            self.static_value(self.severity_level(), &severity.item) == Some(StaticValue::Enum(3))
+        })
+    }
+}
+
+/// True if every path through the statement ends with a statement for which `is_end` holds
+fn ends_every_path(
+    statement: &SequentialStatement,
+    is_end: &impl Fn(&SequentialStatement) -> bool,
+) -> bool {
+    if is_end(statement) {
+        return true;
+    }
+    match statement {
+        SequentialStatement::If(ifstmt) => {
+            ifstmt.conds.else_item.as_ref().is_some_and(|else_item| {
+                list_ends_every_path(else_item, is_end)
+                    && ifstmt
+                        .conds
+                        .conditionals
+                        .iter()
+                        .all(|conditional| list_ends_every_path(&conditional.item, is_end))
+            })
+        }
+        SequentialStatement::Case(case_stmt) => case_stmt
+            .alternatives
+            .iter()
+            .all(|alternative| list_ends_every_path(&alternative.item, is_end)),
+        _ => false,
+    }
+}
+
+fn list_ends_every_path(
+    statements: &[LabeledSequentialStatement],
+    is_end: &impl Fn(&SequentialStatement) -> bool,
+) -> bool {
+    statements
+        .iter()
+        .any(|statement| ends_every_path(&statement.statement.item, is_end))
+}
+
+/// True if the statement always ends with an unconditional return, exit or next
+fn never_continues(statement: &SequentialStatement) -> bool {
+    ends_every_path(statement, &|statement| match statement {
+        SequentialStatement::Return(_) => true,
+        SequentialStatement::Exit(exit) => exit.condition.is_none(),
+        SequentialStatement::Next(next) => next.condition.is_none(),
+        _ => false,
+    })
+}
+
+fn contains_exit(statements: &[LabeledSequentialStatement]) -> bool {
+    statements
+        .iter()
+        .any(|statement| match statement.statement.item {
+            SequentialStatement::Exit(_) => true,
+            SequentialStatement::If(ref ifstmt) => {
+                ifstmt
+                    .conds
+                    .conditionals
+                    .iter()
+                    .any(|conditional| contains_exit(&conditional.item))
+                    || ifstmt.conds.else_item.as_deref().is_some_and(contains_exit)
+            }
+            SequentialStatement::Case(ref case_stmt) => case_stmt
+                .alternatives
+                .iter()
+                .any(|alternative| contains_exit(&alternative.item)),
+            SequentialStatement::Loop(ref loop_stmt) => contains_exit(&loop_stmt.statements),
+            _ => false,
+        })
 }
 
 enum SequentialRoot<'a> {
     Process,
     Procedure,
-    Function(TypeEnt<'a>),
+    Function(EntRef<'a>, TypeEnt<'a>),
+}
+
+/// The entity when the sequential statement is within a process of its statement part
+fn find_entity_of_process(ent: EntRef) -> Option<EntRef> {
+    match ent.kind() {
+        AnyEntKind::Sequential(_) => find_entity_of_process(ent.parent?),
+        AnyEntKind::Concurrent(Some(Concurrent::Process)) => ent
+            .parent
+            .filter(|parent| matches!(parent.kind(), AnyEntKind::Design(Design::Entity(..)))),
+        _ => None,
+    }
 }
 
 fn find_outer_loop(ent: EntRef, label: Option<&Symbol>) -> bool {
@@ -434,7 +681,7 @@ impl<'a> From<EntRef<'a>> for SequentialRoot<'a> {
         match value.kind() {
             AnyEntKind::Overloaded(overloaded) => {
                 if let Some(return_type) = overloaded.signature().return_type() {
-                    SequentialRoot::Function(return_type)
+                    SequentialRoot::Function(value, return_type)
                 } else {
                     SequentialRoot::Procedure
                 }
@@ -96,7 +96,7 @@ impl StandardTypes {
                         Designator::Identifier(type_decl.ident.tree.item.clone()),
                         Some(standard_pkg),
                         Related::None,
-                        AnyEntKind::Type(Type::Incomplete),
+                        AnyEntKind::Type(Type::Incomplete(type_decl.ident.pos(ctx).clone())),
                         Some(type_decl.ident.pos(ctx).clone()),
                         type_decl.span(),
                         standard_pkg.source.clone(),
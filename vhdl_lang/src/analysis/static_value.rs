@@ -0,0 +1,759 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use super::analyze::*;
+use super::static_expression::bit_string_to_string;
+use crate::ast;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::*;
+use crate::named_entity::*;
+use crate::TokenSpan;
+
+/// Resolves the named entities referenced by a static expression
+pub(crate) trait StaticScope<'a> {
+    fn lookup(&self, id: EntityId) -> EntRef<'a>;
+
+    /// The value recorded during analysis of a constant, generic default or physical unit
+    fn recorded_value(&self, id: EntityId) -> Option<StaticValue>;
+
+    /// The range recorded during analysis of an integer type declaration
+    fn recorded_range(&self, id: EntityId) -> Option<StaticRange>;
+
+    /// The value of a named entity, which is known for constants with static values
+    /// and literals unless the scope knows more, such as the values of generics
+    fn value_of(&self, ent: EntRef<'a>) -> Option<StaticValue> {
+        named_value(self, ent)
+    }
+}
+
+impl<'a> StaticScope<'a> for AnalyzeContext<'a, '_> {
+    fn lookup(&self, id: EntityId) -> EntRef<'a> {
+        self.arena.get(id)
+    }
+
+    fn recorded_value(&self, id: EntityId) -> Option<StaticValue> {
+        self.arena.static_value(id)
+    }
+
+    fn recorded_range(&self, id: EntityId) -> Option<StaticRange> {
+        self.arena.static_range(id)
+    }
+}
+
+/// The static range of a scalar subtype, which is the range of its innermost constraint
+pub(crate) fn type_range<'a>(
+    scope: &(impl StaticScope<'a> + ?Sized),
+    typ: TypeEnt<'a>,
+) -> Option<StaticRange> {
+    match typ.kind() {
+        Type::Alias(alias) => type_range(scope, *alias),
+        Type::Subtype(subtype) => subtype_range(scope, subtype),
+        Type::Integer => scope.recorded_range(typ.id()),
+        _ => None,
+    }
+}
+
+/// The range of the constraint of a subtype or else the range of its type mark.
+/// The range of a constraint that is not static is not known
+pub(crate) fn subtype_range<'a>(
+    scope: &(impl StaticScope<'a> + ?Sized),
+    subtype: &Subtype<'a>,
+) -> Option<StaticRange> {
+    if subtype.range_constrained {
+        subtype.range
+    } else {
+        type_range(scope, subtype.type_mark())
+    }
+}
+
+/// Evaluate an integer, enumeration or physical expression that has already been analyzed.
+///
+/// Literals, named entities with static values, physical units and the predefined
+/// arithmetic and relational operators are supported. The type `ttyp` is only required
+/// to evaluate character literals.
+/// `None` is returned when the value cannot be determined.
+pub(crate) fn evaluate<'a>(
+    scope: &impl StaticScope<'a>,
+    ttyp: Option<TypeEnt<'a>>,
+    expr: &Expression,
+) -> Option<StaticValue> {
+    match expr {
+        Expression::Literal(literal) => literal_value(scope, ttyp, literal),
+        Expression::Name(name) => match name.as_ref() {
+            Name::Attribute(attr) => type_attribute_value(scope, attr),
+            _ => scope.value_of(scope.lookup(name.get_suffix_reference()?)),
+        },
+        Expression::Qualified(qexpr) => evaluate(scope, ttyp, &qexpr.expr.item),
+        Expression::Unary(op, operand) => {
+            let op_ent = predefined_operator(scope, &op.item)?;
+            let operand = evaluate(
+                scope,
+                Some(op_ent.formals().nth(0)?.type_mark()),
+                &operand.item,
+            )?;
+            static_unary(op.item.item, operand)
+        }
+        Expression::Binary(op, left, right) => {
+            let op_ent = predefined_operator(scope, &op.item)?;
+            let left = evaluate(
+                scope,
+                Some(op_ent.formals().nth(0)?.type_mark()),
+                &left.item,
+            )?;
+            let right = evaluate(
+                scope,
+                Some(op_ent.formals().nth(1)?.type_mark()),
+                &right.item,
+            )?;
+            static_binary(op.item.item, left, right)
+        }
+        _ => None,
+    }
+}
+
+impl<'a, 't> AnalyzeContext<'a, 't> {
+    /// Evaluate an integer, enumeration or physical expression of type `ttyp` that
+    /// has already been analyzed, see [evaluate]
+    pub fn static_value(&self, ttyp: TypeEnt<'a>, expr: &Expression) -> Option<StaticValue> {
+        evaluate(self, Some(ttyp), expr)
+    }
+
+    /// The range of a range constraint of type `ttyp` whose bounds are both static
+    pub fn static_range(
+        &self,
+        ttyp: TypeEnt<'a>,
+        constraint: &RangeConstraint,
+    ) -> Option<StaticRange> {
+        Some(StaticRange {
+            left: self.static_value(ttyp, &constraint.left_expr.item)?,
+            direction: constraint.direction,
+            right: self.static_value(ttyp, &constraint.right_expr.item)?,
+        })
+    }
+
+    /// LRM 5.2.1: The value of a scalar subtype must belong to the range of the subtype.
+    ///
+    /// The check is skipped when the value is not static.
+    pub fn check_static_range(
+        &self,
+        ttyp: TypeEnt<'a>,
+        range: &StaticRange,
+        span: TokenSpan,
+        expr: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(value) = self.static_value(ttyp, expr) else {
+            return;
+        };
+        if range.contains(value) {
+            return;
+        }
+        let (Some(value), Some(left), Some(right)) = (
+            self.describe_static_value(ttyp, value),
+            self.describe_static_value(ttyp, range.left),
+            self.describe_static_value(ttyp, range.right),
+        ) else {
+            return;
+        };
+        diagnostics.add(
+            span.pos(self.ctx),
+            format!(
+                "Value {value} is outside of the range {left} {} {right}",
+                range.direction
+            ),
+            ErrorCode::OutOfRange,
+        );
+    }
+
+    /// LRM 5.2.1: The bounds of a range constraint must belong to the subtype
+    /// denoted by the type mark unless the range is a null range
+    pub fn check_static_subrange(
+        &self,
+        type_mark: TypeEnt<'a>,
+        range: &StaticRange,
+        span: TokenSpan,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(base_range) = type_range(self, type_mark) else {
+            return;
+        };
+        if range.is_null() || (base_range.contains(range.left) && base_range.contains(range.right))
+        {
+            return;
+        }
+        let describe = |range: &StaticRange| {
+            Some(format!(
+                "{} {} {}",
+                self.describe_static_value(type_mark, range.left)?,
+                range.direction,
+                self.describe_static_value(type_mark, range.right)?
+            ))
+        };
+        let (Some(range_desc), Some(base_desc)) = (describe(range), describe(&base_range)) else {
+            return;
+        };
+        diagnostics.add(
+            span.pos(self.ctx),
+            format!(
+                "Range {range_desc} is not within the range {base_desc} of {}",
+                type_mark.describe()
+            ),
+            ErrorCode::SubtypeRangeOutOfBase,
+        );
+    }
+
+    /// Check a value against the range or the length of a subtype.
+    /// Only the innermost constraint is checked since it implies the constraints
+    /// of the type mark.
+    pub fn check_subtype_constraint(
+        &self,
+        subtype: &Subtype<'a>,
+        span: TokenSpan,
+        expr: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let type_mark = subtype.type_mark();
+        if let Some(ref range) = subtype_range(self, subtype) {
+            self.check_static_range(type_mark, range, span, expr, diagnostics);
+        }
+        if let Some(length) = subtype.static_length() {
+            self.check_static_length(type_mark, length, span, expr, diagnostics);
+        }
+        if let Some(ref index_range) = subtype.static_index_range() {
+            self.check_static_aggregate_indexes(type_mark, index_range, expr, diagnostics);
+        }
+    }
+
+    /// LRM 9.3.3.3: An array aggregate with an others choice or named choices takes the
+    /// index range of the target. Positional elements are associated with the indices
+    /// starting at the left bound in the direction of the index range.
+    ///
+    /// Choices that are not static are skipped.
+    pub fn check_static_aggregate_indexes(
+        &self,
+        ttyp: TypeEnt<'a>,
+        index_range: &StaticRange,
+        expr: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Expression::Aggregate(assocs) = expr else {
+            return;
+        };
+        let Type::Array {
+            indexes, elem_type, ..
+        } = ttyp.base().kind()
+        else {
+            return;
+        };
+        let [Some(index_type)] = indexes.as_slice() else {
+            return;
+        };
+        let index_type: TypeEnt<'a> = (*index_type).into();
+        // The length of purely positional aggregates is checked instead
+        if assocs
+            .iter()
+            .all(|assoc| matches!(assoc, ElementAssociation::Positional(_)))
+        {
+            return;
+        }
+        let Some(range_length) = index_range.length() else {
+            return;
+        };
+        let describe_range = || {
+            Some(format!(
+                "{} {} {}",
+                self.describe_static_value(index_type, index_range.left)?,
+                index_range.direction,
+                self.describe_static_value(index_type, index_range.right)?
+            ))
+        };
+
+        let mut offset = Some(0i64);
+        for assoc in assocs.iter() {
+            match assoc {
+                ElementAssociation::Positional(expr) => {
+                    let Some(current) = offset else {
+                        continue;
+                    };
+                    offset = self
+                        .static_element_length(ttyp, *elem_type, &expr.item)
+                        .and_then(|length| current.checked_add(length));
+                    if offset.is_some_and(|next| next > range_length && next > current) {
+                        let index = index_range
+                            .nth(current.max(range_length))
+                            .and_then(|index| self.describe_static_value(index_type, index));
+                        if let (Some(index), Some(range)) = (index, describe_range()) {
+                            diagnostics.add(
+                                expr.span.pos(self.ctx),
+                                format!(
+                                    "Positional element at index {index} is outside of the range {range}"
+                                ),
+                                ErrorCode::OutOfRange,
+                            );
+                        }
+                        // Only the first element outside of the range is reported
+                        offset = None;
+                    }
+                }
+                ElementAssociation::Named(choices, _) => {
+                    for choice in choices.iter() {
+                        match choice.item {
+                            Choice::Expression(ref choice_expr) => self.check_static_range(
+                                index_type,
+                                index_range,
+                                choice.span,
+                                choice_expr,
+                                diagnostics,
+                            ),
+                            Choice::DiscreteRange(
+                                DiscreteRange::Range(ast::Range::Range(ref constraint))
+                                | DiscreteRange::Discrete(_, Some(ast::Range::Range(ref constraint))),
+                            ) => {
+                                // Null ranges do not denote any index
+                                if self
+                                    .static_range(index_type, constraint)
+                                    .and_then(|range| range.length())
+                                    .is_some_and(|length| length > 0)
+                                {
+                                    for bound in [&constraint.left_expr, &constraint.right_expr] {
+                                        self.check_static_range(
+                                            index_type,
+                                            index_range,
+                                            bound.span,
+                                            &bound.item,
+                                            diagnostics,
+                                        );
+                                    }
+                                }
+                            }
+                            Choice::DiscreteRange(_) | Choice::Others => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// LRM 14.7.3.4: The implicit subtype conversion of an array value to the subtype of
+    /// the target requires the lengths to match.
+    ///
+    /// The check is skipped when the length of the value is not static.
+    pub fn check_static_length(
+        &self,
+        ttyp: TypeEnt<'a>,
+        expected: i64,
+        span: TokenSpan,
+        expr: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(length) = self.static_length(ttyp, expr) {
+            if length != expected {
+                diagnostics.add(
+                    span.pos(self.ctx),
+                    format!("Length mismatch, expected {expected} elements but got {length}"),
+                    ErrorCode::LengthMismatch,
+                );
+            }
+        }
+    }
+
+    /// The length of a one-dimensional array expression of type `ttyp`.
+    ///
+    /// String and bit string literals, constrained objects and slices, positional aggregates
+    /// and the predefined concatenation are supported.
+    pub fn static_length(&self, ttyp: TypeEnt<'a>, expr: &Expression) -> Option<i64> {
+        match expr {
+            Expression::Literal(Literal::String(string)) => i64::try_from(string.len()).ok(),
+            Expression::Literal(Literal::BitString(bit_string)) => {
+                i64::try_from(bit_string_to_string(bit_string).ok()?.len()).ok()
+            }
+            Expression::Name(name) => self.static_name_length(name),
+            Expression::Qualified(qexpr) => {
+                let type_mark = self
+                    .arena
+                    .get(qexpr.type_mark.item.name.item.get_suffix_reference()?);
+                let type_mark = TypeEnt::from_any(type_mark)?;
+                type_mark
+                    .static_length()
+                    .or_else(|| self.static_length(type_mark, &qexpr.expr.item))
+            }
+            Expression::Aggregate(assocs) => {
+                let Type::Array { elem_type, .. } = ttyp.base().kind() else {
+                    return None;
+                };
+                assocs.iter().try_fold(0i64, |length, assoc| match assoc {
+                    ElementAssociation::Positional(expr) => length
+                        .checked_add(self.static_element_length(ttyp, *elem_type, &expr.item)?),
+                    ElementAssociation::Named(..) => None,
+                })
+            }
+            Expression::Binary(op, left, right) if op.item.item == Operator::Concat => {
+                let op_ent = predefined_operator(self, &op.item)?;
+                let left = self.static_element_length(
+                    ttyp,
+                    op_ent.formals().nth(0)?.type_mark(),
+                    &left.item,
+                )?;
+                let right = self.static_element_length(
+                    ttyp,
+                    op_ent.formals().nth(1)?.type_mark(),
+                    &right.item,
+                )?;
+                left.checked_add(right)
+            }
+            _ => None,
+        }
+    }
+
+    /// The number of elements contributed by an operand of a concatenation or a positional
+    /// aggregate, where `operand_type` is either the element type or the array type itself
+    fn static_element_length(
+        &self,
+        ttyp: TypeEnt<'a>,
+        operand_type: TypeEnt<'a>,
+        expr: &Expression,
+    ) -> Option<i64> {
+        if operand_type.base() == ttyp.base() {
+            return self.static_length(ttyp, expr);
+        }
+        match expr {
+            Expression::Literal(Literal::Character(_) | Literal::AbstractLiteral(_)) => Some(1),
+            Expression::Name(name) => {
+                let ent = self.arena.get(name.get_suffix_reference()?);
+                match ent.kind() {
+                    AnyEntKind::Object(object) if object.subtype.base() == operand_type.base() => {
+                        Some(1)
+                    }
+                    AnyEntKind::Overloaded(Overloaded::EnumLiteral(_)) => Some(1),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The length of an array object, record element or slice with a static constraint
+    pub fn static_name_length(&self, name: &Name) -> Option<i64> {
+        match name {
+            Name::Designator(_) | Name::Selected(..) => {
+                let ent = self.arena.get(name.get_suffix_reference()?);
+                // Aliases may denote a slice, use the subtype of the alias rather than the object
+                match ent.kind() {
+                    AnyEntKind::Object(object) => object.subtype.static_length(),
+                    AnyEntKind::ElementDeclaration(subtype) => subtype.static_length(),
+                    AnyEntKind::Type(_) => TypeEnt::from_any(ent)?.static_length(),
+                    _ => None,
+                }
+            }
+            Name::Slice(prefix, drange) => {
+                let ent = self.arena.get(prefix.item.get_suffix_reference()?);
+                let (AnyEntKind::Object(Object { subtype, .. })
+                | AnyEntKind::ElementDeclaration(subtype)) = ent.kind()
+                else {
+                    return None;
+                };
+                let Type::Array { indexes, .. } = subtype.base().kind() else {
+                    return None;
+                };
+                match indexes.as_slice() {
+                    [Some(index_type)] => self.static_drange_length((*index_type).into(), drange),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The length of a discrete range of `index_type` whose bounds are static
+    pub fn static_drange_length(
+        &self,
+        index_type: TypeEnt<'a>,
+        drange: &DiscreteRange,
+    ) -> Option<i64> {
+        match drange {
+            DiscreteRange::Range(range) | DiscreteRange::Discrete(_, Some(range)) => {
+                self.static_range_length(index_type, range)
+            }
+            DiscreteRange::Discrete(type_mark, None) => {
+                if type_mark.item.attr.is_some() {
+                    return None;
+                }
+                let ent = self
+                    .arena
+                    .get(type_mark.item.name.item.get_suffix_reference()?);
+                type_range(self, TypeEnt::from_any(ent)?)?.length()
+            }
+        }
+    }
+
+    /// The range of a discrete range of `index_type` whose bounds are static
+    pub fn static_drange(
+        &self,
+        index_type: TypeEnt<'a>,
+        drange: &DiscreteRange,
+    ) -> Option<StaticRange> {
+        match drange {
+            DiscreteRange::Range(ast::Range::Range(constraint))
+            | DiscreteRange::Discrete(_, Some(ast::Range::Range(constraint))) => {
+                self.static_range(index_type, constraint)
+            }
+            DiscreteRange::Discrete(type_mark, None) => {
+                if type_mark.item.attr.is_some() {
+                    return None;
+                }
+                let ent = self
+                    .arena
+                    .get(type_mark.item.name.item.get_suffix_reference()?);
+                type_range(self, TypeEnt::from_any(ent)?)
+            }
+            DiscreteRange::Range(ast::Range::Attribute(_))
+            | DiscreteRange::Discrete(_, Some(ast::Range::Attribute(_))) => None,
+        }
+    }
+
+    fn static_range_length(&self, index_type: TypeEnt<'a>, range: &ast::Range) -> Option<i64> {
+        match range {
+            ast::Range::Range(constraint) => self.static_range(index_type, constraint)?.length(),
+            ast::Range::Attribute(attr) => match attr.attr.item {
+                AttributeDesignator::Range(_) if attr.expr.is_none() => {
+                    self.static_name_length(&attr.name.item)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn describe_static_value(&self, ttyp: TypeEnt<'a>, value: StaticValue) -> Option<String> {
+        match value {
+            StaticValue::Integer(value) => Some(value.to_string()),
+            StaticValue::Enum(position) => match self.enum_literals(ttyp.base()).get(position)? {
+                literal @ Designator::Character(_) => Some(literal.to_string()),
+                literal => Some(format!("'{literal}'")),
+            },
+            StaticValue::Physical(value) => {
+                let base = ttyp.base();
+                let primary_unit = base.implicits.iter().find(|ent| {
+                    matches!(ent.kind(), AnyEntKind::PhysicalLiteral(_))
+                        && self.recorded_value(ent.id()) == Some(StaticValue::Physical(1))
+                })?;
+                Some(format!("{value} {}", primary_unit.designator()))
+            }
+        }
+    }
+
+    /// The value of a physical literal as a multiple of the primary unit
+    pub fn physical_literal_value(&self, literal: &PhysicalLiteral) -> Option<i64> {
+        physical_literal_value(self, literal)
+    }
+
+    /// The literals of an enumeration type in order of declaration
+    pub fn enum_literals(&self, typ: BaseType<'a>) -> Vec<&'a Designator> {
+        enum_literals(typ)
+    }
+}
+
+/// LRM 5.2.4.1 The value of a physical literal with a real abstract literal
+/// is rounded to the nearest multiple of the primary unit
+fn physical_literal_value<'a>(
+    scope: &impl StaticScope<'a>,
+    literal: &PhysicalLiteral,
+) -> Option<i64> {
+    let unit = scope.lookup(literal.unit.reference.get()?);
+    let Some(StaticValue::Physical(unit_value)) = scope.recorded_value(unit.as_actual().id())
+    else {
+        return None;
+    };
+    match literal.value {
+        AbstractLiteral::Integer(value) => i64::try_from(value).ok()?.checked_mul(unit_value),
+        AbstractLiteral::Real(value) => {
+            let product = (value * unit_value as f64).round();
+            if product.is_finite() && product.abs() < i64::MAX as f64 {
+                Some(product as i64)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn enum_literals(typ: BaseType<'_>) -> Vec<&Designator> {
+    typ.implicits
+        .iter()
+        .filter(|ent| {
+            matches!(
+                ent.kind(),
+                AnyEntKind::Overloaded(Overloaded::EnumLiteral(_))
+            )
+        })
+        .map(|ent| ent.designator())
+        .collect()
+}
+
+fn enum_position(typ: BaseType<'_>, designator: &Designator) -> Option<StaticValue> {
+    if !matches!(typ.kind(), Type::Enum(_)) {
+        return None;
+    }
+    enum_literals(typ)
+        .into_iter()
+        .position(|literal| literal == designator)
+        .map(StaticValue::Enum)
+}
+
+fn literal_value<'a>(
+    scope: &impl StaticScope<'a>,
+    ttyp: Option<TypeEnt<'a>>,
+    literal: &Literal,
+) -> Option<StaticValue> {
+    match literal {
+        Literal::AbstractLiteral(AbstractLiteral::Integer(value)) => {
+            i64::try_from(*value).ok().map(StaticValue::Integer)
+        }
+        Literal::Character(chr) => enum_position(ttyp?.base(), &Designator::Character(*chr)),
+        Literal::Physical(literal) => {
+            physical_literal_value(scope, literal).map(StaticValue::Physical)
+        }
+        _ => None,
+    }
+}
+
+/// Interface objects such as generics have no static value within their declarative region
+pub(crate) fn named_value<'a>(
+    scope: &(impl StaticScope<'a> + ?Sized),
+    ent: EntRef<'a>,
+) -> Option<StaticValue> {
+    match ent.actual_kind() {
+        AnyEntKind::Object(Object {
+            class: ObjectClass::Constant,
+            iface: None,
+            ..
+        })
+        | AnyEntKind::PhysicalLiteral(_) => scope.recorded_value(ent.as_actual().id()),
+        AnyEntKind::Overloaded(Overloaded::EnumLiteral(signature)) => {
+            enum_position(signature.return_type()?.base(), ent.designator())
+        }
+        _ => None,
+    }
+}
+
+/// The value of the 'left, 'right, 'low and 'high attributes of a scalar type
+/// whose range is static
+fn type_attribute_value<'a>(
+    scope: &impl StaticScope<'a>,
+    attr: &AttributeName,
+) -> Option<StaticValue> {
+    if attr.expr.is_some() {
+        return None;
+    }
+    let typ = TypeEnt::from_any(scope.lookup(attr.name.item.get_suffix_reference()?))?;
+    let range = match typ.kind() {
+        Type::Enum(_) => StaticRange {
+            left: StaticValue::Enum(0),
+            direction: Direction::Ascending,
+            right: StaticValue::Enum(enum_literals(typ.base()).len().checked_sub(1)?),
+        },
+        _ => type_range(scope, typ)?,
+    };
+    let (low, high) = match range.direction {
+        Direction::Ascending => (range.left, range.right),
+        Direction::Descending => (range.right, range.left),
+    };
+    match attr.attr.item {
+        AttributeDesignator::Left => Some(range.left),
+        AttributeDesignator::Right => Some(range.right),
+        AttributeDesignator::Low => Some(low),
+        AttributeDesignator::High => Some(high),
+        _ => None,
+    }
+}
+
+/// User defined operators cannot be evaluated
+fn predefined_operator<'a>(
+    scope: &impl StaticScope<'a>,
+    op: &WithRef<Operator>,
+) -> Option<OverloadedEnt<'a>> {
+    let ent = OverloadedEnt::from_any(scope.lookup(op.reference.get()?))?;
+    if ent.is_implicit() {
+        Some(ent)
+    } else {
+        None
+    }
+}
+
+fn static_unary(op: Operator, operand: StaticValue) -> Option<StaticValue> {
+    use StaticValue::*;
+    match (op, operand) {
+        (Operator::Plus, Integer(_) | Physical(_)) => Some(operand),
+        (Operator::Minus, Integer(value)) => value.checked_neg().map(Integer),
+        (Operator::Minus, Physical(value)) => value.checked_neg().map(Physical),
+        (Operator::Abs, Integer(value)) => value.checked_abs().map(Integer),
+        (Operator::Abs, Physical(value)) => value.checked_abs().map(Physical),
+        // The predefined logical operators are only defined for BIT and BOOLEAN
+        (Operator::Not, Enum(value)) => Some(Enum(1 - value.min(1))),
+        _ => None,
+    }
+}
+
+/// LRM 9.2.7: `mod` has the sign of the right operand and `rem` the sign of the left
+fn modulo(left: i64, right: i64) -> Option<i64> {
+    let rem = left.checked_rem(right)?;
+    if rem != 0 && (rem < 0) != (right < 0) {
+        Some(rem + right)
+    } else {
+        Some(rem)
+    }
+}
+
+fn static_binary(op: Operator, left: StaticValue, right: StaticValue) -> Option<StaticValue> {
+    use StaticValue::*;
+    let boolean = |value: bool| Some(Enum(value as usize));
+    match (op, left, right) {
+        (Operator::Plus, Integer(l), Integer(r)) => l.checked_add(r).map(Integer),
+        (Operator::Minus, Integer(l), Integer(r)) => l.checked_sub(r).map(Integer),
+        (Operator::Times, Integer(l), Integer(r)) => l.checked_mul(r).map(Integer),
+        (Operator::Div, Integer(l), Integer(r)) => l.checked_div(r).map(Integer),
+        (Operator::Rem, Integer(l), Integer(r)) => l.checked_rem(r).map(Integer),
+        (Operator::Mod, Integer(l), Integer(r)) => modulo(l, r).map(Integer),
+        (Operator::Pow, Integer(l), Integer(r)) => {
+            l.checked_pow(u32::try_from(r).ok()?).map(Integer)
+        }
+
+        (Operator::Plus, Physical(l), Physical(r)) => l.checked_add(r).map(Physical),
+        (Operator::Minus, Physical(l), Physical(r)) => l.checked_sub(r).map(Physical),
+        (Operator::Times, Physical(l), Integer(r)) | (Operator::Times, Integer(r), Physical(l)) => {
+            l.checked_mul(r).map(Physical)
+        }
+        (Operator::Div, Physical(l), Integer(r)) => l.checked_div(r).map(Physical),
+        (Operator::Div, Physical(l), Physical(r)) => l.checked_div(r).map(Integer),
+
+        (Operator::And, Enum(l), Enum(r)) => boolean(l != 0 && r != 0),
+        (Operator::Or, Enum(l), Enum(r)) => boolean(l != 0 || r != 0),
+        (Operator::Nand, Enum(l), Enum(r)) => boolean(!(l != 0 && r != 0)),
+        (Operator::Nor, Enum(l), Enum(r)) => boolean(!(l != 0 || r != 0)),
+        (Operator::Xor, Enum(l), Enum(r)) => boolean((l != 0) != (r != 0)),
+        (Operator::Xnor, Enum(l), Enum(r)) => boolean((l != 0) == (r != 0)),
+
+        _ => static_relation(op, left, right).and_then(boolean),
+    }
+}
+
+/// The predefined relational operators compare positions and values
+fn static_relation(op: Operator, left: StaticValue, right: StaticValue) -> Option<bool> {
+    use StaticValue::*;
+    let ordering = match (left, right) {
+        (Integer(l), Integer(r)) | (Physical(l), Physical(r)) => l.cmp(&r),
+        (Enum(l), Enum(r)) => l.cmp(&r),
+        _ => return None,
+    };
+    match op {
+        Operator::EQ => Some(ordering.is_eq()),
+        Operator::NE => Some(ordering.is_ne()),
+        Operator::LT => Some(ordering.is_lt()),
+        Operator::LTE => Some(ordering.is_le()),
+        Operator::GT => Some(ordering.is_gt()),
+        Operator::GTE => Some(ordering.is_ge()),
+        _ => None,
+    }
+}
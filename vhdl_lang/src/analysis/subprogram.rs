@@ -5,12 +5,14 @@
 // Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 use super::names::*;
 use super::*;
+use crate::ast::search::{Search, SearchState, Searcher};
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::{Signature, *};
-use crate::{ast, HasTokenSpan};
+use crate::standard::VHDLStandard;
+use crate::{ast, HasTokenSpan, TokenAccess};
 use analyze::*;
 use itertools::Itertools;
 use vhdl_lang::TokenSpan;
@@ -78,9 +80,145 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             &mut body.statements,
             diagnostics,
         )?;
+        if let SubprogramSpecification::Function(ref fun) = body.specification {
+            if fun.pure && !self.is_third_party() {
+                self.check_pure_function_body(subpgm_ent, body, diagnostics);
+            }
+            if !self.always_returns(&body.statements) {
+                diagnostics.add(
+                    fun.designator.tree.pos(self.ctx),
+                    format!(
+                        "Function '{}' can reach its end without returning a value",
+                        fun.designator.tree.item
+                    ),
+                    ErrorCode::MissingReturn,
+                );
+            }
+        }
+        subpgm_region.close(diagnostics);
         Ok(())
     }
 
+    /// LRM 4.1: A pure function may not reference signals, variables or files
+    /// declared outside of it nor call impure functions
+    fn check_pure_function_body(
+        &self,
+        function: EntRef<'a>,
+        body: &SubprogramBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut searcher = FindImpureReferences::new(self.arena, function);
+        let _ = body.declarations.search(self.ctx, &mut searcher);
+        let _ = body.statements.search(self.ctx, &mut searcher);
+
+        for (pos, ent) in searcher.references {
+            let (message, code) = if let Some(overloaded) = OverloadedEnt::from_any(ent) {
+                (
+                    format!("Pure function cannot call impure {}", overloaded.describe()),
+                    ErrorCode::ImpureCall,
+                )
+            } else {
+                // Aliases are described as such, not by the object they denote
+                let object = match ent.kind() {
+                    AnyEntKind::Object(_) => ObjectEnt::from_any(ent)
+                        .map_or_else(|| ent.describe(), |object| object.describe_class()),
+                    _ => ent.describe(),
+                };
+                (
+                    format!("Pure function cannot reference {object} declared outside of it"),
+                    ErrorCode::PurityViolation,
+                )
+            };
+            diagnostics.push(
+                Diagnostic::new(pos, message, code)
+                    .related(
+                        self.ctx.get_pos(function.src_span.start_token),
+                        format!("In function '{}'", function.designator()),
+                    )
+                    .opt_related(ent.decl_pos(), "Declared here"),
+            );
+        }
+    }
+
+    /// LRM 4.2.2.1 The formal parameters of a function shall be of mode in.
+    /// VHDL-2019 lifts this restriction
+    fn check_function_parameter_modes(
+        &self,
+        parameters: &[InterfaceDeclaration],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.standard >= VHDLStandard::VHDL2019 {
+            return;
+        }
+        for parameter in parameters {
+            if let InterfaceDeclaration::Object(object) = parameter {
+                if let ModeIndication::Simple(SimpleModeIndication {
+                    mode: Some(mode), ..
+                }) = object.mode
+                {
+                    if mode != Mode::In {
+                        diagnostics.add(
+                            object.ident.pos(self.ctx),
+                            format!(
+                                "Function parameter '{}' cannot be of mode {mode}",
+                                object.ident.tree.item
+                            ),
+                            ErrorCode::IllegalFunctionParameterMode,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// LRM 6.5.2 A default expression is not allowed for a parameter of mode linkage,
+    /// a signal parameter or a variable parameter of a mode other than in
+    fn check_parameter_defaults(
+        &self,
+        parameters: &[InterfaceDeclaration],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for parameter in parameters {
+            let InterfaceDeclaration::Object(object) = parameter else {
+                continue;
+            };
+            let ModeIndication::Simple(SimpleModeIndication {
+                mode,
+                class,
+                expression: Some(ref expression),
+                ..
+            }) = object.mode
+            else {
+                continue;
+            };
+            let mode = mode.unwrap_or(Mode::In);
+
+            let message = if mode == Mode::Linkage {
+                format!(
+                    "Parameter '{}' of mode linkage cannot have a default value",
+                    object.ident.tree.item
+                )
+            } else if class == ObjectClass::Signal {
+                format!(
+                    "Signal parameter '{}' cannot have a default value",
+                    object.ident.tree.item
+                )
+            } else if class == ObjectClass::Variable && mode != Mode::In {
+                format!(
+                    "Variable parameter '{}' of mode {mode} cannot have a default value",
+                    object.ident.tree.item
+                )
+            } else {
+                continue;
+            };
+            diagnostics.add(
+                expression.pos(self.ctx),
+                message,
+                ErrorCode::IllegalParameterDefault,
+            );
+        }
+    }
+
     pub(crate) fn subprogram_specification(
         &self,
         scope: &Scope<'a>,
@@ -111,6 +249,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 } else {
                     None
                 };
+                self.check_function_parameter_modes(&fun.parameter_list, diagnostics);
+                self.check_parameter_defaults(&fun.parameter_list, diagnostics);
                 let params = self.analyze_parameter_list(
                     &subpgm_region,
                     ent,
@@ -119,7 +259,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 );
                 let return_type =
                     self.resolve_type_mark(&subpgm_region, &mut fun.return_type, diagnostics);
-                (Signature::new(params?, Some(return_type?)), generic_map)
+                let mut signature = Signature::new(params?, Some(return_type?));
+                signature.pure = fun.pure;
+                (signature, generic_map)
             }
             SubprogramSpecification::Procedure(procedure) => {
                 let generic_map = if let Some(header) = &mut procedure.header {
@@ -127,6 +269,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 } else {
                     None
                 };
+                self.check_parameter_defaults(&procedure.parameter_list, diagnostics);
                 let params = self.analyze_parameter_list(
                     &subpgm_region,
                     ent,
@@ -488,3 +631,73 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         None
     }
 }
+
+/// Search for references within a pure function that are only allowed in impure functions
+struct FindImpureReferences<'a> {
+    arena: &'a Arena,
+    function: EntRef<'a>,
+    references: Vec<(SrcPos, EntRef<'a>)>,
+}
+
+impl<'a> FindImpureReferences<'a> {
+    fn new(arena: &'a Arena, function: EntRef<'a>) -> FindImpureReferences<'a> {
+        FindImpureReferences {
+            arena,
+            function,
+            references: Vec::new(),
+        }
+    }
+
+    fn is_impure(&self, ent: EntRef<'a>) -> bool {
+        match ent.kind() {
+            // Subprogram parameters are also referenced as formals of named associations,
+            // ports of an entity or block are ordinary signals
+            AnyEntKind::Object(object) => {
+                object.class != ObjectClass::Constant
+                    && (object.iface.is_none() || !Self::is_parameter(ent))
+                    && !self.is_declared_within(ent)
+            }
+            AnyEntKind::ObjectAlias { base_object, .. } => {
+                base_object.class() != ObjectClass::Constant && !self.is_declared_within(ent)
+            }
+            AnyEntKind::File(_) | AnyEntKind::InterfaceFile(_) => !self.is_declared_within(ent),
+            AnyEntKind::Overloaded(overloaded) => {
+                overloaded.signature().return_type().is_some() && !overloaded.signature().is_pure()
+            }
+            _ => false,
+        }
+    }
+
+    fn is_parameter(ent: EntRef<'a>) -> bool {
+        ent.parent
+            .is_some_and(|parent| matches!(parent.kind(), AnyEntKind::Overloaded(_)))
+    }
+
+    fn is_declared_within(&self, ent: EntRef<'a>) -> bool {
+        let mut parent = ent.parent;
+        while let Some(ent) = parent {
+            if ent.id() == self.function.id() {
+                return true;
+            }
+            parent = ent.parent;
+        }
+        false
+    }
+}
+
+impl Searcher for FindImpureReferences<'_> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            let ent = self.arena.get(id);
+            if self.is_impure(ent) {
+                self.references.push((pos.clone(), ent));
+            }
+        }
+        SearchState::NotFinished
+    }
+}
@@ -1,4 +1,5 @@
 use super::analyze::*;
+use super::names::ResolvedName;
 use super::scope::*;
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
@@ -20,14 +21,159 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         target: &mut WithTokenSpan<Target>,
         assignment_type: AssignmentType,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> EvalResult<TypeEnt<'a>> {
+    ) -> EvalResult<TargetType<'a>> {
         match target.item {
-            Target::Name(ref mut name) => {
-                self.resolve_target_name(scope, name, target.span, assignment_type, diagnostics)
+            Target::Name(ref mut name) => self
+                .resolve_target_name(scope, name, target.span, assignment_type, diagnostics)
+                .map(TargetType::Name),
+            Target::Aggregate(ref mut assocs) => Ok(TargetType::Aggregate(
+                self.resolve_aggregate_target(scope, assocs, assignment_type, diagnostics)?,
+            )),
+        }
+    }
+
+    /// Each element of an aggregate target must itself be a valid target.
+    /// The choices are analyzed once the type of the right hand side is known.
+    fn resolve_aggregate_target(
+        &self,
+        scope: &Scope<'a>,
+        assocs: &mut [ElementAssociation],
+        assignment_type: AssignmentType,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Vec<Option<Subtype<'a>>>> {
+        let mut elements = Vec::with_capacity(assocs.len());
+        for assoc in assocs.iter_mut() {
+            let expr = match assoc {
+                ElementAssociation::Positional(ref mut expr) => expr,
+                ElementAssociation::Named(ref mut choices, ref mut expr) => {
+                    for choice in choices.iter_mut() {
+                        if let Choice::DiscreteRange(ref mut drange) = choice.item {
+                            self.drange_unknown_type(scope, drange, diagnostics)?;
+                        }
+                    }
+                    expr
+                }
+            };
+
+            let subtype = match expr.item {
+                Expression::Name(ref mut name) => as_fatal(self.resolve_target_name(
+                    scope,
+                    name,
+                    expr.span,
+                    assignment_type,
+                    diagnostics,
+                ))?,
+                Expression::Aggregate(ref mut assocs) => {
+                    self.resolve_aggregate_target(scope, assocs, assignment_type, diagnostics)?;
+                    None
+                }
+                _ => {
+                    diagnostics.add(
+                        expr.pos(self.ctx),
+                        "Expression may not be the target of an assignment",
+                        ErrorCode::MismatchedKinds,
+                    );
+                    None
+                }
+            };
+            elements.push(subtype);
+        }
+        Ok(elements)
+    }
+
+    /// Check that the type of the right hand side can be assigned to the elements of
+    /// an aggregate target
+    pub fn check_aggregate_target(
+        &self,
+        target: &mut WithTokenSpan<Target>,
+        elements: &[Option<Subtype<'a>>],
+        typ: TypeEnt<'a>,
+        pos: &SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Target::Aggregate(ref mut assocs) = target.item else {
+            return;
+        };
+
+        match typ.base().kind() {
+            Type::Record(region) => {
+                for (idx, (assoc, subtype)) in assocs.iter_mut().zip(elements).enumerate() {
+                    let (elem, expr) = match assoc {
+                        ElementAssociation::Positional(ref expr) => {
+                            (region.nth(idx).copied(), expr)
+                        }
+                        ElementAssociation::Named(ref mut choices, ref expr) => {
+                            let [choice] = choices.as_mut_slice() else {
+                                continue;
+                            };
+                            let choice_pos = choice.pos(self.ctx);
+                            let Choice::Expression(ref mut choice_expr) = choice.item else {
+                                continue;
+                            };
+                            let Some(simple_name) =
+                                as_name_mut(choice_expr).and_then(as_simple_name_mut)
+                            else {
+                                diagnostics.add(
+                                    choice_pos,
+                                    "Record aggregate choice must be a simple name",
+                                    ErrorCode::MismatchedKinds,
+                                );
+                                continue;
+                            };
+                            let Some(elem) = region.lookup(&simple_name.item) else {
+                                diagnostics.push(Diagnostic::no_declaration_within(
+                                    &typ,
+                                    &choice_pos,
+                                    &simple_name.item,
+                                ));
+                                continue;
+                            };
+                            simple_name.set_unique_reference(&elem);
+                            (Some(elem), expr)
+                        }
+                    };
+
+                    if let (Some(elem), Some(subtype)) = (elem, subtype) {
+                        if subtype.base() != elem.type_mark().base() {
+                            diagnostics.push(Diagnostic::type_mismatch(
+                                &expr.pos(self.ctx),
+                                &format!("Aggregate target of {}", subtype.type_mark().describe()),
+                                elem.type_mark(),
+                            ));
+                        }
+                    }
+                }
+            }
+            Type::Array {
+                elem_type, indexes, ..
+            } if indexes.len() == 1 => {
+                for (assoc, subtype) in assocs.iter().zip(elements) {
+                    let expr = match assoc {
+                        ElementAssociation::Positional(ref expr) => expr,
+                        ElementAssociation::Named(_, ref expr) => expr,
+                    };
+                    if let Some(subtype) = subtype {
+                        // An element is either a single element or a slice of the array
+                        if subtype.base() != elem_type.base() && subtype.base() != typ.base() {
+                            diagnostics.push(Diagnostic::type_mismatch(
+                                &expr.pos(self.ctx),
+                                &format!("Aggregate target of {}", subtype.type_mark().describe()),
+                                *elem_type,
+                            ));
+                        }
+                    }
+                }
             }
-            Target::Aggregate(ref mut assocs) => {
-                self.analyze_aggregate(scope, assocs, diagnostics)?;
-                Err(EvalError::Unknown)
+            Type::Array { .. } => {}
+            _ => {
+                diagnostics.add(
+                    pos,
+                    format!(
+                        "Aggregate target requires a composite type, got {}",
+                        typ.describe()
+                    ),
+                    ErrorCode::TypeMismatch,
+                );
             }
         }
     }
@@ -39,24 +185,56 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         target_pos: TokenSpan,
         assignment_type: AssignmentType,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> EvalResult<TypeEnt<'a>> {
-        let object_name = self.resolve_object_name(
-            scope,
-            target_pos,
-            target,
-            "may not be the target of an assignment",
-            ErrorCode::MismatchedKinds,
-            diagnostics,
-        )?;
-        if !object_name.base.can_be_assigned_to() {
+    ) -> EvalResult<Subtype<'a>> {
+        let resolved = self.name_resolve(scope, target_pos, target, diagnostics)?;
+        let object_name = match resolved {
+            ResolvedName::ObjectName(oname) => oname,
+            ResolvedName::Final(ent) if matches!(ent.kind(), AnyEntKind::LoopParameter(_)) => {
+                diagnostics.push(
+                    Diagnostic::new(
+                        target_pos.pos(self.ctx),
+                        format!("{} may not be the target of an assignment", ent.describe()),
+                        ErrorCode::NotAssignable,
+                    )
+                    .opt_related(ent.decl_pos(), "Declared here"),
+                );
+                return Err(EvalError::Unknown);
+            }
+            _ => {
+                diagnostics.add(
+                    target_pos.pos(self.ctx),
+                    format!(
+                        "{} may not be the target of an assignment",
+                        resolved.describe()
+                    ),
+                    ErrorCode::MismatchedKinds,
+                );
+                return Err(EvalError::Unknown);
+            }
+        };
+        if let Type::Protected(..) = object_name.type_mark().base().kind() {
+            // Protected type objects may only be operated on using their methods
             diagnostics.add(
                 target_pos.pos(self.ctx),
                 format!(
-                    "{} may not be the target of an assignment",
-                    object_name.base.describe_class()
+                    "{} of protected type '{}' may not be the target of an assignment",
+                    object_name.base.describe_class(),
+                    object_name.type_mark().designator()
                 ),
                 ErrorCode::MismatchedKinds,
             );
+        } else if !object_name.base.can_be_assigned_to() {
+            diagnostics.push(
+                Diagnostic::new(
+                    target_pos.pos(self.ctx),
+                    format!(
+                        "{} may not be the target of an assignment",
+                        object_name.base.describe_class()
+                    ),
+                    ErrorCode::NotAssignable,
+                )
+                .opt_related(object_name.base.decl_pos(), "Declared here"),
+            );
         } else if !object_name.base.is_valid_assignment_type(assignment_type) {
             diagnostics.add(
                 target_pos.pos(self.ctx),
@@ -68,10 +246,27 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 ErrorCode::MismatchedKinds,
             );
         }
-        Ok(object_name.type_mark())
+        let subtype = object_name.subtype();
+        if object_name.type_mark.is_some() && subtype.type_mark().static_length().is_none() {
+            // Slices and record elements are constrained by the name rather than the object
+            Ok(Subtype::with_length(
+                subtype.type_mark(),
+                self.static_name_length(target),
+            ))
+        } else {
+            Ok(subtype)
+        }
     }
 }
 
+/// The type of an assignment target
+pub enum TargetType<'a> {
+    Name(Subtype<'a>),
+    /// The subtypes of the elements of an aggregate target.
+    /// Nested aggregates and illegal elements have no subtype.
+    Aggregate(Vec<Option<Subtype<'a>>>),
+}
+
 #[derive(Copy, Clone)]
 pub enum AssignmentType {
     // Assignment with <=
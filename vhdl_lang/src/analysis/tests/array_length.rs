@@ -0,0 +1,196 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+fn length_mismatch(value: Code, expected: i64, got: i64) -> Diagnostic {
+    Diagnostic::new(
+        value,
+        format!("Length mismatch, expected {expected} elements but got {got}"),
+        ErrorCode::LengthMismatch,
+    )
+}
+
+#[test]
+fn length_mismatch_in_assignments() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal byte : bit_vector(7 downto 0);
+  signal nibble : bit_vector(3 downto 0);
+  signal same : bit_vector(byte'range);
+begin
+  byte <= nibble;
+  byte <= x\"F\";
+  byte <= \"101\";
+  byte(3 downto 0) <= byte;
+  byte <= ('0', '1', '0');
+  byte <= nibble & \"10\";
+  same <= nibble;
+
+  byte <= x\"FF\";
+  byte(7 downto 4) <= nibble;
+  byte <= nibble & nibble;
+  byte <= '1' & nibble & \"010\";
+  byte <= (others => '0');
+  same <= byte;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            length_mismatch(code.sa("byte <= ", "nibble"), 8, 4),
+            length_mismatch(code.s1("x\"F\""), 8, 4),
+            length_mismatch(code.s1("\"101\""), 8, 3),
+            length_mismatch(code.sa("byte(3 downto 0) <= ", "byte"), 4, 8),
+            length_mismatch(code.s1("('0', '1', '0')"), 8, 3),
+            length_mismatch(code.s1("nibble & \"10\""), 8, 6),
+            length_mismatch(code.sa("same <= ", "nibble"), 8, 4),
+        ],
+    );
+}
+
+#[test]
+fn length_mismatch_of_constrained_subtypes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  subtype byte_t is bit_vector(7 downto 0);
+  type rec_t is record
+    data : bit_vector(0 to 1);
+  end record;
+
+  constant byte_c : byte_t := \"0101\";
+  constant qualified_c : bit_vector := byte_t'(x\"ABC\");
+  constant rec_c : rec_t := (data => \"011\");
+  constant ok_c : byte_t := x\"AB\";
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            length_mismatch(code.s1("\"0101\""), 8, 4),
+            length_mismatch(code.s1("(x\"ABC\")"), 8, 12),
+            length_mismatch(code.s1("\"011\""), 2, 3),
+        ],
+    );
+}
+
+#[test]
+fn no_length_check_of_non_static_lengths() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : natural);
+end entity;
+
+architecture a of ent is
+  signal data : bit_vector(width - 1 downto 0);
+  signal byte : bit_vector(7 downto 0);
+  function get return bit_vector is
+  begin
+    return \"0\";
+  end function;
+begin
+  data <= byte;
+  byte <= data;
+  byte <= get;
+  byte <= (0 => '1', others => '0');
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn aggregate_indexes_follow_the_direction_of_the_target() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  subtype byte_t is bit_vector(7 downto 0);
+  subtype up_t is bit_vector(0 to 3);
+
+  constant positional_c : byte_t := ('1', '0', '1', '0', '1', '0', '1', '0', '1', others => '0');
+  constant down_c : bit_vector(7 downto 0) := (8 => '1', others => '0');
+  constant range_c : byte_t := (3 downto -1 => '1', others => '0');
+  constant up_c : up_t := (4 => '1', others => '0');
+  constant ok_c : byte_t := ('1', '0', 7 downto 6 => '1', others => '0');
+  constant ok_up_c : up_t := ('1', '0', '1', '0', others => '0');
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("'1'", 5),
+                "Positional element at index -1 is outside of the range 7 downto 0",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("8 =>").s1("8"),
+                "Value 8 is outside of the range 7 downto 0",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("-1 =>").s1("-1"),
+                "Value -1 is outside of the range 7 downto 0",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("4 =>").s1("4"),
+                "Value 4 is outside of the range 0 to 3",
+                ErrorCode::OutOfRange,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn aggregate_indexes_of_assignment_targets() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal byte : bit_vector(7 downto 0);
+begin
+  byte <= ('0', '0', '0', '0', '0', '0', '0', '0', '1', others => '0');
+  byte <= ('1', '0', others => '0');
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("'1', others").s1("'1'"),
+            "Positional element at index -1 is outside of the range 7 downto 0",
+            ErrorCode::OutOfRange,
+        )],
+    );
+}
@@ -6,6 +6,7 @@
 
 use super::*;
 use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
 
 #[test]
 fn overloaded_name_may_not_be_assignment_target() {
@@ -170,13 +171,15 @@ end architecture;
         Diagnostic::new(
             code.s("foo1", 3),
             "constant 'foo1' may not be the target of an assignment",
-            ErrorCode::MismatchedKinds,
-        ),
+            ErrorCode::NotAssignable,
+        )
+        .related(code.s1("foo1"), "Declared here"),
         Diagnostic::new(
             code.s("foo2", 2),
             "alias 'foo2' of constant may not be the target of an assignment",
-            ErrorCode::MismatchedKinds,
-        ),
+            ErrorCode::NotAssignable,
+        )
+        .related(code.s1("foo1"), "Declared here"),
     ];
 
     let diagnostics = builder.analyze();
@@ -185,7 +188,8 @@ end architecture;
 
 #[test]
 fn objects_may_be_assignment_target() {
-    let mut builder = LibraryBuilder::new();
+    // Shared variables of non-protected types are only allowed in VHDL-93
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
     builder.code(
         "libname",
         "
@@ -303,13 +307,15 @@ end architecture;
         Diagnostic::new(
             code.s("foo1", 2),
             "interface constant 'foo1' may not be the target of an assignment",
-            ErrorCode::MismatchedKinds,
-        ),
+            ErrorCode::NotAssignable,
+        )
+        .related(code.s1("foo1"), "Declared here"),
         Diagnostic::new(
             code.s("foo2", 2),
             "interface variable 'foo2' of mode in may not be the target of an assignment",
-            ErrorCode::MismatchedKinds,
-        ),
+            ErrorCode::NotAssignable,
+        )
+        .related(code.s1("foo2"), "Declared here"),
     ];
 
     let diagnostics = builder.analyze();
@@ -605,7 +611,8 @@ end package body Test;",
 
 #[test]
 pub fn assignment_mode_checking() {
-    let mut builder = LibraryBuilder::new();
+    // Shared variables of non-protected types are only allowed in VHDL-93
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
     let code = builder.code(
         "libname",
         "\
@@ -667,6 +674,88 @@ end architecture foo;
     )
 }
 
+#[test]
+pub fn out_and_inout_parameters_require_writable_actuals() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+entity bar is
+    port (
+        p_in : in integer;
+        p_out : out integer
+    );
+end bar;
+
+architecture foo of bar is
+    constant c : integer := 0;
+    signal s : integer;
+
+    procedure proc(
+        variable f_out: out integer;
+        variable f_inout: inout integer;
+        signal f_sig: out integer;
+        f_in: in integer
+    ) is
+    begin
+    end proc;
+
+    procedure wrapper(x : in integer; variable y : out integer) is
+    begin
+        proc(y, y, s, x);
+        proc(x, y, s, 0);
+    end wrapper;
+begin
+
+    baz: process is
+        variable v: integer;
+    begin
+        proc(v, v, s, c + 1);
+        proc(c, v, p_out, 0);
+        proc(v, v + 1, s, 0);
+        proc(v, s, p_in, 0);
+    end process baz;
+
+end architecture foo;
+",
+    );
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("proc(x, y, s, 0)").s1("x"),
+                "actual for out parameter must be a writable object",
+                ErrorCode::InterfaceModeMismatch,
+            )
+            .related(code.s1("f_out"), "Defined here"),
+            Diagnostic::new(
+                code.s1("proc(c, v, p_out, 0)").sa("(", "c"),
+                "actual for out parameter must be a writable object",
+                ErrorCode::InterfaceModeMismatch,
+            )
+            .related(code.s1("f_out"), "Defined here"),
+            Diagnostic::new(
+                code.s1("v + 1"),
+                "actual for inout parameter must be a writable object",
+                ErrorCode::InterfaceModeMismatch,
+            )
+            .related(code.s1("f_inout"), "Defined here"),
+            Diagnostic::new(
+                code.s1("proc(v, s, p_in, 0)").s1("s"),
+                "Name must denote a variable name",
+                ErrorCode::InterfaceModeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("proc(v, s, p_in, 0)").s1("p_in"),
+                "actual for out parameter must be a writable object",
+                ErrorCode::InterfaceModeMismatch,
+            )
+            .related(code.s1("f_sig"), "Defined here"),
+        ],
+    )
+}
+
 #[test]
 fn legal_file_names() {
     let mut builder = LibraryBuilder::new();
@@ -697,3 +786,221 @@ end package body;
     );
     check_no_diagnostics(&builder.analyze())
 }
+
+#[test]
+fn aggregate_target_elements_are_typechecked_against_expression() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type pair_t is record
+    first : bit;
+    second : natural;
+  end record;
+
+  constant pair_value : pair_t := ('0', 0);
+  signal x : bit;
+  signal y : natural;
+  signal bits : bit_vector(0 to 1);
+begin
+  (x, y) <= pair_value;
+  (second => y, first => x) <= pair_value;
+  (x, bits(1)) <= bits;
+  (y, x) <= pair_value;
+  (x, bits) <= bit_vector'(\"010\");
+  (x, y) <= y;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("(y, x) <= ").s1("y"),
+                "Aggregate target of subtype 'NATURAL' does not match type 'BIT'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("(y, x) <= ").s1("x"),
+                "Aggregate target of type 'BIT' does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("(x, y) <= y").s("y", 2),
+                "Aggregate target requires a composite type, got subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn aggregate_target_elements_must_be_writable() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : bit := '0';
+  signal s : bit;
+begin
+  main : process is
+    variable v : bit;
+  begin
+    (v, c) := bit_vector'(\"01\");
+    (v, s) := bit_vector'(\"01\");
+    (v, '1') := bit_vector'(\"01\");
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("(v, c)").s1("c"),
+                "constant 'c' may not be the target of an assignment",
+                ErrorCode::NotAssignable,
+            )
+            .related(code.s1("c :").s1("c"), "Declared here"),
+            Diagnostic::new(
+                code.s1("(v, s)").s1("s"),
+                "signal 's' may not be the target of a variable assignment",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("'1'"),
+                "Expression may not be the target of an assignment",
+                ErrorCode::MismatchedKinds,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn input_ports_and_loop_parameters_may_not_be_assignment_target() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    data : in bit_vector(7 downto 0);
+    valid : in bit
+  );
+end entity;
+
+architecture a of ent is
+  alias data_alias is data;
+begin
+  valid <= '0';
+  data(0) <= '0';
+  data_alias(3 downto 0) <= \"0000\";
+
+  main : process is
+  begin
+    for i in 0 to 1 loop
+      i := 1;
+    end loop;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("valid <=").s1("valid"),
+                "interface signal 'valid' of mode in may not be the target of an assignment",
+                ErrorCode::NotAssignable,
+            )
+            .related(code.s1("valid"), "Declared here"),
+            Diagnostic::new(
+                code.s1("data(0)"),
+                "interface signal 'data' of mode in may not be the target of an assignment",
+                ErrorCode::NotAssignable,
+            )
+            .related(code.s1("data"), "Declared here"),
+            Diagnostic::new(
+                code.s1("data_alias(3 downto 0)"),
+                "interface alias 'data_alias' of signal of mode in may not be the target of an assignment",
+                ErrorCode::NotAssignable,
+            )
+            .related(code.s1("data"), "Declared here"),
+            Diagnostic::new(
+                code.s1("i := 1").s1("i"),
+                "loop parameter 'i' may not be the target of an assignment",
+                ErrorCode::NotAssignable,
+            )
+            .related(code.s1("i in").s1("i"), "Declared here"),
+        ],
+    );
+}
+
+#[test]
+fn pulse_rejection_limit_is_typechecked_and_bounded() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant width : natural := 3;
+  signal pulse : bit;
+begin
+  pulse <= reject width inertial '1' after 5 ns;
+  pulse <= reject 1 ns inertial '1' after 2 ns, '0' after 4 ns;
+  pulse <= reject 5 ns inertial '1' after 2 ns;
+  pulse <= reject -1 ns inertial '1' after 2 ns;
+
+  process
+  begin
+    pulse <= reject 3 ns inertial '0' when width > 2 else '1' after 4 ns;
+    pulse <= transport '0' after 1 ns;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s("width", 2),
+                "constant 'width' of subtype 'NATURAL' does not match physical type 'TIME'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("reject 5 ns").s1("5 ns"),
+                "Pulse rejection limit must not be greater than the delay of the first waveform element",
+                ErrorCode::InvalidRejectTime,
+            ),
+            Diagnostic::new(
+                code.s1("-1 ns"),
+                "Pulse rejection limit must not be negative",
+                ErrorCode::InvalidRejectTime,
+            ),
+            Diagnostic::new(
+                code.s1("3 ns"),
+                "Pulse rejection limit must not be greater than the delay of the first waveform element",
+                ErrorCode::InvalidRejectTime,
+            ),
+        ],
+    );
+}
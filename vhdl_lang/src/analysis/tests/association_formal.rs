@@ -587,3 +587,339 @@ end architecture;
         code.s1("prt1").pos()
     );
 }
+
+#[test]
+fn only_formals_with_default_may_be_open() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+generic (
+    gen_default : natural := 0;
+    gen : natural);
+port (
+    signal inport_default : in natural := 0;
+    signal inport : in natural;
+    signal outport : out natural);
+end entity;
+
+architecture a of ent2 is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+    procedure proc(arg_default : natural := 0; arg : natural) is
+    begin
+    end procedure;
+begin
+    inst: entity work.ent2
+        generic map (
+        gen_default => open,
+        gen => open)
+        port map (
+        inport_default => open,
+        inport => open,
+        outport => open);
+
+    proc(arg_default => open, arg => open);
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("gen => open").s1("open"),
+                "Generic 'gen' without a default value cannot be left open",
+                ErrorCode::Unassociated,
+            ),
+            Diagnostic::new(
+                code.s1("inport => open").s1("open"),
+                "Port 'inport' without a default value cannot be left open",
+                ErrorCode::Unassociated,
+            ),
+            Diagnostic::new(
+                code.s1("arg => open").s1("open"),
+                "Parameter 'arg' without a default value cannot be left open",
+                ErrorCode::Unassociated,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn function_parameter_without_default_may_not_be_open() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    function fun(arg : natural; arg_default : natural := 0) return natural is
+    begin
+        return arg + arg_default;
+    end function;
+
+    constant c0 : natural := fun(1, open);
+    constant c1 : natural := fun(arg => open);
+begin
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("arg => open").s1("open"),
+            "Parameter 'arg' without a default value cannot be left open",
+            ErrorCode::Unassociated,
+        )],
+    );
+}
+
+#[test]
+fn typecheck_block_header_map_aspects() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    signal sig : natural;
+    signal bsig : bit;
+begin
+    blk: block
+        generic (gen : natural);
+        generic map (gen => '0');
+        port (prt : in natural; prt2 : out bit);
+        port map (prt => bsig, missing => sig);
+    begin
+    end block;
+
+    good_blk: block
+        generic (gen : natural);
+        generic map (0);
+        port (prt : in natural; prt2 : out bit);
+        port map (sig, bsig);
+    begin
+    end block;
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("'0'"),
+                "character literal does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("prt => bsig").s1("bsig"),
+                "signal 'bsig' of type 'BIT' does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of 'missing'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn generic_actual_may_reference_parent_generic() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+    generic (width : natural; name : string);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+package gpkg is
+    generic (width : natural);
+end package;
+
+entity parent is
+    generic (parent_width : natural; parent_name : string);
+end entity;
+
+architecture a of parent is
+    package inner is new work.gpkg generic map (width => parent_width);
+begin
+    good: entity work.child
+        generic map (width => parent_width, name => parent_name);
+
+    bad: entity work.child
+        generic map (width => parent_name, name => parent_name);
+end architecture;
+    ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("parent_name", 3),
+            "constant 'parent_name' of array type 'STRING' does not match subtype 'NATURAL'",
+            ErrorCode::TypeMismatch,
+        )],
+    );
+
+    for idx in 2..=3 {
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s("parent_width", idx).end()),
+            Some(code.s1("parent_width").pos())
+        );
+    }
+}
+
+#[test]
+fn component_instance_map_aspects_are_checked_against_the_component() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    component comp is
+        generic (width : natural := 8);
+        port (
+            clk : in bit;
+            en : in bit := '1';
+            dout : out bit_vector(width - 1 downto 0));
+    end component;
+
+    signal clk : bit;
+    signal data : bit_vector(7 downto 0);
+begin
+    good: component comp
+        port map (clk => clk, dout => data);
+
+    bad: component comp
+        generic map (depth => 4)
+        port map (dout => clk);
+end architecture;
+    ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("depth"),
+                "No declaration of 'depth'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("bad: component comp").s("comp", 2),
+                "No association of port 'clk' : in",
+                ErrorCode::Unassociated,
+            )
+            .related(code.s1("clk"), "Defined here"),
+            Diagnostic::new(
+                code.s1("dout => clk").s1("clk"),
+                "signal 'clk' of type 'BIT' does not match array type 'BIT_VECTOR'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("dout => data").start()),
+        Some(code.s1("dout").pos())
+    );
+}
+
+#[test]
+fn generic_actual_must_be_globally_static() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+    generic (width : natural; init : bit_vector);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity parent is
+    generic (parent_width : natural);
+end entity;
+
+architecture a of parent is
+    constant c : natural := 4;
+    signal sig : natural;
+    signal data : bit_vector(7 downto 0);
+
+    impure function get return natural is
+    begin
+        return sig;
+    end function;
+begin
+    good: entity work.child
+        generic map (width => parent_width + c + data'length, init => (others => '0'));
+
+    bad_signal: entity work.child
+        generic map (width => sig + 1, init => data);
+
+    bad_call: entity work.child
+        generic map (width => get, init => \"\" & data(0));
+end architecture;
+    ",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("sig + 1").s1("sig"),
+                "Signal 'sig' is not globally static and cannot be the actual of generic 'width'",
+                ErrorCode::NonStaticGenericActual,
+            )
+            .related(code.s1("width"), "Defined here"),
+            Diagnostic::new(
+                code.s1("init => data").s1("data"),
+                "Signal 'data' is not globally static and cannot be the actual of generic 'init'",
+                ErrorCode::NonStaticGenericActual,
+            )
+            .related(code.s1("init"), "Defined here"),
+            Diagnostic::new(
+                code.s1("width => get").s1("get"),
+                "Function get[return NATURAL] is not globally static and cannot be the actual of generic 'width'",
+                ErrorCode::NonStaticGenericActual,
+            )
+            .related(code.s1("width"), "Defined here"),
+            Diagnostic::new(
+                code.s1("data(0)").s1("data"),
+                "Signal 'data' is not globally static and cannot be the actual of generic 'init'",
+                ErrorCode::NonStaticGenericActual,
+            )
+            .related(code.s1("init"), "Defined here"),
+        ],
+    );
+}
@@ -0,0 +1,421 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn case_must_cover_all_enumeration_literals() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done, error);
+  signal state : state_t;
+  signal b : boolean;
+begin
+  process
+  begin
+    case state is
+      when idle => null;
+      when busy => null;
+    end case;
+
+    case state is
+      when idle | busy => null;
+      when done to error => null;
+    end case;
+
+    case b is
+      when true => null;
+    end case;
+
+    case b is
+      when false => null;
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.sa("case ", "state"),
+                "Missing choices for 'done', 'error' of type 'state_t'. Add the missing choices or a choice 'others'",
+                ErrorCode::MissingChoice,
+            ),
+            Diagnostic::new(
+                code.sa("case ", "b"),
+                "Missing choices for 'FALSE' of type 'BOOLEAN'. Add the missing choices or a choice 'others'",
+                ErrorCode::MissingChoice,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn case_must_cover_all_values_of_bounded_integer_subtypes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type small_t is range 0 to 7;
+  signal i : integer range 0 to 3;
+  signal s : small_t;
+  signal n : natural;
+  signal j : integer;
+begin
+  process
+  begin
+    case i is
+      when 0 | 1 | 2 => null;
+    end case;
+
+    case s is
+      when 1 | 3 to 4 => null;
+    end case;
+
+    case i is
+      when 3 downto 0 => null;
+    end case;
+
+    case n is
+      when 0 => null;
+    end case;
+
+    case j is
+      when 0 => null;
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("case i", 1).s1("i"),
+                "Missing choices for 3 of integer type 'INTEGER'. Add the missing choices or a choice 'others'",
+                ErrorCode::MissingChoice,
+            ),
+            Diagnostic::new(
+                code.sa("case ", "s"),
+                "Missing choices for 0, 2, 5 to 7 of integer type 'small_t'. Add the missing choices or a choice 'others'",
+                ErrorCode::MissingChoice,
+            ),
+            Diagnostic::new(
+                code.sa("case ", "n"),
+                "Missing choices for 1 to 2147483647 of subtype 'NATURAL'. Add the missing choices or a choice 'others'",
+                ErrorCode::MissingChoice,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn case_reports_duplicate_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done);
+  signal state : state_t;
+  signal c : character;
+  signal i : integer;
+begin
+  process
+  begin
+    case state is
+      when idle => null;
+      when busy | idle => null;
+      when idle to done => null;
+    end case;
+
+    case c is
+      when 'a' to 'z' => null;
+      when 'q' => null;
+      when others => null;
+    end case;
+
+    case i is
+      when -1 | 0 => null;
+      when 1 to 10 => null;
+      when 10 downto 5 => null;
+      when 3 to 2 => null;
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("idle", 3),
+                "Choice 'idle' is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s("idle", 2), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("idle to done"),
+                "Choice 'idle' is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s("idle", 2), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("'q'"),
+                "Choice 'q' is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("'a' to 'z'"), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("10 downto 5"),
+                "Choice 5 is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("1 to 10"), "Previously covered here"),
+        ],
+    );
+}
+
+#[test]
+fn case_coverage_is_skipped_for_choices_that_are_not_static() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (n : natural);
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done);
+  subtype active_t is state_t range busy to done;
+  constant first : state_t := state_t'low;
+  signal state : state_t;
+  signal active : active_t;
+  signal i : integer range 0 to n;
+begin
+  process
+  begin
+    case state is
+      when first => null;
+      when busy | done => null;
+    end case;
+
+    case active is
+      when busy => null;
+      when done => null;
+    end case;
+
+    case i is
+      when 0 => null;
+      when 1 => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn selected_assignments_and_case_generate_report_duplicate_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done);
+  constant sel : natural := 1;
+  signal state : state_t;
+  signal i, o : integer;
+begin
+  with state select o <=
+    0 when idle | busy | idle,
+    1 when others;
+
+  with i select o <=
+    0 when 0 to 7,
+    1 when 4 to 9,
+    2 when others;
+
+  process
+    variable v : integer;
+  begin
+    with i select v :=
+      0 when 1,
+      1 when 1,
+      2 when others;
+    wait;
+  end process;
+
+  gen: case sel generate
+    when 0 | 1 =>
+    when 2 to 3 | 1 =>
+    when others =>
+  end generate;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("idle", 3),
+                "Choice 'idle' is already covered",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s("idle", 2), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("4 to 9"),
+                "Choice 4 is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("0 to 7"), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("1 when 1").s("1", 2),
+                "Choice 1 is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("0 when 1").s1("1"), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("2 to 3 | 1").s1("| 1").s1("1"),
+                "Choice 1 is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("0 | 1").s1("1"), "Previously covered here"),
+        ],
+    );
+}
+
+#[test]
+fn case_reports_duplicate_string_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : std_logic_vector(1 downto 0);
+  signal b : bit_vector(3 downto 0);
+  signal o : bit;
+begin
+  process
+  begin
+    case s is
+      when \"00\" => o <= '0';
+      when \"01\" | \"00\" => o <= '1';
+      when others => o <= '0';
+    end case;
+
+    case b is
+      when \"1111\" => o <= '0';
+      when x\"F\" => o <= '1';
+      when others => o <= '0';
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    builder.add_std_logic_1164();
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("\"00\"", 2),
+                "Choice \"00\" is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("\"00\""), "Previously covered here"),
+            Diagnostic::new(
+                code.s1("x\"F\""),
+                "Choice \"1111\" is already covered by a previous alternative",
+                ErrorCode::DuplicateChoice,
+            )
+            .related(code.s1("\"1111\""), "Previously covered here"),
+        ],
+    );
+}
+
+#[test]
+fn selected_assignments_report_duplicate_string_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sel : std_logic_vector(1 downto 0);
+  signal y : std_logic;
+begin
+  with sel select y <=
+    '0' when \"00\",
+    '1' when \"00\",
+    'Z' when \"01\" | \"10\",
+    'X' when others;
+end architecture;
+",
+    );
+    builder.add_std_logic_1164();
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("\"00\"", 2),
+            "Choice \"00\" is already covered by a previous alternative",
+            ErrorCode::DuplicateChoice,
+        )
+        .related(code.s1("\"00\""), "Previously covered here")],
+    );
+}
@@ -32,6 +32,44 @@ end entity;
     )
 }
 
+#[test]
+fn suggests_library_clause_for_unresolved_library_name() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_logic_1164();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+-- The architecture
+architecture a of ent is
+  signal s : ieee.std_logic_1164.std_logic;
+begin
+end architecture;
+
+use ieee.std_logic_1164.all;
+package pkg is
+  constant c : natural := missing.value;
+end package;",
+    );
+
+    let insert_at = |substr: &str| {
+        let start = code.s1(substr).start();
+        SrcPos::new(code.source().clone(), crate::data::Range::new(start, start))
+    };
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing(&code, "ieee", 1).fix(insert_at("architecture a"), "library ieee;\n"),
+            missing(&code, "ieee", 2).fix(insert_at("use ieee"), "library ieee;\n"),
+            missing(&code, "missing", 1),
+        ],
+    );
+}
+
 #[test]
 fn library_clause_extends_into_secondary_units() {
     let mut builder = LibraryBuilder::new();
@@ -190,7 +228,90 @@ end context;
     );
 
     let diagnostics = builder.analyze();
-    check_diagnostics(diagnostics, vec![missing(&code, "work", 1)]);
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("work"),
+            "The library logical name 'work' is not allowed in a context declaration",
+            ErrorCode::IllegalContextItem,
+        )],
+    );
+}
+
+#[test]
+fn work_is_not_allowed_in_context_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant k : natural := 0;
+end package;
+
+context base is
+  library libname;
+  use libname.pkg.all;
+end context;
+
+context ctx is
+  library work;
+  context work.base;
+end context;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("work"),
+                "The library logical name 'work' is not allowed in a context declaration",
+                ErrorCode::IllegalContextItem,
+            ),
+            Diagnostic::new(
+                code.s("work", 2),
+                "The library logical name 'work' is not allowed in a context declaration",
+                ErrorCode::IllegalContextItem,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn context_reference_makes_nested_contexts_visible() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant k : natural := 0;
+end package;
+
+context base is
+  library libname;
+  use libname.pkg.all;
+end context;
+
+context ctx is
+  library libname;
+  context libname.base;
+end context;
+
+context work.ctx;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := k + libname.pkg.k;
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
 }
 
 #[test]
@@ -319,6 +440,7 @@ end entity;
     );
 
     let diagnostics = builder.analyze();
+    let start = code.s1("use").start();
 
     check_diagnostics(
         diagnostics,
@@ -326,6 +448,10 @@ end entity;
             code.s("libname", 1),
             "No declaration of 'libname'",
             ErrorCode::Unresolved,
+        )
+        .fix(
+            SrcPos::new(code.source().clone(), crate::data::Range::new(start, start)),
+            "library libname;\n",
         )],
     )
 }
@@ -525,7 +651,7 @@ end package;
         vec![Diagnostic::new(
             code.s1("const2"),
             "No declaration of 'const2' within package 'pkg'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
 }
@@ -553,11 +679,45 @@ end package;
         vec![Diagnostic::new(
             code.s1("const2"),
             "No declaration of 'const2' within package 'pkg'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
 }
 
+#[test]
+fn check_use_clause_for_missing_name_in_library_package() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_logic_1164();
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.std_ulogic;
+use ieee.std_logic_1164.nonexistent;
+use ieee.missing_pkg.std_ulogic;
+
+package pkg is
+end package;
+        ",
+    );
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("nonexistent"),
+                "No declaration of 'nonexistent' within package 'std_logic_1164'",
+                ErrorCode::NoDeclarationWithin,
+            ),
+            Diagnostic::new(
+                code.s1("missing_pkg"),
+                "No primary unit 'missing_pkg' within library 'ieee'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
 #[test]
 fn check_use_clause_for_missing_name_in_package_instance() {
     let mut builder = LibraryBuilder::new();
@@ -589,7 +749,7 @@ end package;
             Diagnostic::new(
                 code.s1("const2"),
                 "No declaration of 'const2' within package instance 'ipkg'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
         ],
     );
@@ -846,7 +1006,7 @@ end package;
         vec![Diagnostic::new(
             code.s1("missing"),
             "No declaration of 'missing' within package instance 'ipkg'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
 }
@@ -976,7 +1136,7 @@ end package body;
 -- Configuration context clause reference
 use work.pkg.all;
 configuration cfg of ename1 is
-for rtl
+for a
 end for;
 end configuration;
 
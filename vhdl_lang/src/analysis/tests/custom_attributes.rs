@@ -328,3 +328,204 @@ end architecture;
         )],
     );
 }
+
+#[test]
+fn custom_attribute_of_subprograms_labels_and_components() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    attribute myattr : natural;
+
+    procedure proc is
+    begin
+    end procedure;
+    attribute myattr of proc : procedure is 1;
+
+    function fun return natural is
+    begin
+        return 0;
+    end function;
+    function fun(arg : natural) return natural is
+    begin
+        return arg;
+    end function;
+    attribute myattr of fun[return natural] : function is 2;
+
+    component comp is
+    end component;
+    attribute myattr of comp : component is 3;
+
+    attribute myattr of lab : label is 4;
+
+    constant c0 : natural := proc'myattr;
+    constant c1 : natural := fun[return natural]'myattr;
+    constant c2 : natural := comp'myattr;
+    constant c3 : natural := lab'myattr;
+    constant c4 : natural := fun'myattr;
+    constant c5 : natural := fun[natural return natural]'myattr;
+begin
+    lab: process
+    begin
+        wait;
+    end process;
+end architecture;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("fun'myattr"),
+                "Signature required for the prefix 'fun' of a user defined attribute",
+                ErrorCode::SignatureRequired,
+            ),
+            Diagnostic::new(
+                code.s1("fun[natural return natural]'myattr").s1("myattr"),
+                "Attribute 'myattr' is not specified for function fun[NATURAL return NATURAL]",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("fun[return natural]'myattr").start()),
+        Some(code.s1("function fun return natural").s("fun", 2).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("lab'myattr").start()),
+        Some(code.s1("lab: process").s1("lab").pos())
+    );
+}
+
+#[test]
+fn custom_attribute_must_be_specified_for_the_prefix() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    attribute myattr : natural;
+
+    signal good, bad : natural;
+    attribute myattr of good : signal is 1;
+
+    constant c0 : natural := good'myattr;
+    constant c1 : boolean := good'myattr;
+    constant c2 : natural := bad'myattr;
+begin
+end architecture;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("good'myattr;\n    constant c2").s1("good'myattr"),
+                "Expression of integer type 'INTEGER' does not match type 'BOOLEAN'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("bad'myattr").s1("myattr"),
+                "Attribute 'myattr' is not specified for signal 'bad'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+
+    // The unspecified attribute still refers to its declaration
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("bad'myattr").s1("myattr").start()),
+        Some(code.s1("myattr").pos())
+    );
+}
+
+#[test]
+fn attribute_specification_must_follow_the_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    attribute myattr : natural;
+    attribute myattr of late : signal is 1;
+    attribute myattr of missing : signal is 1;
+    signal late : natural;
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("of late").s1("late"),
+                "Attribute specification of 'late' must come after its declaration",
+                ErrorCode::MisplacedAttributeSpec,
+            )
+            .related(code.s1("signal late").s1("late"), "Declared here"),
+            Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of 'missing'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn attribute_specification_must_precede_its_use() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    attribute myattr : natural;
+    signal good, bad : natural;
+    attribute myattr of good : signal is 1;
+    constant c0 : natural := good'myattr;
+    constant c1 : natural := bad'myattr;
+    attribute myattr of bad : signal is 2;
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("bad'myattr").s1("myattr"),
+                "Attribute 'myattr' is not specified for signal 'bad'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("of bad").s1("bad"),
+                "Attribute 'myattr' of signal 'bad' is specified after its use",
+                ErrorCode::MisplacedAttributeSpec,
+            )
+            .related(code.s1("bad'myattr").s1("myattr"), "Used here")
+            .related(code.s1("bad"), "Declared here"),
+        ],
+    );
+}
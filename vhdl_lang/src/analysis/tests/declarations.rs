@@ -21,7 +21,7 @@ architecture arch of ent is
 function my_func return natural is
     signal x : bit;
 begin
-
+    return 0;
 end my_func;
 begin
 
@@ -50,6 +50,52 @@ end architecture;
     )
 }
 
+#[test]
+pub fn non_shared_variable_in_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+package pkg is
+    type counter_t is protected
+        procedure increment;
+    end protected;
+
+    variable count : natural;
+    shared variable counter : counter_t;
+end package;
+
+package body pkg is
+    type counter_t is protected body
+        procedure increment is
+        begin
+        end procedure;
+    end protected body;
+end package body;
+
+package gpkg is
+    generic (width : natural);
+    variable generic_count : natural;
+end package;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("variable count : natural;"),
+                "Variables declared in a package must be shared",
+                ErrorCode::NonSharedVariableInPackage,
+            ),
+            Diagnostic::new(
+                code.s1("variable generic_count : natural;"),
+                "Variables declared in a package must be shared",
+                ErrorCode::NonSharedVariableInPackage,
+            ),
+        ],
+    )
+}
+
 // Issue #242
 #[test]
 pub fn attribute_with_wrong_type() {
@@ -100,3 +146,166 @@ end entity test;
         )],
     )
 }
+
+#[test]
+pub fn signal_of_file_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+entity ent is
+end entity;
+
+architecture arch of ent is
+    type text_file_t is file of string;
+    type line_ptr_t is access string;
+    type rec_t is record
+        line : line_ptr_t;
+    end record;
+    signal log : text_file_t;
+    signal rec : rec_t;
+    constant line : line_ptr_t := null;
+begin
+    process
+        variable file_var : text_file_t;
+        variable line_var : line_ptr_t;
+        variable rec_var : rec_t;
+    begin
+    end process;
+end architecture;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s("text_file_t", 2),
+                "A signal cannot be of file type 'text_file_t'",
+                ErrorCode::IllegalObjectType,
+            ),
+            Diagnostic::new(
+                code.s("rec_t", 2),
+                "A signal cannot be of record type 'rec_t' with a subelement of access type 'line_ptr_t'",
+                ErrorCode::IllegalObjectType,
+            ),
+            Diagnostic::new(
+                code.s("line_ptr_t", 3),
+                "A constant cannot be of access type 'line_ptr_t'",
+                ErrorCode::IllegalObjectType,
+            ),
+            Diagnostic::new(
+                code.s("text_file_t", 3),
+                "A variable cannot be of file type 'text_file_t'",
+                ErrorCode::IllegalObjectType,
+            ),
+        ],
+    );
+}
+
+#[test]
+pub fn array_of_protected_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+package pkg is
+    type counter_t is protected
+    end protected;
+    type counter_arr_t is array (natural range <>) of counter_t;
+    type counter_rec_t is record
+        counter : counter_t;
+    end record;
+    type counter_file_t is file of counter_t;
+    type counter_ptr_t is access counter_t;
+end package;
+
+package body pkg is
+    type counter_t is protected body
+    end protected body;
+end package body;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s("counter_t", 2),
+                "The element type of an array cannot be protected type 'counter_t'",
+                ErrorCode::IllegalElementType,
+            ),
+            Diagnostic::new(
+                code.s("counter_t", 3),
+                "The element type of a record cannot be protected type 'counter_t'",
+                ErrorCode::IllegalElementType,
+            ),
+            Diagnostic::new(
+                code.s("counter_t", 4),
+                "The values of a file cannot be of protected type 'counter_t'",
+                ErrorCode::IllegalElementType,
+            ),
+        ],
+    );
+}
+
+#[test]
+pub fn port_of_access_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+package pkg is
+    type line_ptr_t is access string;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+    port (line : in line_ptr_t);
+end entity;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s("line_ptr_t", 2),
+            "A signal cannot be of access type 'line_ptr_t'",
+            ErrorCode::IllegalObjectType,
+        )],
+    );
+}
+
+#[test]
+pub fn illegal_parameter_defaults() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure legal(constant a : natural := 0; variable b : in natural := 1; c : natural := 2);
+  procedure illegal_variable(variable x : inout natural := 3; variable y : out natural := 4);
+  procedure illegal_signal(signal s : in bit := '0');
+end package;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1(":= 3").s1("3"),
+                "Variable parameter 'x' of mode inout cannot have a default value",
+                ErrorCode::IllegalParameterDefault,
+            ),
+            Diagnostic::new(
+                code.s1(":= 4").s1("4"),
+                "Variable parameter 'y' of mode out cannot have a default value",
+                ErrorCode::IllegalParameterDefault,
+            ),
+            Diagnostic::new(
+                code.s1("'0'"),
+                "Signal parameter 's' cannot have a default value",
+                ErrorCode::IllegalParameterDefault,
+            ),
+        ],
+    );
+}
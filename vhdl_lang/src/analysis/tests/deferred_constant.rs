@@ -156,3 +156,74 @@ end package body;
     let diagnostics = builder.analyze();
     check_diagnostics(diagnostics, vec![duplicate(&code, "a1", 2, 3)]);
 }
+
+#[test]
+fn forbid_use_of_deferred_constant_before_full_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+constant a1 : natural;
+constant b1 : natural := a1 + 1;
+signal s1 : natural := a1;
+end package;
+
+package body pkg is
+constant c1 : natural := a1;
+constant a1 : natural := 0;
+constant d1 : natural := a1;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    let error = |occurence| {
+        Diagnostic::new(
+            code.s("a1", occurence),
+            "Deferred constant 'a1' cannot be used before its full declaration",
+            ErrorCode::DeferredConstantBeforeFullDeclaration,
+        )
+        .related(
+            code.s1("a1"),
+            "Full declaration of 'a1' must appear in the package body",
+        )
+    };
+    check_diagnostics(diagnostics, vec![error(2), error(3), error(4)]);
+}
+
+#[test]
+fn allows_deferred_constant_in_interface_defaults_and_other_packages() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+constant a1 : natural;
+subtype sub_t is natural;
+procedure proc(arg : natural := a1);
+component comp is
+  generic (g : natural := a1);
+end component;
+end package;
+
+package body pkg is
+constant a1 : natural := 0;
+
+procedure proc(arg : natural := a1) is
+  constant c1 : natural := a1;
+begin
+end procedure;
+end package body;
+
+use work.pkg.a1;
+
+package pkg2 is
+constant b1 : natural := a1;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
@@ -0,0 +1,306 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{DesignInterface, DesignInterfaceKind, InterfaceElement};
+use crate::formatting::{FormattingOptions, KeywordCase};
+use pretty_assertions::assert_eq;
+
+fn interface_of(root: &DesignRoot, code: &Code, name: &str) -> Option<DesignInterface> {
+    let ent = root
+        .search_reference(code.source(), code.s1(name).start())
+        .unwrap();
+    root.interface_of(ent)
+}
+
+fn element(
+    name: &str,
+    mode: Option<&str>,
+    subtype: &str,
+    default: Option<&str>,
+) -> InterfaceElement {
+    InterfaceElement {
+        name: name.to_owned(),
+        mode: mode.map(str::to_owned),
+        subtype: subtype.to_owned(),
+        default: default.map(str::to_owned),
+    }
+}
+
+#[test]
+fn lists_generics_and_ports_of_entity_and_component() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity fifo is
+  generic (
+    width : natural := 8;
+    depth : positive);
+  port (
+    clk : bit;
+    data : in bit_vector(width - 1 downto 0);
+    full : out boolean := false);
+end entity;
+
+architecture rtl of fifo is
+  component sub is
+    port (y : inout bit);
+  end component;
+begin
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        interface_of(&root, &code, "fifo"),
+        Some(DesignInterface {
+            kind: DesignInterfaceKind::Entity,
+            library: "libname".to_owned(),
+            name: "fifo".to_owned(),
+            generics: vec![
+                element("width", None, "natural", Some("8")),
+                element("depth", None, "positive", None),
+            ],
+            ports: vec![
+                element("clk", Some("in"), "bit", None),
+                element("data", Some("in"), "bit_vector(width - 1 downto 0)", None),
+                element("full", Some("out"), "boolean", Some("false")),
+            ],
+        })
+    );
+    assert_eq!(
+        interface_of(&root, &code, "sub"),
+        Some(DesignInterface {
+            kind: DesignInterfaceKind::Component,
+            library: "libname".to_owned(),
+            name: "sub".to_owned(),
+            generics: vec![],
+            ports: vec![element("y", Some("inout"), "bit", None)],
+        })
+    );
+    assert_eq!(interface_of(&root, &code, "rtl"), None);
+}
+
+#[test]
+fn instantiation_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity fifo is
+  generic (width : natural := 8);
+  port (clk : in bit; full : out boolean);
+end entity;
+
+entity empty is
+end entity;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        interface_of(&root, &code, "fifo")
+            .unwrap()
+            .instantiation_template("u_fifo", &FormattingOptions::default()),
+        "\
+u_fifo: entity libname.fifo
+  generic map (
+    width => width
+  )
+  port map (
+    clk => clk,
+    full => full
+  );"
+    );
+    assert_eq!(
+        interface_of(&root, &code, "empty")
+            .unwrap()
+            .instantiation_template("u_empty", &FormattingOptions::default()),
+        "u_empty: entity libname.empty;"
+    );
+}
+
+#[test]
+fn fills_in_instantiation_at_cursor() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity fifo is
+  generic (width : natural := 8; depth : positive := 4);
+  port (clk : in bit := '0'; full : out boolean);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component fifo is
+    generic (width : natural := 8);
+    port (clk : in bit := '0');
+  end component;
+  signal clk : bit;
+begin
+  u_direct: entity work.fifo generic map (depth => 16);
+
+  gen: if true generate
+    u_comp: fifo;
+  end generate;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.instantiation_template_at(
+            code.source(),
+            code.s1("work.fifo").start(),
+            &FormattingOptions::default()
+        ),
+        Some((
+            code.s1("u_direct: entity work.fifo generic map (depth => 16);")
+                .pos(),
+            "\
+u_direct: entity work.fifo
+    generic map (
+      width => width,
+      depth => 16
+    )
+    port map (
+      clk => clk,
+      full => full
+    );"
+            .to_owned()
+        ))
+    );
+
+    let options = FormattingOptions {
+        indent_width: 4,
+        keyword_case: KeywordCase::Upper,
+    };
+    assert_eq!(
+        root.instantiation_template_at(code.source(), code.s1("u_comp").start(), &options),
+        Some((
+            code.s1("u_comp: fifo;").pos(),
+            "\
+u_comp: COMPONENT fifo
+        GENERIC MAP (
+            width => width
+        )
+        PORT MAP (
+            clk => clk
+        );"
+            .to_owned()
+        ))
+    );
+
+    assert_eq!(
+        root.instantiation_template_at(
+            code.source(),
+            code.s1("signal clk").start(),
+            &FormattingOptions::default()
+        ),
+        None
+    );
+}
+
+#[test]
+fn instantiation_template_keeps_positional_and_partial_associations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity reg is
+  port (clk, rst : in bit := '0'; d : in bit_vector(1 downto 0) := \"00\"; q : out bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  function to_bit(value : boolean) return bit is
+  begin
+    if value then
+      return '1';
+    end if;
+    return '0';
+  end function;
+
+  function to_boolean(value : bit) return boolean is
+  begin
+    return value = '1';
+  end function;
+
+  signal a_clk, b_rst, x, y, q : bit;
+  signal flag : boolean;
+begin
+  u_pos: entity work.reg port map (a_clk, b_rst, q => q);
+  u_part: entity work.reg port map (clk => a_clk, d(0) => x, d(1) => y);
+  u_conv: entity work.reg port map (clk => a_clk, d => \"00\", q => open, rst => to_bit(flag));
+  u_formal_conv: entity work.reg port map (clk => a_clk, to_boolean(q) => flag);
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let template_at = |name: &str| {
+        root.instantiation_template_at(
+            code.source(),
+            code.s1(name).start(),
+            &FormattingOptions::default(),
+        )
+        .map(|(_, text)| text)
+    };
+
+    assert_eq!(
+        template_at("u_pos").as_deref(),
+        Some(
+            "\
+u_pos: entity work.reg
+    port map (
+      clk => a_clk,
+      rst => b_rst,
+      d => d,
+      q => q
+    );"
+        )
+    );
+    assert_eq!(
+        template_at("u_part").as_deref(),
+        Some(
+            "\
+u_part: entity work.reg
+    port map (
+      clk => a_clk,
+      rst => rst,
+      d(0) => x,
+      d(1) => y,
+      q => q
+    );"
+        )
+    );
+    assert_eq!(
+        template_at("u_conv").as_deref(),
+        Some(
+            "\
+u_conv: entity work.reg
+    port map (
+      clk => a_clk,
+      rst => to_bit(flag),
+      d => \"00\",
+      q => open
+    );"
+        )
+    );
+    // A formal with a conversion function cannot be kept
+    assert_eq!(template_at("u_formal_conv"), None);
+}
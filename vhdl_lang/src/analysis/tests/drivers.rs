@@ -0,0 +1,215 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use pretty_assertions::assert_eq;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn signal_driven_from_two_processes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : natural;
+  signal other : natural;
+begin
+  proc0: process
+  begin
+    sig <= 0;
+    wait;
+  end process;
+
+  proc1: process(other)
+  begin
+    if other = 0 then
+      sig <= 1;
+    else
+      sig <= 2;
+    end if;
+  end process;
+
+  other <= sig;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let sig = root
+        .search_reference(code.source(), code.s1("sig :").start())
+        .unwrap();
+    assert_eq!(
+        root.drivers_of(sig),
+        vec![code.s1("proc0").pos(), code.s1("proc1").pos()]
+    );
+
+    let other = root
+        .search_reference(code.source(), code.s1("other :").start())
+        .unwrap();
+    assert_eq!(
+        root.drivers_of(other),
+        vec![code.s1("other <= sig").s1("other").pos()]
+    );
+}
+
+#[test]
+fn drivers_of_indexed_and_record_targets() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    elem : bit;
+  end record;
+  signal rec : rec_t;
+  signal vec : bit_vector(0 to 1);
+  signal idx : natural;
+begin
+  drv_rec: rec.elem <= '0';
+  drv_vec: vec(idx) <= '1';
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let rec = root
+        .search_reference(code.source(), code.s1("rec ").start())
+        .unwrap();
+    assert_eq!(root.drivers_of(rec), vec![code.s1("drv_rec").pos()]);
+
+    let vec = root
+        .search_reference(code.source(), code.s1("vec ").start())
+        .unwrap();
+    assert_eq!(root.drivers_of(vec), vec![code.s1("drv_vec").pos()]);
+
+    let idx = root
+        .search_reference(code.source(), code.s1("idx").start())
+        .unwrap();
+    assert_eq!(root.drivers_of(idx), vec![]);
+}
+
+#[test]
+fn implied_sensitivity_of_process_all() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit;
+    d : in bit_vector(0 to 1)
+  );
+end entity;
+
+architecture a of ent is
+  signal idx : natural;
+  signal q : bit;
+  signal cnt : natural;
+  signal unused : bit;
+begin
+  comb: process(all)
+    variable v : bit;
+  begin
+    v := d(idx);
+    if clk = '1' then
+      q <= v;
+    end if;
+    cnt <= cnt + 1;
+  end process;
+
+  process(all)
+  begin
+    unused <= q;
+  end process;
+
+  process(clk)
+  begin
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let decl_of = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+    };
+
+    assert_eq!(
+        root.implied_sensitivity(code.source()),
+        vec![
+            (
+                code.s1("comb").pos(),
+                vec![
+                    decl_of("d :"),
+                    decl_of("idx :"),
+                    decl_of("clk :"),
+                    decl_of("cnt :")
+                ]
+            ),
+            (
+                code.s1("process(all)\n  begin\n    unused")
+                    .s1("process")
+                    .pos(),
+                vec![decl_of("q :")]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn driving_attributes_require_a_driver_in_the_process() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig, other : natural;
+  signal flag : boolean;
+begin
+  drives: process
+  begin
+    sig <= 1;
+    other <= sig'driving_value;
+    flag <= sig'driving;
+    wait;
+  end process;
+
+  reads: process
+  begin
+    report integer'image(sig'driving_value);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s("sig'driving_value", 2),
+            "'driving_value requires that signal 'sig' is driven by the process",
+            ErrorCode::MissingDriver,
+        )],
+    );
+}
@@ -0,0 +1,280 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{Instance, InstanceTree};
+use crate::named_entity::StaticValue;
+use pretty_assertions::assert_eq;
+
+fn elaborate<'a>(root: &'a DesignRoot, code: &Code, top: &str) -> InstanceTree<'a> {
+    let ent = root
+        .search_reference(code.source(), code.s1(top).start())
+        .unwrap();
+    root.elaborate(ent).unwrap()
+}
+
+fn designator(ent: Option<EntRef>) -> Option<String> {
+    ent.map(|ent| ent.designator().to_string())
+}
+
+/// The labels of the instance and its descendants in depth first order
+fn labels(instance: &Instance, prefix: &str, result: &mut Vec<String>) {
+    for child in instance.children.iter() {
+        let label = format!("{prefix}{}", child.label);
+        result.push(label.clone());
+        labels(child, &format!("{label}."), result);
+    }
+}
+
+#[test]
+fn elaborates_two_level_hierarchy() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity leaf is
+  generic (width : natural := 1);
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+
+entity mid is
+  generic (count : natural := 1);
+end entity;
+
+architecture rtl of mid is
+  component leaf is
+    generic (width : natural := 4);
+  end component;
+begin
+  gen: for i in 0 to count - 1 generate
+    u_leaf: leaf generic map (width => 2 * i);
+  end generate;
+
+  opt: if count > 2 generate
+    u_extra: entity work.leaf;
+  end generate;
+end architecture;
+
+entity top is
+end entity;
+
+architecture struct of top is
+begin
+  u_mid: entity work.mid(rtl) generic map (count => 2);
+  u_default: entity work.mid;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tree = elaborate(&root, &code, "top");
+    assert_eq!(designator(tree.top.entity), Some("top".to_owned()));
+    assert_eq!(designator(tree.top.architecture), Some("struct".to_owned()));
+
+    let mut result = Vec::new();
+    labels(&tree.top, "", &mut result);
+    assert_eq!(
+        result,
+        vec![
+            "u_mid",
+            "u_mid.gen(0).u_leaf",
+            "u_mid.gen(1).u_leaf",
+            "u_default",
+            "u_default.gen(0).u_leaf",
+        ]
+    );
+
+    let u_mid = tree.find("u_mid").unwrap();
+    assert_eq!(u_mid.pos, Some(code.s1("u_mid").pos()));
+    assert_eq!(designator(u_mid.entity), Some("mid".to_owned()));
+    assert_eq!(designator(u_mid.architecture), Some("rtl".to_owned()));
+    assert_eq!(u_mid.generic("count"), Some(StaticValue::Integer(2)));
+    assert_eq!(
+        tree.find("u_default").unwrap().generic("count"),
+        Some(StaticValue::Integer(1))
+    );
+
+    let u_leaf = tree.find("u_mid.gen(1).u_leaf").unwrap();
+    assert_eq!(designator(u_leaf.component), Some("leaf".to_owned()));
+    assert_eq!(designator(u_leaf.entity), Some("leaf".to_owned()));
+    assert_eq!(designator(u_leaf.architecture), Some("rtl".to_owned()));
+    assert_eq!(u_leaf.generic("width"), Some(StaticValue::Integer(2)));
+    assert_eq!(
+        tree.find("u_mid.gen(0).u_leaf").unwrap().generic("width"),
+        Some(StaticValue::Integer(0))
+    );
+}
+
+#[test]
+fn elaborates_non_static_generates_once() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+
+entity top is
+  generic (count : natural);
+end entity;
+
+architecture struct of top is
+  component missing is
+  end component;
+begin
+  gen: for i in 0 to count - 1 generate
+    u_leaf: entity work.leaf;
+  end generate;
+
+  sel: case count generate
+    when 0 =>
+      u_zero: entity work.leaf;
+    when others =>
+      u_other: entity work.leaf;
+  end generate;
+
+  u_unbound: component missing;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tree = elaborate(&root, &code, "struct");
+    assert_eq!(tree.top.generic("count"), None);
+
+    let mut result = Vec::new();
+    labels(&tree.top, "", &mut result);
+    assert_eq!(
+        result,
+        vec!["gen.u_leaf", "sel.u_zero", "sel.u_other", "u_unbound"]
+    );
+
+    let u_unbound = tree.find("u_unbound").unwrap();
+    assert_eq!(designator(u_unbound.component), Some("missing".to_owned()));
+    assert_eq!(u_unbound.entity, None);
+    assert_eq!(u_unbound.architecture, None);
+}
+
+#[test]
+fn unconstrained_port_takes_length_of_actual() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity leaf is
+  port (
+    clk : in bit;
+    data : in bit_vector;
+    fixed : out bit_vector(3 downto 0)
+  );
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture struct of top is
+  signal clk : bit;
+  signal bus8 : bit_vector(7 downto 0);
+  signal bus4 : bit_vector(0 to 3);
+begin
+  u_named: entity work.leaf port map (clk => clk, data => bus8, fixed => bus4);
+  u_positional: entity work.leaf port map (clk, \"101\", open);
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tree = elaborate(&root, &code, "top");
+    let named = tree.find("u_named").unwrap();
+    assert_eq!(named.port_length("clk"), None);
+    assert_eq!(named.port_length("data"), Some(8));
+    assert_eq!(named.port_length("fixed"), Some(4));
+
+    let positional = tree.find("u_positional").unwrap();
+    assert_eq!(
+        positional
+            .ports
+            .iter()
+            .map(|port| (port.port.designator().to_string(), port.length))
+            .collect::<Vec<_>>(),
+        vec![("clk".to_owned(), None), ("data".to_owned(), Some(3))]
+    );
+}
+
+#[test]
+fn recursive_instances_with_non_static_generics_are_not_elaborated() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity tree is
+  generic (n : natural);
+end entity;
+
+architecture rtl of tree is
+begin
+  gen: if n > 1 generate
+    u0: entity work.tree generic map (n => n / 2);
+    u1: entity work.tree generic map (n => n / 2);
+  end generate;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tree = elaborate(&root, &code, "rtl");
+    let mut result = Vec::new();
+    labels(&tree.top, "", &mut result);
+    assert_eq!(result, vec!["gen.u0", "gen.u1"]);
+    assert_eq!(tree.find("gen.u0").unwrap().generic("n"), None);
+}
+
+#[test]
+fn number_of_elaborated_instances_is_limited() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity tree is
+  generic (n : natural := 30);
+end entity;
+
+architecture rtl of tree is
+begin
+  gen: if n > 0 generate
+    u0: entity work.tree generic map (n => n - 1);
+    u1: entity work.tree generic map (n => n - 1);
+  end generate;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tree = elaborate(&root, &code, "rtl");
+    let mut result = Vec::new();
+    labels(&tree.top, "", &mut result);
+    assert!(result.len() <= 2 * 10000, "{}", result.len());
+    assert_eq!(
+        tree.find("gen.u0.gen.u0").unwrap().generic("n"),
+        Some(StaticValue::Integer(28))
+    );
+}
@@ -0,0 +1,79 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn passive_statements_in_entity() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : natural);
+  port (clk : in bit; data : in bit_vector(width - 1 downto 0));
+begin
+  assert width > 0 report \"width must be positive\";
+
+  check : process (clk)
+    variable count : natural := 0;
+  begin
+    if clk = '1' then
+      count := count + 1;
+    end if;
+    assert data'length = width;
+  end process;
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn signal_assignments_are_not_allowed_in_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk : in bit; rst : out bit);
+begin
+  rst <= '0';
+
+  process (clk)
+  begin
+    if clk = '1' then
+      rst <= '1';
+    end if;
+  end process;
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("rst <= '0';"),
+                "Signal assignment is not allowed in the statement part of an entity",
+                ErrorCode::NonPassiveEntityStatement,
+            ),
+            Diagnostic::new(
+                code.s1("rst <= '1';"),
+                "Signal assignment is not allowed in a passive process",
+                ErrorCode::NonPassiveEntityStatement,
+            )
+            .related(
+                code.sa("entity ", "ent"),
+                "In the statement part of entity 'ent'",
+            ),
+        ],
+    );
+}
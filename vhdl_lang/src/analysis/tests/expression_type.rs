@@ -0,0 +1,49 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn type_of_expression_at_cursor() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type level_t is (low, high);
+  type levels_t is array (natural range <>) of level_t;
+  constant idx : natural := 1 + 2;
+  constant levels : levels_t(0 to 1) := (low, high);
+  constant lvl : level_t := levels(idx);
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let type_at = |substr: &str| {
+        root.expression_type(code.source(), code.s1(substr).start())
+            .map(|typ| typ.id())
+    };
+    let decl_of = |substr: &str| {
+        root.search_reference(code.source(), code.s1(substr).start())
+            .map(|ent| ent.id())
+    };
+
+    assert_eq!(type_at("+ 2"), decl_of("natural :="));
+    assert_eq!(
+        type_at("(low, high);\n  constant lvl"),
+        decl_of("levels_t(0")
+    );
+    assert_eq!(
+        type_at("low, high);\n  constant lvl"),
+        decl_of("level_t is")
+    );
+    assert_eq!(type_at("levels(idx)"), decl_of("level_t :="));
+    assert_eq!(type_at("type level_t"), None);
+}
@@ -0,0 +1,430 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::VHDLStandard;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn signal_assignment_and_wait_not_allowed_in_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : bit;
+
+  impure function f return natural is
+  begin
+    s <= '1';
+    wait for 1 ns;
+    return 0;
+  end function;
+
+  procedure p is
+  begin
+    s <= '1';
+    wait for 1 ns;
+  end procedure;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("s <= '1';"),
+                "Signal assignment is not allowed in a function",
+                ErrorCode::IllegalStatementInFunction,
+            )
+            .related(code.s1("impure"), "In function 'f'"),
+            Diagnostic::new(
+                code.s1("wait for 1 ns;"),
+                "Wait statement is not allowed in a function",
+                ErrorCode::WaitInFunction,
+            )
+            .related(code.s1("impure"), "In function 'f'"),
+        ],
+    );
+}
+
+#[test]
+fn wait_in_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function f(arg : natural) return natural;
+end package;
+
+package body pkg is
+  function f(arg : natural) return natural is
+  begin
+    for i in 0 to arg loop
+      if i = 2 then
+        wait;
+      end if;
+    end loop;
+    return arg;
+  end function;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("wait;"),
+            "Wait statement is not allowed in a function",
+            ErrorCode::WaitInFunction,
+        )
+        .related(code.s("function", 2), "In function 'f'")],
+    );
+}
+
+#[test]
+fn pure_function_may_not_reference_objects_declared_outside() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := 0;
+  signal s : natural;
+  alias s_alias is s;
+
+  impure function get return natural is
+  begin
+    return s;
+  end function;
+
+  function good(arg : natural) return natural is
+    variable v : natural;
+  begin
+    v := arg + c;
+    return v;
+  end function;
+
+  function bad return natural is
+  begin
+    return s + s_alias + get;
+  end function;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("return s +").s1("s"),
+                "Pure function cannot reference signal 's' declared outside of it",
+                ErrorCode::PurityViolation,
+            )
+            .related(code.s1("function bad").s1("function"), "In function 'bad'")
+            .related(code.s1("s :").s1("s"), "Declared here"),
+            Diagnostic::new(
+                code.s1("+ s_alias").s1("s_alias"),
+                "Pure function cannot reference object alias 's_alias' declared outside of it",
+                ErrorCode::PurityViolation,
+            )
+            .related(code.s1("function bad").s1("function"), "In function 'bad'")
+            .related(code.s1("s_alias"), "Declared here"),
+            Diagnostic::new(
+                code.s1("+ get").s1("get"),
+                "Pure function cannot call impure function get[return NATURAL]",
+                ErrorCode::ImpureCall,
+            )
+            .related(code.s1("function bad").s1("function"), "In function 'bad'")
+            .related(code.s1("get"), "Declared here"),
+        ],
+    );
+}
+
+#[test]
+fn impure_function_may_read_signals_and_ports() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (din : in natural);
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+
+  impure function sum return natural is
+    variable acc : natural;
+  begin
+    acc := s + din;
+    if s'event then
+      return acc;
+    end if;
+    return s;
+  end function;
+
+  function sum_of(arg : natural) return natural is
+  begin
+    return arg + din;
+  end function;
+
+  procedure check(signal sig : in natural) is
+  begin
+  end procedure;
+
+  function checked(signal sig : in natural) return natural is
+  begin
+    check(sig => sig);
+    return sig;
+  end function;
+begin
+  s <= sum + sum_of(0) + checked(s);
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s("din", 3),
+            "Pure function cannot reference port 'din' declared outside of it",
+            ErrorCode::PurityViolation,
+        )
+        .related(
+            code.s1("function sum_of").s1("function"),
+            "In function 'sum_of'",
+        )
+        .related(code.s1("din"), "Declared here")],
+    );
+}
+
+#[test]
+fn function_parameters_must_be_of_mode_in() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function f(a : in natural; b : natural; c : out natural; d : inout natural) return natural;
+  procedure p(c : out natural; d : inout natural);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("c : out").s1("c"),
+                "Function parameter 'c' cannot be of mode out",
+                ErrorCode::IllegalFunctionParameterMode,
+            ),
+            Diagnostic::new(
+                code.s1("d : inout").s1("d"),
+                "Function parameter 'd' cannot be of mode inout",
+                ErrorCode::IllegalFunctionParameterMode,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn function_parameters_of_any_mode_are_allowed_in_vhdl_2019() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2019);
+    builder.code(
+        "libname",
+        "
+package pkg is
+  function f(c : out natural; d : inout natural) return natural;
+end package;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn every_path_of_a_function_must_return_a_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function no_else(x : integer) return integer is
+  begin
+    if x < 0 then
+      return -1;
+    end if;
+  end function;
+
+  function no_others(x : integer) return integer is
+  begin
+    case x is
+      when 0 => return 0;
+      when others => null;
+    end case;
+  end function;
+
+  function exits(x : integer) return integer is
+  begin
+    loop
+      exit when x = 0;
+      return x;
+    end loop;
+  end function;
+
+  function wrong_type(x : integer) return integer is
+  begin
+    return true;
+  end function;
+
+  function all_paths(x : integer) return integer is
+  begin
+    if x < 0 then
+      return -1;
+    elsif x = 0 then
+      return 0;
+    else
+      return 1;
+    end if;
+  end function;
+
+  function forever(x : integer) return integer is
+  begin
+    loop
+      if x > 0 then
+        return x;
+      end if;
+    end loop;
+  end function;
+end package body;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("no_else"),
+                "Function 'no_else' can reach its end without returning a value",
+                ErrorCode::MissingReturn,
+            ),
+            Diagnostic::new(
+                code.s1("no_others"),
+                "Function 'no_others' can reach its end without returning a value",
+                ErrorCode::MissingReturn,
+            ),
+            Diagnostic::new(
+                code.s1("exits"),
+                "Function 'exits' can reach its end without returning a value",
+                ErrorCode::MissingReturn,
+            ),
+            Diagnostic::new(
+                code.s1("true"),
+                "'true' does not match integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn endless_loops_and_failures_end_the_path_of_a_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function while_true(x : integer) return integer is
+  begin
+    while true loop
+      if x > 0 then
+        return x;
+      end if;
+    end loop;
+  end function;
+
+  function while_false(x : integer) return integer is
+  begin
+    while false loop
+      return x;
+    end loop;
+  end function;
+
+  function reports_failure(x : integer) return integer is
+  begin
+    if x > 0 then
+      return x;
+    end if;
+    report \"unexpected value\" severity failure;
+  end function;
+
+  function reports_error(x : integer) return integer is
+  begin
+    if x > 0 then
+      return x;
+    end if;
+    report \"unexpected value\" severity error;
+  end function;
+
+  function asserts_false(x : integer) return integer is
+  begin
+    case x is
+      when 0 => return 0;
+      when others => assert false report \"unexpected value\" severity failure;
+    end case;
+  end function;
+
+  function asserts_condition(x : integer) return integer is
+  begin
+    assert x = 0 severity failure;
+  end function;
+end package body;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("while_false"),
+                "Function 'while_false' can reach its end without returning a value",
+                ErrorCode::MissingReturn,
+            ),
+            Diagnostic::new(
+                code.s1("reports_error"),
+                "Function 'reports_error' can reach its end without returning a value",
+                ErrorCode::MissingReturn,
+            ),
+            Diagnostic::new(
+                code.s1("asserts_condition"),
+                "Function 'asserts_condition' can reach its end without returning a value",
+                ErrorCode::MissingReturn,
+            ),
+        ],
+    );
+}
@@ -82,6 +82,7 @@ package body pkg is
     function fun0(arg : natural) return natural is
         variable v0 : natural;
     begin
+        return v0;
     end function;
 end package body;
       ",
@@ -368,6 +369,15 @@ end architecture;
                 "Next can only be used inside a loop",
                 ErrorCode::NextOutsideLoop,
             ),
+            Diagnostic::new(
+                code.s1("next;"),
+                "Unreachable statement",
+                ErrorCode::UnreachableCode,
+            )
+            .related(
+                code.s1("exit;"),
+                "Execution never continues after this statement",
+            ),
         ],
     );
 }
@@ -424,3 +434,49 @@ end architecture;
         ],
     );
 }
+
+#[test]
+fn full_name_of_package_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : natural := 0;
+  function fun(arg : natural) return natural;
+  function fun(arg : bit) return natural;
+end package;
+
+package body pkg is
+  function fun(arg : natural) return natural is
+  begin
+    return arg;
+  end function;
+
+  function fun(arg : bit) return natural is
+  begin
+    return c0;
+  end function;
+end package body;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let full_name =
+        |pos: &SrcPos| root.full_name(root.search_reference(code.source(), pos.start()).unwrap());
+    assert_eq!(full_name(&code.s1("c0").pos()), "libname.pkg.c0");
+    assert_eq!(
+        full_name(&code.s1("fun(arg : natural)").pos()),
+        "libname.pkg.fun[NATURAL return NATURAL]"
+    );
+    assert_eq!(
+        full_name(&code.s1("fun(arg : bit)").pos()),
+        "libname.pkg.fun[BIT return NATURAL]"
+    );
+    assert_eq!(
+        full_name(&code.s("arg", 3).pos()),
+        "libname.pkg.fun[NATURAL return NATURAL].arg"
+    );
+}
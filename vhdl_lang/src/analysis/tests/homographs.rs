@@ -639,6 +639,46 @@ end package pkg;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_homograph_of_constant_and_enum_literal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type color_t is (red, green);
+  constant red : color_t := green;
+end package;
+
+package pkg2 is
+  constant blue : natural := 0;
+  type color_t is (blue, yellow);
+end package;
+
+package body pkg is
+  -- The package body extends the region of the package
+  constant green : natural := 0;
+
+  procedure proc is
+    -- Ok since the constant hides the literal of the enclosing region
+    constant red : color_t := green;
+  begin
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            duplicate(&code, "red", 1, 2),
+            duplicate(&code, "blue", 1, 2),
+            duplicate(&code, "green", 1, 3),
+        ],
+    );
+}
+
 #[test]
 fn homograph_of_enum_literal_declared_by_alias() {
     let mut builder = LibraryBuilder::new();
@@ -784,6 +824,46 @@ end architecture;
     check_diagnostics(diagnostics, expected);
 }
 
+#[test]
+fn forbid_architecture_signal_with_name_of_entity_port() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+port (
+clk : in bit;
+data : out bit
+);
+end entity;
+
+architecture rtl of ent is
+signal clk : bit;
+signal DATA : bit;
+begin
+process
+variable clk : bit;
+begin
+wait;
+end process;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            duplicate(&code, "clk", 1, 2),
+            Diagnostic::new(
+                code.s1("DATA"),
+                "Duplicate declaration of 'data'",
+                ErrorCode::Duplicate,
+            )
+            .related(code.s1("data"), "Previously defined here"),
+        ],
+    );
+}
+
 #[test]
 fn forbid_homographs_in_package_extended_declarative_regions() {
     let mut builder = LibraryBuilder::new();
@@ -994,6 +1074,7 @@ end package;
 package body pkg is
   function name1 return natural is
   begin
+    return 0;
   end;
 end package body;
 ",
@@ -255,7 +255,7 @@ fn implicit_functions_on_integer_type() {
     check_code_with_no_diagnostics(
         "
 package pkg is
-    type type_t is range 0 to 1;
+    type type_t is range -100 to 100;
 
     constant c0 : type_t := 10;
     constant good1 : type_t := - c0;
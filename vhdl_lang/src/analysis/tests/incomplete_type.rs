@@ -4,7 +4,6 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
-// @TODO
 // 5.4.2 Incomplete type declarations
 // Prior to the end of the corresponding full type declaration, the only allowed use of a name that denotes a type
 // declared by an incomplete type declaration is as the type mark in the subtype indication of an access type
@@ -169,6 +168,120 @@ end package;
     check_diagnostics(diagnostics, vec![missing_full_error(&code.s1("rec_t"))]);
 }
 
+#[test]
+fn error_on_array_of_itself() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type arr_t;
+  subtype sub_t is arr_t;
+  type arr_t is array (0 to 1) of sub_t;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("sub_t", 2),
+            "Found circular type dependency arr_t -> sub_t -> arr_t",
+            ErrorCode::CircularTypeDependency,
+        )
+        .related(code.s1("sub_t"), "'sub_t' depends on 'arr_t'")],
+    );
+}
+
+#[test]
+fn error_on_record_containing_itself() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t;
+  type arr_t is array (0 to 1) of rec_t;
+  type wrapper_t is record
+    elems : arr_t;
+  end record;
+  type rec_t is record
+    valid : boolean;
+    wrapper : wrapper_t;
+  end record;
+
+  constant c : rec_t := (valid => true);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("wrapper_t", 2),
+            "Found circular type dependency rec_t -> wrapper_t -> arr_t -> rec_t",
+            ErrorCode::CircularTypeDependency,
+        )
+        .related(code.s1("wrapper_t"), "'wrapper_t' depends on 'arr_t'")
+        .related(code.s1("arr_t"), "'arr_t' depends on 'rec_t'")],
+    );
+}
+
+#[test]
+fn uses_before_full_declaration_outside_of_a_cycle_are_reported() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t;
+  type arr_t is array (0 to 1) of rec_t;
+  subtype other_t is rec_t;
+  type rec_t is record
+    elements : arr_t;
+  end record;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            before_full_error(&code.s("rec_t", 3), &code.s1("rec_t"), "rec_t"),
+            Diagnostic::new(
+                code.s("arr_t", 2),
+                "Found circular type dependency rec_t -> arr_t -> rec_t",
+                ErrorCode::CircularTypeDependency,
+            )
+            .related(code.s1("arr_t"), "'arr_t' depends on 'rec_t'"),
+        ],
+    );
+}
+
+#[test]
+fn access_types_break_circular_type_dependencies() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type node_t;
+  type node_ptr_t is access node_t;
+  type node_arr_t is array (0 to 1) of node_ptr_t;
+  type node_t is record
+    children : node_arr_t;
+  end record;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
 fn missing_full_error(pos: &impl AsRef<SrcPos>) -> Diagnostic {
     let mut error = Diagnostic::new(
         pos,
@@ -181,3 +294,75 @@ fn missing_full_error(pos: &impl AsRef<SrcPos>) -> Diagnostic {
     );
     error
 }
+
+#[test]
+fn error_on_use_of_incomplete_type_before_full_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t;
+  type ptr_t is access rec_t;
+  type bad_ptr_t is access rec_t(0 to 1);
+  signal s : rec_t;
+  function f return rec_t;
+  type rec_t is record
+    tail : ptr_t;
+  end record;
+  subtype d is rec_t;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("(0 to 1)"),
+                "Incomplete type 'rec_t' cannot be constrained",
+                ErrorCode::IncompleteTypeBeforeFullDeclaration,
+            ),
+            before_full_error(&code.s("rec_t", 4), &code.s1("rec_t"), "rec_t"),
+            before_full_error(&code.s("rec_t", 5), &code.s1("rec_t"), "rec_t"),
+        ],
+    );
+}
+
+#[test]
+fn error_on_missing_full_type_definition_in_nested_regions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  main : process is
+    type rec_t;
+  begin
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, vec![missing_full_error(&code.s1("rec_t"))]);
+}
+
+fn before_full_error(
+    pos: &impl AsRef<SrcPos>,
+    decl_pos: &impl AsRef<SrcPos>,
+    name: &str,
+) -> Diagnostic {
+    Diagnostic::new(
+        pos,
+        format!("Incomplete type '{name}' cannot be used before its full declaration"),
+        ErrorCode::IncompleteTypeBeforeFullDeclaration,
+    )
+    .related(decl_pos, format!("Incomplete type '{name}' declared here"))
+}
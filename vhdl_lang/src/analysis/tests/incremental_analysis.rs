@@ -7,6 +7,7 @@
 use super::*;
 use crate::analysis::DesignRoot;
 use crate::ast::search::*;
+use crate::ast::UnitId;
 use crate::data::SrcPos;
 use crate::named_entity::{EntityId, Reference};
 use crate::syntax::TokenAccess;
@@ -158,7 +159,7 @@ end architecture;
         "libname",
         "
 configuration cfg of ent is
-for rtl
+for a
 end for;
 end configuration;
 ",
@@ -258,6 +259,80 @@ end package body;
     check_incremental_analysis(builder, vec![]);
 }
 
+#[test]
+fn change_of_source_only_affects_dependent_units() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  constant const : natural := 0;
+end package;
+
+package unrelated is
+end package;
+",
+    );
+
+    let ent = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+",
+    );
+
+    let arch = builder.code(
+        "libname",
+        "
+use work.pkg.const;
+
+architecture a of ent is
+begin
+end architecture;
+",
+    );
+
+    let (mut root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let names = |units: &FnvHashSet<UnitId>| -> FnvHashSet<String> {
+        units
+            .iter()
+            .map(|unit_id| match unit_id.secondary_name() {
+                Some(name) => format!("{}({})", unit_id.primary_name(), name),
+                None => unit_id.primary_name().to_string(),
+            })
+            .collect()
+    };
+    let expected = |units: &[&str]| -> FnvHashSet<String> {
+        units.iter().map(|unit| unit.to_string()).collect()
+    };
+
+    assert_eq!(
+        names(&root.affected_by_sources(&[arch.source().clone()])),
+        expected(&["ent(a)"])
+    );
+    assert_eq!(
+        names(&root.affected_by_sources(&[ent.source().clone()])),
+        expected(&["ent", "ent(a)"])
+    );
+    assert_eq!(
+        names(&root.affected_by_sources(&[pkg.source().clone()])),
+        expected(&["pkg", "unrelated", "ent(a)"])
+    );
+
+    // Only the affected units are re-analyzed after the change
+    let libname = root.symbol_utf8("libname");
+    let affected = root.affected_by_sources(&[arch.source().clone()]);
+    root.remove_source(libname.clone(), arch.source());
+    root.add_design_file(libname, arch.design_file());
+    let mut diagnostics = Vec::new();
+    let reanalyzed: FnvHashSet<_> = root.analyze(&mut diagnostics).into_iter().collect();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(reanalyzed, affected);
+}
+
 fn check_incremental_analysis(builder: LibraryBuilder, expected_diagnostics: Vec<Diagnostic>) {
     let symbols = builder.symbols();
     let codes = builder.take_code();
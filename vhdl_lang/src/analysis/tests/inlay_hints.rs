@@ -0,0 +1,127 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::error_codes::ErrorCode;
+use crate::{InlayHint, InlayHintKind};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn hints_for_positional_actuals_and_return_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+  port (clk : in bit; q : out bit);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  function add(lhs, rhs : natural) return natural is
+  begin
+    return lhs + rhs;
+  end function;
+
+  procedure log(msg : string; level : natural) is
+  begin
+  end procedure;
+
+  signal clk, q : bit;
+begin
+  inst: entity work.child port map (clk, q);
+
+  process
+    variable sum : natural;
+  begin
+    sum := add(1, 2);
+    log(\"sum\", level => sum);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let decl_of = |substr: &str| {
+        root.search_reference(code.source(), code.s1(substr).start())
+            .unwrap()
+    };
+
+    assert_eq!(
+        root.inlay_hints(code.source()),
+        vec![
+            InlayHint {
+                pos: code.s1("(clk, q)").s1("clk").pos().pos_at_beginning(),
+                kind: InlayHintKind::Formal(decl_of("clk : in")),
+            },
+            InlayHint {
+                pos: code.s1("(clk, q)").s1("q").pos().pos_at_beginning(),
+                kind: InlayHintKind::Formal(decl_of("q : out")),
+            },
+            InlayHint {
+                pos: code.s1("1, 2").pos().pos_at_beginning(),
+                kind: InlayHintKind::Formal(decl_of("lhs")),
+            },
+            InlayHint {
+                pos: code.s1("2)").pos().pos_at_beginning(),
+                kind: InlayHintKind::Formal(decl_of("rhs")),
+            },
+            InlayHint {
+                pos: code.s1("add(1, 2)").pos().pos_at_end(),
+                kind: InlayHintKind::ReturnType(decl_of("natural) return")),
+            },
+            InlayHint {
+                pos: code.s1("\"sum\"").pos().pos_at_beginning(),
+                kind: InlayHintKind::Formal(decl_of("msg")),
+            },
+        ]
+    );
+}
+
+#[test]
+fn no_hints_for_ambiguous_calls() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type a_t is (x, y);
+  type b_t is (x, z);
+  procedure p(value : a_t);
+  procedure p(value : b_t);
+end package;
+
+package body pkg is
+  procedure p(value : a_t) is
+  begin
+  end procedure;
+
+  procedure p(value : b_t) is
+  begin
+  end procedure;
+
+  procedure q is
+  begin
+    p(x);
+  end procedure;
+end package body;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, ErrorCode::AmbiguousCall);
+    assert_eq!(root.inlay_hints(code.source()), vec![]);
+}
@@ -0,0 +1,102 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::VHDLStandard;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+const OUT_PORT_READ: &str = "
+entity sub is
+  port (o : out bit);
+end entity;
+
+architecture a of sub is
+begin
+end architecture;
+
+entity ent is
+  port (valid : out bit; ready : out bit);
+end entity;
+
+architecture a of ent is
+  procedure drive(signal o : out bit) is
+  begin
+  end procedure;
+begin
+  ready <= valid;
+  inst: entity work.sub port map (o => valid);
+  drive(ready);
+end architecture;";
+
+#[test]
+fn out_ports_cannot_be_read_before_vhdl_2008() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+    let code = builder.code("libname", OUT_PORT_READ);
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("<= valid").s1("valid"),
+            "interface signal 'valid' of mode out cannot be read before VHDL-2008",
+            ErrorCode::ReadOutPort,
+        )],
+    );
+
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", OUT_PORT_READ);
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn lenient_analysis_reports_out_port_reads_as_non_standard() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+    builder.set_lenient();
+    let code = builder.code("libname", OUT_PORT_READ);
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("<= valid").s1("valid"),
+            "interface signal 'valid' of mode out cannot be read before VHDL-2008",
+            ErrorCode::NonStandard,
+        )],
+    );
+}
+
+#[test]
+fn lenient_analysis_reports_unprotected_shared_variables_as_non_standard() {
+    let contents = "
+entity ent is
+end entity;
+
+architecture a of ent is
+  shared variable counter : natural;
+begin
+end architecture;";
+
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code("libname", contents);
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("natural"),
+            "Shared variable must be of a protected type, got subtype 'NATURAL'",
+            ErrorCode::SharedVariableNotProtected,
+        )],
+    );
+
+    let mut builder = LibraryBuilder::new();
+    builder.set_lenient();
+    let code = builder.code("libname", contents);
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("natural"),
+            "Shared variable must be of a protected type, got subtype 'NATURAL'",
+            ErrorCode::NonStandard,
+        )],
+    );
+}
@@ -0,0 +1,363 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Analysis of incomplete and garbled code, such as while it is typed in an editor,
+//! must not fail with an internal error
+
+use super::*;
+use crate::analysis::DesignRoot;
+use crate::syntax::VHDLParser;
+use std::path::Path;
+use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
+
+const DESIGN: &str = "
+package pkg is
+  type state_t is (idle, busy, done);
+  type rec_t is record
+    a : natural;
+    b : bit_vector(3 downto 0);
+  end record;
+  type arr_t is array (natural range <>) of rec_t;
+  constant c_width : natural := 8;
+  function f(x : natural; y : state_t := idle) return natural;
+  procedure p(signal s : out bit; constant v : in natural);
+  component comp is
+    generic (g : natural := 1);
+    port (clk : in bit; q : out bit_vector(g - 1 downto 0));
+  end component;
+  alias width is c_width;
+  subtype byte_t is bit_vector(c_width - 1 downto 0);
+  type prot_t is protected
+    procedure inc;
+    impure function get return natural;
+  end protected;
+end package;
+
+package body pkg is
+  function f(x : natural; y : state_t := idle) return natural is
+    variable v : natural := x;
+  begin
+    case y is
+      when idle => return v + 1;
+      when busy | done => return v;
+    end case;
+  end function;
+
+  procedure p(signal s : out bit; constant v : in natural) is
+  begin
+    for i in 0 to v loop
+      s <= '1' when i mod 2 = 0 else '0';
+      exit when i > 3;
+    end loop;
+  end procedure;
+
+  type prot_t is protected body
+    variable cnt : natural := 0;
+    procedure inc is
+    begin
+      cnt := cnt + 1;
+    end procedure;
+    impure function get return natural is
+    begin
+      return cnt;
+    end function;
+  end protected body;
+end package body;
+
+entity sub is
+  generic (depth : natural);
+  port (clk, rst : in bit; din : in bit_vector; dout : out bit_vector);
+end entity;
+
+architecture rtl of sub is
+begin
+end architecture;
+
+use work.pkg.all;
+
+entity ent is
+  generic (depth : natural := 4);
+  port (
+    clk, rst : in bit;
+    din : in byte_t;
+    dout : out byte_t);
+end entity;
+
+architecture rtl of ent is
+  signal state : state_t;
+  signal mem : arr_t(0 to depth - 1);
+  signal q : bit_vector(0 downto 0);
+  shared variable counter : prot_t;
+begin
+  seq : process (clk, rst)
+    variable idx : natural range 0 to depth - 1;
+  begin
+    if rst = '1' then
+      state <= idle;
+    elsif rising_edge(clk) then
+      mem(idx) <= (a => f(idx, state), b => din(3 downto 0));
+      dout <= din when state = busy else (others => '0');
+      state <= state_t'succ(state);
+      counter.inc;
+    end if;
+  end process;
+
+  gen : for i in 0 to depth - 1 generate
+    inst : component comp
+      generic map (g => 1)
+      port map (clk => clk, q => q);
+  end generate;
+
+  sub_inst : entity work.sub(rtl)
+    generic map (depth => depth / 2)
+    port map (clk, rst, din, open);
+
+  p(q(0), mem'length);
+  assert mem(0).a < 10 report \"bad \" & integer'image(mem(0).a) severity warning;
+end architecture;
+
+configuration cfg of ent is
+  for rtl
+    for gen(0)
+      for inst : comp
+        use open;
+      end for;
+    end for;
+  end for;
+end configuration;
+";
+
+/// Overloaded subprograms that are made visible by use clauses, including homographs
+const OVERLOADED_DESIGN: &str = "
+package pkg_a is
+  function conv(x : natural) return bit;
+  function conv(x : bit) return natural;
+  procedure log(msg : string);
+end package;
+
+package body pkg_a is
+  function conv(x : natural) return bit is
+  begin
+    return '0';
+  end function;
+
+  function conv(x : bit) return natural is
+  begin
+    return bit'pos(x);
+  end function;
+
+  procedure log(msg : string) is
+  begin
+    report msg;
+  end procedure;
+end package body;
+
+package pkg_b is
+  function conv(x : natural) return bit;
+  alias to_bit is conv[natural return bit];
+end package;
+
+package body pkg_b is
+  function conv(x : natural) return bit is
+  begin
+    return '1';
+  end function;
+end package body;
+
+use work.pkg_a.all;
+use work.pkg_b.all;
+
+entity user is
+end entity;
+
+architecture rtl of user is
+  signal s : bit;
+  function \"+\"(l : bit; r : natural) return bit is
+  begin
+    return l;
+  end function;
+begin
+  process
+    variable n : natural;
+  begin
+    n := conv(s);
+    s <= s + n;
+    s <= to_bit(n);
+    log(\"done\");
+    wait;
+  end process;
+end architecture;
+";
+
+/// Analyzes code in a design root where the standard library stays analyzed between checks,
+/// like in a language server where the code is edited
+struct Editor {
+    root: DesignRoot,
+    parser: VHDLParser,
+    source: Option<Source>,
+}
+
+impl Editor {
+    fn new() -> Editor {
+        let builder = LibraryBuilder::new();
+        let (root, _) = builder.get_analyzed_root();
+        Editor {
+            root,
+            parser: VHDLParser {
+                symbols: builder.symbols(),
+                standard: VHDLStandard::default(),
+            },
+            source: None,
+        }
+    }
+
+    fn check_no_internal_error(&mut self, code: &str) {
+        let library_name = self.root.symbol_utf8("libname");
+        if let Some(source) = self.source.take() {
+            self.root.remove_source(library_name.clone(), &source);
+        }
+
+        // The code is parsed with errors, as opposed to LibraryBuilder::code
+        let source = Source::inline(Path::new("malformed.vhd"), code);
+        let design_file = self.parser.parse_design_source(&source, &mut Vec::new());
+        self.root.add_design_file(library_name, design_file);
+        self.source = Some(source);
+
+        let mut diagnostics = Vec::new();
+        self.root.analyze(&mut diagnostics);
+        for diagnostic in diagnostics {
+            assert_ne!(
+                diagnostic.code,
+                ErrorCode::Internal,
+                "{}\nfor code:\n{code}",
+                diagnostic.message
+            );
+        }
+    }
+}
+
+#[test]
+fn design_is_valid() {
+    for design in [DESIGN, OVERLOADED_DESIGN] {
+        let mut builder = LibraryBuilder::new();
+        builder.code("libname", design);
+        check_no_diagnostics(&builder.analyze());
+    }
+}
+
+/// Analyzes every prefix of the design that ends with a word
+fn check_truncated(design: &str) {
+    let word_ends = design
+        .char_indices()
+        .zip(design.chars().skip(1))
+        .filter(|((_, chr), next)| !chr.is_whitespace() && next.is_whitespace())
+        .map(|((idx, _), _)| idx + 1);
+    let mut editor = Editor::new();
+    for end in word_ends {
+        editor.check_no_internal_error(&design[..end]);
+    }
+}
+
+/// Analyzes the design with each word dropped or swapped with the following word
+fn check_garbled(design: &str) {
+    let words: Vec<_> = design.split_inclusive(char::is_whitespace).collect();
+    let mut editor = Editor::new();
+    for idx in 0..words.len() {
+        if words[idx].trim().is_empty() {
+            continue;
+        }
+        // Drop a word
+        editor.check_no_internal_error(&[&words[..idx], &words[idx + 1..]].concat().concat());
+        // Swap a word with the following word
+        if let Some(next) = (idx + 1..words.len()).find(|&next| !words[next].trim().is_empty()) {
+            let mut swapped = words.clone();
+            swapped.swap(idx, next);
+            editor.check_no_internal_error(&swapped.concat());
+        }
+    }
+}
+
+#[test]
+fn truncated_code_has_no_internal_error() {
+    check_truncated(DESIGN);
+}
+
+#[test]
+fn garbled_code_has_no_internal_error() {
+    check_garbled(DESIGN);
+}
+
+#[test]
+fn truncated_overloaded_names_have_no_internal_error() {
+    check_truncated(OVERLOADED_DESIGN);
+}
+
+#[test]
+fn garbled_overloaded_names_have_no_internal_error() {
+    check_garbled(OVERLOADED_DESIGN);
+}
+
+/// Analyzes the code with a std library that consists of the given code
+fn analyze_with_std(std_code: &str, code: &str) -> Vec<Diagnostic> {
+    let symbols = LibraryBuilder::new().symbols();
+    let parser = VHDLParser {
+        symbols: symbols.clone(),
+        standard: VHDLStandard::default(),
+    };
+    let mut root = DesignRoot::new(symbols);
+    for (library_name, file_name, code) in
+        [("std", "std.vhd", std_code), ("libname", "lib.vhd", code)]
+    {
+        let source = Source::inline(Path::new(file_name), code);
+        let design_file = parser.parse_design_source(&source, &mut Vec::new());
+        root.add_design_file(root.symbol_utf8(library_name), design_file);
+    }
+    let mut diagnostics = Vec::new();
+    root.analyze(&mut diagnostics);
+    diagnostics
+}
+
+#[test]
+fn std_library_without_standard_package_is_reported() {
+    let diagnostics = analyze_with_std(
+        "
+package pkg is
+  constant c : natural := 0;
+end package;
+
+package body pkg is
+end package body;
+
+entity standard is
+end entity;
+",
+        "
+use std.pkg.all;
+
+package lib_pkg is
+end package;
+",
+    );
+
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.code == ErrorCode::Unresolved));
+    let mut messages: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    messages.sort_unstable();
+    assert_eq!(
+        messages,
+        vec![
+            "Entity 'standard' cannot be analyzed without the standard package 'std.standard'",
+            "Package 'lib_pkg' cannot be analyzed without the standard package 'std.standard'",
+            "Package 'pkg' cannot be analyzed without the standard package 'std.standard'",
+            "Package body 'pkg' cannot be analyzed without the standard package 'std.standard'",
+        ]
+    );
+}
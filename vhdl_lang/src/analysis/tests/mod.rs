@@ -4,28 +4,44 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+mod array_length;
 mod assignment_typecheck;
 mod association_formal;
+mod case_coverage;
 mod circular_dependencies;
 mod context_clause;
 mod custom_attributes;
 mod declarations;
 mod deferred_constant;
+mod design_interface;
+mod drivers;
+mod elaboration;
+mod entity_statements;
+mod expression_type;
+mod function_body;
 mod hierarchy;
 mod homographs;
 mod implicit;
 mod incomplete_type;
 mod incremental_analysis;
+mod inlay_hints;
+mod lenient_analysis;
+mod malformed_input;
 mod package_instance;
 mod protected_type;
+mod resolution_indication;
 mod resolves_design_units;
 mod resolves_names;
 mod resolves_type_mark;
+mod semantic_tokens;
 mod sensitivity_list;
+mod static_range;
+mod static_value;
 mod subprogram_arguments;
 mod subprogram_instance;
 mod tool_directive;
 mod typecheck_expression;
+mod unreachable_code;
 mod util;
 mod view_declarations;
 mod visibility;
@@ -180,7 +180,7 @@ package bad_pkg2 is new work.gpkg
             Diagnostic::new(
                 code.s1("natural"),
                 "subtype 'NATURAL' cannot be used in an expression",
-                ErrorCode::MismatchedKinds,
+                ErrorCode::TypeUsedAsValue,
             ),
             Diagnostic::new(
                 code.s1("=> work").s1("work"),
@@ -455,7 +455,7 @@ end package;
         vec![Diagnostic::new(
             code.s1("ipkg.type_t").s1("type_t"),
             "No declaration of 'type_t' within package instance 'ipkg'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
 }
@@ -661,3 +661,60 @@ end package;
     let diag = builder.analyze();
     check_no_diagnostics(&diag);
 }
+
+#[test]
+fn interface_subprogram_rhs_overload_is_selected_by_signature() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    function to_string(value : type_t) return string
+  );
+end package;
+
+package pkg is
+  function my_to_string(arg: real) return string;
+  function my_to_string(arg: integer) return string;
+
+  package ipkg is new work.gpkg
+    generic map (
+      type_t => natural,
+      to_string => my_to_string);
+
+  package ipkg2 is new work.gpkg
+    generic map (
+      type_t => bit,
+      to_string => my_to_string);
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("to_string => my_to_string", 2).s1("my_to_string"),
+            "Cannot map 'my_to_string' to subprogram generic to_string[BIT return STRING]",
+            ErrorCode::MismatchedKinds,
+        )
+        .related(
+            code.s1("my_to_string"),
+            "Does not match function my_to_string[REAL return STRING]",
+        )
+        .related(
+            code.s("my_to_string", 2),
+            "Does not match function my_to_string[INTEGER return STRING]",
+        )],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(
+            code.source(),
+            code.sa("to_string => ", "my_to_string").start()
+        ),
+        Some(code.s("my_to_string", 2).pos())
+    );
+}
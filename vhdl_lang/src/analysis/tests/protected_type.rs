@@ -6,6 +6,7 @@
 
 use super::*;
 use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
 
 #[test]
 fn error_on_missing_protected_body() {
@@ -361,3 +362,132 @@ end package body;
         2
     );
 }
+
+#[test]
+fn protected_type_objects_are_operated_on_using_methods() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type prot_t is protected
+    procedure set(value : natural);
+    impure function get return natural;
+  end protected;
+
+  type prot_t is protected body
+    variable state : natural;
+
+    procedure set(value : natural) is
+    begin
+      state := value;
+    end procedure;
+
+    impure function get return natural is
+    begin
+      return state;
+    end function;
+  end protected body;
+
+  shared variable pvar : prot_t;
+begin
+  main : process is
+    variable lvar : prot_t;
+    variable x : natural;
+    variable b : bit;
+  begin
+    pvar.set(1);
+    x := pvar.get;
+    pvar.set('1');
+    b := pvar.get;
+    pvar.missing(1);
+    x := pvar.state;
+    lvar := pvar;
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("'1'"),
+                "character literal does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("b := pvar.get").s1("get"),
+                "Invalid call to 'get'",
+                ErrorCode::InvalidCall,
+            )
+            .related(
+                code.s("get", 2),
+                "Does not match return type of function get[return NATURAL]",
+            ),
+            Diagnostic::new(
+                code.s1("missing"),
+                "No method 'missing' in protected type 'prot_t'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s("state", 4),
+                "Protected type selection must be a method, got variable 'state'",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("lvar := pvar").s1("lvar"),
+                "variable 'lvar' of protected type 'prot_t' may not be the target of an assignment",
+                ErrorCode::MismatchedKinds,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn shared_variables_must_be_of_protected_type() {
+    let contents = "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type prot_t is protected
+  end protected;
+
+  type prot_t is protected body
+  end protected body;
+
+  shared variable pvar : prot_t;
+  shared variable counter : natural;
+begin
+end architecture;";
+
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code("libname", contents);
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("natural"),
+            "Shared variable must be of a protected type, got subtype 'NATURAL'",
+            ErrorCode::SharedVariableNotProtected,
+        )],
+    );
+
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  shared variable counter : natural;
+begin
+end architecture;",
+    );
+    check_no_diagnostics(&builder.analyze());
+}
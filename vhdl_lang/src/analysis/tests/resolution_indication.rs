@@ -0,0 +1,111 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn resolves_record_element_resolution_functions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function resolve_bit(arg : bit_vector) return bit;
+  function resolve_int(arg : integer_vector) return integer;
+
+  type rec_t is record
+    flag : bit;
+    value : integer;
+    flags : bit_vector(0 to 3);
+  end record;
+
+  type pair_t is record
+    inner : rec_t;
+  end record;
+
+  subtype resolved_rec_t is (flag resolve_bit, value resolve_int, flags (resolve_bit)) rec_t;
+  subtype resolved_pair_t is (inner (flag resolve_bit)) pair_t;
+  subtype resolved_bits_t is (resolve_bit) bit_vector;
+end package;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    for (occurence, decl) in [
+        (2, "resolve_int"),
+        (2, "resolve_bit"),
+        (3, "resolve_bit"),
+        (4, "resolve_bit"),
+        (5, "resolve_bit"),
+    ] {
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s(decl, occurence).start()),
+            Some(code.s1(decl).pos()),
+        );
+    }
+}
+
+#[test]
+fn mismatched_resolution_functions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function resolve_bit(arg : bit_vector) return bit;
+  function resolve_int(arg : integer_vector) return integer;
+  constant not_a_function : bit := '0';
+
+  type rec_t is record
+    flag : bit;
+  end record;
+
+  subtype s1_t is (flag resolve_int) rec_t;
+  subtype s2_t is (missing resolve_bit) rec_t;
+  subtype s3_t is (resolve_bit) bit;
+  subtype s4_t is (elem resolve_bit) bit_vector;
+  subtype s5_t is not_a_function bit;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("resolve_int", 2),
+                "'resolve_int' is not a resolution function of type 'BIT'",
+                ErrorCode::InvalidResolution,
+            )
+            .related(
+                code.s1("resolve_int"),
+                "Does not match function resolve_int[INTEGER_VECTOR return INTEGER]",
+            ),
+            Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of 'missing' within record type 'rec_t'",
+                ErrorCode::NoDeclarationWithin,
+            ),
+            Diagnostic::new(
+                code.sa("s3_t is (", "resolve_bit"),
+                "Element resolution requires an array type, got type 'BIT'",
+                ErrorCode::InvalidResolution,
+            ),
+            Diagnostic::new(
+                code.s1("elem"),
+                "Record element resolution requires a record type, got array type 'BIT_VECTOR'",
+                ErrorCode::InvalidResolution,
+            ),
+            Diagnostic::new(
+                code.s("not_a_function", 2),
+                "constant 'not_a_function' cannot be used as a resolution function",
+                ErrorCode::MismatchedKinds,
+            ),
+        ],
+    );
+}
@@ -22,6 +22,10 @@ end configuration;
 
 entity ent is
 end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
 ",
     );
 
@@ -67,6 +71,10 @@ fn good_configurations() {
 entity ent is
 end entity;
 
+architecture rtl of ent is
+begin
+end architecture;
+
 configuration cfg_good1 of ent is
 for rtl
 end for;
@@ -119,6 +127,173 @@ end configuration;
     );
 }
 
+#[test]
+fn resolves_block_and_component_configurations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component leaf is
+  end component;
+begin
+  u0 : component leaf;
+
+  gen : for i in 0 to 1 generate
+    u1 : component leaf;
+  end generate;
+
+  blk : block
+  begin
+    u2 : component leaf;
+  end block;
+end architecture;
+
+configuration leaf_cfg of leaf is
+  for rtl
+  end for;
+end configuration;
+
+configuration cfg of top is
+  for rtl
+    for u0 : leaf
+      use entity work.leaf(rtl);
+      for rtl
+      end for;
+    end for;
+    for gen(0)
+      for all : leaf
+        use configuration work.leaf_cfg;
+      end for;
+    end for;
+    for blk
+      for u2 : leaf
+        use open;
+      end for;
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    // The architecture of the configured entity
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("rtl", 4).start()),
+        Some(code.s("rtl", 2).pos())
+    );
+    // The architecture of the entity bound to an instance
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("rtl", 6).start()),
+        Some(code.s("rtl", 1).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("work.leaf").s1("leaf").start()),
+        Some(code.s1("leaf").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("leaf_cfg", 2).start()),
+        Some(code.s1("leaf_cfg").pos())
+    );
+}
+
+#[test]
+fn error_on_configuration_of_missing_architecture_or_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component leaf is
+  end component;
+begin
+  u0 : component leaf;
+  u1 : entity work.leaf;
+end architecture;
+
+configuration cfg_missing of top is
+  for missing_arch
+  end for;
+end configuration;
+
+configuration cfg of top is
+  for rtl
+    for missing_label : leaf
+    end for;
+    for u1 : leaf
+    end for;
+    for missing_gen
+    end for;
+    for u0 : leaf
+      use entity work.missing_ent;
+    end for;
+    for all : leaf
+      use entity work.leaf(missing_rtl);
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("missing_arch"),
+                "No architecture 'missing_arch' for entity 'libname.top'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("missing_label"),
+                "No instance 'missing_label' within architecture 'rtl'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s("u1", 2),
+                "'u1' is not an instance of component 'leaf'",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("missing_gen"),
+                "No block or generate statement 'missing_gen' within architecture 'rtl'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("missing_ent"),
+                "No primary unit 'missing_ent' within library 'libname'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("missing_rtl"),
+                "No architecture 'missing_rtl' for entity 'libname.leaf'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
 #[test]
 fn search_reference_from_configuration_to_entity() {
     check_search_reference(
@@ -126,6 +301,10 @@ fn search_reference_from_configuration_to_entity() {
 entity decl is
 end entity;
 
+architecture rtl of decl is
+begin
+end architecture;
+
 configuration cfg_good1 of decl is
 for rtl
 end for;
@@ -365,12 +544,19 @@ fn search_configuration_instance() {
 entity ent is
 end entity;
 
+architecture a of ent is
+begin
+end architecture;
+
 configuration decl of ent is
   for a
   end for;
 end configuration;
 
-architecture a of ent is
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
 begin
   inst : configuration work.decl;
 end architecture;
@@ -42,6 +42,33 @@ end package;",
     );
 }
 
+#[test]
+fn suggests_similar_names_for_unresolved_identifiers() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant counter : natural := 0;
+  constant Counters : natural := 0;
+  constant pointer : natural := 0;
+  constant c0 : natural := countr;
+  constant c1 : natural := unrelated;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing(&code, "countr", 1)
+                .fix(code.s1("countr"), "counter")
+                .fix(code.s1("countr"), "Counters"),
+            missing(&code, "unrelated", 1),
+        ],
+    );
+}
+
 #[test]
 fn resolves_names_in_iface_object_decl_init_expressions() {
     check_missing(
@@ -184,6 +211,59 @@ end architecture;
     }
 }
 
+#[test]
+fn path_attributes_on_invalid_prefix() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp is
+    generic (
+      width : natural;
+      name : string := width'path_name
+    );
+  end component;
+
+  constant lib_name : string := work'simple_name;
+  constant lib_path : string := work'path_name;
+begin
+  lab : process
+    constant name : string := lab'path_name;
+  begin
+    report ent'instance_name & lab'simple_name;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("width'path_name"),
+                "constant 'width' of subtype 'NATURAL' cannot be the the prefix of 'path_name attribute",
+                ErrorCode::CannotBePrefixed,
+            ),
+            Diagnostic::new(
+                code.s1("work'path_name"),
+                "library libname cannot be the the prefix of 'path_name attribute",
+                ErrorCode::CannotBePrefixed,
+            ),
+        ],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("lab", 2).start()),
+        Some(code.s1("lab").pos())
+    );
+}
+
 #[test]
 fn resolves_names_in_discrete_ranges() {
     check_missing(
@@ -326,6 +406,7 @@ package body pkg is
      constant c5 : natural := missing'val(0);
      constant c6 : boolean := boolean'val(missing);
   begin
+    return 0;
   end;
 
 end package body;
@@ -361,11 +442,12 @@ package body pkg is
 
      -- Slice
      constant vec : integer_vector(0 to 1) := (0, 1);
-     constant c4 : integer_vector(0 to 1) := vec(decl to decl);
+     constant c4 : integer_vector(0 to 0) := vec(decl to decl);
 
      constant c5 : string := decl'simple_name;
      constant c6 : boolean := boolean'val(decl);
   begin
+    return 0;
   end;
 
 end package body;
@@ -518,7 +600,7 @@ package body pkg is
          proc2(i); -- Index is defined
          missing;
 
-         exit missing;
+         exit missing when missing;
          next missing;
        end loop;
        
@@ -632,9 +714,13 @@ end entity;
 
 architecture a of ent is
 begin
-  main : process(missing) is
+  waiting : process is
   begin
     wait on missing until missing = 0 ns for missing;
+  end process;
+
+  main : process(missing) is
+  begin
     missing <= missing after missing;
     missing <= force missing;
     missing <= release;
@@ -660,7 +746,6 @@ architecture a of ent is
 begin
   main : process (decl) is
   begin
-    wait on decl until decl = 0 ns for decl;
     decl <= decl after decl;
     decl <= force decl;
     decl <= release;
@@ -668,6 +753,11 @@ begin
        decl <= decl when decl,
                decl when others;
   end process;
+
+  waiting : process is
+  begin
+    wait on decl until decl = 0 ns for decl;
+  end process;
 end architecture;
 ",
     );
@@ -681,11 +771,11 @@ entity ent is
 end entity;
 
 architecture a of ent is
-  signal decl : natural;
 begin
   main : process is
+    variable decl : natural;
   begin
-   (0 => decl) := (0 => 1);
+   (0 => decl) := integer_vector'(0 => 1);
   end process;
 end architecture;
 ",
@@ -939,6 +1029,57 @@ end architecture;
     );
 }
 
+#[test]
+fn nested_generates_have_their_own_parameter_scope() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type matrix_t is array (0 to 3) of bit_vector(0 to 3);
+  signal sig : matrix_t;
+begin
+  outer: for idx in 0 to 3 generate
+    inner: for jdx in 0 to 3 generate
+      sig(idx)(jdx) <= '1';
+    end generate;
+    sibling: for jdx in 0 to 1 generate
+      assert sig(idx)(jdx) = '1' report integer'image(idx + jdx);
+    end generate;
+  end generate;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let outer = code.s("idx", 1).pos();
+    for occurence in 2..=4 {
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s("idx", occurence).start()),
+            Some(outer.clone())
+        );
+    }
+
+    let inner = code.s("jdx", 1).pos();
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("jdx", 2).start()),
+        Some(inner)
+    );
+
+    let sibling = code.s("jdx", 3).pos();
+    for occurence in 4..=5 {
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s("jdx", occurence).start()),
+            Some(sibling.clone())
+        );
+    }
+}
+
 #[test]
 fn search_if_generate_conditions() {
     check_search_reference(
@@ -1116,6 +1257,30 @@ end package body;
         vec![Diagnostic::new(
             code.s("subpgm", 2),
             "Signature required for alias of subprogram and enum literals",
+            ErrorCode::SubprogramAliasWithoutSignature,
+        )],
+    );
+}
+
+#[test]
+fn error_on_non_signature_for_enum_literal_alias() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  alias alias_t is alpha;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("alpha", 2),
+            "Signature required for alias of subprogram and enum literals",
             ErrorCode::SignatureRequired,
         )],
     );
@@ -1135,6 +1300,7 @@ package body pkg is
 
   function subpgm(arg: sub_type2) return sub_type2 is
   begin
+    return arg;
   end;
 
   alias alias1 is subpgm[integer return integer];
@@ -1158,10 +1324,12 @@ end package;
 package body pkg is
   function subpgm(arg: natural) return natural is
   begin
+    return arg;
   end;
 
   function subpgm(arg: boolean) return boolean is
   begin
+    return arg;
   end;
 
   alias alias1 is subpgm[boolean return boolean];
@@ -1185,6 +1353,118 @@ end package body;
     );
 }
 
+#[test]
+fn alias_without_signature_aliases_all_overloaded_subprograms() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_lenient();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function subpgm(arg: natural) return natural;
+  function subpgm(arg: boolean) return boolean;
+  procedure proc(arg: natural);
+
+  alias alias1 is subpgm;
+  alias alias2 is proc;
+
+  constant c0 : natural := alias1(0);
+  constant c1 : boolean := alias1(false);
+  constant c2 : character := alias1('a');
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("is subpgm").s1("subpgm"),
+                "Signature required for alias of subprogram and enum literals",
+                ErrorCode::NonStandard,
+            ),
+            Diagnostic::new(
+                code.s1("is proc").s1("proc"),
+                "Signature required for alias of subprogram and enum literals",
+                ErrorCode::NonStandard,
+            ),
+            Diagnostic::new(
+                code.s1("alias1('a')").s1("alias1"),
+                "Could not resolve call to 'alias1'",
+                ErrorCode::AmbiguousCall,
+            )
+            .related(
+                code.s1("alias alias1").s1("alias1"),
+                "Does not match function alias1[NATURAL return NATURAL]",
+            )
+            .related(
+                code.s1("alias alias1").s1("alias1"),
+                "Does not match function alias1[BOOLEAN return BOOLEAN]",
+            ),
+        ],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("alias1(0)").start()),
+        Some(code.s1("alias alias1").s1("alias1").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("is proc").s1("proc").start()),
+        Some(code.s1("procedure proc").s("proc", 2).pos())
+    );
+}
+
+#[test]
+fn names_can_be_selected_through_package_alias() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package math_pkg is
+  constant pi : real := 3.14;
+  function square(arg: natural) return natural;
+  function square(arg: real) return real;
+end package;
+
+package pkg is
+  alias my_math is work.math_pkg;
+  alias my_math2 is my_math;
+
+  constant c0 : real := my_math.pi;
+  constant c1 : natural := my_math.square(2);
+  constant c2 : real := my_math2.square(my_math.pi);
+  constant c3 : natural := my_math.missing;
+end package;
+
+use work.pkg.my_math.all;
+
+package pkg2 is
+  constant c4 : real := square(pi);
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("missing"),
+            "No declaration of 'missing' within package 'math_pkg'",
+            ErrorCode::NoDeclarationWithin,
+        )],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("pi", 2).start()),
+        Some(code.s1("pi").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("my_math.pi").start()),
+        Some(code.s1("my_math").pos())
+    );
+}
+
 #[test]
 fn overloaded_name_can_be_selected() {
     let mut builder = LibraryBuilder::new();
@@ -1218,6 +1498,57 @@ end package body;
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn operator_symbol_can_only_be_selected_from_package_or_library() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    f : natural;
+  end record;
+
+  function \"+\"(l, r : rec_t) return rec_t;
+end package;
+
+package body pkg is
+  function \"+\"(l, r : rec_t) return rec_t is
+  begin
+    return (f => l.f + r.f);
+  end function;
+end package body;
+
+entity ent is
+end entity;
+
+use work.pkg.rec_t;
+
+architecture a of ent is
+  constant c0 : rec_t := (f => 0);
+  constant c1 : rec_t := work.pkg.\"+\"(c0, c0);
+  constant c2 : integer := std.standard.\"+\"(1, 2);
+  constant c3 : rec_t := c0.\"+\"(c0, c0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("\"+\"", 5),
+            "Operator symbol \"+\" can only be selected from a package or library, not from a value of record type 'rec_t'",
+            ErrorCode::MismatchedKinds,
+        )],
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("\"+\"", 3).start()),
+        Some(code.s("\"+\"", 1).pos())
+    );
+}
+
 #[test]
 fn record_fields_are_resolved() {
     let mut builder = LibraryBuilder::new();
@@ -1259,22 +1590,22 @@ end package;
             Diagnostic::new(
                 code.s("missing", 1),
                 "No declaration of 'missing' within record type 'rec1_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
             Diagnostic::new(
                 code.s("missing", 2),
                 "No declaration of 'missing' within record type 'rec2_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
             Diagnostic::new(
                 code.s("missing", 3),
                 "No declaration of 'missing' within record type 'rec1_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
             Diagnostic::new(
                 code.s("missing", 4),
                 "No declaration of 'missing' within record type 'rec2_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
         ],
     );
@@ -1345,7 +1676,7 @@ end package body;
         vec![Diagnostic::new(
             code.s("missing", 1),
             "No declaration of 'missing' within record type 'rec_t'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
 }
@@ -1387,7 +1718,7 @@ end architecture;
         vec![Diagnostic::new(
             code.s("missing", 1),
             "No declaration of 'missing' within record type 'rec_t'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
 }
@@ -1435,7 +1766,7 @@ end architecture;
         diagnostics,
         vec![Diagnostic::new(
             code.s("missing", 1),
-            "No declaration of 'missing' within protected type 'prot_t'",
+            "No method 'missing' in protected type 'prot_t'",
             ErrorCode::Unresolved,
         )],
     );
@@ -1484,12 +1815,12 @@ end architecture;
             Diagnostic::new(
                 code.s("missing", 1),
                 "No declaration of 'missing' within record type 'rec_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
             Diagnostic::new(
                 code.s("missing", 2),
                 "No declaration of 'missing' within record type 'rec_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
         ],
     );
@@ -1569,7 +1900,7 @@ constant bad : rec_t := (missing => 0);
         vec![Diagnostic::new(
             code.s1("missing"),
             "No declaration of 'missing' within record type 'rec_t'",
-            ErrorCode::Unresolved,
+            ErrorCode::NoDeclarationWithin,
         )],
     );
     let field = root
@@ -1962,6 +2293,7 @@ architecture a1 of ent1 is
 
     function fun1 return integer is
     begin
+      return 0;
     end function fun1;
 
     procedure proc1 is
@@ -2047,7 +2379,7 @@ begin
     end if if0;
 
     loop0: for i in 0 to 1 loop
-      next loop0;
+      next loop0 when i = 0;
       exit loop0;
     end loop loop0;
 
@@ -2096,11 +2428,21 @@ end architecture;
 #[test]
 pub fn parse_selected_all() {
     let mut builder = LibraryBuilder::new();
-    let code = builder.in_declarative_region(
+    let code = builder.code(
+        "libname",
         "\
-type t_str_ptr is access string;
-signal str_value_ptr : t_str_ptr;
-signal v_value : str_value_ptr.all'subtype;
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    type t_str_ptr is access string;
+    variable str_value_ptr : t_str_ptr;
+    variable v_value : str_value_ptr.all'subtype;
+  begin
+  end process;
+end architecture;
     ",
     );
     let (root, diagnostics) = builder.get_analyzed_root();
@@ -2132,7 +2474,7 @@ package pkg2 is
 end package pkg2;
  
 package pkg3 is
-    variable v1 : work.pkg2.pkg1.type1_t;
+    constant c1 : work.pkg2.pkg1.type1_t := \"01\";
 end package;",
     );
     let (_root, diagnostics) = builder.get_analyzed_root();
@@ -2158,9 +2500,230 @@ end package pkg2;
 
 package pkg3 is
     package pkg2 is new work.pkg2 generic map (1);
-    variable v1 : pkg2.pkg1.type1_t;
+    constant c1 : pkg2.pkg1.type1_t := \"01\";
 end package;",
     );
     let (_root, diagnostics) = builder.get_analyzed_root();
     check_no_diagnostics(&diagnostics);
 }
+
+#[test]
+fn goto_custom_operator_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    f : natural;
+  end record;
+  function \"+\"(l, r : rec_t) return rec_t;
+end package;
+
+package body pkg is
+  function \"+\"(l, r : rec_t) return rec_t is
+  begin
+    return (f => l.f + r.f);
+  end function;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant a, b : rec_t := (f => 0);
+  constant c : rec_t := a + b;
+  constant name : string := \"+\"'simple_name;
+  constant path : string := work.pkg.\"+\"[rec_t, rec_t return rec_t]'path_name;
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let op = root
+        .search_reference(code.source(), code.s1("a + b").s1("+").start())
+        .unwrap();
+    assert_eq!(op.decl_pos(), Some(&code.s1("\"+\"").pos()));
+    assert_eq!(op.describe(), "operator \"+\"[rec_t, rec_t return rec_t]");
+    assert_eq!(
+        root.find_definition_of(op).and_then(|ent| ent.decl_pos()),
+        Some(&code.s("\"+\"", 2).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("\"+\"", 4).start()),
+        Some(code.s1("\"+\"").pos())
+    );
+}
+
+#[test]
+fn resolves_names_within_nested_packages() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package outer is
+  package inner is
+    constant c0 : natural := 0;
+    function fun(arg : natural) return natural;
+  end package;
+end package;
+
+package body outer is
+  package body inner is
+    function fun(arg : natural) return natural is
+    begin
+      return arg + c0;
+    end function;
+  end package body;
+end package body;
+
+use work.outer.inner.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  package local is
+    constant c1 : natural := 1;
+  end package;
+
+  constant c2 : natural := work.outer.inner.c0 + fun(c0) + local.c1;
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("inner", 3).start()),
+        Some(code.s1("inner").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("c0", 4).start()),
+        Some(code.s1("c0").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("fun(c0)").start()),
+        Some(code.s1("fun(arg").s1("fun").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("c1", 2).start()),
+        Some(code.s1("c1").pos())
+    );
+}
+
+#[test]
+fn nested_package_body_without_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package outer is
+  constant inner : natural := 0;
+end package;
+
+package body outer is
+  package body inner is
+  end package body;
+
+  package body missing is
+  end package body;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("inner", 2),
+                "'inner' is not a package",
+                ErrorCode::MismatchedKinds,
+            )
+            .related(code.s1("inner"), "Found constant 'inner'"),
+            Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of package 'missing'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn alias_of_selected_subprogram_with_signature() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function g(arg : integer) return integer;
+  function g(arg : boolean) return integer;
+end package;
+
+package body pkg is
+  function g(arg : integer) return integer is
+  begin
+    return arg;
+  end function;
+
+  function g(arg : boolean) return integer is
+  begin
+    return 0;
+  end function;
+end package body;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  alias f is work.pkg.g [integer return integer];
+  alias bad is work.pkg.g [bit return integer];
+  constant c0 : integer := f(1);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("g [bit").s1("g"),
+            "Could not find declaration of 'g' with given signature",
+            ErrorCode::NoOverloadedWithSignature,
+        )
+        .related(
+            code.s1("g(arg : integer)").s1("g"),
+            "Found function g[INTEGER return INTEGER]",
+        )
+        .related(
+            code.s1("g(arg : boolean)").s1("g"),
+            "Found function g[BOOLEAN return INTEGER]",
+        )],
+    );
+
+    // The alias denotes the overload named by its signature
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("g [integer").s1("g").start()),
+        Some(code.s1("g(arg : integer)").s1("g").pos())
+    );
+
+    // Calls through the alias resolve to the alias, which denotes the aliased function
+    let alias = root
+        .search_reference(code.source(), code.s1("f(1)").start())
+        .unwrap();
+    assert_eq!(alias.decl_pos(), Some(&code.s1("alias f").s1("f").pos()));
+    assert_eq!(
+        alias.as_actual().decl_pos(),
+        Some(&code.s1("g(arg : integer)").s1("g").pos())
+    );
+}
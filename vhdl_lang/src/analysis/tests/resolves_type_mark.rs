@@ -343,6 +343,7 @@ end package;
 package body pkg is
   function bad return natural is
   begin
+    return 0;
   end function;
 
   constant err : bad := 0;
@@ -0,0 +1,108 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::SemanticTokenKind::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn classifies_declarations_and_references() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : natural);
+  port (clk : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy);
+  constant reset_state : state_t := idle;
+  signal state : state_t;
+  function is_busy(s : state_t) return boolean is
+  begin
+    return s = busy;
+  end function;
+begin
+  main: process (clk)
+    variable count : natural;
+  begin
+    count := width;
+    state <= reset_state;
+    q <= '1' when is_busy(state) else '0';
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tokens: Vec<_> = root
+        .semantic_tokens(code.source())
+        .into_iter()
+        .map(|token| {
+            (
+                token.pos,
+                token.kind,
+                token.is_declaration,
+                token.is_readonly,
+            )
+        })
+        .collect();
+
+    let standard_types: Vec<_> = tokens
+        .iter()
+        .filter(|(pos, ..)| [code.s1("natural").pos(), code.s1("bit").pos()].contains(pos))
+        .collect();
+    assert_eq!(standard_types.len(), 2);
+    assert!(standard_types
+        .iter()
+        .all(|(_, kind, is_declaration, _)| *kind == Type && !is_declaration));
+
+    let expected = [
+        (
+            code.s1("entity ent").s("ent", 2).pos(),
+            DesignUnit,
+            true,
+            false,
+        ),
+        (code.s1("width").pos(), Generic, true, true),
+        (code.s1("clk").pos(), Port, true, true),
+        (code.s1("q :").s1("q").pos(), Port, true, false),
+        (code.s1("state_t").pos(), Type, true, false),
+        (code.s1("idle").pos(), EnumLiteral, true, true),
+        (code.s1("reset_state").pos(), Constant, true, true),
+        (
+            code.s1("signal state").s1("state").pos(),
+            Signal,
+            true,
+            false,
+        ),
+        (code.s1("is_busy").pos(), Function, true, false),
+        (code.s1("s :").s1("s").pos(), Parameter, true, true),
+        (code.s1("main").pos(), Label, true, false),
+        (code.s1("count").pos(), Variable, true, false),
+        (code.s("count", 2).pos(), Variable, false, false),
+        (code.s("width", 2).pos(), Generic, false, true),
+        (
+            code.s("state <=", 1).s1("state").pos(),
+            Signal,
+            false,
+            false,
+        ),
+        (code.s("is_busy", 2).pos(), Function, false, false),
+    ];
+    for token in expected {
+        assert!(tokens.contains(&token), "missing {token:?}");
+    }
+
+    // The equality operator is not classified
+    assert!(!tokens
+        .iter()
+        .any(|(pos, ..)| *pos == code.s1("= busy").s1("=").pos()));
+}
@@ -119,3 +119,59 @@ end architecture;
     let (_, diagnostics) = builder.get_analyzed_root();
     check_no_diagnostics(&diagnostics);
 }
+
+#[test]
+fn wait_not_allowed_in_process_with_sensitivity_list() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, rst, q : bit;
+begin
+  process (clk, rst)
+    procedure pause is
+    begin
+      wait for 1 ns;
+    end procedure;
+  begin
+    if rst = '1' then
+      wait until rst = '0';
+    end if;
+  end process;
+
+  process (all)
+  begin
+    q <= clk;
+    wait on clk;
+  end process;
+
+  process
+  begin
+    wait until clk = '1' for 10 ns;
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("wait until rst = '0';"),
+                "Wait statement is not allowed in a process with a sensitivity list",
+                ErrorCode::WaitInSensitizedProcess,
+            )
+            .related(code.s1("(clk, rst)"), "Sensitivity list of the process"),
+            Diagnostic::new(
+                code.s1("wait on clk;"),
+                "Wait statement is not allowed in a process with a sensitivity list",
+                ErrorCode::WaitInSensitizedProcess,
+            )
+            .related(code.s1("(all)"), "Sensitivity list of the process"),
+        ],
+    )
+}
@@ -0,0 +1,257 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn value_outside_of_range_constraint() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal byte : natural range 0 to 255 := 256;
+  signal index : integer range 7 downto 0;
+begin
+  byte <= 300;
+  byte <= 255;
+
+  process
+    variable count : integer range -1 to 1;
+  begin
+    count := -2;
+    index <= 8 when count = 0 else 0;
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("256"),
+                "Value 256 is outside of the range 0 to 255",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("300"),
+                "Value 300 is outside of the range 0 to 255",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("-2"),
+                "Value -2 is outside of the range -1 to 1",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.sa("index <= ", "8"),
+                "Value 8 is outside of the range 7 downto 0",
+                ErrorCode::OutOfRange,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn only_innermost_range_constraint_is_checked() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type small_t is range 0 to 100;
+  signal s : small_t range 0 to 10 := 300;
+begin
+  s <= 50;
+  s <= 200;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("300"),
+                "Value 300 is outside of the range 0 to 10",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("50"),
+                "Value 50 is outside of the range 0 to 10",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("200"),
+                "Value 200 is outside of the range 0 to 10",
+                ErrorCode::OutOfRange,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn value_outside_of_subtype_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy, done);
+  subtype active_t is state_t range idle to busy;
+  type distance_t is range 0 to 1000000
+    units
+      mm;
+      cm = 10 mm;
+    end units;
+  subtype short_t is distance_t range 0 mm to 5 cm;
+  subtype tiny_t is short_t;
+
+  constant state_c : active_t := done;
+  constant short_c : short_t := 7 cm;
+  constant tiny_c : tiny_t := 51 mm;
+  constant ok_c : tiny_t := 5 cm;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.sa("active_t := ", "done"),
+                "Value 'done' is outside of the range 'idle' to 'busy'",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("7 cm"),
+                "Value 70 mm is outside of the range 0 mm to 50 mm",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.s1("51 mm"),
+                "Value 51 mm is outside of the range 0 mm to 50 mm",
+                ErrorCode::OutOfRange,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn no_range_check_of_non_static_values() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : natural);
+end entity;
+
+architecture a of ent is
+  subtype word_t is natural range 0 to 2 ** width - 1;
+  signal byte : natural range 0 to 255;
+  signal word : word_t := 300;
+begin
+  byte <= width + 300;
+  byte <= byte + 1;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn value_outside_of_range_with_attribute_bounds() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy, done);
+  subtype active_t is state_t range state_t'low to busy;
+  subtype small_t is integer range 7 downto 0;
+  subtype positive_small_t is integer range small_t'low + 1 to small_t'left;
+
+  constant active_c : active_t := done;
+  constant positive_c : positive_small_t := 0;
+  constant ok_c : positive_small_t := 7;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.sa("active_t := ", "done"),
+                "Value 'done' is outside of the range 'idle' to 'busy'",
+                ErrorCode::OutOfRange,
+            ),
+            Diagnostic::new(
+                code.sa("positive_small_t := ", "0"),
+                "Value 0 is outside of the range 1 to 7",
+                ErrorCode::OutOfRange,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn subtype_range_outside_of_type_mark_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy, done);
+  subtype active_t is state_t range idle to busy;
+  subtype small is integer range 0 to 300;
+  subtype s is natural range -1 to 5;
+  subtype byte is small range 255 downto 0;
+  subtype nibble is byte range 0 to 256;
+  subtype empty is natural range 5 to -1;
+  subtype late_t is active_t range busy to done;
+  constant count : small range 0 to 400 := 3;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("range -1 to 5"),
+                "Range -1 to 5 is not within the range 0 to 2147483647 of subtype 'NATURAL'",
+                ErrorCode::SubtypeRangeOutOfBase,
+            ),
+            Diagnostic::new(
+                code.s1("range 0 to 256"),
+                "Range 0 to 256 is not within the range 255 downto 0 of subtype 'byte'",
+                ErrorCode::SubtypeRangeOutOfBase,
+            ),
+            Diagnostic::new(
+                code.s1("range busy to done"),
+                "Range 'busy' to 'done' is not within the range 'idle' to 'busy' of subtype 'active_t'",
+                ErrorCode::SubtypeRangeOutOfBase,
+            ),
+            Diagnostic::new(
+                code.s1("range 0 to 400"),
+                "Range 0 to 400 is not within the range 0 to 300 of subtype 'small'",
+                ErrorCode::SubtypeRangeOutOfBase,
+            ),
+        ],
+    );
+}
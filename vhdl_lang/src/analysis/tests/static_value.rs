@@ -0,0 +1,171 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::ast::Direction;
+use crate::named_entity::{AnyEntKind, StaticRange, StaticValue};
+use pretty_assertions::assert_eq;
+
+fn static_value_of(root: &DesignRoot, code: &Code, name: &str) -> Option<StaticValue> {
+    let ent = root
+        .search_reference(code.source(), code.s1(&format!("{name} :")).start())
+        .unwrap();
+    match ent.kind() {
+        AnyEntKind::Object(_) => root.static_value(ent.id()),
+        other => panic!("Expected object, got {}", other.describe()),
+    }
+}
+
+#[test]
+fn evaluates_static_constants() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy, done);
+  type distance_t is range 0 to 1000000
+    units
+      mm;
+      cm = 10 mm;
+      m = 100 cm;
+    end units;
+
+  constant int_c : integer := 3 * (4 + 1) - 2 ** 3;
+  constant neg_c : integer := (-7) mod 3;
+  constant rem_c : integer := (-7) rem 3;
+  constant div_c : integer := 7 / 2;
+  constant ref_c : integer := int_c + 1;
+  constant enum_c : state_t := busy;
+  constant char_c : character := 'a';
+  constant rel_c : boolean := ref_c > int_c and enum_c /= done;
+  constant bit_c : bit := not '1';
+  constant time_c : time := 2 ns + 500 ps;
+  constant dist_c : distance_t := 2 m + 5 cm;
+  constant ratio_c : integer := dist_c / 1 cm;
+  constant high_c : integer := integer'high;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let value = |name| static_value_of(&root, &code, name);
+    assert_eq!(value("int_c"), Some(StaticValue::Integer(7)));
+    assert_eq!(value("neg_c"), Some(StaticValue::Integer(2)));
+    assert_eq!(value("rem_c"), Some(StaticValue::Integer(-1)));
+    assert_eq!(value("div_c"), Some(StaticValue::Integer(3)));
+    assert_eq!(value("ref_c"), Some(StaticValue::Integer(8)));
+    assert_eq!(value("high_c"), Some(StaticValue::Integer(2147483647)));
+    assert_eq!(value("enum_c"), Some(StaticValue::Enum(1)));
+    assert_eq!(value("char_c"), Some(StaticValue::Enum(97)));
+    assert_eq!(value("rel_c"), Some(StaticValue::Enum(1)));
+    assert_eq!(value("bit_c"), Some(StaticValue::Enum(0)));
+    assert_eq!(value("time_c"), Some(StaticValue::Physical(2_500_000)));
+    assert_eq!(value("dist_c"), Some(StaticValue::Physical(2050)));
+    assert_eq!(value("ratio_c"), Some(StaticValue::Integer(205)));
+}
+
+#[test]
+fn evaluates_physical_literals_with_real_values() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type distance_t is range 0 to 1000000
+    units
+      mm;
+      inch = 25.4 mm;
+    end units;
+
+  constant real_c : time := 2.5 ns;
+  constant rounded_c : time := 1.0005 ps;
+  constant less_c : boolean := 2.5 ns < 3 ns;
+  constant inch_c : distance_t := 2 inch;
+  constant half_c : distance_t := 0.5 inch;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let value = |name| static_value_of(&root, &code, name);
+    assert_eq!(value("real_c"), Some(StaticValue::Physical(2_500_000)));
+    assert_eq!(value("rounded_c"), Some(StaticValue::Physical(1001)));
+    assert_eq!(value("less_c"), Some(StaticValue::Enum(1)));
+    assert_eq!(value("inch_c"), Some(StaticValue::Physical(50)));
+    assert_eq!(value("half_c"), Some(StaticValue::Physical(13)));
+}
+
+#[test]
+fn non_static_constants_have_no_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function f return integer;
+  function \"+\"(a, b : bit) return bit;
+
+  constant call_c : integer := f;
+  constant real_c : real := 1.0;
+  constant attr_c : time := time'high;
+  constant user_op_c : bit := '1' + '0';
+  constant overflow_c : integer := 2 ** 64;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    for name in ["call_c", "real_c", "attr_c", "user_op_c", "overflow_c"] {
+        assert_eq!(static_value_of(&root, &code, name), None, "{name}");
+    }
+}
+
+#[test]
+fn ranges_of_integer_types_are_recorded() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type byte_t is range 255 downto 0;
+  type big_t is range 0 to byte_t'high * 2;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let range = |name: &str| {
+        let ent = root
+            .search_reference(code.source(), code.s1(name).start())
+            .unwrap();
+        root.static_range(ent.id())
+    };
+    assert_eq!(
+        range("byte_t"),
+        Some(StaticRange {
+            left: StaticValue::Integer(255),
+            direction: Direction::Descending,
+            right: StaticValue::Integer(0),
+        })
+    );
+    assert_eq!(
+        range("big_t"),
+        Some(StaticRange {
+            left: StaticValue::Integer(0),
+            direction: Direction::Ascending,
+            right: StaticValue::Integer(510),
+        })
+    );
+}
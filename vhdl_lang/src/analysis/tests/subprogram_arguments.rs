@@ -14,6 +14,7 @@ fn wrong_number_of_arguments() {
         "
 function subpgm(arg: natural) return natural
 is begin
+    return 0;
 end;
 
 signal good : natural := subpgm(0);
@@ -45,6 +46,7 @@ end entity;
 architecture a of ent is
     function subpgm(arg: natural) return natural
     is begin
+        return 0;
     end;
 
     procedure theproc(arg: natural)
@@ -90,10 +92,12 @@ fn resolve_overloaded_subprogram_by_return_type() {
         "
 function subpgm(arg: natural) return character
 is begin
+    return 'a';
 end;
 
 function subpgm(arg: natural) return natural
 is begin
+    return 0;
 end;
 
 
@@ -157,10 +161,12 @@ fn resolve_overloaded_subprogram_by_argument() {
         "
 function subpgm(arg: character) return natural
 is begin
+    return 0;
 end;
 
 function subpgm(arg: natural) return natural
 is begin
+    return 0;
 end;
 
 
@@ -188,6 +194,7 @@ fn subprogram_argument_not_associated() {
         "
 function subpgm(arg1: natural; arg2: character) return natural
 is begin
+    return 0;
 end;
 
 signal bad : natural := subpgm(0);
@@ -211,6 +218,43 @@ signal bad : natural := subpgm(0);
     );
 }
 
+#[test]
+fn overloaded_procedure_argument_not_associated() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure proc(arg1 : natural; arg2 : natural) is
+begin
+end;
+
+procedure proc(arg1 : character) is
+begin
+end;
+
+procedure caller is
+begin
+  proc(arg2 => 0);
+end;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("proc(arg2 => 0)"),
+            "No association of parameter 'arg1'",
+            ErrorCode::Unassociated,
+        )
+        .related(code.s1("arg1"), "Defined here")],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("proc(arg2").start()),
+        Some(code.sb("proc", "(arg1 : natural").pos())
+    );
+}
+
 #[test]
 fn subprogram_extra_argument_not_associated() {
     let mut builder = LibraryBuilder::new();
@@ -218,6 +262,7 @@ fn subprogram_extra_argument_not_associated() {
         "
 function subpgm(arg1: natural) return natural
 is begin
+    return 0;
 end;
 
 signal bad : natural := subpgm(1111, 2222);
@@ -434,3 +479,57 @@ end procedure;
         .related(code.s1("arg(0)"), "Previously associated here")],
     );
 }
+
+#[test]
+fn concurrent_procedure_call_to_overloaded_functions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    function subpgm(arg: natural) return natural
+    is begin
+        return 0;
+    end;
+
+    function subpgm(arg: character) return natural
+    is begin
+        return 0;
+    end;
+begin
+    lbl: subpgm(0);
+    postponed subpgm('a');
+
+    process
+    begin
+        subpgm(0);
+        wait;
+    end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    let invalid_call = |occurence| {
+        Diagnostic::new(
+            code.s("subpgm", occurence),
+            "Invalid procedure call",
+            ErrorCode::InvalidCall,
+        )
+        .related(
+            code.s("subpgm", 1),
+            "function subpgm[NATURAL return NATURAL] is not a procedure",
+        )
+        .related(
+            code.s("subpgm", 2),
+            "function subpgm[CHARACTER return NATURAL] is not a procedure",
+        )
+    };
+    check_diagnostics(
+        diagnostics,
+        vec![invalid_call(3), invalid_call(4), invalid_call(5)],
+    );
+}
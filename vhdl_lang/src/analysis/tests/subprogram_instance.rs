@@ -189,6 +189,7 @@ function func is new prok;
 function funk
     generic ( x: natural := 1 ) return bit is
 begin
+    return '0';
 end funk;
 
 procedure proc is new funk;
@@ -210,6 +211,7 @@ procedure proc is new funk;
         "\
 function proc generic (type T) return bit is
 begin
+    return '0';
 end proc;
 
 function proc is new proc;
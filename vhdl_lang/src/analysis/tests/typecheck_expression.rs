@@ -6,6 +6,7 @@
 
 use super::*;
 use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
 
 #[test]
 fn test_integer_literal_expression_typecheck() {
@@ -883,6 +884,57 @@ constant bad1 : integer := (3, 4, 5);
     );
 }
 
+#[test]
+fn aggregate_without_determinable_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s0, s1 : bit;
+begin
+  main : process is
+    variable v0, v1 : natural;
+  begin
+    (v0, v1) := (0, 1);
+    (v0, v1) := integer_vector'(0, 1);
+    case (s0, s1) is
+      when others =>
+    end case;
+  end process;
+
+  (s0, s1) <= ('0', '1');
+  (s0, s1) <= bit_vector'(\"01\");
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("(0, 1)"),
+                "Type of aggregate cannot be determined from the context. You can use a qualified expression type'(expr) to specify the type.",
+                ErrorCode::UntypedAggregate,
+            ),
+            Diagnostic::new(
+                code.s1("(s0, s1) is").s1("(s0, s1)"),
+                "Type of aggregate cannot be determined from the context. You can use a qualified expression type'(expr) to specify the type.",
+                ErrorCode::UntypedAggregate,
+            ),
+            Diagnostic::new(
+                code.s1("('0', '1')"),
+                "Type of aggregate cannot be determined from the context. You can use a qualified expression type'(expr) to specify the type.",
+                ErrorCode::UntypedAggregate,
+            ),
+        ],
+    );
+}
+
 #[test]
 fn typecheck_multi_dimensional_array_aggregate() {
     let mut builder = LibraryBuilder::new();
@@ -1484,6 +1536,56 @@ end;
     );
 }
 
+#[test]
+fn typecheck_nested_return_statements() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function bad1(n : natural) return integer is
+begin
+  for i in 0 to n loop
+    if i = 2 then
+      return true;
+    end if;
+  end loop;
+  case n is
+    when 0 => return;
+    when others => return n;
+  end case;
+end;
+
+procedure bad2(n : natural) is
+begin
+  while n > 0 loop
+    return n;
+  end loop;
+end;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("true"),
+                "'true' does not match integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("return;"),
+                "Functions cannot return without a value",
+                ErrorCode::VoidReturn,
+            ),
+            Diagnostic::new(
+                code.s("return n;", 2),
+                "Procedures cannot return a value",
+                ErrorCode::NonVoidReturn,
+            ),
+        ],
+    );
+}
+
 #[test]
 fn typecheck_report_statement() {
     let mut builder = LibraryBuilder::new();
@@ -1560,6 +1662,177 @@ end;
     );
 }
 
+#[test]
+fn typecheck_conditional_and_selected_expressions() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2019);
+    let code = builder.in_declarative_region(
+        "
+constant c0 : natural := (1 when true else 2);
+constant c1 : natural := (1 when 0 else 'a');
+constant c2 : natural := (with c0 select 1 when 0 | 1, 2 when others);
+constant c3 : natural := (with c0 select 1 when 'a', 2 when others);
+constant c4 : boolean := (c0 when c1 = 0 else c1) = 0;
+constant c5 : boolean := (c0 when c1 = 0 else false) = 0;
+constant c6 : natural := ('b' when c0 = 0 else c0 when c1 = 0 else 2);
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("when 0 else").s1("0"),
+                "type universal_integer cannot be implicitly converted to type 'BOOLEAN'. Operator ?? is not defined for this type.",
+                ErrorCode::NoImplicitConversion,
+            ),
+            Diagnostic::new(
+                code.s1("'a')").s1("'a'"),
+                "character literal does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("when 'a'").s1("'a'"),
+                "character literal does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("else false").s1("false"),
+                "'false' does not match integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("'b'"),
+                "character literal does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn conditional_expression_references() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2019);
+    let code = builder.in_declarative_region(
+        "
+constant c0 : natural := 0;
+constant c1 : natural := (c0 when c0 > 0 else 1);
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("c0", 2).start()),
+        Some(code.s1("c0").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("c0", 3).start()),
+        Some(code.s1("c0").pos())
+    );
+}
+
+#[test]
+fn type_name_cannot_be_used_as_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure wrapper is
+    variable x : integer;
+    subtype sub_t is integer range 0 to 1;
+begin
+    x := integer;
+    x := sub_t + 1;
+    x := integer(x) + integer'(1) + sub_t'high;
+end;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1(":= integer").s1("integer"),
+                "integer type 'INTEGER' cannot be used in an expression",
+                ErrorCode::TypeUsedAsValue,
+            ),
+            Diagnostic::new(
+                code.s1("sub_t + 1").s1("sub_t"),
+                "subtype 'sub_t' cannot be used in an expression",
+                ErrorCode::TypeUsedAsValue,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn typecheck_unknown_severity_level() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type my_severity_t is (fatal, note);
+
+procedure wrapper is
+begin
+   report \"good\" severity note;
+   report \"bad\" severity fialure;
+   assert true report \"bad\" severity fatal;
+end;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("fialure"),
+                "Unknown severity level 'fialure'. Expected one of note, warning, error or failure",
+                ErrorCode::Unresolved,
+            )
+            .fix(code.s1("fialure"), "FAILURE"),
+            Diagnostic::new(
+                code.s("fatal", 2),
+                "'fatal' does not match type 'SEVERITY_LEVEL'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn unknown_severity_level_message_is_only_used_for_misspelled_levels() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure wrapper is
+begin
+   report \"bad\" severity warnin;
+   report \"bad\" severity unrelated;
+end;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("warnin"),
+                "Unknown severity level 'warnin'. Expected one of note, warning, error or failure",
+                ErrorCode::Unresolved,
+            )
+            .fix(code.s1("warnin"), "WARNING"),
+            Diagnostic::new(
+                code.s1("unrelated"),
+                "No declaration of 'unrelated'",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
 #[test]
 fn resolves_unambiguous_boolean_reference() {
     let mut builder = LibraryBuilder::new();
@@ -1847,7 +2120,7 @@ subtype bad3_t is integer(bad('a' to 'b'));
             Diagnostic::new(
                 code.s1("missing"),
                 "No declaration of 'missing' within record type 'rec_t'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
             Diagnostic::new(
                 code.s1("integer(").s1("integer"),
@@ -1976,3 +2249,270 @@ end package;
     let diagnostics = builder.analyze();
     check_no_diagnostics(&diagnostics);
 }
+
+#[test]
+fn operator_pushes_operand_types_into_overloaded_calls() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type t1 is range 0 to 1;
+type t2 is range 0 to 1;
+type rec_t is record
+  field : natural;
+end record;
+
+function f(arg : natural) return t1;
+function f(arg : natural) return rec_t;
+function g(arg : natural) return t2;
+function g(arg : natural) return rec_t;
+
+function \"+\"(left : t1; right : t2) return rec_t;
+
+constant good : rec_t := f(0) + g(0);
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("f(0) +").start()),
+        Some(code.s1("f(arg : natural) return t1").s1("f").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("g(0);").start()),
+        Some(code.s1("g(arg : natural) return t2").s1("g").pos())
+    );
+}
+
+#[test]
+fn ambiguous_operator_with_overloaded_call_operands() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type t1 is range 0 to 1;
+type t2 is range 0 to 1;
+type rec_t is record
+  field : natural;
+end record;
+
+function f(arg : natural) return t1;
+function f(arg : natural) return t2;
+function g(arg : natural) return t1;
+function g(arg : natural) return t2;
+
+function \"+\"(left : t1; right : t2) return rec_t;
+function \"+\"(left : t2; right : t1) return rec_t;
+
+constant bad : rec_t := f(0) + g(0);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("f(0) + g(0)").s1("+"),
+            "ambiguous use of operator \"+\"",
+            ErrorCode::AmbiguousCall,
+        )
+        .related(
+            code.s1("\"+\"(left : t1").s1("\"+\""),
+            "might be operator \"+\"[t1, t2 return rec_t]",
+        )
+        .related(
+            code.s1("\"+\"(left : t2").s1("\"+\""),
+            "might be operator \"+\"[t2, t1 return rec_t]",
+        )],
+    );
+}
+
+#[test]
+fn matching_case_and_select_with_dont_care_choices() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_logic_1164();
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+    signal sel : std_ulogic_vector(2 downto 0);
+    signal bits : bit_vector(1 downto 0);
+    signal s : std_ulogic;
+    signal b : boolean;
+    signal int : integer;
+begin
+    process (all)
+        variable v : std_ulogic;
+    begin
+        case? sel is
+            when \"1--\" => s <= '1';
+            when \"01-\" | \"001\" => s <= '0';
+            when others => s <= 'X';
+        end case?;
+        case? s is
+            when '-' => null;
+            when others => null;
+        end case?;
+        case? bits is
+            when \"1-\" => null;
+            when others => null;
+        end case?;
+        case? int is
+            when 0 => null;
+            when others => null;
+        end case?;
+        with sel select? v := '1' when \"1--\", '0' when others;
+        if sel ?= \"1-0\" then
+        end if;
+        b <= ?? (sel(0) ?/= '-');
+    end process;
+
+    with sel select? s <= '1' when \"1--\", '0' when others;
+    with int select? s <= '1' when 0, '0' when others;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("\"1-\""),
+                "type 'BIT' does not define character '-'",
+                ErrorCode::InvalidLiteral,
+            ),
+            Diagnostic::new(
+                code.s1("case? int").s1("int"),
+                "integer type 'INTEGER' cannot be the expression of a matching case. Expected 'BIT', 'STD_ULOGIC' or a one-dimensional array of these",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("with int").s1("int"),
+                "integer type 'INTEGER' cannot be the expression of a matching case. Expected 'BIT', 'STD_ULOGIC' or a one-dimensional array of these",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn allocator_must_match_designated_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type int_ptr_t is access integer;
+type vec_ptr_t is access integer_vector;
+
+procedure proc is
+  variable good0 : int_ptr_t := new integer'(0);
+  variable good1 : int_ptr_t := new natural;
+  variable good2 : vec_ptr_t := new integer_vector(0 to 1);
+  variable good3 : vec_ptr_t := new integer_vector'(0, 1);
+
+  variable bad0 : int_ptr_t := new boolean'(false);
+  variable bad1 : vec_ptr_t := new bit_vector(0 to 1);
+  variable bad2 : integer := new integer'(0);
+begin
+end procedure;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("boolean'(false)"),
+                "type 'BOOLEAN' does not match integer type 'INTEGER' designated by access type 'int_ptr_t'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("bit_vector(0 to 1)"),
+                "array type 'BIT_VECTOR' does not match array type 'INTEGER_VECTOR' designated by access type 'vec_ptr_t'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("integer := new integer'(0)").s1("integer'(0)"),
+                "allocator of integer type 'INTEGER' does not match integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn range_bounds_must_have_the_same_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type good_arr_t is array (0 to 1) of bit;
+type bad_arr_t is array (0 to true) of bit;
+
+procedure proc is
+begin
+  for i in 'a' to 'z' loop
+  end loop;
+  for i in 'a' to 10 loop
+  end loop;
+end procedure;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("0 to true"),
+                "Range type mismatch, left is type universal_integer, right is type 'BOOLEAN'",
+                ErrorCode::RangeBoundTypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("'a' to 10"),
+                "Range type mismatch, left is type 'CHARACTER', right is type universal_integer",
+                ErrorCode::RangeBoundTypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn range_with_attribute_bounds() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type state_t is (idle, busy, done);
+subtype int_t is integer range integer'low to integer'high;
+subtype nat_t is integer range natural'low + 1 to natural'high;
+subtype state_sub_t is state_t range state_t'low to state_t'high;
+type arr_t is array (state_t'low to state_t'high) of bit;
+
+procedure proc is
+  variable v : state_t;
+begin
+  for i in state_t'low to state_t'high loop
+    v := i;
+  end loop;
+  for i in character'low to state_t'high loop
+  end loop;
+end procedure;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("character'low to state_t'high"),
+            "Range type mismatch, left is type 'CHARACTER', right is type 'state_t'",
+            ErrorCode::RangeBoundTypeMismatch,
+        )],
+    );
+}
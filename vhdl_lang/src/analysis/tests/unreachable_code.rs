@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use vhdl_lang::data::error_codes::ErrorCode;
+
+#[test]
+fn statements_after_return_exit_and_next_are_unreachable() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  function inc(x : natural) return natural is
+  begin
+    return x + 1;
+    report \"after return\";
+  end function;
+begin
+  process
+  begin
+    for i in 0 to 7 loop
+      exit;
+      report \"after exit\";
+    end loop;
+    for i in 0 to 7 loop
+      next;
+      report \"after next\";
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("report \"after return\";"),
+                "Unreachable statement",
+                ErrorCode::UnreachableCode,
+            )
+            .related(
+                code.s1("return x + 1;"),
+                "Execution never continues after this statement",
+            ),
+            Diagnostic::new(
+                code.s1("report \"after exit\";"),
+                "Unreachable statement",
+                ErrorCode::UnreachableCode,
+            )
+            .related(
+                code.s1("exit;"),
+                "Execution never continues after this statement",
+            ),
+            Diagnostic::new(
+                code.s1("report \"after next\";"),
+                "Unreachable statement",
+                ErrorCode::UnreachableCode,
+            )
+            .related(
+                code.s1("next;"),
+                "Execution never continues after this statement",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn statements_after_branches_that_all_return_are_unreachable() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function sign(x : integer) return integer is
+  begin
+    if x < 0 then
+      return -1;
+    elsif x = 0 then
+      return 0;
+    else
+      return 1;
+    end if;
+    return 2;
+  end function;
+
+  function bits(x : bit) return natural is
+  begin
+    case x is
+      when '0' => return 0;
+      when '1' => return 1;
+    end case;
+    return 2;
+  end function;
+end package body;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("return 2;"),
+                "Unreachable statement",
+                ErrorCode::UnreachableCode,
+            )
+            .related(
+                code.between("if x < 0", "end if;"),
+                "Execution never continues after this statement",
+            ),
+            Diagnostic::new(
+                code.s("return 2;", 2),
+                "Unreachable statement",
+                ErrorCode::UnreachableCode,
+            )
+            .related(
+                code.between("case x", "end case;"),
+                "Execution never continues after this statement",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn conditional_control_flow_is_not_unreachable() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function clip(x : integer) return integer is
+  begin
+    if x < 0 then
+      return 0;
+    end if;
+    for i in 0 to 7 loop
+      exit when i = x;
+      next when i = 2;
+      report \"reachable\";
+    end loop;
+    return x;
+  end function;
+end package body;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
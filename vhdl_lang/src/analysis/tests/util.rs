@@ -17,6 +17,7 @@ use vhdl_lang::VHDLStandard;
 pub struct LibraryBuilder {
     code_builder: CodeBuilder,
     libraries: HashMap<Symbol, Vec<Code>>,
+    lenient: bool,
 }
 
 impl LibraryBuilder {
@@ -28,9 +29,15 @@ impl LibraryBuilder {
         LibraryBuilder {
             code_builder: CodeBuilder::with_standard(standard),
             libraries: HashMap::default(),
+            lenient: false,
         }
     }
 
+    /// Analyze in lenient mode, see [DesignRoot::set_lenient]
+    pub fn set_lenient(&mut self) {
+        self.lenient = true;
+    }
+
     fn add_code(&mut self, library_name: &str, code: Code) {
         let library_name = self.code_builder.symbol(library_name);
         match self.libraries.entry(library_name) {
@@ -76,7 +83,11 @@ end architecture;"
     }
 
     pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
-        let mut root = DesignRoot::new(self.code_builder.symbols.clone());
+        let mut root = DesignRoot::with_standard(
+            self.code_builder.symbols.clone(),
+            self.code_builder.standard,
+        );
+        root.set_lenient(self.lenient);
         let mut diagnostics = Vec::new();
 
         add_standard_library(self.symbols(), &mut root);
@@ -206,12 +206,12 @@ end entity;
             Diagnostic::new(
                 code.s("const1", 3),
                 "No declaration of 'const1' within package 'pkg'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
             Diagnostic::new(
                 code.s("const2", 3),
                 "No declaration of 'const2' within package 'pkg'",
-                ErrorCode::Unresolved,
+                ErrorCode::NoDeclarationWithin,
             ),
         ],
     );
@@ -790,3 +790,157 @@ fn generics_are_visible_in_procedures_but_not_outside() {
         )]
     )
 }
+
+#[test]
+fn homographs_made_visible_from_different_packages_are_ambiguous() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package types is
+  type my_t is range 0 to 10;
+end package;
+
+use work.types.my_t;
+package pkg1 is
+  function \"+\"(l, r : my_t) return my_t;
+  function clip(arg : my_t) return my_t;
+end package;
+
+use work.types.my_t;
+package pkg2 is
+  function \"+\"(l, r : my_t) return my_t;
+  function clip(arg : my_t) return my_t;
+end package;
+
+use work.types.my_t;
+use work.pkg1.all;
+use work.pkg2.all;
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant a : my_t := 1;
+  constant b : my_t := a + a;
+  constant c : my_t := clip(a);
+begin
+end architecture;
+",
+    );
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("+", 3),
+                "Ambiguous use of operator \"+\"[my_t, my_t return my_t], \
+                 it is made visible from both package 'pkg1' and package 'pkg2'",
+                ErrorCode::AmbiguousCall,
+            )
+            .related(code.s1("\"+\""), "Declared in package 'pkg1'")
+            .related(code.s("\"+\"", 2), "Declared in package 'pkg2'"),
+            Diagnostic::new(
+                code.s("clip", 3),
+                "Ambiguous use of function clip[my_t return my_t], \
+                 it is made visible from both package 'pkg1' and package 'pkg2'",
+                ErrorCode::AmbiguousCall,
+            )
+            .related(code.s1("clip"), "Declared in package 'pkg1'")
+            .related(code.s("clip", 2), "Declared in package 'pkg2'"),
+        ],
+    );
+}
+
+#[test]
+fn conflicting_names_made_visible_by_use_all_are_not_directly_visible() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg1 is
+  type state_t is (idle, busy);
+end package;
+
+package pkg2 is
+  type state_t is (idle, done);
+end package;
+
+use work.pkg1.all;
+use work.pkg2.all;
+
+package user is
+  signal state : state_t;
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![hidden_error(
+            &code,
+            "state_t",
+            3,
+            &[
+                (&code, "work.pkg1.all", 1, false),
+                (&code, "state_t", 1, true),
+                (&code, "work.pkg2.all", 1, false),
+                (&code, "state_t", 2, true),
+            ],
+        )],
+    );
+}
+
+#[test]
+fn homographs_made_visible_from_different_packages_cannot_be_selected_by_signature() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg1 is
+  procedure reset(arg : inout natural);
+end package;
+
+package pkg2 is
+  procedure reset(arg : inout natural);
+end package;
+
+use work.pkg1.all;
+use work.pkg2.all;
+
+package user is
+  alias clear is reset[natural];
+end package;
+
+use work.pkg1.all;
+use work.pkg2.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable v : natural;
+  begin
+    reset(v);
+    wait;
+  end process;
+end architecture;
+        ",
+    );
+
+    let homograph_error = |occ| {
+        Diagnostic::new(
+            code.s("reset", occ),
+            "Ambiguous use of procedure reset[NATURAL], \
+             it is made visible from both package 'pkg1' and package 'pkg2'",
+            ErrorCode::AmbiguousCall,
+        )
+        .related(code.s1("reset"), "Declared in package 'pkg1'")
+        .related(code.s("reset", 2), "Declared in package 'pkg2'")
+    };
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, vec![homograph_error(3), homograph_error(4)]);
+}
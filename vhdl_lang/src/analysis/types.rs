@@ -5,12 +5,16 @@
 // Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::ast;
+use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::named_entity::{Signature, *};
 use crate::HasTokenSpan;
 use analyze::*;
+use fnv::FnvHashSet;
+use names::ResolvedName;
 
 impl<'a, 't> AnalyzeContext<'a, 't> {
     pub fn resolve_subtype_indication(
@@ -19,26 +23,330 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         subtype_indication: &mut SubtypeIndication,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<Subtype<'a>> {
-        // @TODO more
+        let base_type =
+            self.resolve_type_mark(scope, &mut subtype_indication.type_mark, diagnostics)?;
+        self.constrain_subtype(scope, base_type, subtype_indication, diagnostics)
+    }
+
+    /// The designated subtype of an access type may be an incomplete type without constraint
+    fn resolve_designated_subtype(
+        &self,
+        scope: &Scope<'a>,
+        subtype_indication: &mut SubtypeIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Subtype<'a>> {
+        let base_type = self.resolve_type_mark_allow_incomplete(
+            scope,
+            &mut subtype_indication.type_mark,
+            diagnostics,
+        )?;
+
+        if matches!(base_type.kind(), Type::Incomplete(_)) {
+            if let Some(ref constraint) = subtype_indication.constraint {
+                diagnostics.add(
+                    constraint.pos(self.ctx),
+                    format!(
+                        "Incomplete type '{}' cannot be constrained",
+                        base_type.designator()
+                    ),
+                    ErrorCode::IncompleteTypeBeforeFullDeclaration,
+                );
+            }
+            return Ok(Subtype::new(base_type));
+        }
+
+        self.constrain_subtype(scope, base_type, subtype_indication, diagnostics)
+    }
+
+    fn constrain_subtype(
+        &self,
+        scope: &Scope<'a>,
+        base_type: TypeEnt<'a>,
+        subtype_indication: &mut SubtypeIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Subtype<'a>> {
         let SubtypeIndication {
+            resolution,
             type_mark,
             constraint,
-            ..
         } = subtype_indication;
 
-        let base_type = self.resolve_type_mark(scope, type_mark, diagnostics)?;
+        self.analyze_resolution_indication(scope, base_type, resolution, diagnostics)?;
+        let resolved = !matches!(resolution, ResolutionIndication::Unresolved);
+
+        let Some(constraint) = constraint else {
+            return Ok(Subtype::new(base_type).with_resolution(resolved));
+        };
+
+        self.analyze_subtype_constraint(
+            scope,
+            &type_mark.pos(self.ctx),
+            base_type.base(),
+            &mut constraint.item,
+            diagnostics,
+        )?;
+
+        let subtype = match constraint.item {
+            SubtypeConstraint::Range(ast::Range::Range(ref rc)) => {
+                let range = self.static_range(base_type, rc);
+                if let Some(ref range) = range {
+                    self.check_static_subrange(base_type, range, constraint.span, diagnostics);
+                }
+                Subtype::with_range(base_type, range)
+            }
+            SubtypeConstraint::Range(_) => Subtype::with_range(base_type, None),
+            SubtypeConstraint::Array(ref dranges, _) => {
+                match (base_type.base().kind(), &dranges[..]) {
+                    (Type::Array { indexes, .. }, [drange]) if indexes.len() == 1 => {
+                        let length = indexes[0].and_then(|index_type| {
+                            self.static_drange_length(index_type.into(), drange)
+                        });
+                        let index_range = indexes[0]
+                            .and_then(|index_type| self.static_drange(index_type.into(), drange));
+                        Subtype {
+                            index_range,
+                            ..Subtype::with_length(base_type, length)
+                        }
+                    }
+                    _ => Subtype::new(base_type),
+                }
+            }
+            _ => Subtype::new(base_type),
+        };
+        Ok(subtype.with_resolution(resolved))
+    }
+
+    /// LRM 6.3: The resolution of an array or record subtype may be given per element
+    fn analyze_resolution_indication(
+        &self,
+        scope: &Scope<'a>,
+        typ: TypeEnt<'a>,
+        resolution: &mut ResolutionIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        match resolution {
+            ResolutionIndication::Unresolved => {}
+            ResolutionIndication::FunctionName(name) => {
+                self.analyze_resolution_function(scope, typ, name, diagnostics)?;
+            }
+            ResolutionIndication::ArrayElement(name) => {
+                if let Type::Array { elem_type, .. } = typ.base().kind() {
+                    self.analyze_resolution_function(scope, *elem_type, name, diagnostics)?;
+                } else {
+                    diagnostics.add(
+                        name.pos(self.ctx),
+                        format!(
+                            "Element resolution requires an array type, got {}",
+                            typ.describe()
+                        ),
+                        ErrorCode::InvalidResolution,
+                    );
+                }
+            }
+            ResolutionIndication::Record(elem_resolutions) => {
+                let Type::Record(region) = typ.base().kind() else {
+                    if let Some(first) = elem_resolutions.first() {
+                        diagnostics.add(
+                            first.ident.pos(self.ctx),
+                            format!(
+                                "Record element resolution requires a record type, got {}",
+                                typ.describe()
+                            ),
+                            ErrorCode::InvalidResolution,
+                        );
+                    }
+                    return Ok(());
+                };
+
+                for elem_resolution in elem_resolutions.iter_mut() {
+                    let RecordElementResolution { ident, resolution } = elem_resolution;
+                    let designator = Designator::Identifier(ident.item.clone());
+                    if let Some(elem) = region.lookup(&designator) {
+                        self.analyze_resolution_indication(
+                            scope,
+                            elem.type_mark(),
+                            resolution,
+                            diagnostics,
+                        )?;
+                    } else {
+                        diagnostics.push(Diagnostic::no_declaration_within(
+                            &typ,
+                            ident.pos(self.ctx),
+                            &designator,
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// LRM 4.6: A resolution function of `typ` is a function with a single parameter that is
+    /// a one-dimensional array of `typ` and that returns `typ`
+    fn analyze_resolution_function(
+        &self,
+        scope: &Scope<'a>,
+        typ: TypeEnt<'a>,
+        name: &mut WithTokenSpan<Name>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let Some(resolved) =
+            as_fatal(self.name_resolve(scope, name.span, &mut name.item, diagnostics))?
+        else {
+            return Ok(());
+        };
+
+        if let ResolvedName::Overloaded(des, overloaded) = resolved {
+            let candidates: Vec<_> = overloaded
+                .entities()
+                .filter(|ent| is_resolution_function(ent, typ))
+                .collect();
+            match candidates.as_slice() {
+                [ent] => name.set_unique_reference(ent),
+                [] => {
+                    let mut diag = Diagnostic::new(
+                        name.pos(self.ctx),
+                        format!("'{des}' is not a resolution function of {}", typ.describe()),
+                        ErrorCode::InvalidResolution,
+                    );
+                    diag.add_subprogram_candidates("Does not match", overloaded.entities());
+                    diagnostics.push(diag);
+                }
+                candidates => diagnostics.push(Diagnostic::ambiguous_call(
+                    self.ctx,
+                    &des,
+                    candidates.iter().copied(),
+                )),
+            }
+        } else {
+            diagnostics.add(
+                name.pos(self.ctx),
+                format!(
+                    "{} cannot be used as a resolution function",
+                    resolved.describe()
+                ),
+                ErrorCode::MismatchedKinds,
+            );
+        }
+        Ok(())
+    }
 
-        if let Some(constraint) = constraint {
-            self.analyze_subtype_constraint(
-                scope,
-                &type_mark.pos(self.ctx),
-                base_type.base(),
-                &mut constraint.item,
-                diagnostics,
-            )?;
+    /// The full declaration of an incomplete type may not contain itself
+    /// other than through an access type
+    fn check_circular_type_dependency(
+        &self,
+        name: &Symbol,
+        id: EntityId,
+        elem_type: TypeEnt<'a>,
+        pos: SrcPos,
+    ) -> Option<Diagnostic> {
+        let mut path = type_dependency_path(elem_type, id, &mut FnvHashSet::default())?;
+        path.reverse();
+
+        // The type that depends directly on the incomplete type uses it before its
+        // full declaration, which is not reported separately
+        match path.last() {
+            Some(typ) => self.record_circular_type_use(typ.src_span.pos(self.ctx)),
+            None => self.record_circular_type_use(pos.clone()),
+        }
+
+        let mut cycle = vec![name.to_string()];
+        cycle.extend(path.iter().map(|typ| typ.designator().to_string()));
+        cycle.push(name.to_string());
+
+        let mut diagnostic = Diagnostic::new(
+            pos,
+            format!("Found circular type dependency {}", cycle.join(" -> ")),
+            ErrorCode::CircularTypeDependency,
+        );
+        for (idx, typ) in path.iter().enumerate() {
+            if let Some(decl_pos) = typ.decl_pos() {
+                diagnostic.add_related(
+                    decl_pos,
+                    format!("'{}' depends on '{}'", typ.designator(), cycle[idx + 2]),
+                );
+            }
         }
+        Some(diagnostic)
+    }
+
+    /// Signals and constants may not be of a file, access or protected type
+    /// or of a composite type with such a subelement, variables may not be of a file type
+    pub(crate) fn check_object_type(
+        &self,
+        class: ObjectClass,
+        subtype: &Subtype<'a>,
+        pos: SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let typ = subtype.type_mark();
+        let restricted = match class {
+            ObjectClass::Signal | ObjectClass::Constant => find_restricted_type(typ, &|kind| {
+                matches!(kind, Type::File | Type::Access(..) | Type::Protected(..))
+            }),
+            ObjectClass::Variable | ObjectClass::SharedVariable => {
+                find_restricted_type(typ, &|kind| matches!(kind, Type::File))
+            }
+        };
+
+        if let Some(restricted) = restricted {
+            diagnostics.add(
+                pos,
+                format!(
+                    "A {class} cannot be of {}",
+                    describe_restricted(typ, restricted)
+                ),
+                ErrorCode::IllegalObjectType,
+            );
+        }
+    }
+
+    /// The elements of arrays and records may not be of a file or protected type
+    fn check_element_type(
+        &self,
+        composite: &str,
+        elem_type: TypeEnt<'a>,
+        pos: SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let base = elem_type.base_type();
+        if matches!(base.kind(), Type::File | Type::Protected(..)) {
+            diagnostics.add(
+                pos,
+                format!(
+                    "The element type of {composite} cannot be {}",
+                    base.describe()
+                ),
+                ErrorCode::IllegalElementType,
+            );
+        }
+    }
+
+    /// The values of a file may not be of a file, access or protected type
+    /// or of a composite type with an access subelement
+    fn check_file_value_type(
+        &self,
+        type_mark: TypeEnt<'a>,
+        pos: SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let base = type_mark.base_type();
+        let restricted = if matches!(base.kind(), Type::File | Type::Protected(..)) {
+            Some(base)
+        } else {
+            find_restricted_type(type_mark, &|kind| matches!(kind, Type::Access(..)))
+        };
 
-        Ok(Subtype::new(base_type))
+        if let Some(restricted) = restricted {
+            diagnostics.add(
+                pos,
+                format!(
+                    "The values of a file cannot be of {}",
+                    describe_restricted(type_mark, restricted)
+                ),
+                ErrorCode::IllegalElementType,
+            );
+        }
     }
 
     pub(crate) fn analyze_type_declaration(
@@ -139,6 +447,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                             &mut body.decl,
                                             diagnostics,
                                         )?;
+                                        region.close(diagnostics);
 
                                         let kind = Type::Protected(region.into_region(), true);
                                         unsafe {
@@ -249,6 +558,24 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     let subtype =
                         self.resolve_subtype_indication(scope, &mut elem_decl.subtype, diagnostics);
                     if let Some(subtype) = as_fatal(subtype)? {
+                        if let Some(id) = overwrite_id {
+                            if let Some(err) = self.check_circular_type_dependency(
+                                &type_decl.ident.tree.item,
+                                id,
+                                subtype.type_mark(),
+                                elem_decl.subtype.type_mark.pos(self.ctx),
+                            ) {
+                                // Leave out the element to break the cycle
+                                diagnostics.push(err);
+                                continue;
+                            }
+                        }
+                        self.check_element_type(
+                            "a record",
+                            subtype.type_mark(),
+                            elem_decl.subtype.type_mark.pos(self.ctx),
+                            diagnostics,
+                        );
                         let elem = self.arena.define(
                             self.ctx,
                             &mut elem_decl.ident,
@@ -279,7 +606,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
             TypeDefinition::Access(ref mut subtype_indication) => {
                 let subtype =
-                    self.resolve_subtype_indication(scope, subtype_indication, diagnostics);
+                    self.resolve_designated_subtype(scope, subtype_indication, diagnostics);
                 if let Some(subtype) = as_fatal(subtype)? {
                     let type_ent = TypeEnt::define_with_opt_id(
                         self.ctx,
@@ -322,6 +649,25 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     None => return Ok(()),
                 };
 
+                if let Some(id) = overwrite_id {
+                    if let Some(err) = self.check_circular_type_dependency(
+                        &type_decl.ident.tree.item,
+                        id,
+                        elem_type,
+                        subtype_indication.type_mark.pos(self.ctx),
+                    ) {
+                        // The type remains incomplete
+                        diagnostics.push(err);
+                        return Ok(());
+                    }
+                }
+                self.check_element_type(
+                    "an array",
+                    elem_type,
+                    subtype_indication.type_mark.pos(self.ctx),
+                    diagnostics,
+                );
+
                 let is_1d = indexes.len() == 1;
                 let array_ent = TypeEnt::define_with_opt_id(
                     self.ctx,
@@ -394,6 +740,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     src_span,
                     Some(self.source()),
                 );
+                self.arena
+                    .set_static_value(primary.id(), StaticValue::Physical(1));
 
                 unsafe {
                     self.arena.add_implicit(phys_type.id(), primary);
@@ -426,6 +774,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         src_span,
                         Some(self.source()),
                     );
+                    if let Some(unit_value) = self.physical_literal_value(value) {
+                        self.arena.set_static_value(
+                            secondary_unit.id(),
+                            StaticValue::Physical(unit_value),
+                        );
+                    }
                     unsafe {
                         self.arena.add_implicit(phys_type.id(), secondary_unit);
                     }
@@ -479,6 +833,15 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     src_span,
                     self.source(),
                 );
+                if let (UniversalType::Integer, ast::Range::Range(ref constraint)) =
+                    (universal_type, &range)
+                {
+                    if let Some(range) =
+                        self.static_range(self.universal_integer().into(), constraint)
+                    {
+                        self.arena.set_static_range(type_ent.id(), range);
+                    }
+                }
                 scope.add(type_ent.into(), diagnostics);
 
                 for ent in self.numeric_implicits(universal_type, type_ent) {
@@ -502,9 +865,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     self.source(),
                 );
 
+                let pos = type_mark.pos(self.ctx);
                 if let Some(type_mark) =
                     as_fatal(self.resolve_type_mark(scope, type_mark, diagnostics))?
                 {
+                    self.check_file_value_type(type_mark, pos, diagnostics);
                     for ent in self.create_implicit_file_type_subprograms(file_type, type_mark) {
                         unsafe {
                             self.arena.add_implicit(file_type.id(), ent);
@@ -521,7 +886,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     }
 
     /// The matching operators such as ?= are defined for 1d arrays of bit and std_ulogic element type
-    fn has_matching_op(&self, typ: TypeEnt<'a>) -> bool {
+    pub fn has_matching_op(&self, typ: TypeEnt<'a>) -> bool {
         if self.is_std_logic_1164 {
             // Within the std_logic_1164 we do not have efficient access to the types
             typ.designator() == &Designator::Identifier(self.root.symbol_utf8("std_ulogic"))
@@ -668,3 +1033,79 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             .map(|_| ())
     }
 }
+
+fn is_resolution_function(ent: &OverloadedEnt<'_>, typ: TypeEnt<'_>) -> bool {
+    let Some(return_type) = ent.return_type() else {
+        return false;
+    };
+    let Some(formal) = ent.formals().unary() else {
+        return false;
+    };
+    match formal.base().kind() {
+        Type::Array {
+            indexes, elem_type, ..
+        } => {
+            indexes.len() == 1 && elem_type.base() == typ.base() && return_type.base() == typ.base()
+        }
+        _ => false,
+    }
+}
+
+/// The types through which `typ` contains the type with the `target` id as an element,
+/// innermost first. Access types are not followed since they break the dependency.
+fn type_dependency_path<'a>(
+    typ: TypeEnt<'a>,
+    target: EntityId,
+    visited: &mut FnvHashSet<EntityId>,
+) -> Option<Vec<TypeEnt<'a>>> {
+    if typ.id() == target {
+        return Some(Vec::new());
+    }
+    if !visited.insert(typ.id()) {
+        return None;
+    }
+
+    let mut path = match typ.kind() {
+        Type::Subtype(subtype) => type_dependency_path(subtype.type_mark(), target, visited),
+        Type::Alias(aliased) => type_dependency_path(*aliased, target, visited),
+        Type::Array { elem_type, .. } => type_dependency_path(*elem_type, target, visited),
+        Type::Record(region) => region
+            .iter()
+            .find_map(|elem| type_dependency_path(elem.type_mark(), target, visited)),
+        _ => None,
+    }?;
+    path.push(typ);
+    Some(path)
+}
+
+/// The first type among `typ` and its subelements with a kind matching `is_restricted`.
+/// The designated types of access types are not searched.
+fn find_restricted_type<'a>(
+    typ: TypeEnt<'a>,
+    is_restricted: &dyn Fn(&Type<'a>) -> bool,
+) -> Option<TypeEnt<'a>> {
+    let base = typ.base_type();
+    if is_restricted(base.kind()) {
+        return Some(base);
+    }
+
+    match base.kind() {
+        Type::Array { elem_type, .. } => find_restricted_type(*elem_type, is_restricted),
+        Type::Record(region) => region
+            .iter()
+            .find_map(|elem| find_restricted_type(elem.type_mark(), is_restricted)),
+        _ => None,
+    }
+}
+
+fn describe_restricted(typ: TypeEnt<'_>, restricted: TypeEnt<'_>) -> String {
+    if typ.base_type() == restricted {
+        restricted.describe()
+    } else {
+        format!(
+            "{} with a subelement of {}",
+            typ.describe(),
+            restricted.describe()
+        )
+    }
+}
@@ -299,6 +299,14 @@ pub enum Expression {
 
     /// LRM 9.3.7 Allocators
     New(Box<WithTokenSpan<Allocator>>),
+
+    /// Conditional expression within parenthesis
+    /// (value when condition else value)
+    Conditional(Box<Conditionals<WithTokenSpan<Expression>>>),
+
+    /// Selected expression within parenthesis
+    /// (with expression select value when choices, value when others)
+    Selected(Box<Selection<WithTokenSpan<Expression>>>),
 }
 
 /// An identifier together with the lexical source location it occurs in.
@@ -852,6 +860,10 @@ pub enum Declaration {
     SubprogramBody(SubprogramBody),
     Use(UseClause),
     Package(PackageInstantiation),
+    /// A package declared within a declarative part (LRM 4.7)
+    PackageDeclaration(PackageDeclaration),
+    /// The body of a package declared within a declarative part (LRM 4.8)
+    PackageBody(PackageBody),
     Configuration(ConfigurationSpecification),
     View(ModeViewDeclaration),
 }
@@ -980,6 +992,8 @@ pub struct Alternative<T> {
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Selection<T> {
+    /// true for a matching selection, i.e. `select?`
+    pub is_matching: bool,
     pub expression: WithTokenSpan<Expression>,
     pub alternatives: Vec<Alternative<T>>,
 }
@@ -451,6 +451,42 @@ impl Display for Expression {
             Expression::Name(ref name) => write!(f, "{name}"),
             Expression::Literal(ref literal) => write!(f, "{literal}"),
             Expression::New(ref alloc) => write!(f, "new {alloc}"),
+            Expression::Conditional(ref conditionals) => {
+                write!(f, "(")?;
+                for Conditional { condition, item } in conditionals.conditionals.iter() {
+                    write!(f, "{item} when {condition} else ")?;
+                }
+                if let Some(ref item) = conditionals.else_item {
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+            Expression::Selected(ref selection) => {
+                write!(f, "(with {} select", selection.expression)?;
+                if selection.is_matching {
+                    write!(f, "?")?;
+                }
+                write!(f, " ")?;
+                let mut first = true;
+                for Alternative { choices, item } in selection.alternatives.iter() {
+                    if first {
+                        write!(f, "{item} when ")?;
+                    } else {
+                        write!(f, ", {item} when ")?;
+                    }
+                    first = false;
+                    let mut first_choice = true;
+                    for choice in choices {
+                        if first_choice {
+                            write!(f, "{choice}")?;
+                        } else {
+                            write!(f, " | {choice}")?;
+                        }
+                        first_choice = false;
+                    }
+                }
+                write!(f, ")")
+            }
         }
     }
 }
@@ -1209,6 +1245,7 @@ impl Display for PackageDeclaration {
 mod tests {
     use super::*;
     use crate::syntax::test::Code;
+    use crate::VHDLStandard;
     use assert_matches::assert_matches;
 
     pub fn assert_format_eq<F, R: Display>(code: &str, res: &str, code_fun: F)
@@ -1225,6 +1262,19 @@ mod tests {
         assert_format_eq(code, code, code_fun);
     }
 
+    pub fn assert_format_vhdl2019<F, R: Display>(code: &str, code_fun: F)
+    where
+        F: FnOnce(&Code) -> R,
+    {
+        assert_eq!(
+            format!(
+                "{}",
+                code_fun(&Code::with_standard(code, VHDLStandard::VHDL2019))
+            ),
+            code
+        );
+    }
+
     #[test]
     fn test_selected_name_single() {
         assert_format("foo", Code::name);
@@ -1433,6 +1483,17 @@ mod tests {
         assert_format("(1 | 2 => 3)", Code::expr);
     }
 
+    #[test]
+    fn test_expression_conditional() {
+        assert_format_vhdl2019("(a when b else c when d else e)", Code::expr);
+    }
+
+    #[test]
+    fn test_expression_selected() {
+        assert_format_vhdl2019("(with a select b when 1 | 2, c when others)", Code::expr);
+        assert_format_vhdl2019("(with a select? b when \"1-\", c when others)", Code::expr);
+    }
+
     #[test]
     fn test_expression_aggregate_many_named_others() {
         assert_format("(1 | 2 => 3, others => 4)", Code::expr);
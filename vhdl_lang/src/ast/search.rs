@@ -9,6 +9,7 @@ use super::*;
 use crate::analysis::DesignRoot;
 use crate::named_entity::{EntRef, HasEntityId, Reference, Related};
 use crate::syntax::{HasTokenSpan, TokenAccess};
+use fnv::FnvHashMap;
 
 #[must_use]
 #[derive(PartialEq, Debug)]
@@ -199,6 +200,7 @@ fn search_selection<T: Search>(
     ctx: &dyn TokenAccess,
 ) -> SearchResult {
     let Selection {
+        is_matching: _,
         expression,
         alternatives,
     } = selection;
@@ -229,6 +231,7 @@ fn search_assignment<T: Search>(
         }
         AssignmentRightHand::Selected(selection) => {
             let Selection {
+                is_matching: _,
                 expression,
                 alternatives,
             } = selection;
@@ -368,8 +371,12 @@ impl Search for LabeledSequentialStatement {
                 }
             }
             SequentialStatement::SignalAssignment(ref assign) => {
-                // @TODO more
-                let SignalAssignment { target, rhs, .. } = assign;
+                let SignalAssignment {
+                    target,
+                    delay_mechanism,
+                    rhs,
+                } = assign;
+                return_if_found!(delay_mechanism.search(ctx, searcher));
                 return_if_found!(search_assignment(target, rhs, searcher, ctx));
             }
             SequentialStatement::VariableAssignment(ref assign) => {
@@ -522,7 +529,13 @@ impl Search for LabeledConcurrentStatement {
                 return_if_found!(inst.search(ctx, searcher));
             }
             ConcurrentStatement::Assignment(ref assign) => {
-                let ConcurrentSignalAssignment { target, rhs, .. } = assign;
+                let ConcurrentSignalAssignment {
+                    target,
+                    delay_mechanism,
+                    rhs,
+                    ..
+                } = assign;
+                return_if_found!(delay_mechanism.search(ctx, searcher));
                 return_if_found!(search_assignment(target, rhs, searcher, ctx));
             }
             ConcurrentStatement::ProcedureCall(ref pcall) => {
@@ -671,18 +684,35 @@ impl Search for WithTokenSpan<SubtypeConstraint> {
 
 impl Search for SubtypeIndication {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
-        // @TODO more
         let SubtypeIndication {
+            resolution,
             type_mark,
             constraint,
-            ..
         } = self;
+        return_if_found!(resolution.search(ctx, searcher));
         return_if_found!(type_mark.search(ctx, searcher));
         return_if_found!(constraint.search(ctx, searcher));
         NotFound
     }
 }
 
+impl Search for ResolutionIndication {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        match self {
+            ResolutionIndication::FunctionName(name) | ResolutionIndication::ArrayElement(name) => {
+                name.search(ctx, searcher)
+            }
+            ResolutionIndication::Record(elem_resolutions) => {
+                for elem_resolution in elem_resolutions {
+                    return_if_found!(elem_resolution.resolution.search(ctx, searcher));
+                }
+                NotFound
+            }
+            ResolutionIndication::Unresolved => NotFound,
+        }
+    }
+}
+
 impl Search for WithTokenSpan<TypeMark> {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
         return_if_finished!(searcher.search_with_pos(ctx, &self.pos(ctx)));
@@ -864,6 +894,10 @@ fn search_pos_expr(
             }
             _ => NotFound,
         },
+        Expression::Conditional(ref conditionals) => {
+            search_conditionals(conditionals, true, searcher, ctx)
+        }
+        Expression::Selected(ref selection) => search_selection(selection, true, searcher, ctx),
     }
 }
 
@@ -922,6 +956,15 @@ impl Search for CallOrIndexed {
     }
 }
 
+impl Search for DelayMechanism {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        match self {
+            DelayMechanism::Transport => NotFound,
+            DelayMechanism::Inertial { reject } => reject.search(ctx, searcher),
+        }
+    }
+}
+
 impl Search for Waveform {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
         match self {
@@ -1079,6 +1122,12 @@ impl Search for Declaration {
             Declaration::Package(ref package_instance) => {
                 return_if_found!(package_instance.search(ctx, searcher));
             }
+            Declaration::PackageDeclaration(ref package) => {
+                return_if_found!(package.search(ctx, searcher));
+            }
+            Declaration::PackageBody(ref body) => {
+                return_if_found!(body.search(ctx, searcher));
+            }
 
             Declaration::Configuration(_) => {
                 // @TODO
@@ -1343,7 +1392,42 @@ impl Search for ConfigurationDeclaration {
         return_if_found!(searcher
             .search_decl(ctx, FoundDeclaration::Configuration(self))
             .or_not_found());
-        self.entity_name.search(ctx, searcher)
+        return_if_found!(self.entity_name.search(ctx, searcher));
+        for ConfigurationDeclarativeItem::Use(ref use_clause) in self.decl.iter() {
+            return_if_found!(use_clause.name_list.search(ctx, searcher));
+        }
+        self.block_config.search(ctx, searcher)
+    }
+}
+
+impl Search for BlockConfiguration {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_found!(self.block_spec.search(ctx, searcher));
+        for use_clause in self.use_clauses.iter() {
+            return_if_found!(use_clause.name_list.search(ctx, searcher));
+        }
+        for item in self.items.iter() {
+            match item {
+                ConfigurationItem::Block(ref block_config) => {
+                    return_if_found!(block_config.search(ctx, searcher));
+                }
+                ConfigurationItem::Component(ref component) => {
+                    let entity_aspect = component
+                        .bind_ind
+                        .as_ref()
+                        .and_then(|bind_ind| bind_ind.entity_aspect.as_ref());
+                    match entity_aspect {
+                        Some(EntityAspect::Entity(ref name, _))
+                        | Some(EntityAspect::Configuration(ref name)) => {
+                            return_if_found!(name.search(ctx, searcher));
+                        }
+                        Some(EntityAspect::Open) | None => {}
+                    }
+                    return_if_found!(component.block_config.search(ctx, searcher));
+                }
+            }
+        }
+        NotFound
     }
 }
 
@@ -1664,6 +1748,431 @@ impl<'a> Searcher for FindAllReferences<'a> {
     }
 }
 
+// Search for all concurrent statements that drive a signal
+//
+// A process drives every signal that is the target of a signal assignment
+// within its statement part. A concurrent signal assignment drives its target.
+#[derive(Default)]
+pub struct FindAllDrivers {
+    /// Maps a signal to each statement that drives it
+    pub drivers: FnvHashMap<EntityId, Vec<Driver>>,
+}
+
+/// A concurrent statement that drives a signal
+#[derive(Clone, Debug, PartialEq)]
+pub struct Driver {
+    /// The label of the statement when it has one and the first token of the
+    /// statement otherwise
+    pub pos: SrcPos,
+    /// The parts of the signal that are assigned by the statement,
+    /// no selection means the whole signal is assigned
+    pub(crate) parts: Vec<Vec<TargetPart>>,
+}
+
+impl FindAllDrivers {
+    fn add_driver(&mut self, target: &WithTokenSpan<Target>, pos: &SrcPos) {
+        let mut parts = Vec::new();
+        target_parts(&target.item, &mut parts);
+        for (id, part) in parts {
+            let drivers = self.drivers.entry(id).or_default();
+            // A statement only counts once even if it assigns the signal several times
+            match drivers.last_mut() {
+                Some(driver) if &driver.pos == pos => driver.parts.push(part),
+                _ => drivers.push(Driver {
+                    pos: pos.clone(),
+                    parts: vec![part],
+                }),
+            }
+        }
+    }
+
+    fn add_sequential_drivers(&mut self, statements: &[LabeledSequentialStatement], pos: &SrcPos) {
+        let mut targets = Vec::new();
+        sequential_signal_targets(statements, &mut targets);
+        for target in targets {
+            self.add_driver(target, pos);
+        }
+    }
+}
+
+/// The signals and aliases of signals that are assigned within a sequential statement part
+pub(crate) fn sequential_driven_signals(
+    statements: &[LabeledSequentialStatement],
+) -> Vec<EntityId> {
+    let mut targets = Vec::new();
+    sequential_signal_targets(statements, &mut targets);
+    let mut ids = Vec::new();
+    for target in targets {
+        target_references(&target.item, &mut ids);
+    }
+    ids
+}
+
+/// Collect the targets of all signal assignments within a sequential statement part
+pub(crate) fn sequential_signal_targets<'s>(
+    statements: &'s [LabeledSequentialStatement],
+    targets: &mut Vec<&'s WithTokenSpan<Target>>,
+) {
+    for statement in statements {
+        match statement.statement.item {
+            SequentialStatement::SignalAssignment(ref assign) => {
+                targets.push(&assign.target);
+            }
+            SequentialStatement::SignalForceAssignment(ref assign) => {
+                targets.push(&assign.target);
+            }
+            SequentialStatement::SignalReleaseAssignment(ref assign) => {
+                targets.push(&assign.target);
+            }
+            SequentialStatement::If(ref ifstmt) => {
+                for conditional in ifstmt.conds.conditionals.iter() {
+                    sequential_signal_targets(&conditional.item, targets);
+                }
+                if let Some(ref else_item) = ifstmt.conds.else_item {
+                    sequential_signal_targets(else_item, targets);
+                }
+            }
+            SequentialStatement::Case(ref case_stmt) => {
+                for alternative in case_stmt.alternatives.iter() {
+                    sequential_signal_targets(&alternative.item, targets);
+                }
+            }
+            SequentialStatement::Loop(ref loop_stmt) => {
+                sequential_signal_targets(&loop_stmt.statements, targets);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether any of the statements, or any statement nested within them, satisfies the predicate
+pub(crate) fn any_sequential_statement(
+    statements: &[LabeledSequentialStatement],
+    predicate: &impl Fn(&SequentialStatement) -> bool,
+) -> bool {
+    statements.iter().any(|statement| {
+        predicate(&statement.statement.item)
+            || match statement.statement.item {
+                SequentialStatement::If(ref ifstmt) => {
+                    ifstmt
+                        .conds
+                        .conditionals
+                        .iter()
+                        .any(|cond| any_sequential_statement(&cond.item, predicate))
+                        || ifstmt
+                            .conds
+                            .else_item
+                            .as_ref()
+                            .is_some_and(|else_item| any_sequential_statement(else_item, predicate))
+                }
+                SequentialStatement::Case(ref case_stmt) => case_stmt
+                    .alternatives
+                    .iter()
+                    .any(|alternative| any_sequential_statement(&alternative.item, predicate)),
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    any_sequential_statement(&loop_stmt.statements, predicate)
+                }
+                _ => false,
+            }
+    })
+}
+
+/// The position of a concurrent statement is its label when it has one
+/// and the first token of the statement otherwise.
+pub(crate) fn concurrent_statement_pos(
+    ctx: &dyn TokenAccess,
+    statement: &LabeledConcurrentStatement,
+) -> SrcPos {
+    if let Some(ref label) = statement.label.tree {
+        label.pos(ctx).clone()
+    } else {
+        ctx.get_pos(statement.statement.span.start_token).clone()
+    }
+}
+
+/// Collect the references of the object(s) denoted by an assignment target.
+/// Index and slice expressions are not part of the target and are skipped.
+fn target_references(target: &Target, ids: &mut Vec<EntityId>) {
+    let mut parts = Vec::new();
+    target_parts(target, &mut parts);
+    ids.extend(parts.into_iter().map(|(id, _)| id));
+}
+
+/// A statically known selection of an element, index or slice of an assignment target
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TargetPart {
+    Element(EntityId),
+    Index(u64),
+    Slice(u64, u64),
+    /// The selection is not known statically
+    Unknown,
+}
+
+impl TargetPart {
+    /// Whether the parts are known to select at least one common element
+    fn overlaps(&self, other: &TargetPart) -> bool {
+        let (low, high, other_low, other_high) = match (self, other) {
+            (TargetPart::Element(id), TargetPart::Element(other_id)) => return id == other_id,
+            (TargetPart::Index(index), TargetPart::Index(other_index)) => {
+                (index, index, other_index, other_index)
+            }
+            (TargetPart::Index(index), TargetPart::Slice(low, high)) => (index, index, low, high),
+            (TargetPart::Slice(low, high), TargetPart::Index(index)) => (low, high, index, index),
+            (TargetPart::Slice(low, high), TargetPart::Slice(other_low, other_high)) => {
+                (low, high, other_low, other_high)
+            }
+            _ => return false,
+        };
+        low <= other_high && other_low <= high
+    }
+}
+
+/// Whether two targets of the same object are known to assign a common element.
+/// The parts are the selections applied to the object, in order from the object outwards.
+pub(crate) fn target_parts_overlap(parts: &[TargetPart], other: &[TargetPart]) -> bool {
+    parts
+        .iter()
+        .zip(other.iter())
+        .all(|(part, other)| part.overlaps(other))
+}
+
+/// Collect the references of the object(s) denoted by an assignment target together with
+/// the parts of each object that are selected by the target.
+pub(crate) fn target_parts(target: &Target, parts: &mut Vec<(EntityId, Vec<TargetPart>)>) {
+    match target {
+        Target::Name(name) => name_target_parts(name, &mut Vec::new(), parts),
+        Target::Aggregate(assocs) => {
+            for assoc in assocs {
+                let expr = match assoc {
+                    ElementAssociation::Positional(expr) => expr,
+                    ElementAssociation::Named(_, expr) => expr,
+                };
+                if let Expression::Name(ref name) = expr.item {
+                    name_target_parts(name, &mut Vec::new(), parts);
+                }
+            }
+        }
+    }
+}
+
+// The selections of the enclosing names are in reverse order
+fn name_target_parts(
+    name: &Name,
+    selected: &mut Vec<TargetPart>,
+    parts: &mut Vec<(EntityId, Vec<TargetPart>)>,
+) {
+    let mut add = |id: EntityId, selected: &[TargetPart]| {
+        parts.push((id, selected.iter().rev().cloned().collect()));
+    };
+
+    let (prefix, part) = match name {
+        Name::Designator(designator) => {
+            if let Some(id) = designator.reference.get() {
+                add(id, selected);
+            }
+            return;
+        }
+        Name::Selected(prefix, suffix) => {
+            let id = suffix.item.reference.get();
+            if let Some(id) = id {
+                add(id, selected);
+            }
+            (
+                prefix.as_ref(),
+                id.map_or(TargetPart::Unknown, TargetPart::Element),
+            )
+        }
+        Name::Slice(prefix, drange) => {
+            let part = match drange.as_ref() {
+                DiscreteRange::Range(Range::Range(constraint)) => {
+                    match (
+                        integer_literal(&constraint.left_expr.item),
+                        integer_literal(&constraint.right_expr.item),
+                    ) {
+                        (Some(left), Some(right)) => {
+                            TargetPart::Slice(left.min(right), left.max(right))
+                        }
+                        _ => TargetPart::Unknown,
+                    }
+                }
+                _ => TargetPart::Unknown,
+            };
+            (prefix.as_ref(), part)
+        }
+        Name::CallOrIndexed(fcall) => {
+            let part = match fcall.parameters.as_slice() {
+                [AssociationElement {
+                    formal: None,
+                    actual,
+                }] => match actual.item {
+                    ActualPart::Expression(ref expr) => {
+                        integer_literal(expr).map_or(TargetPart::Unknown, TargetPart::Index)
+                    }
+                    ActualPart::Open => TargetPart::Unknown,
+                },
+                _ => TargetPart::Unknown,
+            };
+            (&fcall.name, part)
+        }
+        Name::SelectedAll(prefix) => (prefix.as_ref(), TargetPart::Unknown),
+        Name::Attribute(..) | Name::External(..) => return,
+    };
+
+    selected.push(part);
+    name_target_parts(&prefix.item, selected, parts);
+    selected.pop();
+}
+
+fn integer_literal(expr: &Expression) -> Option<u64> {
+    if let Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) = expr {
+        Some(*value)
+    } else {
+        None
+    }
+}
+
+impl Searcher for FindAllDrivers {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::ConcurrentStatement(statement) = decl {
+            let pos = concurrent_statement_pos(ctx, statement);
+
+            match statement.statement.item {
+                ConcurrentStatement::Process(ref process) => {
+                    self.add_sequential_drivers(&process.statements, &pos);
+                }
+                ConcurrentStatement::Assignment(ref assign) => {
+                    self.add_driver(&assign.target, &pos);
+                }
+                _ => {}
+            }
+        }
+        NotFinished
+    }
+}
+
+// Search for the implied sensitivity of every process with the sensitivity list `all`
+//
+// LRM 11.3: The sensitivity list of such a process contains every signal that is read
+// within its statement part.
+#[derive(Default)]
+pub struct FindImpliedSensitivity {
+    /// The position of each process, as for drivers, together with the
+    /// references that are read within it in order of first occurrence.
+    pub processes: Vec<(SrcPos, Vec<EntityId>)>,
+}
+
+impl Searcher for FindImpliedSensitivity {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::ConcurrentStatement(statement) = decl {
+            if let ConcurrentStatement::Process(ref process) = statement.statement.item {
+                if matches!(process.sensitivity_list, Some(SensitivityList::All)) {
+                    self.processes.push((
+                        concurrent_statement_pos(ctx, statement),
+                        read_references(ctx, &process.statements),
+                    ));
+                }
+            }
+        }
+        NotFinished
+    }
+}
+
+/// The references that are read within the statements in order of first occurrence.
+/// A name that is only the target of a signal assignment is not read,
+/// but the index expressions of a target are.
+pub(crate) fn read_references(
+    ctx: &dyn TokenAccess,
+    statements: &[LabeledSequentialStatement],
+) -> Vec<EntityId> {
+    struct CollectReferences(Vec<EntityId>);
+
+    impl Searcher for CollectReferences {
+        fn search_pos_with_ref(
+            &mut self,
+            _ctx: &dyn TokenAccess,
+            _pos: &SrcPos,
+            reference: &Reference,
+        ) -> SearchState {
+            self.0.extend(reference.get());
+            NotFinished
+        }
+    }
+
+    let mut references = CollectReferences(Vec::new());
+    for statement in statements {
+        let _ = statement.search(ctx, &mut references);
+    }
+    let mut read = references.0;
+
+    let mut targets = Vec::new();
+    sequential_signal_targets(statements, &mut targets);
+    let mut written = Vec::new();
+    for target in targets {
+        target_references(&target.item, &mut written);
+    }
+    for id in written {
+        if let Some(idx) = read.iter().position(|read_id| *read_id == id) {
+            read.remove(idx);
+        }
+    }
+
+    let mut ids: Vec<EntityId> = Vec::with_capacity(read.len());
+    for id in read {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// The references of the object(s) denoted by the names of a sensitivity list
+pub(crate) fn sensitivity_list_references(names: &[WithTokenSpan<Name>]) -> Vec<EntityId> {
+    let mut parts = Vec::new();
+    for name in names {
+        name_target_parts(&name.item, &mut Vec::new(), &mut parts);
+    }
+    parts.into_iter().map(|(id, _)| id).collect()
+}
+
+// Search for every name that declares or references a named entity
+pub struct FindNamedEntities<'a> {
+    root: &'a DesignRoot,
+    /// The position of each name, the named entity and whether it is the declaration
+    pub names: Vec<(SrcPos, EntityId, bool)>,
+}
+
+impl<'a> FindNamedEntities<'a> {
+    pub fn new(root: &'a DesignRoot) -> FindNamedEntities<'a> {
+        FindNamedEntities {
+            root,
+            names: Vec::new(),
+        }
+    }
+}
+
+impl Searcher for FindNamedEntities<'_> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            if let Some(pos) = self.root.get_ent(id).decl_pos() {
+                self.names.push((pos.clone(), id, true));
+            }
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.names.push((pos.clone(), id, false));
+        }
+        NotFinished
+    }
+}
+
 impl<'a> FoundDeclaration<'a> {
     fn end_ident_pos(&self) -> Option<TokenId> {
         match self {
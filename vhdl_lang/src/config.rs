@@ -12,12 +12,13 @@ use std::io;
 use std::io::prelude::*;
 use std::path::Path;
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use subst::VariableMap;
 use toml::{Table, Value};
 
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
+use crate::formatting::{FormattingOptions, KeywordCase};
 use crate::standard::VHDLStandard;
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
@@ -27,6 +28,10 @@ pub struct Config {
     standard: VHDLStandard,
     // Defines the severity that diagnostics are displayed with
     severities: SeverityMap,
+    // The error codes that are explicitly given a severity other than ignored
+    configured_codes: FnvHashSet<ErrorCode>,
+    // The layout of code that is formatted or generated
+    formatting: FormattingOptions,
 }
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
@@ -178,26 +183,58 @@ impl Config {
             );
         }
 
-        let severities = if let Some(lint) = config.get("lint") {
+        let (severities, configured_codes) = if let Some(lint) = config.get("lint") {
             Self::read_severity_overwrites(lint.as_table().ok_or("lint must be a table")?)?
         } else {
-            SeverityMap::default()
+            (SeverityMap::default(), FnvHashSet::default())
+        };
+
+        let formatting = if let Some(format) = config.get("format") {
+            Self::read_formatting_options(format.as_table().ok_or("format must be a table")?)?
+        } else {
+            FormattingOptions::default()
         };
 
         Ok(Config {
             libraries,
             severities,
+            configured_codes,
             standard,
+            formatting,
         })
     }
 
-    fn read_severity_overwrites(severity_overwrites: &Table) -> Result<SeverityMap, String> {
+    fn read_formatting_options(format: &Table) -> Result<FormattingOptions, String> {
+        let mut options = FormattingOptions::default();
+
+        if let Some(indent_width) = format.get("indent_width") {
+            options.indent_width = indent_width
+                .as_integer()
+                .and_then(|width| usize::try_from(width).ok())
+                .ok_or("indent_width must be a non-negative integer")?;
+        }
+
+        if let Some(keyword_case) = format.get("keyword_case") {
+            options.keyword_case = match keyword_case.as_str() {
+                Some("lower") => KeywordCase::Lower,
+                Some("upper") => KeywordCase::Upper,
+                _ => return Err("keyword_case must be 'lower' or 'upper'".to_string()),
+            };
+        }
+        Ok(options)
+    }
+
+    fn read_severity_overwrites(
+        severity_overwrites: &Table,
+    ) -> Result<(SeverityMap, FnvHashSet<ErrorCode>), String> {
         let mut severities = SeverityMap::default();
+        let mut configured_codes = FnvHashSet::default();
 
         for (name, severity) in severity_overwrites {
             let error_code = ErrorCode::try_from(name.as_str())
                 .map_err(|_| format!("'{name}' is not a valid error code"))?;
             match severity {
+                Value::String(severity) if severity == "ignored" => severities[error_code] = None,
                 Value::String(severity) => {
                     let severity = Severity::try_from(severity.as_str())
                         .map_err(|_| format!("'{severity}' is not a valid severity level"))?;
@@ -210,8 +247,11 @@ impl Config {
                 }
                 _ => return Err("severity must be a string or boolean".to_string()),
             }
+            if severities[error_code].is_some() {
+                configured_codes.insert(error_code);
+            }
         }
-        Ok(severities)
+        Ok((severities, configured_codes))
     }
 
     pub fn read_file_path(file_name: &Path) -> io::Result<Config> {
@@ -250,6 +290,8 @@ impl Config {
             }
         }
         self.severities = config.severities;
+        self.configured_codes = config.configured_codes.clone();
+        self.formatting = config.formatting.clone();
     }
 
     /// Load configuration file from installation folder
@@ -352,6 +394,17 @@ impl Config {
         &self.severities
     }
 
+    /// Whether the error code is given a severity in the `lint` table.
+    /// Opt-in lints are enabled by giving their error code a severity
+    pub fn is_configured(&self, code: ErrorCode) -> bool {
+        self.configured_codes.contains(&code)
+    }
+
+    /// The options of the `format` table
+    pub fn formatting(&self) -> &FormattingOptions {
+        &self.formatting
+    }
+
     /// The VHDL standard to use if no more specific config is present.
     /// By default, VHDL 2008 is assumed
     pub fn standard(&self) -> VHDLStandard {
@@ -483,6 +536,7 @@ lib1.files = [
 [lint]
 unused = 'error'
 duplicate = false
+out_of_range = 'ignored'
 ",
                 absolute_vhd.to_str().unwrap()
             ),
@@ -508,7 +562,11 @@ duplicate = false
         let mut expected_map = SeverityMap::default();
         expected_map[ErrorCode::Unused] = Some(Severity::Error);
         expected_map[ErrorCode::Duplicate] = None;
-        assert_eq!(config.severities, expected_map)
+        expected_map[ErrorCode::OutOfRange] = None;
+        assert_eq!(config.severities, expected_map);
+        assert!(config.is_configured(ErrorCode::Unused));
+        assert!(!config.is_configured(ErrorCode::Duplicate));
+        assert!(!config.is_configured(ErrorCode::OutOfRange));
     }
 
     #[test]
@@ -687,6 +745,43 @@ work.files = [
         assert_eq!(config.expect_err("Expected erroneous config"), "The 'work' library is not a valid library.\nHint: To use a library that contains all files, use a common name for all libraries, i.e., 'defaultlib'")
     }
 
+    #[test]
+    fn formatting_options_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+
+[format]
+indent_width = 4
+keyword_case = 'upper'
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.formatting(),
+            &FormattingOptions {
+                indent_width: 4,
+                keyword_case: KeywordCase::Upper,
+            }
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+
+[format]
+keyword_case = 'title'
+",
+            parent,
+        );
+        assert_eq!(
+            config.expect_err("Expected erroneous config"),
+            "keyword_case must be 'lower' or 'upper'"
+        );
+    }
+
     #[test]
     #[cfg(unix)]
     fn substitute() {
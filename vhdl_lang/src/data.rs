@@ -7,6 +7,8 @@
 
 mod contents;
 mod diagnostic;
+mod diagnostic_format;
+mod diagnostic_report;
 pub mod error_codes;
 mod latin_1;
 mod message;
@@ -15,6 +17,8 @@ mod symbol_table;
 
 pub use contents::*;
 pub use diagnostic::*;
+pub use diagnostic_format::*;
+pub use diagnostic_report::*;
 pub use error_codes::*;
 pub use latin_1::*;
 pub use message::*;
@@ -78,6 +78,49 @@ impl Contents {
         self.lines.get(lineno).map(|string| string.as_str())
     }
 
+    /// Convert a byte offset into the UTF-8 encoded contents to a position.
+    /// Line endings are counted as a single '\n' byte.
+    /// An offset within a multi-byte character maps to the start of that character
+    /// and an offset beyond the end maps to the end of the contents.
+    pub fn byte_offset_to_position(&self, offset: usize) -> Position {
+        let mut remaining = offset;
+        for (lineno, line) in self.lines.iter().enumerate() {
+            if remaining < line.len() {
+                let mut character = 0;
+                for (idx, chr) in line.char_indices() {
+                    if idx + chr.len_utf8() > remaining {
+                        break;
+                    }
+                    character += chr.len_utf16();
+                }
+                return Position::new(lineno as u32, character as u32);
+            }
+            remaining -= line.len();
+        }
+        self.end()
+    }
+
+    /// Convert a position to a byte offset into the UTF-8 encoded contents.
+    /// This is the inverse of [Contents::byte_offset_to_position].
+    /// A character within a UTF-16 surrogate pair maps to the start of the pair
+    /// and a character beyond the end of the line maps to the end of the line.
+    pub fn position_to_byte_offset(&self, pos: Position) -> usize {
+        let lineno = pos.line as usize;
+        let mut offset: usize = self.lines.iter().take(lineno).map(|line| line.len()).sum();
+
+        if let Some(line) = self.lines.get(lineno) {
+            let mut character = 0;
+            for chr in line.chars() {
+                if chr == '\n' || character + chr.len_utf16() > pos.character as usize {
+                    break;
+                }
+                character += chr.len_utf16();
+                offset += chr.len_utf8();
+            }
+        }
+        offset
+    }
+
     pub fn change(&mut self, range: &Range, content: &str) {
         if self.lines.is_empty() {
             self.lines = split_lines(content);
@@ -485,6 +528,55 @@ mod tests {
         assert_eq!(reader.pos(), Position::new(0, 4));
     }
 
+    #[test]
+    fn byte_offset_to_position_with_multi_byte_comments() {
+        // Bomb emojii requires 4 utf-8 bytes and 2 utf-16 codes
+        let bomb = '\u{1F4A3}';
+        let contents = new(&format!("-- é{bomb}\r\n/* {bomb} */ sig;\n"));
+
+        assert_eq!(contents.byte_offset_to_position(0), Position::new(0, 0));
+        assert_eq!(contents.byte_offset_to_position(3), Position::new(0, 3));
+        // Inside of é
+        assert_eq!(contents.byte_offset_to_position(4), Position::new(0, 3));
+        assert_eq!(contents.byte_offset_to_position(5), Position::new(0, 4));
+        // Inside of the bomb
+        assert_eq!(contents.byte_offset_to_position(7), Position::new(0, 4));
+        assert_eq!(contents.byte_offset_to_position(9), Position::new(0, 6));
+        // The line ending has been normalized to a single byte
+        assert_eq!(contents.byte_offset_to_position(10), Position::new(1, 0));
+        // The sig identifier after the block comment
+        assert_eq!(contents.byte_offset_to_position(21), Position::new(1, 9));
+        assert_eq!(contents.byte_offset_to_position(100), contents.end());
+    }
+
+    #[test]
+    fn position_to_byte_offset_with_multi_byte_comments() {
+        let bomb = '\u{1F4A3}';
+        let contents = new(&format!("-- é{bomb}\n/* {bomb} */ sig;\n"));
+
+        assert_eq!(contents.position_to_byte_offset(Position::new(0, 3)), 3);
+        assert_eq!(contents.position_to_byte_offset(Position::new(0, 4)), 5);
+        // Within the surrogate pair of the bomb
+        assert_eq!(contents.position_to_byte_offset(Position::new(0, 5)), 5);
+        assert_eq!(contents.position_to_byte_offset(Position::new(0, 6)), 9);
+        // Beyond the end of the line
+        assert_eq!(contents.position_to_byte_offset(Position::new(0, 100)), 9);
+        assert_eq!(contents.position_to_byte_offset(Position::new(1, 0)), 10);
+        assert_eq!(contents.position_to_byte_offset(Position::new(1, 9)), 21);
+    }
+
+    #[test]
+    fn byte_offset_and_position_round_trip() {
+        let bomb = '\u{1F4A3}';
+        let code = format!("-- åäö {bomb}\nsignal {bomb}s : bit; -- ü\n");
+        let contents = new(&code);
+
+        for (offset, _) in code.char_indices() {
+            let pos = contents.byte_offset_to_position(offset);
+            assert_eq!(contents.position_to_byte_offset(pos), offset);
+        }
+    }
+
     fn flatten(contents: &Contents) -> String {
         let mut result = String::new();
         for line in contents.lines.iter() {
@@ -11,6 +11,8 @@ use strum::{EnumString, IntoStaticStr};
 
 #[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, EnumString, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Severity {
     Hint,
     Info,
@@ -25,6 +27,15 @@ pub struct Diagnostic {
     pub message: String,
     pub related: Vec<(SrcPos, String)>,
     pub code: ErrorCode,
+    /// Suggested changes of the source code that resolve the diagnostic
+    pub fixes: Vec<Fix>,
+}
+
+/// A replacement of the source code at a position
+#[derive(PartialEq, Debug, Clone, Eq, Hash)]
+pub struct Fix {
+    pub pos: SrcPos,
+    pub replacement: String,
 }
 
 impl Diagnostic {
@@ -34,6 +45,7 @@ impl Diagnostic {
             message: msg.into(),
             related: vec![],
             code,
+            fixes: vec![],
         }
     }
 
@@ -43,6 +55,7 @@ impl Diagnostic {
             pos: self.pos,
             related: vec![],
             code: self.code,
+            fixes: self.fixes,
         }
     }
 
@@ -64,6 +77,16 @@ impl Diagnostic {
         diagnostic
     }
 
+    /// Suggest to replace the source code at the position
+    pub fn fix(self, item: impl AsRef<SrcPos>, replacement: impl Into<String>) -> Diagnostic {
+        let mut diagnostic = self;
+        diagnostic.fixes.push(Fix {
+            pos: item.as_ref().to_owned(),
+            replacement: replacement.into(),
+        });
+        diagnostic
+    }
+
     pub fn add_related(&mut self, item: impl AsRef<SrcPos>, message: impl Into<String>) {
         self.related
             .push((item.as_ref().to_owned(), message.into()));
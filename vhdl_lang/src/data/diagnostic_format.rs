@@ -0,0 +1,256 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{Diagnostic, DiagnosticReport, Range, RelatedLocation, Severity};
+use crate::data::error_codes::{ErrorCode, SeverityMap};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Machine readable output formats of diagnostics
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DiagnosticFormat {
+    /// One JSON object per line and diagnostic
+    JsonLines,
+    /// A SARIF 2.1.0 log as used by code scanning tools
+    Sarif,
+}
+
+impl DiagnosticFormat {
+    /// Format all diagnostics whose error code is enabled in the severity map.
+    ///
+    /// The rule id of a diagnostic is the name of its error code as used in the `[lint]`
+    /// section of the configuration, for example `unused` for [ErrorCode::Unused].
+    pub fn format(&self, diagnostics: &[Diagnostic], severities: &SeverityMap) -> String {
+        let report = DiagnosticReport::new(diagnostics, severities);
+        match self {
+            DiagnosticFormat::JsonLines => format_json_lines(&report),
+            DiagnosticFormat::Sarif => format_sarif(&report),
+        }
+    }
+}
+
+fn rule_id(code: ErrorCode) -> &'static str {
+    code.into()
+}
+
+fn format_json_lines(report: &DiagnosticReport) -> String {
+    let mut result = String::new();
+    for (path, diagnostic) in report.iter() {
+        let severity: &str = diagnostic.severity.into();
+        let related = diagnostic
+            .related
+            .iter()
+            .map(|related| {
+                format!(
+                    "{{\"file\":{},\"range\":{},\"message\":{}}}",
+                    json_path(&related.path),
+                    json_range(&related.range),
+                    json_string(&related.message)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let _ = writeln!(
+            result,
+            "{{\"file\":{},\"code\":{},\"severity\":{},\"range\":{},\"message\":{},\"related\":[{}]}}",
+            json_path(path),
+            json_string(rule_id(diagnostic.code)),
+            json_string(severity),
+            json_range(&diagnostic.range),
+            json_string(&diagnostic.message),
+            related,
+        );
+    }
+    result
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "note",
+    }
+}
+
+/// SARIF lines and columns are one-based, the end column is exclusive
+fn sarif_region(range: &Range) -> String {
+    format!(
+        "{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{}}}",
+        range.start.line + 1,
+        range.start.character + 1,
+        range.end.line + 1,
+        range.end.character + 1,
+    )
+}
+
+fn sarif_location(path: &Path, range: &Range) -> String {
+    format!(
+        "\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}},\"region\":{}}}",
+        json_string(&file_uri(path)),
+        sarif_region(range)
+    )
+}
+
+fn sarif_related(idx: usize, related: &RelatedLocation) -> String {
+    format!(
+        "{{\"id\":{idx},{},\"message\":{{\"text\":{}}}}}",
+        sarif_location(&related.path, &related.range),
+        json_string(&related.message)
+    )
+}
+
+fn format_sarif(report: &DiagnosticReport) -> String {
+    // Rules are listed in order of first occurrence and referenced by index
+    let mut rules: Vec<ErrorCode> = Vec::new();
+    let mut results = Vec::new();
+
+    for (path, diagnostic) in report.iter() {
+        let rule_index = match rules.iter().position(|code| *code == diagnostic.code) {
+            Some(idx) => idx,
+            None => {
+                rules.push(diagnostic.code);
+                rules.len() - 1
+            }
+        };
+        let related = diagnostic
+            .related
+            .iter()
+            .enumerate()
+            .map(|(idx, related)| sarif_related(idx, related))
+            .collect::<Vec<_>>()
+            .join(",");
+        results.push(format!(
+            "{{\"ruleId\":{},\"ruleIndex\":{rule_index},\"level\":\"{}\",\"message\":{{\"text\":{}}},\"locations\":[{{{}}}],\"relatedLocations\":[{related}]}}",
+            json_string(rule_id(diagnostic.code)),
+            sarif_level(diagnostic.severity),
+            json_string(&diagnostic.message),
+            sarif_location(path, &diagnostic.range),
+        ));
+    }
+
+    let rules = rules
+        .into_iter()
+        .map(|code| format!("{{\"id\":{}}}", json_string(rule_id(code))))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{{\"version\":\"2.1.0\",\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"vhdl_lang\",\"version\":\"{}\",\"informationUri\":\"https://github.com/VHDL-LS/rust_hdl\",\"rules\":[{rules}]}}}},\"results\":[{}]}}]}}\n",
+        env!("CARGO_PKG_VERSION"),
+        results.join(","),
+    )
+}
+
+/// Absolute paths are converted to `file` URIs, relative paths are kept relative
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::with_capacity(path.len());
+    if path.starts_with('/') {
+        uri.push_str("file://");
+    } else if path.as_bytes().get(1) == Some(&b':') {
+        // Windows drive letter
+        uri.push_str("file:///");
+    }
+    for chr in path.chars() {
+        match chr {
+            ' ' => uri.push_str("%20"),
+            '%' => uri.push_str("%25"),
+            '#' => uri.push_str("%23"),
+            '?' => uri.push_str("%3F"),
+            _ => uri.push(chr),
+        }
+    }
+    uri
+}
+
+fn json_path(path: &Path) -> String {
+    json_string(&path.to_string_lossy())
+}
+
+fn json_range(range: &Range) -> String {
+    format!(
+        "{{\"start\":{{\"line\":{},\"character\":{}}},\"end\":{{\"line\":{},\"character\":{}}}}}",
+        range.start.line, range.start.character, range.end.line, range.end.character
+    )
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for chr in value.chars() {
+        match chr {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            chr if (chr as u32) < 0x20 => {
+                let _ = write!(result, "\\u{:04x}", chr as u32);
+            }
+            chr => result.push(chr),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use pretty_assertions::assert_eq;
+
+    fn diagnostics() -> Vec<Diagnostic> {
+        let code = Code::new_with_file_name(Path::new("/src/my file.vhd"), "hello\nworld \"x\"\n");
+        vec![
+            Diagnostic::new(code.s1("world"), "Greetings \"x\"", ErrorCode::Unused),
+            Diagnostic::new(code.s1("hello"), "Failure", ErrorCode::SyntaxError)
+                .related(code.s1("\"x\""), "Here"),
+        ]
+    }
+
+    #[test]
+    fn json_lines() {
+        assert_eq!(
+            DiagnosticFormat::JsonLines.format(&diagnostics(), &SeverityMap::default()),
+            "\
+{\"file\":\"/src/my file.vhd\",\"code\":\"syntax_error\",\"severity\":\"error\",\"range\":{\"start\":{\"line\":0,\"character\":0},\"end\":{\"line\":0,\"character\":5}},\"message\":\"Failure\",\"related\":[{\"file\":\"/src/my file.vhd\",\"range\":{\"start\":{\"line\":1,\"character\":6},\"end\":{\"line\":1,\"character\":9}},\"message\":\"Here\"}]}
+{\"file\":\"/src/my file.vhd\",\"code\":\"unused\",\"severity\":\"warning\",\"range\":{\"start\":{\"line\":1,\"character\":0},\"end\":{\"line\":1,\"character\":5}},\"message\":\"Greetings \\\"x\\\"\",\"related\":[]}
+"
+        );
+    }
+
+    #[test]
+    fn sarif() {
+        let mut severities = SeverityMap::default();
+        severities[ErrorCode::Unused] = Some(Severity::Hint);
+
+        assert_eq!(
+            DiagnosticFormat::Sarif.format(&diagnostics(), &severities),
+            format!(
+                "{{\"version\":\"2.1.0\",\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"vhdl_lang\",\"version\":\"{}\",\"informationUri\":\"https://github.com/VHDL-LS/rust_hdl\",\"rules\":[{{\"id\":\"syntax_error\"}},{{\"id\":\"unused\"}}]}}}},\"results\":[\
+{{\"ruleId\":\"syntax_error\",\"ruleIndex\":0,\"level\":\"error\",\"message\":{{\"text\":\"Failure\"}},\"locations\":[{{\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":\"file:///src/my%20file.vhd\"}},\"region\":{{\"startLine\":1,\"startColumn\":1,\"endLine\":1,\"endColumn\":6}}}}}}],\"relatedLocations\":[{{\"id\":0,\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":\"file:///src/my%20file.vhd\"}},\"region\":{{\"startLine\":2,\"startColumn\":7,\"endLine\":2,\"endColumn\":10}}}},\"message\":{{\"text\":\"Here\"}}}}]}},\
+{{\"ruleId\":\"unused\",\"ruleIndex\":1,\"level\":\"note\",\"message\":{{\"text\":\"Greetings \\\"x\\\"\"}},\"locations\":[{{\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":\"file:///src/my%20file.vhd\"}},\"region\":{{\"startLine\":2,\"startColumn\":1,\"endLine\":2,\"endColumn\":6}}}}}}],\"relatedLocations\":[]}}\
+]}}]}}\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn escapes_control_characters() {
+        assert_eq!(json_string("a\tb\u{1}"), "\"a\\tb\\u0001\"");
+    }
+
+    #[test]
+    fn relative_and_windows_uris() {
+        assert_eq!(file_uri(Path::new("src/a.vhd")), "src/a.vhd");
+        assert_eq!(
+            file_uri(Path::new("C:\\src\\a.vhd")),
+            "file:///C:/src/a.vhd"
+        );
+    }
+}
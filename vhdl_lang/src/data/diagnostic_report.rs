@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{Diagnostic, Range, Severity, SrcPos};
+use crate::data::error_codes::{ErrorCode, SeverityMap};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// All diagnostics of an analysis grouped by the file they occur in.
+/// Intended for tooling which consumes diagnostics in a machine readable form.
+#[derive(PartialEq, Debug, Clone, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticReport {
+    /// Files sorted by path, only files with at least one diagnostic are present
+    pub files: Vec<FileDiagnostics>,
+}
+
+#[derive(PartialEq, Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileDiagnostics {
+    pub path: PathBuf,
+    pub diagnostics: Vec<ReportedDiagnostic>,
+}
+
+#[derive(PartialEq, Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportedDiagnostic {
+    pub code: ErrorCode,
+    pub severity: Severity,
+    pub range: Range,
+    pub message: String,
+    pub related: Vec<RelatedLocation>,
+}
+
+#[derive(PartialEq, Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelatedLocation {
+    pub path: PathBuf,
+    pub range: Range,
+    pub message: String,
+}
+
+impl RelatedLocation {
+    fn new(pos: &SrcPos, message: &str) -> RelatedLocation {
+        RelatedLocation {
+            path: pos.file_name().to_owned(),
+            range: pos.range(),
+            message: message.to_owned(),
+        }
+    }
+}
+
+impl DiagnosticReport {
+    /// Diagnostics whose error code is disabled in the severity map are omitted.
+    pub fn new(diagnostics: &[Diagnostic], severities: &SeverityMap) -> DiagnosticReport {
+        let mut files: BTreeMap<PathBuf, Vec<ReportedDiagnostic>> = BTreeMap::new();
+
+        for diagnostic in diagnostics.iter() {
+            let Some(severity) = severities[diagnostic.code] else {
+                continue;
+            };
+
+            files
+                .entry(diagnostic.pos.file_name().to_owned())
+                .or_default()
+                .push(ReportedDiagnostic {
+                    code: diagnostic.code,
+                    severity,
+                    range: diagnostic.pos.range(),
+                    message: diagnostic.message.clone(),
+                    related: diagnostic
+                        .related
+                        .iter()
+                        .map(|(pos, message)| RelatedLocation::new(pos, message))
+                        .collect(),
+                });
+        }
+
+        DiagnosticReport {
+            files: files
+                .into_iter()
+                .map(|(path, mut diagnostics)| {
+                    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+                    FileDiagnostics { path, diagnostics }
+                })
+                .collect(),
+        }
+    }
+
+    /// The diagnostics of all files
+    pub fn iter(&self) -> impl Iterator<Item = (&PathBuf, &ReportedDiagnostic)> {
+        self.files.iter().flat_map(|file| {
+            file.diagnostics
+                .iter()
+                .map(move |diagnostic| (&file.path, diagnostic))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use std::path::Path;
+
+    #[test]
+    fn groups_diagnostics_by_file() {
+        let code_b = Code::new_with_file_name(Path::new("b.vhd"), "hello\nworld\n");
+        let code_a = Code::new_with_file_name(Path::new("a.vhd"), "foo bar\n");
+
+        let diagnostics = vec![
+            Diagnostic::new(code_b.s1("world"), "Second", ErrorCode::Unused),
+            Diagnostic::new(code_b.s1("hello"), "First", ErrorCode::SyntaxError)
+                .related(code_a.s1("bar"), "Defined here"),
+            Diagnostic::new(code_a.s1("foo"), "Third", ErrorCode::Unresolved),
+        ];
+
+        let report = DiagnosticReport::new(&diagnostics, &SeverityMap::default());
+        assert_eq!(
+            report,
+            DiagnosticReport {
+                files: vec![
+                    FileDiagnostics {
+                        path: PathBuf::from("a.vhd"),
+                        diagnostics: vec![ReportedDiagnostic {
+                            code: ErrorCode::Unresolved,
+                            severity: Severity::Error,
+                            range: code_a.s1("foo").pos().range(),
+                            message: "Third".to_owned(),
+                            related: vec![],
+                        }],
+                    },
+                    FileDiagnostics {
+                        path: PathBuf::from("b.vhd"),
+                        diagnostics: vec![
+                            ReportedDiagnostic {
+                                code: ErrorCode::SyntaxError,
+                                severity: Severity::Error,
+                                range: code_b.s1("hello").pos().range(),
+                                message: "First".to_owned(),
+                                related: vec![RelatedLocation {
+                                    path: PathBuf::from("a.vhd"),
+                                    range: code_a.s1("bar").pos().range(),
+                                    message: "Defined here".to_owned(),
+                                }],
+                            },
+                            ReportedDiagnostic {
+                                code: ErrorCode::Unused,
+                                severity: Severity::Warning,
+                                range: code_b.s1("world").pos().range(),
+                                message: "Second".to_owned(),
+                                related: vec![],
+                            },
+                        ],
+                    },
+                ],
+            }
+        );
+        assert_eq!(report.iter().count(), 3);
+    }
+
+    #[test]
+    fn omits_disabled_diagnostics() {
+        let code = Code::new_with_file_name(Path::new("a.vhd"), "hello\n");
+        let mut severities = SeverityMap::default();
+        severities[ErrorCode::Unused] = None;
+
+        let diagnostics = vec![Diagnostic::new(
+            code.s1("hello"),
+            "Unused",
+            ErrorCode::Unused,
+        )];
+        assert_eq!(
+            DiagnosticReport::new(&diagnostics, &severities),
+            DiagnosticReport::default()
+        );
+    }
+}
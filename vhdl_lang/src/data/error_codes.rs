@@ -8,10 +8,14 @@ use crate::{Diagnostic, Severity, SrcPos};
 use enum_map::{enum_map, Enum, EnumMap};
 use std::fmt::{Display, Formatter};
 use std::ops::{Index, IndexMut};
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumString, IntoStaticStr, VariantArray};
 
-#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, EnumString, IntoStaticStr, Enum)]
+#[derive(
+    PartialEq, Debug, Clone, Copy, Eq, Hash, EnumString, IntoStaticStr, VariantArray, Enum,
+)]
 #[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ErrorCode {
     /// A syntax error happens during tokenization or parsing.
     ///
@@ -44,6 +48,19 @@ pub enum ErrorCode {
     /// ```
     CircularDependency,
 
+    /// A type depends on itself through the element types of arrays or records.
+    /// Only access types may be used to declare recursive types.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// type rec_t;
+    /// type arr_t is array (0 to 1) of rec_t;
+    /// type rec_t is record
+    ///     elements: arr_t;
+    /// end record;
+    /// ```
+    CircularTypeDependency,
+
     /// A formal parameter is invalid / malformed in a certain context
     ///
     /// # Example
@@ -228,6 +245,16 @@ pub enum ErrorCode {
     /// ```
     SignatureRequired,
 
+    /// An alias of an overloaded subprogram without a signature.
+    /// All overloads are aliased, as commonly accepted by vendor tools.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// procedure foo(arg: natural);
+    /// alias bar is foo;
+    /// ```
+    SubprogramAliasWithoutSignature,
+
     /// The value of an expression is ambiguous
     AmbiguousExpression,
 
@@ -348,6 +375,16 @@ pub enum ErrorCode {
     /// ```
     Unresolved,
 
+    /// A selected name does not denote a declaration within its prefix,
+    /// for example a package, a record or a protected type
+    ///
+    /// # Example
+    /// ```vhdl
+    /// -- There is nothing named 'bar' in package 'pkg'
+    /// use work.pkg.bar;
+    /// ```
+    NoDeclarationWithin,
+
     /// An index that is out of range for an N-Dimensional array
     DimensionMismatch,
 
@@ -388,6 +425,361 @@ pub enum ErrorCode {
     /// Calling a name like a function or procedure where that is not applicable
     InvalidCall,
 
+    /// An aggregate is used in a context where its type cannot be determined
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal a, b : bit;
+    /// -- The target is an aggregate, so the type of the right hand side
+    /// -- must be determined without it
+    /// (a, b) <= ('0', '1');
+    /// ```
+    UntypedAggregate,
+
+    /// The value of a deferred constant is used before its full declaration
+    ///
+    /// # Example
+    /// ```vhdl
+    /// package pkg is
+    ///   constant deferred : natural;
+    ///   constant c : natural := deferred + 1;
+    /// end package;
+    /// ```
+    DeferredConstantBeforeFullDeclaration,
+
+    /// An incomplete type is used before its full declaration other than
+    /// as the designated type of an access type
+    ///
+    /// # Example
+    /// ```vhdl
+    /// type rec_t;
+    /// type arr_t is array (0 to 1) of rec_t;
+    /// type rec_t is record
+    /// end record;
+    /// ```
+    IncompleteTypeBeforeFullDeclaration,
+
+    /// A shared variable is not of a protected type.
+    /// This is only allowed in VHDL-93 and earlier.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// architecture arch of ent is
+    ///   shared variable counter : natural;
+    /// begin
+    /// end architecture;
+    /// ```
+    SharedVariableNotProtected,
+
+    /// A type or subtype name is used where a value is expected
+    ///
+    /// # Example
+    /// ```vhdl
+    /// variable x : integer;
+    /// -- ...
+    /// x := integer;
+    /// ```
+    TypeUsedAsValue,
+
+    /// Not all values of the case expression are covered by the choices
+    /// and there is no choice `others`
+    ///
+    /// # Example
+    /// ```vhdl
+    /// type state_t is (idle, busy, done);
+    /// -- ...
+    /// case state is
+    ///     when idle => -- ...
+    ///     when busy => -- ...
+    /// end case;
+    /// ```
+    MissingChoice,
+
+    /// A value is covered by more than one choice
+    ///
+    /// # Example
+    /// ```vhdl
+    /// case state is
+    ///     when idle => -- ...
+    ///     when idle | busy => -- ...
+    ///     when others => -- ...
+    /// end case;
+    /// ```
+    DuplicateChoice,
+
+    /// A static value does not belong to the range of a constrained subtype
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal byte : natural range 0 to 255;
+    /// -- ...
+    /// byte <= 300;
+    /// ```
+    OutOfRange,
+
+    /// The static range constraint of a subtype is not within the range of its type mark
+    ///
+    /// # Example
+    /// ```vhdl
+    /// subtype s is natural range -1 to 5;
+    /// ```
+    SubtypeRangeOutOfBase,
+
+    /// The length of a static array value differs from the length of the target
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal byte : bit_vector(7 downto 0);
+    /// -- ...
+    /// byte <= x"F";
+    /// ```
+    LengthMismatch,
+
+    /// The pulse rejection limit of an inertially delayed signal assignment is negative
+    /// or greater than the delay of the first waveform element
+    ///
+    /// # Example
+    /// ```vhdl
+    /// pulse <= reject 5 ns inertial '1' after 2 ns;
+    /// ```
+    InvalidRejectTime,
+
+    /// A resolution function does not match the resolved type or element resolution
+    /// is used for a type that has no such elements
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function resolve(arg : integer_vector) return integer;
+    /// subtype resolved_bit is resolve bit;
+    /// ```
+    InvalidResolution,
+
+    /// A port of mode `out` is read, which is only allowed since VHDL-2008
+    ///
+    /// # Example
+    /// ```vhdl
+    /// entity ent is
+    ///     port (valid : out bit; ready : out bit);
+    /// end entity;
+    /// -- ...
+    /// ready <= valid;
+    /// ```
+    ReadOutPort,
+
+    /// An object is of a type that is not allowed for its class,
+    /// e.g. a signal or constant of a file, access or protected type
+    ///
+    /// # Example
+    /// ```vhdl
+    /// type text_file_t is file of string;
+    /// signal log : text_file_t;
+    /// ```
+    IllegalObjectType,
+
+    /// The element type of a composite type or the type of a file type
+    /// is a type that is not allowed in that position
+    ///
+    /// # Example
+    /// ```vhdl
+    /// type counter_t is protected
+    /// end protected;
+    /// type counter_arr_t is array (natural range <>) of counter_t;
+    /// ```
+    IllegalElementType,
+
+    /// The left and right bounds of a range are not of the same type
+    ///
+    /// # Example
+    /// ```vhdl
+    /// type arr_t is array (0 to true) of bit;
+    /// ```
+    RangeBoundTypeMismatch,
+
+    /// The target of an assignment is not writable, such as a constant,
+    /// an interface object of mode `in` or a loop parameter
+    ///
+    /// # Example
+    /// ```vhdl
+    /// entity ent is
+    ///     port (clk : in bit);
+    /// end entity;
+    /// -- ...
+    /// clk <= '0';
+    /// ```
+    NotAssignable,
+
+    /// A statement that is only allowed in a process or procedure
+    /// appears within a function
+    ///
+    /// # Example
+    /// ```vhdl
+    /// impure function f return natural is
+    /// begin
+    ///     s <= '1';
+    ///     return 0;
+    /// end function;
+    /// ```
+    IllegalStatementInFunction,
+
+    /// A statement in the statement part of an entity that is not passive
+    ///
+    /// # Example
+    /// ```vhdl
+    /// entity ent is
+    ///     port (clk : out bit);
+    /// begin
+    ///     clk <= '0';
+    /// end entity;
+    /// ```
+    NonPassiveEntityStatement,
+
+    /// A pure function references a signal, variable or file declared outside of it
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal counter : natural;
+    /// -- ...
+    /// function next_count return natural is
+    /// begin
+    ///     return counter + 1;
+    /// end function;
+    /// ```
+    PurityViolation,
+
+    /// A context declaration defines or refers to the library logical name `work`
+    ///
+    /// # Example
+    /// ```vhdl
+    /// context ctx is
+    ///     library work;
+    ///     use work.pkg.all;
+    /// end context;
+    /// ```
+    IllegalContextItem,
+
+    /// A parameter of a function is declared with a mode other than `in`.
+    /// Only allowed since VHDL-2019
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function next_value(x : inout natural) return natural;
+    /// ```
+    IllegalFunctionParameterMode,
+
+    /// A default value of a subprogram parameter of mode linkage,
+    /// of a signal parameter or of a variable parameter of a mode other than `in`
+    ///
+    /// # Example
+    /// ```vhdl
+    /// procedure increment(variable x : inout natural := 0);
+    /// ```
+    IllegalParameterDefault,
+
+    /// The attributes `'driving` and `'driving_value` are used within a process
+    /// on a signal that the process does not drive
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process
+    /// begin
+    ///     report boolean'image(sig'driving);
+    ///     wait;
+    /// end process;
+    /// ```
+    MissingDriver,
+
+    /// A pure function calls an impure function.
+    /// This is a warning by default since libraries such as VITAL rely on it.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function elapsed(start : time) return time is
+    /// begin
+    ///     return now - start;
+    /// end function;
+    /// ```
+    ImpureCall,
+
+    /// A violation of the standard that vendor tools commonly accept, see [ErrorCode::is_non_standard].
+    /// Reported instead of the error code of the violation when analyzing in lenient mode.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// -- With VHDL-2008
+    /// shared variable counter : natural;
+    /// ```
+    NonStandard,
+
+    /// A function whose statements can complete without a return statement
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function sign(x : integer) return integer is
+    /// begin
+    ///     if x < 0 then
+    ///         return -1;
+    ///     end if;
+    /// end function;
+    /// ```
+    MissingReturn,
+
+    /// A wait statement within a process that has a sensitivity list
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process (clk)
+    /// begin
+    ///     wait until rst = '0';
+    /// end process;
+    /// ```
+    WaitInSensitizedProcess,
+
+    /// A wait statement within a function, functions cannot suspend
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function f return natural is
+    /// begin
+    ///     wait for 1 ns;
+    ///     return 0;
+    /// end function;
+    /// ```
+    WaitInFunction,
+
+    /// A variable in a package declaration that is not a shared variable
+    ///
+    /// # Example
+    /// ```vhdl
+    /// package pkg is
+    ///     variable count : natural;
+    /// end package;
+    /// ```
+    NonSharedVariableInPackage,
+
+    /// The actual of a generic constant is not a globally static expression
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal depth : natural;
+    /// -- ...
+    /// inst: entity work.fifo
+    ///     generic map (depth => depth);
+    /// ```
+    NonStaticGenericActual,
+
+    /// A sequential statement that follows an unconditional return, exit or next statement,
+    /// or a statement whose every branch ends with one
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function inc(x : natural) return natural is
+    /// begin
+    ///     return x + 1;
+    ///     report "done";
+    /// end function;
+    /// ```
+    UnreachableCode,
+
     // Linting
     /// A declaration that is unused
     Unused,
@@ -410,6 +802,113 @@ pub enum ErrorCode {
     /// ```
     UnassociatedContext,
 
+    /// The generics or ports of a component declaration do not match
+    /// the entity that the component is bound to by default
+    ///
+    /// # Example
+    /// ```vhdl
+    /// entity ent is
+    ///     port (data : in bit_vector(7 downto 0));
+    /// end entity;
+    /// -- ...
+    /// component ent is
+    ///     port (data : in integer);
+    /// end component;
+    /// ```
+    ComponentEntityMismatch,
+
+    /// A port or generic of an entity that is not used by any of its architectures.
+    /// This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// entity ent is
+    ///     port (valid : out bit);
+    /// end entity;
+    ///
+    /// architecture a of ent is
+    /// begin
+    /// end architecture;
+    /// ```
+    UnusedPort,
+
+    /// A subprogram that calls itself before any branch or return could end the recursion.
+    /// This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// function fact(n : natural) return natural is
+    /// begin
+    ///     return n * fact(n - 1);
+    /// end function;
+    /// ```
+    InfiniteRecursion,
+
+    /// A signal that is driven by more than one concurrent statement,
+    /// while its type is not resolved. This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// architecture a of ent is
+    ///     signal valid : bit;
+    /// begin
+    ///     valid <= '1';
+    ///     valid <= '0';
+    /// end architecture;
+    /// ```
+    MultipleDrivers,
+
+    /// A use clause that makes nothing visible that is used,
+    /// or only what is already made visible. This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// use work.pkg.all;
+    /// use work.pkg.all;
+    ///
+    /// entity ent is
+    /// end entity;
+    /// ```
+    RedundantUseClause,
+
+    /// A use clause that is unused since the name it makes visible is hidden
+    /// by a local declaration. This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// use work.pkg.width;
+    ///
+    /// entity ent is
+    ///     generic (width : natural);
+    /// end entity;
+    /// ```
+    ShadowedUseClause,
+
+    /// A signal that is read in a combinational process but is not in its sensitivity list.
+    /// This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process (a)
+    /// begin
+    ///     y <= a and b;
+    /// end process;
+    /// ```
+    IncompleteSensitivity,
+
+    /// A signal that is read after it is assigned in the same process without the process
+    /// suspending in between, so the read does not return the assigned value. This check is opt-in.
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process (clk)
+    /// begin
+    ///     count <= 0;
+    ///     next_count <= count + 1;
+    /// end process;
+    /// ```
+    ReadAfterSignalAssignment,
+
     // Misc
     /// An internal error that signifies that some precondition within vhdl_lang wasn't met.
     /// If an error with this error code occurs,
@@ -443,6 +942,7 @@ impl Default for SeverityMap {
         let map = enum_map! {
             SyntaxError
             | CircularDependency
+            | CircularTypeDependency
             | InvalidFormal
             | InvalidFormalConversion
             | TypeMismatch
@@ -459,6 +959,7 @@ impl Default for SeverityMap {
             | NoOverloadedWithSignature
             | IllegalSignature
             | SignatureRequired
+            | SubprogramAliasWithoutSignature
             | AmbiguousExpression
             | Duplicate
             | ConflictingUseClause
@@ -479,6 +980,7 @@ impl Default for SeverityMap {
             | IllegalConstraint
             | InvalidOperatorSymbol
             | Unresolved
+            | NoDeclarationWithin
             | DimensionMismatch
             | InvalidLiteral
             | DeclaredBefore
@@ -491,10 +993,48 @@ impl Default for SeverityMap {
             | UnexpectedSignature
             | MissingDeferredDeclaration
             | MissingFullTypeDeclaration
-            | InvalidCall => Some(Error),
+            | InvalidCall
+            | UntypedAggregate
+            | DeferredConstantBeforeFullDeclaration
+            | IncompleteTypeBeforeFullDeclaration
+            | SharedVariableNotProtected
+            | TypeUsedAsValue
+            | MissingChoice
+            | DuplicateChoice
+            | InvalidResolution
+            | ReadOutPort
+            | IllegalObjectType
+            | IllegalElementType
+            | RangeBoundTypeMismatch
+            | NotAssignable
+            | IllegalStatementInFunction
+            | NonPassiveEntityStatement
+            | PurityViolation
+            | IllegalContextItem
+            | IllegalFunctionParameterMode
+            | IllegalParameterDefault
+            | MissingDriver
+            | LengthMismatch
+            | InvalidRejectTime
+            | MissingReturn
+            | WaitInSensitizedProcess
+            | WaitInFunction
+            | NonSharedVariableInPackage
+            | NonStaticGenericActual => Some(Error),
+            OutOfRange | SubtypeRangeOutOfBase | NonStandard | ImpureCall | UnreachableCode => {
+                Some(Warning)
+            }
             Unused
             | UnnecessaryWorkLibrary
             | UnassociatedContext => Some(Warning),
+            ComponentEntityMismatch => Some(Error),
+            UnusedPort
+            | InfiniteRecursion
+            | MultipleDrivers
+            | RedundantUseClause
+            | ShadowedUseClause
+            | IncompleteSensitivity
+            | ReadAfterSignalAssignment => Some(Warning),
             Internal => Some(Error),
             Related => Some(Hint)
         };
@@ -516,10 +1056,42 @@ impl IndexMut<ErrorCode> for SeverityMap {
     }
 }
 
+impl SeverityMap {
+    /// Attach the severity of each diagnostic,
+    /// diagnostics with a disabled error code are dropped
+    pub fn apply<'a>(
+        &'a self,
+        diagnostics: impl IntoIterator<Item = Diagnostic> + 'a,
+    ) -> impl Iterator<Item = (Diagnostic, Severity)> + 'a {
+        diagnostics.into_iter().filter_map(|diagnostic| {
+            let severity = self[diagnostic.code]?;
+            Some((diagnostic, severity))
+        })
+    }
+}
+
 impl ErrorCode {
-    pub fn as_str(&self) -> &str {
+    /// Every error code in order of declaration
+    pub fn all() -> &'static [ErrorCode] {
+        ErrorCode::VARIANTS
+    }
+
+    /// The snake case name of the error code, as used in the configuration.
+    /// The name is parsed back using [std::str::FromStr]
+    pub fn as_str(&self) -> &'static str {
         self.into()
     }
+
+    /// Violations of the standard that vendor tools commonly accept.
+    /// These are reported as [ErrorCode::NonStandard] when analyzing in lenient mode.
+    pub fn is_non_standard(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::ReadOutPort
+                | ErrorCode::SharedVariableNotProtected
+                | ErrorCode::SubprogramAliasWithoutSignature
+        )
+    }
 }
 
 #[test]
@@ -552,6 +1124,49 @@ fn serialize_to_string() {
     assert_eq!(ErrorCode::SyntaxError.as_str(), "syntax_error");
 }
 
+#[test]
+fn all_error_codes_round_trip_through_their_name() {
+    use std::str::FromStr;
+
+    assert!(ErrorCode::all().contains(&ErrorCode::SyntaxError));
+    assert!(ErrorCode::all().contains(&ErrorCode::Related));
+    for code in ErrorCode::all() {
+        assert_eq!(ErrorCode::from_str(code.as_str()), Ok(*code));
+    }
+
+    let mut names: Vec<_> = ErrorCode::all().iter().map(ErrorCode::as_str).collect();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), ErrorCode::all().len());
+}
+
+#[test]
+fn apply_severity_overrides() {
+    use crate::syntax::test::Code;
+
+    let code = Code::new("foo bar baz");
+    let diagnostics = vec![
+        Diagnostic::new(code.s1("foo"), "foo", ErrorCode::Unused),
+        Diagnostic::new(code.s1("bar"), "bar", ErrorCode::Duplicate),
+        Diagnostic::new(code.s1("baz"), "baz", ErrorCode::OutOfRange),
+    ];
+
+    let mut severities = SeverityMap::default();
+    severities[ErrorCode::Unused] = Some(Severity::Error);
+    severities[ErrorCode::Duplicate] = None;
+
+    assert_eq!(
+        severities
+            .apply(diagnostics)
+            .map(|(diagnostic, severity)| (diagnostic.message, severity))
+            .collect::<Vec<_>>(),
+        vec![
+            ("foo".to_owned(), Severity::Error),
+            ("baz".to_owned(), Severity::Warning)
+        ]
+    );
+}
+
 /// Specialized diagnostics with pre-defined messages and error codes
 impl Diagnostic {
     pub fn syntax_error(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
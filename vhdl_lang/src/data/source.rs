@@ -175,6 +175,24 @@ impl Source {
         }
     }
 
+    /// The range in lines and UTF-16 code units, as used by the Language Server Protocol,
+    /// of a byte range into the UTF-8 encoded contents.
+    /// See [Contents::byte_offset_to_position] for offsets within characters or beyond the end.
+    pub fn to_lsp_range(&self, bytes: std::ops::Range<usize>) -> Range {
+        let contents = self.contents();
+        Range::new(
+            contents.byte_offset_to_position(bytes.start),
+            contents.byte_offset_to_position(bytes.end),
+        )
+    }
+
+    /// The byte range into the UTF-8 encoded contents of a range in lines and UTF-16 code units.
+    /// This is the inverse of [Source::to_lsp_range].
+    pub fn to_byte_range(&self, range: Range) -> std::ops::Range<usize> {
+        let contents = self.contents();
+        contents.position_to_byte_offset(range.start)..contents.position_to_byte_offset(range.end)
+    }
+
     pub fn change(&self, range: Option<&Range>, content: &str) {
         let mut contents = self.source.contents.write();
         if let Some(range) = range {
@@ -187,10 +205,11 @@ impl Source {
 
 /// A lexical position (line, column) in a source.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     /// Line (zero-based).
     pub line: u32,
-    /// Column (zero-based).
+    /// Column (zero-based), counted in UTF-16 code units as in the Language Server Protocol.
     pub character: u32,
 }
 
@@ -234,6 +253,7 @@ impl Position {
 
 /// A lexical range in a source.
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range {
     /// Start of the range (inclusive).
     pub start: Position,
@@ -582,6 +602,43 @@ mod tests {
         assert_eq!(code.s1("d").pos().combine(&code.s1("h").pos()), code.pos());
     }
 
+    #[test]
+    fn lsp_range_of_multi_byte_characters() {
+        let text = "-- caf\u{e9} \u{1F600} x\nsignal s\u{e9} : bit; -- \u{1F680}\u{1F680} y\n";
+        let source = Source::inline(Path::new("file.vhd"), text);
+
+        let x = text.find('x').unwrap();
+        // 'é' is two bytes and one UTF-16 code unit, the emoji four bytes and a surrogate pair
+        assert_eq!(
+            source.to_lsp_range(x..x + 1),
+            Range::new(Position::new(0, 11), Position::new(0, 12))
+        );
+        assert_eq!(
+            source.to_byte_range(Range::new(Position::new(0, 11), Position::new(0, 12))),
+            x..x + 1
+        );
+
+        let ident = text.find("s\u{e9}").unwrap();
+        let ident_range = Range::new(Position::new(1, 7), Position::new(1, 9));
+        assert_eq!(source.to_lsp_range(ident..ident + 3), ident_range);
+        assert_eq!(source.to_byte_range(ident_range), ident..ident + 3);
+
+        let y = text.find('y').unwrap();
+        let y_range = Range::new(Position::new(1, 25), Position::new(1, 26));
+        assert_eq!(source.to_lsp_range(y..y + 1), y_range);
+        assert_eq!(source.to_byte_range(y_range), y..y + 1);
+
+        // A range that covers a whole surrogate pair
+        let rocket = text.find('\u{1F680}').unwrap();
+        let rocket_range = Range::new(Position::new(1, 20), Position::new(1, 22));
+        assert_eq!(source.to_lsp_range(rocket..rocket + 4), rocket_range);
+        assert_eq!(source.to_byte_range(rocket_range), rocket..rocket + 4);
+
+        // A position within a surrogate pair maps to the start of the character
+        let within = Range::new(Position::new(1, 21), Position::new(1, 21));
+        assert_eq!(source.to_byte_range(within), rocket..rocket);
+    }
+
     fn with_code_from_file<F, R>(contents: &str, fun: F) -> R
     where
         F: Fn(Code) -> R,
@@ -0,0 +1,214 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::ast::Designator;
+use crate::completion::CompletionItem;
+use crate::data::{Diagnostic, Position, Source, SrcPos};
+use crate::named_entity::{AnyEntKind, EntRef};
+use crate::project::Project;
+use crate::syntax::Token;
+use crate::EntHierarchy;
+
+/// The queries needed by a language server gathered in one place.
+///
+/// Every query takes the source and a zero-based cursor position within that source
+/// and every location returned is a [`SrcPos`].
+pub struct LanguageServerFacade {
+    project: Project,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A symbol of a document together with the symbols declared within it
+pub struct DocumentSymbol<'a> {
+    pub ent: EntRef<'a>,
+    /// The position of the designator, or the first token for unnamed symbols such as processes
+    pub pos: SrcPos,
+    /// The position of the entire declaration
+    pub span: SrcPos,
+    pub children: Vec<DocumentSymbol<'a>>,
+}
+
+/// A public symbol of a library, see [`LanguageServerFacade::workspace_symbols`]
+pub struct WorkspaceSymbol<'a> {
+    pub ent: EntRef<'a>,
+    /// The position of the designator
+    pub pos: SrcPos,
+}
+
+/// A replacement of the text at `pos` by `new_text`
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct TextEdit {
+    pub pos: SrcPos,
+    pub new_text: String,
+}
+
+impl LanguageServerFacade {
+    pub fn new(project: Project) -> LanguageServerFacade {
+        LanguageServerFacade {
+            project,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn project(&self) -> &Project {
+        &self.project
+    }
+
+    pub fn project_mut(&mut self) -> &mut Project {
+        &mut self.project
+    }
+
+    pub fn update_source(&mut self, source: &Source) {
+        self.project.update_source(source)
+    }
+
+    /// Analyse the project and return the diagnostics of all sources
+    pub fn analyse(&mut self) -> &[Diagnostic] {
+        self.diagnostics = self.project.analyse();
+        &self.diagnostics
+    }
+
+    /// The diagnostics of the most recent analysis that are located within the source
+    pub fn diagnostics(&self, source: &Source) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diag| &diag.pos.source == source)
+            .collect()
+    }
+
+    /// The position of the definition of the item at the cursor
+    pub fn definition_at(&self, source: &Source, cursor: Position) -> Option<SrcPos> {
+        let ent = self.project.find_definition(source, cursor)?;
+        ent.decl_pos().cloned()
+    }
+
+    /// The position of the declaration of the item at the cursor
+    pub fn declaration_at(&self, source: &Source, cursor: Position) -> Option<SrcPos> {
+        let ent = self.project.find_declaration(source, cursor)?;
+        ent.decl_pos().cloned()
+    }
+
+    /// All references to the item at the cursor, including its declaration
+    pub fn references(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        if let Some(ent) = self.project.find_declaration(source, cursor) {
+            self.project.find_all_references(ent)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The formatted declaration of the item at the cursor
+    pub fn hover(&self, source: &Source, cursor: Position) -> Option<String> {
+        let ent = self.project.find_declaration(source, cursor)?;
+        self.project.format_declaration(ent)
+    }
+
+    pub fn completions(&self, source: &Source, cursor: Position) -> Vec<CompletionItem<'_>> {
+        self.project.list_completion_options(source, cursor)
+    }
+
+    /// The symbols declared within the source.
+    /// Sources that are mapped to several libraries use the symbols of the first library.
+    pub fn document_symbols(&self, source: &Source) -> Vec<DocumentSymbol<'_>> {
+        let Some(library_name) = self.project.library_mapping_of(source).into_iter().next() else {
+            return Vec::new();
+        };
+        self.project
+            .document_symbols(&library_name, source)
+            .into_iter()
+            .map(|(hierarchy, tokens)| DocumentSymbol::new(hierarchy, tokens))
+            .collect()
+    }
+
+    /// The primary design units of all libraries and the declarations within them
+    /// whose name contains `query`, ignoring case.
+    /// Symbols whose name starts with `query` come first.
+    pub fn workspace_symbols(&self, query: &str) -> Vec<WorkspaceSymbol<'_>> {
+        self.project
+            .workspace_symbols(query)
+            .into_iter()
+            .filter_map(WorkspaceSymbol::new)
+            .collect()
+    }
+
+    /// The edits that rename the item at the cursor to `new_name`.
+    ///
+    /// Operator symbols and character literals cannot be renamed since they are
+    /// written differently at declaration and use, as in `function "+"` and `a + b`.
+    pub fn rename(
+        &self,
+        source: &Source,
+        cursor: Position,
+        new_name: &str,
+    ) -> Option<Vec<TextEdit>> {
+        let ent = self.project.find_declaration(source, cursor)?;
+        if !matches!(ent.designator(), Designator::Identifier(_)) {
+            return None;
+        }
+        Some(
+            self.project
+                .find_all_references(ent)
+                .into_iter()
+                .map(|pos| TextEdit {
+                    pos,
+                    new_text: new_name.to_owned(),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'a> DocumentSymbol<'a> {
+    /// The symbols of a design unit as returned by [`Project::document_symbols`]
+    pub fn new(EntHierarchy { ent, children }: EntHierarchy<'a>, tokens: &Vec<Token>) -> Self {
+        let pos = ent
+            .decl_pos()
+            .unwrap_or(ent.src_span.start_token.pos(tokens))
+            .clone();
+        DocumentSymbol {
+            ent,
+            pos,
+            span: ent.src_span.pos(tokens),
+            children: children
+                .into_iter()
+                .map(|hierarchy| DocumentSymbol::new(hierarchy, tokens))
+                .collect(),
+        }
+    }
+
+    /// The name of the symbol as written at its declaration
+    pub fn name(&self) -> String {
+        self.ent.designator().to_string()
+    }
+
+    pub fn kind(&self) -> &'a AnyEntKind<'a> {
+        self.ent.kind()
+    }
+}
+
+impl<'a> WorkspaceSymbol<'a> {
+    /// The symbol of an entity as returned by [`Project::workspace_symbols`]
+    pub fn new(ent: EntRef<'a>) -> Option<Self> {
+        Some(WorkspaceSymbol {
+            ent,
+            pos: ent.decl_pos()?.clone(),
+        })
+    }
+
+    /// The name of the symbol as written at its declaration
+    pub fn name(&self) -> String {
+        self.ent.designator().to_string()
+    }
+
+    /// The full path of the enclosing design unit or library, such as `work.pkg`
+    pub fn container(&self) -> Option<String> {
+        self.ent.parent.map(|parent| parent.path_name())
+    }
+
+    pub fn kind(&self) -> &'a AnyEntKind<'a> {
+        self.ent.kind()
+    }
+}
@@ -0,0 +1,1112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::data::{Position, Range, Source, SrcPos};
+use crate::syntax::{kind_str, Comment, Kind, Token};
+use crate::HasTokenSpan;
+use Kind::{
+    AbstractLiteral, All, Begin, BitString, Character, Circ, CommAt, Comma, Dot, Else, Elsif, End,
+    For, Identifier, LeftPar, LeftSquare, Minus, Plus, RightPar, RightSquare, SemiColon,
+    StringLiteral, Text, Tick, Use, When,
+};
+
+/// The case of reserved words in formatted code
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum KeywordCase {
+    #[default]
+    Lower,
+    Upper,
+}
+
+impl KeywordCase {
+    /// A reserved word written in this case
+    pub fn apply(&self, keyword: &str) -> String {
+        match self {
+            KeywordCase::Lower => keyword.to_ascii_lowercase(),
+            KeywordCase::Upper => keyword.to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Options that control the layout of formatted code
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct FormattingOptions {
+    /// The number of spaces of each indentation level
+    pub indent_width: usize,
+    pub keyword_case: KeywordCase,
+}
+
+impl Default for FormattingOptions {
+    fn default() -> Self {
+        FormattingOptions {
+            indent_width: 2,
+            keyword_case: KeywordCase::Lower,
+        }
+    }
+}
+
+/// Format a design file into canonical VHDL.
+///
+/// Each declaration and statement is placed on its own lines and indented by its nesting,
+/// the tokens of a line are separated by canonical spacing and reserved words are written in
+/// the configured case. Identifiers and literals are kept as written and comments are kept
+/// together with the tokens they belong to.
+///
+/// The design file is expected to be free of syntax errors,
+/// tokens that the parser dropped are not part of the output.
+pub fn format_design_file(
+    source: &Source,
+    design_file: &DesignFile,
+    options: &FormattingOptions,
+) -> String {
+    let mut formatter = Formatter::new(options);
+    formatter.design_units(&design_file.design_units);
+
+    // Comments after the last token are not attached to any token
+    let contents = source.contents();
+    let end = design_file
+        .design_units
+        .iter()
+        .rev()
+        .find_map(|(tokens, _)| tokens.last())
+        .map_or(contents.start(), |last| token_extent(last).1);
+    let text: String = (0..contents.num_lines())
+        .filter_map(|lineno| contents.get_line(lineno))
+        .collect();
+    formatter.trailing_text(&text[contents.position_to_byte_offset(end)..]);
+    formatter.newline();
+    formatter.output
+}
+
+/// Format the declarations and statements within a selection of a design file.
+///
+/// A selection that partially covers a declaration or statement is extended to all of it
+/// and a selection that spans several design units is extended to those design units.
+/// The formatted code is indented relative to the source indentation of the construct that
+/// encloses the selection. Comments are formatted together with the tokens they belong to.
+///
+/// Returns the extended selection together with the text that replaces it,
+/// or `None` when the selection does not contain any code.
+pub fn format_range(
+    design_file: &DesignFile,
+    range: &SrcPos,
+    options: &FormattingOptions,
+) -> Option<(SrcPos, String)> {
+    let (start, end) = (range.start(), range.end());
+    let overlaps = |token: &Token| {
+        let (first, last) = token_extent(token);
+        if start == end {
+            first <= start && start <= last
+        } else {
+            first < end && last > start
+        }
+    };
+    let units = &design_file.design_units;
+    let lo = units
+        .iter()
+        .position(|(tokens, _)| tokens.iter().any(overlaps))?;
+    let hi = units
+        .iter()
+        .rposition(|(tokens, _)| tokens.iter().any(overlaps))?;
+
+    let mut formatter = Formatter::new(options);
+    let (start, end) = if let [(tokens, unit)] = &units[lo..=hi] {
+        let first = tokens.iter().position(overlaps)?;
+        let last = tokens.iter().rposition(overlaps)?;
+        formatter.tokens = tokens;
+        let items = formatter.unit_items(unit);
+        let (selected, enclosing) = select(&items, first, last).unwrap_or((&items, None));
+        if let Some(enclosing) = enclosing {
+            formatter.margin = line_prefix(&range.source, tokens[enclosing].pos.start())
+                .chars()
+                .take_while(|chr| chr.is_whitespace())
+                .collect();
+            formatter.indent = 1;
+        }
+        let (first, last) = (selected[0].start(), selected[selected.len() - 1].end());
+        formatter.nested_items(first, last, selected, 0);
+        (
+            token_extent(&tokens[first]).0,
+            token_extent(&tokens[last]).1,
+        )
+    } else {
+        let units = &units[lo..=hi];
+        formatter.design_units(units);
+        (
+            units.first()?.0.first().map(token_extent)?.0,
+            units.last()?.0.last().map(token_extent)?.1,
+        )
+    };
+    formatter.newline();
+
+    // The code before the selection on its first line is kept as written
+    let at_line_start = line_prefix(&range.source, start)
+        .chars()
+        .all(char::is_whitespace);
+    let (start, text) = if at_line_start {
+        (Position::new(start.line, 0), formatter.output.trim_end())
+    } else {
+        (start, formatter.output.trim())
+    };
+    Some((
+        SrcPos::new(range.source.clone(), Range::new(start, end)),
+        text.to_owned(),
+    ))
+}
+
+/// The consecutive items that cover the tokens from `first` to `last`
+/// at the innermost level where the selection is not within a single item,
+/// together with the first token of the item that encloses them
+fn select(items: &[Item], first: usize, last: usize) -> Option<(&[Item], Option<usize>)> {
+    let lo = items.iter().position(|item| item.end() >= first)?;
+    let hi = items.iter().rposition(|item| item.start() <= last)?;
+    if lo > hi {
+        // The selection is between the items
+        return None;
+    }
+    if let (true, Item::Nested(start, _, children)) = (lo == hi, &items[lo]) {
+        let within_children = children.first().is_some_and(|child| child.start() <= first)
+            && children.last().is_some_and(|child| last <= child.end());
+        if within_children {
+            if let Some((selected, enclosing)) = select(children, first, last) {
+                return Some((selected, enclosing.or(Some(*start))));
+            }
+        }
+    }
+    Some((&items[lo..=hi], None))
+}
+
+/// A part of a construct that is placed on lines of its own
+enum Item {
+    /// Tokens that are formatted on a single line
+    Line(usize, usize),
+    /// Tokens where the nested items are placed on their own lines, one level deeper
+    Nested(usize, usize, Vec<Item>),
+    /// The tokens of a configuration declaration
+    Configuration(usize, usize),
+}
+
+impl Item {
+    fn start(&self) -> usize {
+        match self {
+            Item::Line(start, _) | Item::Nested(start, ..) | Item::Configuration(start, _) => {
+                *start
+            }
+        }
+    }
+
+    fn end(&self) -> usize {
+        match self {
+            Item::Line(_, end) | Item::Nested(_, end, _) | Item::Configuration(_, end) => *end,
+        }
+    }
+}
+
+fn span_of(item: &impl HasTokenSpan) -> (usize, usize) {
+    (item.get_start_token().idx(), item.get_end_token().idx())
+}
+
+fn is_reserved_word(kind: Kind) -> bool {
+    kind_str(kind)
+        .chars()
+        .all(|chr| chr.is_ascii_alphabetic() || chr == '_')
+}
+
+/// Tokens that end an operand, a following `+` or `-` is a binary operator
+fn ends_operand(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Identifier
+            | AbstractLiteral
+            | StringLiteral
+            | BitString
+            | Character
+            | RightPar
+            | RightSquare
+            | All
+    )
+}
+
+/// The start and end of a token together with its comments
+fn token_extent(token: &Token) -> (Position, Position) {
+    let comments = token.comments.as_ref();
+    let start = comments
+        .and_then(|comments| comments.leading.first())
+        .map_or(token.pos.start(), |comment| comment.range.start);
+    let end = comments
+        .and_then(|comments| comments.trailing.as_ref())
+        .map_or(token.pos.end(), |comment| comment.range.end);
+    (start, end)
+}
+
+/// The text of the line of a position before that position
+fn line_prefix(source: &Source, pos: Position) -> String {
+    let mut character = 0;
+    source
+        .contents()
+        .get_line(pos.line as usize)
+        .unwrap_or_default()
+        .chars()
+        .take_while(|chr| {
+            character += chr.len_utf16() as u32;
+            character <= pos.character
+        })
+        .collect()
+}
+
+/// The text of a token as written in the source
+fn source_text(pos: &SrcPos) -> String {
+    let contents = pos.source.contents();
+    let range = pos.range();
+    let mut character = 0;
+    contents
+        .get_line(range.start.line as usize)
+        .unwrap_or_default()
+        .chars()
+        .filter(|chr| {
+            let within = character >= range.start.character && character < range.end.character;
+            character += chr.len_utf16() as u32;
+            within
+        })
+        .collect()
+}
+
+struct Formatter<'a> {
+    options: &'a FormattingOptions,
+    tokens: &'a [Token],
+    output: String,
+    line: String,
+    /// The text that each line starts with before the indentation
+    margin: String,
+    indent: usize,
+    /// The current line continues the code of the previous line
+    continuation: bool,
+    /// The source line where the previously formatted token or comment ends
+    prev_line: Option<u32>,
+    /// The kinds of the two previously formatted tokens
+    prev_kinds: [Option<Kind>; 2],
+}
+
+impl<'a> Formatter<'a> {
+    fn new(options: &'a FormattingOptions) -> Self {
+        Formatter {
+            options,
+            tokens: &[],
+            output: String::new(),
+            line: String::new(),
+            margin: String::new(),
+            indent: 0,
+            continuation: false,
+            prev_line: None,
+            prev_kinds: [None, None],
+        }
+    }
+
+    fn design_units(&mut self, units: &'a [(Vec<Token>, AnyDesignUnit)]) {
+        for (tokens, unit) in units.iter() {
+            self.newline();
+            if !self.output.is_empty() {
+                // Design units are separated by exactly one blank line
+                self.output.push('\n');
+                self.prev_line = None;
+            }
+            self.tokens = tokens;
+            let items = self.unit_items(unit);
+            self.items(&items);
+        }
+    }
+
+    /// The items at the outermost level of a design unit
+    fn unit_items(&self, unit: &AnyDesignUnit) -> Vec<Item> {
+        let (context_clause, item) = match unit {
+            AnyDesignUnit::Primary(primary) => match primary {
+                AnyPrimaryUnit::Entity(entity) => {
+                    let mut items = self.interface_list(entity.generic_clause.as_deref());
+                    items.extend(self.interface_list(entity.port_clause.as_deref()));
+                    items.extend(self.declarations(&entity.decl));
+                    items.extend(self.concurrent_statements(&entity.statements));
+                    (&entity.context_clause, self.nested(entity, items))
+                }
+                AnyPrimaryUnit::Configuration(configuration) => {
+                    let (start, end) = span_of(configuration);
+                    (
+                        &configuration.context_clause,
+                        Item::Configuration(start, end),
+                    )
+                }
+                AnyPrimaryUnit::Package(package) => {
+                    let mut items = self.interface_list(package.generic_clause.as_deref());
+                    items.extend(self.declarations(&package.decl));
+                    (&package.context_clause, self.nested(package, items))
+                }
+                AnyPrimaryUnit::PackageInstance(instance) => {
+                    let items = self.map_aspect(instance.generic_map.as_ref());
+                    (&instance.context_clause, self.nested(instance, items))
+                }
+                AnyPrimaryUnit::Context(context) => {
+                    let items = self.lines(&context.items);
+                    return vec![self.nested(context, items)];
+                }
+            },
+            AnyDesignUnit::Secondary(secondary) => match secondary {
+                AnySecondaryUnit::Architecture(architecture) => {
+                    let mut items = self.declarations(&architecture.decl);
+                    items.extend(self.concurrent_statements(&architecture.statements));
+                    (
+                        &architecture.context_clause,
+                        self.nested(architecture, items),
+                    )
+                }
+                AnySecondaryUnit::PackageBody(body) => {
+                    let items = self.declarations(&body.decl);
+                    (&body.context_clause, self.nested(body, items))
+                }
+            },
+        };
+
+        let mut items = self.lines(context_clause);
+        items.push(item);
+        items
+    }
+
+    /// Format all tokens of the design unit with the items at the outermost level
+    fn items(&mut self, items: &[Item]) {
+        let end = self.tokens.len().saturating_sub(1);
+        self.nested_items(0, end, items, 0);
+    }
+
+    fn nested(&self, span: &impl HasTokenSpan, items: Vec<Item>) -> Item {
+        let (start, end) = span_of(span);
+        Item::Nested(start, end, items)
+    }
+
+    fn line(&self, span: &impl HasTokenSpan) -> Item {
+        let (start, end) = span_of(span);
+        Item::Line(start, end)
+    }
+
+    fn lines<T: HasTokenSpan>(&self, spans: &[T]) -> Vec<Item> {
+        spans.iter().map(|span| self.line(span)).collect()
+    }
+
+    /// The index of the first token of the given kind at or after `from`
+    fn find(&self, from: usize, kind: Kind) -> usize {
+        (from..self.tokens.len())
+            .find(|&idx| self.tokens[idx].kind == kind)
+            .unwrap_or(from)
+    }
+
+    /// The index of the last token of the given kind before `to`
+    fn rfind(&self, to: usize, kind: Kind) -> usize {
+        (0..to)
+            .rev()
+            .find(|&idx| self.tokens[idx].kind == kind)
+            .unwrap_or(to)
+    }
+
+    /// A generic or port clause with one interface declaration on each line
+    fn interface_list(&self, list: Option<&[InterfaceDeclaration]>) -> Vec<Item> {
+        let (Some(first), Some(last)) = (
+            list.and_then(|list| list.first()),
+            list.and_then(|list| list.last()),
+        ) else {
+            return Vec::new();
+        };
+        let (first, _) = span_of(first);
+        let (_, last) = span_of(last);
+        let start = self.rfind(first, LeftPar).saturating_sub(1);
+        vec![Item::Nested(
+            start,
+            self.find(last + 1, RightPar),
+            self.lines(list.unwrap_or_default()),
+        )]
+    }
+
+    /// A generic or port map aspect with one association on each line
+    fn map_aspect(&self, aspect: Option<&MapAspect>) -> Vec<Item> {
+        let Some(aspect) = aspect else {
+            return Vec::new();
+        };
+        let items = aspect
+            .list
+            .items
+            .iter()
+            .map(|assoc| {
+                let start = assoc
+                    .formal
+                    .as_ref()
+                    .map_or(assoc.actual.span, |formal| formal.span);
+                Item::Line(start.start_token.idx(), assoc.actual.span.end_token.idx())
+            })
+            .collect();
+        vec![Item::Nested(
+            aspect.start.idx(),
+            aspect.closing_paren.idx(),
+            items,
+        )]
+    }
+
+    /// A case alternative with the choices on the first line
+    fn alternative(&self, choices: &[WithTokenSpan<Choice>], items: Vec<Item>) -> Item {
+        let (first, _) = choices.first().map(span_of).unwrap_or_default();
+        let (_, last) = choices.last().map(span_of).unwrap_or_default();
+        let start = self.rfind(first, When);
+        let end = items.last().map_or(last + 1, Item::end);
+        Item::Nested(start, end, items)
+    }
+
+    fn declarations(&self, decls: &[WithTokenSpan<Declaration>]) -> Vec<Item> {
+        decls.iter().map(|decl| self.declaration(decl)).collect()
+    }
+
+    fn declaration(&self, decl: &WithTokenSpan<Declaration>) -> Item {
+        let items = match &decl.item {
+            Declaration::Type(typ) => match &typ.def {
+                TypeDefinition::Record(elements) => self.lines(elements),
+                TypeDefinition::Protected(protected) => protected
+                    .items
+                    .iter()
+                    .map(|ProtectedTypeDeclarativeItem::Subprogram(subprogram)| {
+                        self.line(subprogram)
+                    })
+                    .collect(),
+                TypeDefinition::ProtectedBody(body) => self.declarations(&body.decl),
+                TypeDefinition::Physical(physical) => std::iter::once(&physical.primary_unit)
+                    .chain(physical.secondary_units.iter().map(|(ident, _)| ident))
+                    .map(|ident| {
+                        let start = ident.tree.token.idx();
+                        Item::Line(start, self.find(start, SemiColon))
+                    })
+                    .collect(),
+                _ => return self.line(decl),
+            },
+            Declaration::Component(component) => {
+                let mut items = self.interface_list(Some(&component.generic_list));
+                items.extend(self.interface_list(Some(&component.port_list)));
+                items
+            }
+            Declaration::SubprogramBody(body) => {
+                let mut items = self.declarations(&body.declarations);
+                items.extend(self.sequential_statements(&body.statements));
+                items
+            }
+            Declaration::Package(instance) => self.map_aspect(instance.generic_map.as_ref()),
+            Declaration::PackageDeclaration(package) => {
+                let mut items = self.interface_list(package.generic_clause.as_deref());
+                items.extend(self.declarations(&package.decl));
+                items
+            }
+            Declaration::PackageBody(body) => self.declarations(&body.decl),
+            Declaration::View(view) => self.lines(&view.elements),
+            _ => return self.line(decl),
+        };
+        self.nested(decl, items)
+    }
+
+    fn concurrent_statements(&self, statements: &[LabeledConcurrentStatement]) -> Vec<Item> {
+        statements
+            .iter()
+            .map(|statement| self.concurrent_statement(statement))
+            .collect()
+    }
+
+    fn concurrent_statement(&self, statement: &LabeledConcurrentStatement) -> Item {
+        let items = match &statement.statement.item {
+            ConcurrentStatement::Process(process) => {
+                let mut items = self.declarations(&process.decl);
+                items.extend(self.sequential_statements(&process.statements));
+                items
+            }
+            ConcurrentStatement::Block(block) => {
+                let header = &block.header;
+                let mut items = self.interface_list(header.generic_clause.as_deref());
+                items.extend(self.map_aspect(header.generic_map.as_ref()));
+                items.extend(self.interface_list(header.port_clause.as_deref()));
+                items.extend(self.map_aspect(header.port_map.as_ref()));
+                items.extend(self.declarations(&block.decl));
+                items.extend(self.concurrent_statements(&block.statements));
+                items
+            }
+            ConcurrentStatement::Instance(instance) => {
+                let mut items = self.map_aspect(instance.generic_map.as_ref());
+                items.extend(self.map_aspect(instance.port_map.as_ref()));
+                items
+            }
+            ConcurrentStatement::ForGenerate(generate) => self.generate_body(&generate.body),
+            ConcurrentStatement::IfGenerate(generate) => generate
+                .conds
+                .conditionals
+                .iter()
+                .map(|cond| &cond.item)
+                .chain(generate.conds.else_item.as_ref())
+                .flat_map(|body| self.generate_body(body))
+                .collect(),
+            ConcurrentStatement::CaseGenerate(generate) => generate
+                .sels
+                .alternatives
+                .iter()
+                .map(|alternative| {
+                    self.alternative(&alternative.choices, self.generate_body(&alternative.item))
+                })
+                .collect(),
+            ConcurrentStatement::ProcedureCall(_)
+            | ConcurrentStatement::Assert(_)
+            | ConcurrentStatement::Assignment(_) => return self.line(statement),
+        };
+        self.nested(statement, items)
+    }
+
+    fn generate_body(&self, body: &GenerateBody) -> Vec<Item> {
+        let mut items = body
+            .decl
+            .as_ref()
+            .map(|decl| self.declarations(decl))
+            .unwrap_or_default();
+        items.extend(self.concurrent_statements(&body.statements));
+        items
+    }
+
+    fn sequential_statements(&self, statements: &[LabeledSequentialStatement]) -> Vec<Item> {
+        statements
+            .iter()
+            .map(|statement| self.sequential_statement(statement))
+            .collect()
+    }
+
+    fn sequential_statement(&self, statement: &LabeledSequentialStatement) -> Item {
+        let items = match &statement.statement.item {
+            SequentialStatement::If(ifstmt) => ifstmt
+                .conds
+                .conditionals
+                .iter()
+                .map(|cond| &cond.item)
+                .chain(ifstmt.conds.else_item.as_ref())
+                .flat_map(|statements| self.sequential_statements(statements))
+                .collect(),
+            SequentialStatement::Case(case) => case
+                .alternatives
+                .iter()
+                .map(|alternative| {
+                    self.alternative(
+                        &alternative.choices,
+                        self.sequential_statements(&alternative.item),
+                    )
+                })
+                .collect(),
+            SequentialStatement::Loop(lstmt) => self.sequential_statements(&lstmt.statements),
+            _ => return self.line(statement),
+        };
+        self.nested(statement, items)
+    }
+
+    fn item(&mut self, item: &Item) {
+        self.newline();
+        match *item {
+            Item::Line(start, end) => {
+                for idx in start..=end {
+                    self.token(idx);
+                }
+            }
+            Item::Nested(start, end, ref items) => self.nested_items(start, end, items, 1),
+            Item::Configuration(start, end) => self.configuration(start, end),
+        }
+    }
+
+    /// Format the tokens from `start` to `end` where the items are placed on lines of their own.
+    /// The tokens between the items start a new line as well as each `begin`, `else`, `elsif` and `end`
+    fn nested_items(&mut self, start: usize, end: usize, items: &[Item], indent: usize) {
+        let mut idx = start;
+        for item in items {
+            if item.start() < idx {
+                // Several declarations of an identifier list share their tokens
+                continue;
+            }
+            self.between_items(idx, item.start());
+            self.indent += indent;
+            self.item(item);
+            self.indent -= indent;
+            idx = item.end() + 1;
+        }
+        self.between_items(idx, end + 1);
+    }
+
+    fn between_items(&mut self, start: usize, end: usize) {
+        for idx in start..end {
+            let kind = self.tokens[idx].kind;
+            if (idx == start && !matches!(kind, SemiColon | Comma))
+                || matches!(kind, Begin | Else | Elsif | End)
+            {
+                self.newline();
+            }
+            self.token(idx);
+        }
+    }
+
+    /// Configurations nest block and component configurations that start with `for`
+    fn configuration(&mut self, start: usize, end: usize) {
+        let indent = self.indent;
+        self.token(start);
+        self.indent += 1;
+        for idx in start + 1..=end {
+            match self.tokens[idx].kind {
+                For if self.prev_kinds[0] != Some(End) => {
+                    self.newline();
+                    self.token(idx);
+                    self.indent += 1;
+                }
+                End => {
+                    self.indent = self.indent.saturating_sub(1);
+                    self.newline();
+                    self.token(idx);
+                }
+                Use => {
+                    self.newline();
+                    self.token(idx);
+                }
+                _ => self.token(idx),
+            }
+        }
+        self.indent = indent;
+    }
+
+    fn token(&mut self, idx: usize) {
+        let token = &self.tokens[idx];
+
+        if let Some(comments) = &token.comments {
+            for comment in comments.leading.iter() {
+                if !self.line.is_empty() {
+                    self.flush();
+                    self.continuation = true;
+                }
+                self.comment(comment);
+                self.flush();
+            }
+        }
+
+        if self.line.is_empty() {
+            self.begin_line(token.pos.start().line);
+        } else if self.needs_space(token.kind) {
+            self.line.push(' ');
+        }
+        let text = self.text(token);
+        self.line.push_str(&text);
+        self.prev_line = Some(token.pos.end().line);
+        self.prev_kinds = [Some(token.kind), self.prev_kinds[0]];
+
+        if let Some(comment) = token
+            .comments
+            .as_ref()
+            .and_then(|comments| comments.trailing.as_ref())
+        {
+            self.line.push(' ');
+            self.comment(comment);
+            self.flush();
+            self.continuation = true;
+        }
+    }
+
+    fn comment(&mut self, comment: &Comment) {
+        self.begin_line(comment.range.start.line);
+        if comment.multi_line {
+            self.line.push_str(&format!("/*{}*/", comment.value));
+        } else {
+            self.line.push_str(&format!("--{}", comment.value));
+        }
+        self.prev_line = Some(comment.range.end.line);
+    }
+
+    fn text(&self, token: &Token) -> String {
+        if is_reserved_word(token.kind) {
+            self.options.keyword_case.apply(kind_str(token.kind))
+        } else {
+            match token.kind {
+                Identifier | AbstractLiteral | StringLiteral | BitString | Character | Text => {
+                    source_text(&token.pos)
+                }
+                _ => kind_str(token.kind).to_owned(),
+            }
+        }
+    }
+
+    fn needs_space(&self, kind: Kind) -> bool {
+        let [Some(prev), prev_prev] = self.prev_kinds else {
+            return false;
+        };
+        match (prev, kind) {
+            (_, SemiColon | Comma | RightPar | RightSquare | Tick) => false,
+            (LeftPar | LeftSquare | Tick | Dot | Circ | CommAt, _) => false,
+            // No space after a sign
+            (Plus | Minus, _) => prev_prev.is_some_and(ends_operand),
+            (Identifier | StringLiteral | RightPar | RightSquare, LeftPar) => false,
+            (Identifier | RightPar | All, Dot) => false,
+            _ => true,
+        }
+    }
+
+    /// Start a new line at the source line of the next token or comment.
+    /// A blank line in the source is kept as one blank line
+    fn begin_line(&mut self, source_line: u32) {
+        if !self.line.is_empty() {
+            return;
+        }
+        if !self.continuation
+            && !self.output.is_empty()
+            && self.prev_line.is_some_and(|prev| source_line > prev + 1)
+        {
+            self.output.push('\n');
+        }
+        let depth = self.indent + usize::from(self.continuation);
+        self.line.push_str(&self.margin);
+        self.line
+            .push_str(&" ".repeat(depth * self.options.indent_width));
+    }
+
+    fn flush(&mut self) {
+        if !self.line.is_empty() {
+            self.output.push_str(self.line.trim_end());
+            self.output.push('\n');
+            self.line.clear();
+        }
+    }
+
+    fn newline(&mut self) {
+        self.flush();
+        self.continuation = false;
+    }
+
+    /// Text after the last token, which can only contain comments
+    fn trailing_text(&mut self, text: &str) {
+        let mut blank = false;
+        for (lineno, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                blank = lineno > 0;
+            } else {
+                self.newline();
+                if blank && !self.output.is_empty() {
+                    self.output.push('\n');
+                }
+                self.line.push_str(line);
+                blank = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::{check_no_diagnostics, Code};
+    use crate::syntax::VHDLParser;
+    use crate::VHDLStandard;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    fn format(code: &str, options: &FormattingOptions) -> String {
+        let source = Source::inline(Path::new("file.vhd"), code);
+        let mut diagnostics = Vec::new();
+        let design_file =
+            VHDLParser::new(VHDLStandard::default()).parse_design_source(&source, &mut diagnostics);
+        check_no_diagnostics(&diagnostics);
+        format_design_file(&source, &design_file, options)
+    }
+
+    #[test]
+    fn formats_entity_and_architecture() {
+        let code = "
+library ieee;use ieee.std_logic_1164.all;
+entity ent is generic(width:natural:=8);port(clk:in std_logic;
+q : out std_logic_vector(width-1 downto 0));end entity;
+architecture rtl of ent is signal cnt:integer:=-1; begin
+proc: process(clk) begin if rising_edge(clk) then case cnt is when 0|1 => cnt<=cnt+1;
+when others=>null; end case; end if; end process;
+inst: entity work.other generic map(width=>width) port map(clk=>clk,q=>open);
+end architecture rtl;
+";
+
+        assert_eq!(
+            format(code, &FormattingOptions::default()),
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+entity ent is
+  generic (
+    width : natural := 8
+  );
+  port (
+    clk : in std_logic;
+    q : out std_logic_vector(width - 1 downto 0)
+  );
+end entity;
+
+architecture rtl of ent is
+  signal cnt : integer := -1;
+begin
+  proc : process (clk)
+  begin
+    if rising_edge(clk) then
+      case cnt is
+        when 0 | 1 =>
+          cnt <= cnt + 1;
+        when others =>
+          null;
+      end case;
+    end if;
+  end process;
+  inst : entity work.other
+    generic map (
+      width => width
+    )
+    port map (
+      clk => clk,
+      q => open
+    );
+end architecture rtl;
+"
+        );
+    }
+
+    #[test]
+    fn formats_keywords_in_configured_case_and_indent() {
+        let code = "
+package Pkg is
+  type Rec is record Field : Boolean; end record;
+  function F(X : Integer) return Integer;
+end package;
+
+package body Pkg is
+  function F(X : Integer) return Integer is
+  begin
+    for I in 0 to 3 loop
+      next when I = X;
+    end loop;
+    return X'Length;
+  end function;
+end package body;
+";
+
+        let options = FormattingOptions {
+            indent_width: 4,
+            keyword_case: KeywordCase::Upper,
+        };
+        assert_eq!(
+            format(code, &options),
+            "\
+PACKAGE Pkg IS
+    TYPE Rec IS RECORD
+        Field : Boolean;
+    END RECORD;
+    FUNCTION F(X : Integer) RETURN Integer;
+END PACKAGE;
+
+PACKAGE BODY Pkg IS
+    FUNCTION F(X : Integer) RETURN Integer IS
+    BEGIN
+        FOR I IN 0 TO 3 LOOP
+            NEXT WHEN I = X;
+        END LOOP;
+        RETURN X'Length;
+    END FUNCTION;
+END PACKAGE BODY;
+"
+        );
+    }
+
+    #[test]
+    fn preserves_comments_and_blank_lines() {
+        let code = "
+-- The package
+package pkg is -- a trailing comment
+  constant a : natural := 1;
+
+
+  -- A leading comment
+  constant b : natural := -- the value
+                          2;
+  /* multi
+     line */
+  constant c : natural := 3;
+end package;
+-- The end
+";
+
+        assert_eq!(
+            format(code, &FormattingOptions::default()),
+            "\
+-- The package
+package pkg is -- a trailing comment
+  constant a : natural := 1;
+
+  -- A leading comment
+  constant b : natural := -- the value
+    2;
+  /* multi
+     line */
+  constant c : natural := 3;
+end package;
+-- The end
+"
+        );
+    }
+
+    /// Format the selection and apply the result to the code
+    fn format_selection(code: &Code, selection: &Code) -> String {
+        let (pos, text) = format_range(
+            &code.design_file(),
+            &selection.pos(),
+            &FormattingOptions::default(),
+        )
+        .unwrap();
+        let contents = code.source().contents();
+        let whole: String = (0..contents.num_lines())
+            .filter_map(|lineno| contents.get_line(lineno))
+            .collect();
+        let start = contents.position_to_byte_offset(pos.start());
+        let end = contents.position_to_byte_offset(pos.end());
+        format!("{}{text}{}", &whole[..start], &whole[end..])
+    }
+
+    #[test]
+    fn range_formatting_snaps_to_statements_and_keeps_enclosing_indentation() {
+        let code = Code::new(
+            "\
+entity ent is end entity;
+architecture a of ent is
+begin
+      process
+      begin
+  x<=1;
+                y   <=   2 ;
+        wait ;
+      end process;
+end architecture;
+",
+        );
+
+        assert_eq!(
+            format_selection(&code, &code.between("<=1", "y   <=")),
+            "\
+entity ent is end entity;
+architecture a of ent is
+begin
+      process
+      begin
+        x <= 1;
+        y <= 2;
+        wait ;
+      end process;
+end architecture;
+"
+        );
+
+        // A selection of tokens between the statements snaps to the enclosing statement
+        assert_eq!(
+            format_selection(&code, &code.s("begin", 2)),
+            "\
+entity ent is end entity;
+architecture a of ent is
+begin
+  process
+  begin
+    x <= 1;
+    y <= 2;
+    wait;
+  end process;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn range_formatting_keeps_comments_at_the_selection_boundary() {
+        let code = Code::new(
+            "\
+package pkg is
+  -- before a
+  constant a:natural:=1; -- after a
+  -- before b
+  constant   b : natural := 2;
+end package;
+",
+        );
+
+        assert_eq!(
+            format_selection(&code, &code.between("a:", "1")),
+            "\
+package pkg is
+  -- before a
+  constant a : natural := 1; -- after a
+  -- before b
+  constant   b : natural := 2;
+end package;
+"
+        );
+    }
+
+    #[test]
+    fn range_formatting_across_design_units_formats_the_units() {
+        let code = Code::new(
+            "\
+entity ent is end entity;
+
+architecture a of ent is
+begin
+end   architecture;
+package pkg is end package;
+",
+        );
+
+        assert_eq!(
+            format_selection(&code, &code.between("end   architecture", "pkg")),
+            "\
+entity ent is end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+package pkg is
+end package;
+"
+        );
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let code = "
+context ctx is library ieee; use ieee.numeric_std.all; end context;
+entity ent is end entity;
+architecture a of ent is
+  component comp is port(x : in bit); end component;
+  type state_t is (idle, busy); -- states
+  function f(x : integer) return integer is begin return -x; end function;
+begin
+  gen: for i in 0 to 1 generate
+    signal s : bit;
+  begin
+    u: comp port map(x => s);
+  end generate;
+  blk: block is begin
+    assert true report \"ok\" severity note;
+  end block;
+  g2: if true generate s2 <= '1'; else generate end generate;
+end architecture;
+configuration cfg of ent is
+  for a
+    for gen(0)
+      for u : comp use entity work.ent; end for;
+    end for;
+  end for;
+end configuration;
+";
+
+        let options = FormattingOptions::default();
+        let formatted = format(code, &options);
+        assert_eq!(format(&formatted, &options), formatted);
+    }
+}
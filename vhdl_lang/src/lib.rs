@@ -15,6 +15,8 @@ pub mod ast;
 mod analysis;
 mod config;
 mod data;
+mod facade;
+mod formatting;
 mod lint;
 mod named_entity;
 mod project;
@@ -24,17 +26,24 @@ mod completion;
 mod standard;
 
 pub use crate::config::Config;
+pub use crate::data::error_codes::ErrorCode;
 pub use crate::data::{
-    Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
-    NullDiagnostics, NullMessages, Position, Range, Severity, SeverityMap, Source, SrcPos,
+    Diagnostic, DiagnosticFormat, DiagnosticReport, FileDiagnostics, Fix, Latin1String, Message,
+    MessageHandler, MessagePrinter, MessageType, NullDiagnostics, NullMessages, Position, Range,
+    RelatedLocation, ReportedDiagnostic, Severity, SeverityMap, Source, SrcPos,
 };
 
-pub use crate::analysis::EntHierarchy;
+pub use crate::analysis::{
+    AssociatedPort, DesignInterface, DesignInterfaceKind, EntHierarchy, GenericValue, InlayHint,
+    InlayHintKind, Instance, InstanceTree, InterfaceElement, SemanticToken, SemanticTokenKind,
+};
 pub use crate::named_entity::{
     AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, HasEntityId, InterfaceEnt, Object,
-    Overloaded, Reference, Related, Sequential, Type,
+    Overloaded, Reference, Related, Sequential, StaticValue, Type,
 };
 
+pub use crate::facade::{DocumentSymbol, LanguageServerFacade, TextEdit, WorkspaceSymbol};
+pub use crate::formatting::{format_design_file, format_range, FormattingOptions, KeywordCase};
 pub use crate::project::{Project, SourceFile};
 pub use crate::syntax::{
     kind_str, HasTokenSpan, ParserResult, Token, TokenAccess, TokenId, TokenSpan, VHDLParser,
@@ -4,4 +4,11 @@
 //
 // Copyright (c) 2022, Olof Kraigher olof.kraigher@gmail.com
 
+pub mod component_entity;
 pub mod dead_code;
+pub mod infinite_recursion;
+pub mod multiple_drivers;
+pub mod read_after_assignment;
+pub mod sensitivity_list;
+pub mod unused_port;
+pub mod unused_use_clause;
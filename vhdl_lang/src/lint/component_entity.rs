@@ -0,0 +1,350 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::default_entity;
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::ast::search::FoundDeclaration;
+use crate::ast::search::Search;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::syntax::TokenAccess;
+use crate::AnyEntKind;
+use crate::Config;
+use crate::Design;
+use crate::Diagnostic;
+use crate::EntRef;
+use crate::InterfaceEnt;
+use crate::Type;
+
+#[derive(Default)]
+struct ComponentSearcher {
+    components: Vec<crate::EntityId>,
+}
+
+impl Searcher for ComponentSearcher {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::Component(component) = decl {
+            if let Some(id) = component.ident.decl.get() {
+                self.components.push(id);
+            }
+        }
+        SearchState::NotFinished
+    }
+}
+
+/// Generic types of the entity and the component are different declarations
+/// and are considered equal when they have the same name
+fn same_type(comp_iface: &InterfaceEnt, ent_iface: &InterfaceEnt) -> bool {
+    let comp_type = comp_iface.base_type();
+    let ent_type = ent_iface.base_type();
+    match (comp_type.kind(), ent_type.kind()) {
+        (Type::Interface, Type::Interface) => comp_type.designator() == ent_type.designator(),
+        _ => comp_iface.base() == ent_iface.base(),
+    }
+}
+
+fn check_interfaces(
+    kind: &str,
+    component: EntRef,
+    comp_ifaces: &[InterfaceEnt],
+    entity: EntRef,
+    ent_ifaces: &[InterfaceEnt],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for comp_iface in comp_ifaces {
+        let Some(comp_pos) = comp_iface.decl_pos() else {
+            continue;
+        };
+
+        match ent_ifaces
+            .iter()
+            .find(|ent_iface| ent_iface.designator() == comp_iface.designator())
+        {
+            Some(ent_iface) => {
+                if !same_type(comp_iface, ent_iface) {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            comp_pos,
+                            format!(
+                                "{kind} '{}' of component '{}' has {} but entity '{}' declares {}",
+                                comp_iface.designator(),
+                                component.designator(),
+                                comp_iface.type_mark().describe(),
+                                entity.designator(),
+                                ent_iface.type_mark().describe(),
+                            ),
+                            ErrorCode::ComponentEntityMismatch,
+                        )
+                        .opt_related(ent_iface.decl_pos(), "Defined here"),
+                    );
+                }
+            }
+            None => diagnostics.push(
+                Diagnostic::new(
+                    comp_pos,
+                    format!(
+                        "{kind} '{}' of component '{}' is not declared by entity '{}'",
+                        comp_iface.designator(),
+                        component.designator(),
+                        entity.designator(),
+                    ),
+                    ErrorCode::ComponentEntityMismatch,
+                )
+                .opt_related(entity.decl_pos(), "Defined here"),
+            ),
+        }
+    }
+
+    // Interfaces with defaults and outputs may be left unassociated
+    for ent_iface in ent_ifaces {
+        if ent_iface.has_default() || ent_iface.is_out_or_inout() {
+            continue;
+        }
+        if comp_ifaces
+            .iter()
+            .any(|comp_iface| comp_iface.designator() == ent_iface.designator())
+        {
+            continue;
+        }
+        let Some(comp_pos) = component.decl_pos() else {
+            continue;
+        };
+        diagnostics.push(
+            Diagnostic::new(
+                comp_pos,
+                format!(
+                    "Component '{}' is missing {} '{}' of entity '{}'",
+                    component.designator(),
+                    kind.to_ascii_lowercase(),
+                    ent_iface.designator(),
+                    entity.designator(),
+                ),
+                ErrorCode::ComponentEntityMismatch,
+            )
+            .opt_related(ent_iface.decl_pos(), "Defined here"),
+        );
+    }
+}
+
+/// Compare the generics and ports of the components declared within the library
+/// with those of the entities they are bound to by default
+pub(crate) fn find_component_entity_mismatches(
+    root: &DesignRoot,
+    library: &Library,
+) -> Vec<Diagnostic> {
+    let mut searcher = ComponentSearcher::default();
+    for unit in library.units() {
+        let _ = unit
+            .unit
+            .expect_analyzed()
+            .search(&unit.tokens, &mut searcher);
+    }
+
+    let mut diagnostics = Vec::new();
+    for id in searcher.components {
+        let component = root.get_ent(id);
+        let AnyEntKind::Component(comp_region) = component.kind() else {
+            continue;
+        };
+        let Some(entity) = default_entity(root, library, component) else {
+            continue;
+        };
+        let AnyEntKind::Design(Design::Entity(_, ent_region)) = entity.kind() else {
+            continue;
+        };
+
+        let (comp_ports, comp_generics) = comp_region.ports_and_generics();
+        let (ent_ports, ent_generics) = ent_region.ports_and_generics();
+        check_interfaces(
+            "Generic",
+            component,
+            &comp_generics,
+            entity,
+            &ent_generics,
+            &mut diagnostics,
+        );
+        check_interfaces(
+            "Port",
+            component,
+            &comp_ports,
+            entity,
+            &ent_ports,
+            &mut diagnostics,
+        );
+    }
+    diagnostics
+}
+
+/// Opt-in check that components match the entities they are bound to by default
+#[derive(Default)]
+pub(crate) struct ComponentEntityLinter;
+
+impl ComponentEntityLinter {
+    pub fn lint(
+        &self,
+        root: &DesignRoot,
+        config: &Config,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        // A changed entity does not cause the units declaring its components to be
+        // re-analyzed so all libraries are checked every time
+        for library in root.libraries() {
+            if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+                if library_config.is_third_party {
+                    continue;
+                }
+            }
+            diagnostics.append(find_component_entity_mismatches(root, library));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+
+    #[test]
+    fn component_port_type_differs_from_entity() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  generic (width : natural);
+  port (
+    clk : in bit;
+    data : in bit_vector(width - 1 downto 0);
+    valid : out bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+    generic (width : natural);
+    port (
+      clk : in bit;
+      data : in integer;
+      valid : out bit);
+  end component;
+begin
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        check_diagnostics(
+            find_component_entity_mismatches(&root, lib),
+            vec![Diagnostic::new(
+                code.s("data", 2),
+                "Port 'data' of component 'ent' has integer type 'INTEGER' but entity 'ent' declares array type 'BIT_VECTOR'",
+                ErrorCode::ComponentEntityMismatch,
+            )
+            .related(code.s1("data"), "Defined here")],
+        );
+    }
+
+    #[test]
+    fn component_interfaces_missing_in_entity_or_component() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  generic (width : natural; depth : natural := 4);
+  port (
+    clk : in bit;
+    valid : out bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+    port (
+      clock : in bit);
+  end component;
+begin
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        check_diagnostics(
+            find_component_entity_mismatches(&root, lib),
+            vec![
+                Diagnostic::new(
+                    code.sa("component ", "ent"),
+                    "Component 'ent' is missing generic 'width' of entity 'ent'",
+                    ErrorCode::ComponentEntityMismatch,
+                )
+                .related(code.s1("width"), "Defined here"),
+                Diagnostic::new(
+                    code.s1("clock"),
+                    "Port 'clock' of component 'ent' is not declared by entity 'ent'",
+                    ErrorCode::ComponentEntityMismatch,
+                )
+                .related(code.sa("entity ", "ent"), "Defined here"),
+                Diagnostic::new(
+                    code.sa("component ", "ent"),
+                    "Component 'ent' is missing port 'clk' of entity 'ent'",
+                    ErrorCode::ComponentEntityMismatch,
+                )
+                .related(code.s1("clk"), "Defined here"),
+            ],
+        );
+    }
+
+    #[test]
+    fn matching_component_and_component_without_entity() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+  generic (type data_t);
+  port (
+    clk : in bit;
+    data : in data_t);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+    generic (type data_t);
+    port (
+      clk : in bit;
+      data : in data_t);
+  end component;
+
+  component other is
+    port (clk : in bit);
+  end component;
+begin
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        check_no_diagnostics(&find_component_entity_mismatches(&root, lib));
+    }
+}
@@ -14,6 +14,7 @@ use crate::ast::UnitId;
 use crate::data::error_codes::ErrorCode;
 use crate::data::DiagnosticHandler;
 use crate::data::Symbol;
+use crate::lint::unused_port::is_entity_interface;
 use crate::named_entity::{HasEntityId, Reference, Related};
 use crate::syntax::TokenAccess;
 use crate::AnyEntKind;
@@ -163,7 +164,7 @@ fn can_be_locally_unused(ent: EntRef) -> bool {
 }
 
 /// Find *local* unused declarations
-fn find_unused_declarations<'a>(
+pub(super) fn find_unused_declarations<'a>(
     root: &'a DesignRoot,
     lib: &Library,
     primary_unit_name: &Symbol,
@@ -200,6 +201,8 @@ fn find_unused_declarations<'a>(
 pub(crate) struct UnusedDeclarationsLinter {
     // library name, primary name
     diagnostics: FnvHashMap<(Symbol, Symbol), Vec<Diagnostic>>,
+    /// Ports and generics of entities are reported by the unused port lint instead
+    pub(crate) ignore_entity_interfaces: bool,
 }
 
 impl UnusedDeclarationsLinter {
@@ -226,6 +229,7 @@ impl UnusedDeclarationsLinter {
             false
         });
 
+        let ignore_entity_interfaces = self.ignore_entity_interfaces;
         for unit in analyzed_units {
             let key = (unit.library_name().clone(), unit.primary_name().clone());
 
@@ -233,6 +237,7 @@ impl UnusedDeclarationsLinter {
                 self.diagnostics.entry(key).or_insert_with(|| {
                     find_unused_declarations(root, library, unit.primary_name())
                         .into_iter()
+                        .filter(|ent| !(ignore_entity_interfaces && is_entity_interface(ent)))
                         .filter_map(|ent| {
                             Some(Diagnostic::new(
                                 ent.decl_pos()?,
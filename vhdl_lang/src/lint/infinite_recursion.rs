@@ -0,0 +1,332 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::ast::search::FoundDeclaration;
+use crate::ast::search::Search;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::named_entity::Reference;
+use crate::syntax::TokenAccess;
+use crate::Config;
+use crate::Diagnostic;
+use crate::EntityId;
+use crate::SrcPos;
+use crate::TokenSpan;
+
+/// Finds calls of a subprogram to itself that are executed every time the subprogram is called
+struct SelfCalls<'t> {
+    ctx: &'t dyn TokenAccess,
+    // The subprogram body and its declaration
+    ids: [EntityId; 2],
+}
+
+impl SelfCalls<'_> {
+    fn is_self(&self, reference: &Reference) -> bool {
+        reference.get().is_some_and(|id| self.ids.contains(&id))
+    }
+
+    /// The first unconditional call among the statements.
+    /// Any statement that could branch or return ends the search
+    fn statements(&self, statements: &[LabeledSequentialStatement]) -> Option<SrcPos> {
+        for statement in statements {
+            match statement.statement.item {
+                SequentialStatement::ProcedureCall(ref pcall) => {
+                    if let Some(pos) = self.call(&pcall.item) {
+                        return Some(pos);
+                    }
+                }
+                SequentialStatement::VariableAssignment(ref assignment) => {
+                    if let AssignmentRightHand::Simple(ref expr) = assignment.rhs {
+                        if let Some(pos) = self.expr(expr.span, &expr.item) {
+                            return Some(pos);
+                        }
+                    }
+                }
+                SequentialStatement::Assert(ref assert) => {
+                    if let Some(pos) = self.expr(assert.condition.span, &assert.condition.item) {
+                        return Some(pos);
+                    }
+                }
+                SequentialStatement::Return(ref ret) => {
+                    return ret
+                        .expression
+                        .as_ref()
+                        .and_then(|expr| self.expr(expr.span, &expr.item));
+                }
+                SequentialStatement::Report(_)
+                | SequentialStatement::SignalAssignment(_)
+                | SequentialStatement::SignalForceAssignment(_)
+                | SequentialStatement::SignalReleaseAssignment(_)
+                | SequentialStatement::Null => {}
+                SequentialStatement::Wait(_)
+                | SequentialStatement::If(_)
+                | SequentialStatement::Case(_)
+                | SequentialStatement::Loop(_)
+                | SequentialStatement::Next(_)
+                | SequentialStatement::Exit(_) => return None,
+            }
+        }
+        None
+    }
+
+    fn expr(&self, span: TokenSpan, expr: &Expression) -> Option<SrcPos> {
+        match expr {
+            Expression::Binary(ref op, ref left, ref right) => {
+                if self.is_self(&op.item.reference) {
+                    return Some(op.pos(self.ctx).clone());
+                }
+                // The right operand of a short-circuit operator is not always evaluated
+                self.expr(left.span, &left.item).or_else(|| {
+                    if matches!(
+                        op.item.item,
+                        Operator::And | Operator::Or | Operator::Nand | Operator::Nor
+                    ) {
+                        None
+                    } else {
+                        self.expr(right.span, &right.item)
+                    }
+                })
+            }
+            Expression::Unary(ref op, ref operand) => {
+                if self.is_self(&op.item.reference) {
+                    Some(op.pos(self.ctx).clone())
+                } else {
+                    self.expr(operand.span, &operand.item)
+                }
+            }
+            Expression::Aggregate(ref assocs) => assocs.iter().find_map(|assoc| match assoc {
+                ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr) => {
+                    self.expr(expr.span, &expr.item)
+                }
+            }),
+            Expression::Qualified(ref qexpr) => self.expr(qexpr.expr.span, &qexpr.expr.item),
+            Expression::Name(ref name) => self.name(span, name),
+            // Only the first condition and the selector are always evaluated
+            Expression::Conditional(ref conditionals) => conditionals
+                .conditionals
+                .first()
+                .and_then(|cond| self.expr(cond.condition.span, &cond.condition.item)),
+            Expression::Selected(ref selection) => {
+                self.expr(selection.expression.span, &selection.expression.item)
+            }
+            Expression::Literal(_) | Expression::New(_) => None,
+        }
+    }
+
+    fn name(&self, span: TokenSpan, name: &Name) -> Option<SrcPos> {
+        match name {
+            Name::Designator(designator) => {
+                if self.is_self(&designator.reference) {
+                    Some(span.pos(self.ctx))
+                } else {
+                    None
+                }
+            }
+            Name::Selected(prefix, suffix) => {
+                if self.is_self(&suffix.item.reference) {
+                    Some(span.pos(self.ctx))
+                } else {
+                    self.name(prefix.span, &prefix.item)
+                }
+            }
+            Name::CallOrIndexed(call) => self.call(call),
+            Name::Slice(prefix, _) => self.name(prefix.span, &prefix.item),
+            Name::Attribute(attr) => self.name(attr.name.span, &attr.name.item),
+            Name::SelectedAll(_) | Name::External(_) => None,
+        }
+    }
+
+    fn call(&self, call: &CallOrIndexed) -> Option<SrcPos> {
+        self.name(call.name.span, &call.name.item).or_else(|| {
+            call.parameters
+                .iter()
+                .find_map(|assoc| match assoc.actual.item {
+                    ActualPart::Expression(ref expr) => self.expr(assoc.actual.span, expr),
+                    ActualPart::Open => None,
+                })
+        })
+    }
+}
+
+/// Search for subprogram bodies that call themselves unconditionally
+struct FindInfiniteRecursion<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl FindInfiniteRecursion<'_> {
+    fn check_body(&mut self, ctx: &dyn TokenAccess, body: &SubprogramBody) {
+        let (kind, reference) = match body.specification {
+            SubprogramSpecification::Procedure(ref procedure) => {
+                ("Procedure", &procedure.designator.decl)
+            }
+            SubprogramSpecification::Function(ref function) => {
+                ("Function", &function.designator.decl)
+            }
+        };
+        let Some(id) = reference.get() else {
+            return;
+        };
+        let ent = self.root.get_ent(id);
+        let calls = SelfCalls {
+            ctx,
+            ids: [ent.id(), ent.declaration().id()],
+        };
+
+        if let Some(pos) = calls.statements(&body.statements) {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    pos,
+                    format!(
+                        "{kind} '{}' calls itself unconditionally, the recursion never ends",
+                        ent.designator()
+                    ),
+                    ErrorCode::InfiniteRecursion,
+                )
+                .opt_related(ent.decl_pos(), "Defined here"),
+            );
+        }
+    }
+}
+
+impl Searcher for FindInfiniteRecursion<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::Subprogram(body) = decl {
+            self.check_body(ctx, body);
+        }
+        SearchState::NotFinished
+    }
+}
+
+/// Find the subprograms within the library that call themselves before
+/// reaching any statement that could end the recursion
+pub(crate) fn find_infinite_recursion(root: &DesignRoot, library: &Library) -> Vec<Diagnostic> {
+    let mut searcher = FindInfiniteRecursion {
+        root,
+        diagnostics: Vec::new(),
+    };
+    for unit in library.units() {
+        let _ = unit
+            .unit
+            .expect_analyzed()
+            .search(&unit.tokens, &mut searcher);
+    }
+    searcher.diagnostics
+}
+
+/// Opt-in check for subprograms that call themselves unconditionally
+#[derive(Default)]
+pub(crate) struct InfiniteRecursionLinter;
+
+impl InfiniteRecursionLinter {
+    pub fn lint(
+        &self,
+        root: &DesignRoot,
+        config: &Config,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for library in root.libraries() {
+            if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+                if library_config.is_third_party {
+                    continue;
+                }
+            }
+            diagnostics.append(find_infinite_recursion(root, library));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+
+    #[test]
+    fn unconditional_self_call() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+package pkg is
+  function fact(n : natural) return natural;
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    return n * fact(n - 1);
+  end function;
+
+  procedure count(n : inout natural) is
+  begin
+    n := n + 1;
+    count(n);
+  end procedure;
+end package body;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        check_diagnostics(
+            find_infinite_recursion(&root, lib),
+            vec![
+                Diagnostic::new(
+                    code.s("fact", 3),
+                    "Function 'fact' calls itself unconditionally, the recursion never ends",
+                    ErrorCode::InfiniteRecursion,
+                )
+                .related(code.s("fact", 2), "Defined here"),
+                Diagnostic::new(
+                    code.s("count", 2),
+                    "Procedure 'count' calls itself unconditionally, the recursion never ends",
+                    ErrorCode::InfiniteRecursion,
+                )
+                .related(code.s1("count"), "Defined here"),
+            ],
+        );
+    }
+
+    #[test]
+    fn guarded_recursion() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+package pkg is
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    if n = 0 then
+      return 1;
+    end if;
+    return n * fact(n - 1);
+  end function;
+
+  function any_zero(n : natural) return boolean is
+  begin
+    return n = 0 or any_zero(n / 2);
+  end function;
+end package body;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        check_no_diagnostics(&find_infinite_recursion(&root, lib));
+    }
+}
@@ -0,0 +1,378 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::ast::search::target_parts_overlap;
+use crate::ast::search::Driver;
+use crate::ast::search::FindAllDrivers;
+use crate::ast::search::Search;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::named_entity::{AnyEntKind, Object};
+use crate::Config;
+use crate::Diagnostic;
+
+/// Whether two statements are known to drive a common element of a signal
+fn conflicts(driver: &Driver, other: &Driver) -> bool {
+    driver.parts.iter().any(|parts| {
+        other
+            .parts
+            .iter()
+            .any(|other_parts| target_parts_overlap(parts, other_parts))
+    })
+}
+
+/// Find the signals of the library that are driven by more than one concurrent statement
+/// of the same design unit. Statements that drive distinct record elements or statically
+/// disjoint indexes and slices are not reported, neither are those that select
+/// an index or slice that is not a literal. A statement that drives the whole signal
+/// conflicts with any other driver.
+/// Signals of a resolved subtype are only reported when `warn_resolved` is set.
+pub(crate) fn find_multiple_drivers(
+    root: &DesignRoot,
+    library: &Library,
+    warn_resolved: bool,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for unit in library.units() {
+        let mut searcher = FindAllDrivers::default();
+        let _ = unit
+            .unit
+            .expect_analyzed()
+            .search(&unit.tokens, &mut searcher);
+
+        // The driven signals in order of the first driver
+        let mut signals: Vec<_> = searcher.drivers.into_iter().collect();
+        signals.sort_by(|(_, drivers), (_, other)| drivers[0].pos.cmp(&other[0].pos));
+
+        for (id, drivers) in signals {
+            let drivers: Vec<_> = drivers
+                .iter()
+                .enumerate()
+                .filter(|(i, driver)| {
+                    drivers
+                        .iter()
+                        .enumerate()
+                        .any(|(j, other)| *i != j && conflicts(driver, other))
+                })
+                .map(|(_, driver)| driver)
+                .collect();
+            if drivers.len() < 2 {
+                continue;
+            }
+
+            let ent = root.get_ent(id);
+            let AnyEntKind::Object(Object {
+                class: ObjectClass::Signal,
+                subtype,
+                ..
+            }) = ent.kind()
+            else {
+                continue;
+            };
+
+            let message = if subtype.is_resolved() {
+                if !warn_resolved {
+                    continue;
+                }
+                format!(
+                    "Signal '{}' of resolved type '{}' has multiple drivers",
+                    ent.designator(),
+                    subtype.type_mark().designator()
+                )
+            } else {
+                format!(
+                    "Signal '{}' has multiple drivers but its type '{}' is not resolved",
+                    ent.designator(),
+                    subtype.type_mark().designator()
+                )
+            };
+
+            let mut diagnostic =
+                Diagnostic::new(&drivers[1].pos, message, ErrorCode::MultipleDrivers);
+            for driver in drivers {
+                diagnostic.add_related(&driver.pos, "Driven here");
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// Opt-in check for signals with more than one driver within a design unit
+#[derive(Default)]
+pub(crate) struct MultipleDriversLinter {
+    /// Also report signals of a resolved subtype, such as std_logic
+    pub warn_resolved: bool,
+}
+
+impl MultipleDriversLinter {
+    pub fn lint(
+        &self,
+        root: &DesignRoot,
+        config: &Config,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for library in root.libraries() {
+            if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+                if library_config.is_third_party {
+                    continue;
+                }
+            }
+            diagnostics.append(find_multiple_drivers(root, library, self.warn_resolved));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+    use crate::SrcPos;
+
+    fn analyze(builder: &LibraryBuilder, warn_resolved: bool) -> Vec<Diagnostic> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        find_multiple_drivers(&root, lib, warn_resolved)
+    }
+
+    fn multiple_drivers(message: &str, drivers: &[SrcPos]) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(&drivers[1], message, ErrorCode::MultipleDrivers);
+        for driver in drivers {
+            diagnostic.add_related(driver, "Driven here");
+        }
+        diagnostic
+    }
+
+    #[test]
+    fn unresolved_signal_with_multiple_drivers() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, rst : bit;
+  signal cnt : natural;
+begin
+  clk <= not clk after 5 ns;
+
+  counter: process
+  begin
+    wait until clk = '1';
+    cnt <= cnt + 1;
+    if rst = '1' then
+      cnt <= 0;
+    end if;
+  end process;
+
+  cnt <= 0 when rst = '1';
+end architecture;",
+        );
+
+        check_diagnostics(
+            analyze(&builder, false),
+            vec![multiple_drivers(
+                "Signal 'cnt' has multiple drivers but its type 'NATURAL' is not resolved",
+                &[
+                    code.s1("counter").pos(),
+                    code.s("cnt <= 0 when", 1).s1("cnt").pos(),
+                ],
+            )],
+        );
+    }
+
+    #[test]
+    fn disjoint_slices_and_elements_are_not_multiple_drivers() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    a, b : bit;
+  end record;
+
+  signal vec : bit_vector(0 to 4);
+  signal rec : rec_t;
+begin
+  vec(0 to 1) <= \"00\";
+  vec(2) <= '1';
+  vec(4) <= '0';
+
+  rec.a <= '1';
+  rec.b <= '0';
+
+  process
+  begin
+    vec(3) <= '0';
+    vec(3) <= '1';
+    wait;
+  end process;
+end architecture;",
+        );
+
+        check_no_diagnostics(&analyze(&builder, false));
+    }
+
+    #[test]
+    fn whole_signal_conflicts_with_driven_element() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal word : bit_vector(0 to 3);
+begin
+  word(0) <= '1';
+  word <= \"0000\";
+end architecture;",
+        );
+
+        check_diagnostics(
+            analyze(&builder, false),
+            vec![multiple_drivers(
+                "Signal 'word' has multiple drivers but its type 'BIT_VECTOR' is not resolved",
+                &[code.s("word", 2).pos(), code.s("word", 3).pos()],
+            )],
+        );
+    }
+
+    #[test]
+    fn overlapping_elements_and_slices_are_multiple_drivers() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    a, b : bit;
+  end record;
+
+  signal s : bit_vector(0 to 3);
+  signal vec : bit_vector(7 downto 0);
+  signal rec : rec_t;
+begin
+  s(0) <= '0';
+  s(0) <= '1';
+
+  vec(7 downto 4) <= \"0000\";
+  vec(3 downto 0) <= \"0000\";
+  vec(4) <= '1';
+
+  rec.a <= '0';
+  rec.b <= '0';
+  rec.a <= '1';
+end architecture;",
+        );
+
+        check_diagnostics(
+            analyze(&builder, false),
+            vec![
+                multiple_drivers(
+                    "Signal 's' has multiple drivers but its type 'BIT_VECTOR' is not resolved",
+                    &[
+                        code.s("s(0)", 1).s1("s").pos(),
+                        code.s("s(0)", 2).s1("s").pos(),
+                    ],
+                ),
+                multiple_drivers(
+                    "Signal 'vec' has multiple drivers but its type 'BIT_VECTOR' is not resolved",
+                    &[
+                        code.s1("vec(7 downto 4)").s1("vec").pos(),
+                        code.s1("vec(4)").s1("vec").pos(),
+                    ],
+                ),
+                multiple_drivers(
+                    "Signal 'rec' has multiple drivers but its type 'rec_t' is not resolved",
+                    &[
+                        code.s("rec.a", 1).s1("rec").pos(),
+                        code.s("rec.a", 2).s1("rec").pos(),
+                    ],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn non_static_indexes_are_not_multiple_drivers() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal vec : bit_vector(0 to 3);
+begin
+  gen: for i in 0 to 3 generate
+    vec(i) <= '0';
+  end generate;
+
+  vec(0) <= '1';
+end architecture;",
+        );
+
+        check_no_diagnostics(&analyze(&builder, false));
+    }
+
+    #[test]
+    fn resolved_signals_are_only_reported_on_request() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal bus_line : std_logic;
+  signal data : std_logic_vector(7 downto 0);
+begin
+  bus_line <= 'Z';
+  bus_line <= '0';
+  data <= (others => 'Z');
+  data <= (others => 'H');
+end architecture;",
+        );
+
+        check_no_diagnostics(&analyze(&builder, false));
+        check_diagnostics(
+            analyze(&builder, true),
+            vec![
+                multiple_drivers(
+                    "Signal 'bus_line' of resolved type 'STD_LOGIC' has multiple drivers",
+                    &[code.s("bus_line", 2).pos(), code.s("bus_line", 3).pos()],
+                ),
+                multiple_drivers(
+                    "Signal 'data' of resolved type 'STD_LOGIC_VECTOR' has multiple drivers",
+                    &[code.s("data", 2).pos(), code.s("data", 3).pos()],
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,259 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::ast::search::any_sequential_statement;
+use crate::ast::search::sequential_signal_targets;
+use crate::ast::search::FoundDeclaration;
+use crate::ast::search::Search;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::named_entity::{AnyEntKind, Object, Reference};
+use crate::syntax::TokenAccess;
+use crate::Config;
+use crate::Diagnostic;
+use crate::EntityId;
+use crate::SrcPos;
+use std::slice;
+
+/// Collect the references within a statement together with their position
+struct CollectReferences(Vec<(SrcPos, EntityId)>);
+
+impl Searcher for CollectReferences {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.0.push((pos.clone(), id));
+        }
+        SearchState::NotFinished
+    }
+}
+
+/// Search for processes that read a signal after assigning it
+struct FindReadAfterAssignment<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl FindReadAfterAssignment<'_> {
+    fn is_signal(&self, id: EntityId) -> bool {
+        matches!(
+            self.root.get_ent(id).kind(),
+            AnyEntKind::Object(Object {
+                class: ObjectClass::Signal,
+                ..
+            })
+        )
+    }
+
+    /// Only the statements of the process itself are considered, and only signals that are
+    /// assigned as a whole by a statement that is always executed. Any statement that could
+    /// suspend the process, including a procedure call, forgets the assigned signals.
+    fn check_process(&mut self, ctx: &dyn TokenAccess, statements: &[LabeledSequentialStatement]) {
+        let mut assigned: Vec<(EntityId, SrcPos)> = Vec::new();
+        let mut reported: Vec<EntityId> = Vec::new();
+
+        for statement in statements {
+            let statement = slice::from_ref(statement);
+            if any_sequential_statement(statement, &|statement| {
+                matches!(
+                    statement,
+                    SequentialStatement::Wait(_) | SequentialStatement::ProcedureCall(_)
+                )
+            }) {
+                assigned.clear();
+                continue;
+            }
+
+            if !assigned.is_empty() {
+                let mut targets = Vec::new();
+                sequential_signal_targets(statement, &mut targets);
+                let targets: Vec<_> = targets.iter().map(|target| target.pos(ctx)).collect();
+
+                let mut references = CollectReferences(Vec::new());
+                let _ = statement[0].search(ctx, &mut references);
+                for (pos, id) in references.0 {
+                    if reported.contains(&id)
+                        || targets.iter().any(|target| target.contains(pos.start()))
+                    {
+                        continue;
+                    }
+                    if let Some((_, assign_pos)) = assigned.iter().find(|(prev, _)| *prev == id) {
+                        self.diagnostics.push(
+                            Diagnostic::new(
+                                &pos,
+                                format!(
+                                    "Signal '{}' is read after it is assigned, the assigned value only takes effect when the process suspends",
+                                    self.root.get_ent(id).designator()
+                                ),
+                                ErrorCode::ReadAfterSignalAssignment,
+                            )
+                            .related(assign_pos, "Assigned here"),
+                        );
+                        reported.push(id);
+                    }
+                }
+            }
+
+            if let SequentialStatement::SignalAssignment(ref assign) = statement[0].statement.item {
+                if let Target::Name(Name::Designator(ref designator)) = assign.target.item {
+                    if let Some(id) = designator.reference.get().filter(|id| self.is_signal(*id)) {
+                        assigned.retain(|(prev, _)| *prev != id);
+                        assigned.push((id, assign.target.pos(ctx)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Searcher for FindReadAfterAssignment<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::ConcurrentStatement(statement) = decl {
+            if let ConcurrentStatement::Process(ref process) = statement.statement.item {
+                self.check_process(ctx, &process.statements);
+            }
+        }
+        SearchState::NotFinished
+    }
+}
+
+/// Find the processes of the library that read a signal after assigning it,
+/// where the read still returns the value from before the assignment.
+pub(crate) fn find_read_after_assignment(root: &DesignRoot, library: &Library) -> Vec<Diagnostic> {
+    let mut searcher = FindReadAfterAssignment {
+        root,
+        diagnostics: Vec::new(),
+    };
+    for unit in library.units() {
+        let _ = unit
+            .unit
+            .expect_analyzed()
+            .search(&unit.tokens, &mut searcher);
+    }
+    searcher.diagnostics
+}
+
+/// Opt-in check for signals that are read after being assigned in the same process
+#[derive(Default)]
+pub(crate) struct ReadAfterAssignmentLinter;
+
+impl ReadAfterAssignmentLinter {
+    pub fn lint(
+        &self,
+        root: &DesignRoot,
+        config: &Config,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for library in root.libraries() {
+            if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+                if library_config.is_third_party {
+                    continue;
+                }
+            }
+            diagnostics.append(find_read_after_assignment(root, library));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+
+    fn analyze(builder: &LibraryBuilder) -> Vec<Diagnostic> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        find_read_after_assignment(&root, lib)
+    }
+
+    #[test]
+    fn signal_read_after_assignment() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal cnt, total : natural;
+  signal flag : boolean;
+begin
+  process (flag)
+  begin
+    cnt <= 0;
+    if flag then
+      total <= cnt + 1;
+    end if;
+    cnt <= cnt + 2;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            analyze(&builder),
+            vec![Diagnostic::new(
+                code.s("cnt", 3),
+                "Signal 'cnt' is read after it is assigned, the assigned value only takes effect when the process suspends",
+                ErrorCode::ReadAfterSignalAssignment,
+            )
+            .related(code.s("cnt", 2), "Assigned here")],
+        );
+    }
+
+    #[test]
+    fn reads_separated_by_wait_or_before_assignment_are_not_reported() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal cnt, total : natural;
+  signal flag : boolean;
+
+  procedure sync is
+  begin
+    wait for 1 ns;
+  end procedure;
+begin
+  process
+  begin
+    total <= cnt;
+    cnt <= 0;
+    wait for 1 ns;
+    total <= cnt;
+    cnt <= 1;
+    sync;
+    total <= cnt;
+    if flag then
+      cnt <= 2;
+    end if;
+    total <= cnt;
+    cnt <= 3;
+    cnt <= 4;
+    wait;
+  end process;
+end architecture;",
+        );
+
+        check_no_diagnostics(&analyze(&builder));
+    }
+}
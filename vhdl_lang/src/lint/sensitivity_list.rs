@@ -0,0 +1,293 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::ast::search::any_sequential_statement;
+use crate::ast::search::read_references;
+use crate::ast::search::sensitivity_list_references;
+use crate::ast::search::FoundDeclaration;
+use crate::ast::search::Search;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::named_entity::{AnyEntKind, Object};
+use crate::standard::VHDLStandard;
+use crate::syntax::TokenAccess;
+use crate::Config;
+use crate::Diagnostic;
+
+/// Whether the statements contain an if statement with a clock edge condition,
+/// such as `rising_edge(clk)` or `clk'event and clk = '1'`
+fn is_clocked(statements: &[LabeledSequentialStatement]) -> bool {
+    any_sequential_statement(statements, &|statement| {
+        matches!(statement, SequentialStatement::If(ifstmt)
+            if ifstmt.conds.conditionals.iter().any(|cond| is_edge(&cond.condition.item)))
+    })
+}
+
+fn is_edge(expr: &Expression) -> bool {
+    match expr {
+        Expression::Binary(_, ref left, ref right) => is_edge(&left.item) || is_edge(&right.item),
+        Expression::Name(ref name) => match name.as_ref() {
+            Name::CallOrIndexed(fcall) => {
+                let designator = match fcall.name.item {
+                    Name::Designator(ref designator) => &designator.item,
+                    Name::Selected(_, ref suffix) => &suffix.item.item,
+                    _ => return false,
+                };
+                matches!(designator, Designator::Identifier(sym)
+                    if ["rising_edge", "falling_edge"]
+                        .iter()
+                        .any(|edge| sym.name_utf8().eq_ignore_ascii_case(edge)))
+            }
+            Name::Attribute(attr) => matches!(
+                attr.attr.item,
+                AttributeDesignator::Signal(SignalAttribute::Event)
+            ),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Search for combinational processes with signals missing from their sensitivity list
+struct FindIncompleteSensitivity<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl FindIncompleteSensitivity<'_> {
+    fn check_process(&mut self, ctx: &dyn TokenAccess, process: &ProcessStatement) {
+        let Some(SensitivityList::Names(ref names)) = process.sensitivity_list else {
+            return;
+        };
+        let (Some(first), Some(last)) = (names.first(), names.last()) else {
+            return;
+        };
+        if any_sequential_statement(&process.statements, &|statement| {
+            matches!(statement, SequentialStatement::Wait(_))
+        }) || is_clocked(&process.statements)
+        {
+            return;
+        }
+
+        let listed = sensitivity_list_references(names);
+        let missing: Vec<_> = read_references(ctx, &process.statements)
+            .into_iter()
+            .filter(|id| !listed.contains(id))
+            .map(|id| self.root.get_ent(id))
+            .filter(|ent| {
+                matches!(
+                    ent.kind(),
+                    AnyEntKind::Object(Object {
+                        class: ObjectClass::Signal,
+                        ..
+                    })
+                )
+            })
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        let signals = missing
+            .iter()
+            .map(|ent| format!("'{}'", ent.designator()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = if missing.len() == 1 {
+            format!("Signal {signals} is read in the process but is not in its sensitivity list")
+        } else {
+            format!("Signals {signals} are read in the process but are not in its sensitivity list")
+        };
+
+        let list_pos = ctx.get_span(first.span.start_token, last.span.end_token);
+        let additions: String = missing
+            .iter()
+            .map(|ent| format!(", {}", ent.designator()))
+            .collect();
+        let mut diagnostic = Diagnostic::new(&list_pos, message, ErrorCode::IncompleteSensitivity)
+            .fix(list_pos.end_pos(), additions);
+        if self.root.standard() >= VHDLStandard::VHDL2008 {
+            diagnostic = diagnostic.fix(&list_pos, "all");
+        }
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl Searcher for FindIncompleteSensitivity<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::ConcurrentStatement(statement) = decl {
+            if let ConcurrentStatement::Process(ref process) = statement.statement.item {
+                self.check_process(ctx, process);
+            }
+        }
+        SearchState::NotFinished
+    }
+}
+
+/// Find the processes of the library that read signals which are not in their sensitivity list.
+/// Processes with wait statements and clocked processes that check for a clock edge
+/// are not combinational and are skipped.
+pub(crate) fn find_incomplete_sensitivity(root: &DesignRoot, library: &Library) -> Vec<Diagnostic> {
+    let mut searcher = FindIncompleteSensitivity {
+        root,
+        diagnostics: Vec::new(),
+    };
+    for unit in library.units() {
+        let _ = unit
+            .unit
+            .expect_analyzed()
+            .search(&unit.tokens, &mut searcher);
+    }
+    searcher.diagnostics
+}
+
+/// Opt-in check for combinational processes with an incomplete sensitivity list
+#[derive(Default)]
+pub(crate) struct IncompleteSensitivityLinter;
+
+impl IncompleteSensitivityLinter {
+    pub fn lint(
+        &self,
+        root: &DesignRoot,
+        config: &Config,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for library in root.libraries() {
+            if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+                if library_config.is_third_party {
+                    continue;
+                }
+            }
+            diagnostics.append(find_incomplete_sensitivity(root, library));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+
+    fn analyze(builder: &LibraryBuilder) -> Vec<Diagnostic> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        find_incomplete_sensitivity(&root, lib)
+    }
+
+    #[test]
+    fn signals_missing_from_sensitivity_list() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  port (sel : in bit);
+end entity;
+
+architecture a of ent is
+  signal a, b, y, z : bit;
+begin
+  mux: process (a)
+  begin
+    if sel = '1' then
+      y <= a;
+    else
+      y <= b;
+    end if;
+  end process;
+
+  inv: process (b)
+  begin
+    z <= not a;
+  end process;
+end architecture;",
+        );
+
+        let list = code.s1("(a)").s1("a").pos();
+        let single = code.s("(b)", 1).s1("b").pos();
+        check_diagnostics(
+            analyze(&builder),
+            vec![
+                Diagnostic::new(
+                    &list,
+                    "Signals 'sel', 'b' are read in the process but are not in its sensitivity list",
+                    ErrorCode::IncompleteSensitivity,
+                )
+                .fix(list.end_pos(), ", sel, b")
+                .fix(&list, "all"),
+                Diagnostic::new(
+                    &single,
+                    "Signal 'a' is read in the process but is not in its sensitivity list",
+                    ErrorCode::IncompleteSensitivity,
+                )
+                .fix(single.end_pos(), ", a")
+                .fix(&single, "all"),
+            ],
+        );
+    }
+
+    #[test]
+    fn complete_clocked_and_waiting_processes_are_not_reported() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, rst, d, q, q2, y : std_logic;
+  signal vec : std_logic_vector(1 downto 0);
+begin
+  comb: process (d, vec)
+  begin
+    y <= d and vec(0);
+  end process;
+
+  reg: process (clk, rst)
+  begin
+    if rst = '1' then
+      q <= '0';
+    elsif rising_edge(clk) then
+      q <= d;
+    end if;
+  end process;
+
+  reg2: process (clk)
+  begin
+    if clk'event and clk = '1' then
+      q2 <= d;
+    end if;
+  end process;
+
+  any: process (all)
+  begin
+    y <= d;
+  end process;
+
+  waiting: process
+  begin
+    wait on d;
+    y <= d;
+  end process;
+end architecture;",
+        );
+
+        check_no_diagnostics(&analyze(&builder));
+    }
+}
@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::dead_code::find_unused_declarations;
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::ast::UnitId;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::data::Symbol;
+use crate::named_entity::ObjectInterface;
+use crate::AnyEntKind;
+use crate::Config;
+use crate::Design;
+use crate::Diagnostic;
+use crate::EntRef;
+use fnv::FnvHashMap;
+
+/// A port or generic of an entity
+pub(crate) fn is_entity_interface(ent: EntRef) -> bool {
+    let AnyEntKind::Object(object) = ent.kind() else {
+        return false;
+    };
+    matches!(
+        object.iface,
+        Some(ObjectInterface::Port(_) | ObjectInterface::Generic)
+    ) && matches!(
+        ent.parent.map(|parent| parent.kind()),
+        Some(AnyEntKind::Design(Design::Entity(..)))
+    )
+}
+
+/// Find the ports and generics of an entity that none of its architectures refer to
+fn find_unused_ports<'a>(
+    root: &'a DesignRoot,
+    library: &Library,
+    entity_name: &Symbol,
+) -> Vec<EntRef<'a>> {
+    // Nothing is known about the usage within an entity without architectures
+    if library.secondary_units(entity_name).next().is_none() {
+        return Vec::new();
+    }
+
+    let mut unused: Vec<_> = find_unused_declarations(root, library, entity_name)
+        .into_iter()
+        .filter(|ent| is_entity_interface(ent))
+        .collect();
+    unused.sort_by_key(|ent| ent.decl_pos().map(|pos| pos.start()));
+    unused
+}
+
+/// Opt-in check for ports and generics that are not used by any architecture of their entity
+#[derive(Default)]
+pub(crate) struct UnusedPortLinter {
+    // library name, entity name
+    diagnostics: FnvHashMap<(Symbol, Symbol), Vec<Diagnostic>>,
+}
+
+impl UnusedPortLinter {
+    pub fn lint(
+        &mut self,
+        root: &DesignRoot,
+        config: &Config,
+        analyzed_units: &[UnitId],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        // Ports can only be referenced by the entity and its architectures
+        for unit in analyzed_units {
+            let key = (unit.library_name().clone(), unit.primary_name().clone());
+            self.diagnostics.remove(&key);
+        }
+
+        self.diagnostics.retain(|(library_name, primary_name), _| {
+            root.get_lib(library_name)
+                .is_some_and(|library| library.primary_unit(primary_name).is_some())
+        });
+
+        for unit in analyzed_units {
+            let key = (unit.library_name().clone(), unit.primary_name().clone());
+
+            if let Some(library) = root.get_lib(unit.library_name()) {
+                self.diagnostics.entry(key).or_insert_with(|| {
+                    find_unused_ports(root, library, unit.primary_name())
+                        .into_iter()
+                        .filter_map(|ent| {
+                            let kind = match ent.kind() {
+                                AnyEntKind::Object(object) if object.is_generic() => "Generic",
+                                _ => "Port",
+                            };
+                            Some(Diagnostic::new(
+                                ent.decl_pos()?,
+                                format!(
+                                    "{kind} '{}' is not used by any architecture of entity '{}'",
+                                    ent.designator(),
+                                    ent.parent?.designator()
+                                ),
+                                ErrorCode::UnusedPort,
+                            ))
+                        })
+                        .collect()
+                });
+            }
+        }
+
+        for ((library_name, _), unit_diagnostics) in self.diagnostics.iter() {
+            if let Some(library_config) = config.get_library(&library_name.name_utf8()) {
+                if !library_config.is_third_party {
+                    diagnostics.append(unit_diagnostics.iter().cloned());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_no_diagnostics;
+
+    fn unused_ports(builder: LibraryBuilder, entity_name: &str) -> Vec<String> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let library = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        find_unused_ports(&root, library, &root.symbol_utf8(entity_name))
+            .into_iter()
+            .map(|ent| ent.designator().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn unused_output_port() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+  generic (
+    width : natural;
+    unused_g : natural
+  );
+  port (
+    data : in bit_vector(width - 1 downto 0);
+    valid : out bit;
+    ready : out bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+  ready <= data(0);
+end architecture;",
+        );
+
+        assert_eq!(unused_ports(builder, "ent"), vec!["unused_g", "valid"]);
+    }
+
+    #[test]
+    fn ports_used_by_any_architecture_are_used() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+  port (
+    a : out bit;
+    b : out bit;
+    c : out bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  a <= '0';
+end architecture;
+
+architecture sim of ent is
+begin
+  b <= '1';
+end architecture;",
+        );
+
+        assert_eq!(unused_ports(builder, "ent"), vec!["c"]);
+    }
+
+    #[test]
+    fn entities_without_architecture_are_not_checked() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+  port (a : out bit);
+end entity;",
+        );
+
+        assert_eq!(unused_ports(builder, "ent"), Vec::<String>::new());
+    }
+}
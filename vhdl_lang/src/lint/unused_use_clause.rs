@@ -0,0 +1,501 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::analysis::LockedUnit;
+use crate::ast::search::FoundDeclaration;
+use crate::ast::search::Search;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::DiagnosticHandler;
+use crate::named_entity::{HasEntityId, Reference, Region, Related};
+use crate::syntax::TokenAccess;
+use crate::AnyEntKind;
+use crate::Config;
+use crate::Design;
+use crate::Diagnostic;
+use crate::EntRef;
+use crate::SrcPos;
+
+/// Libraries whose use clauses are conventionally present in every design unit
+const CONVENTIONAL_LIBRARIES: [&str; 2] = ["std", "ieee"];
+
+#[derive(Clone, Copy)]
+enum UsedTarget<'a> {
+    // use pkg.all
+    All(EntRef<'a>),
+    // use pkg.name
+    Name(EntRef<'a>),
+}
+
+impl<'a> UsedTarget<'a> {
+    fn ent(&self) -> EntRef<'a> {
+        match self {
+            UsedTarget::All(ent) | UsedTarget::Name(ent) => ent,
+        }
+    }
+
+    /// True if everything made visible by other is already made visible by self
+    fn covers(&self, other: &UsedTarget) -> bool {
+        match (self, other) {
+            (UsedTarget::All(ent), UsedTarget::All(other))
+            | (UsedTarget::Name(ent), UsedTarget::Name(other)) => ent.id() == other.id(),
+            (UsedTarget::All(ent), UsedTarget::Name(other)) => {
+                other.parent.is_some_and(|parent| parent.id() == ent.id())
+            }
+            (UsedTarget::Name(_), UsedTarget::All(_)) => false,
+        }
+    }
+
+    /// True if the referenced entity was made visible by the use clause
+    fn is_used_by(&self, reference: EntRef) -> bool {
+        match self {
+            // A reference to the package itself is not made visible by .all
+            UsedTarget::All(ent) => reference.id() != ent.id() && is_within(reference, ent),
+            UsedTarget::Name(ent) => is_within(reference, ent),
+        }
+    }
+
+    /// True if a declaration with the designator hides a name made visible by the use clause
+    fn is_hidden_by(&self, designator: &Designator) -> bool {
+        match self {
+            UsedTarget::All(ent) => package_region(ent)
+                .is_some_and(|region| region.lookup_immediate(designator).is_some()),
+            UsedTarget::Name(ent) => ent.designator() == designator,
+        }
+    }
+}
+
+/// True if the entity is declared within the scope, or is an implicit declaration of it
+fn is_within(ent: EntRef, scope: EntRef) -> bool {
+    let mut current = Some(ent);
+    while let Some(ent) = current {
+        if ent.id() == scope.id() {
+            return true;
+        }
+        if let Related::ImplicitOf(of) = ent.related {
+            if is_within(of, scope) {
+                return true;
+            }
+        }
+        current = ent.parent;
+    }
+    false
+}
+
+fn package_region<'a>(ent: EntRef<'a>) -> Option<&'a Region<'a>> {
+    match ent.kind() {
+        AnyEntKind::Design(
+            Design::Package(_, region)
+            | Design::UninstPackage(_, region)
+            | Design::PackageInstance(region)
+            | Design::InterfacePackageInstance(region),
+        ) => Some(region),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+struct UseItem<'a> {
+    name: String,
+    pos: SrcPos,
+    target: UsedTarget<'a>,
+}
+
+impl<'a> UseItem<'a> {
+    fn new(
+        ctx: &dyn TokenAccess,
+        root: &'a DesignRoot,
+        name: &WithTokenSpan<Name>,
+    ) -> Option<UseItem<'a>> {
+        if let Some(Designator::Identifier(library)) = name.item.prefix() {
+            let library = library.name_utf8();
+            if CONVENTIONAL_LIBRARIES
+                .iter()
+                .any(|conventional| library.eq_ignore_ascii_case(conventional))
+            {
+                return None;
+            }
+        }
+
+        let target = match name.item {
+            Name::SelectedAll(ref prefix) => {
+                let ent = root.get_ent(prefix.item.get_suffix_reference()?);
+                if matches!(ent.kind(), AnyEntKind::Library) {
+                    return None;
+                }
+                UsedTarget::All(ent)
+            }
+            Name::Selected(..) => UsedTarget::Name(root.get_ent(name.item.get_suffix_reference()?)),
+            _ => return None,
+        };
+
+        Some(UseItem {
+            name: name.item.to_string(),
+            pos: name.pos(ctx),
+            target,
+        })
+    }
+}
+
+/// The use clauses within the context clause and top-level declarative part of a design unit
+fn use_items<'a>(root: &'a DesignRoot, unit: &LockedUnit) -> Vec<UseItem<'a>> {
+    let design_unit = unit.unit.expect_analyzed();
+    let (context_clause, decl) = match *design_unit {
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref unit)) => {
+            (&unit.context_clause, &unit.decl)
+        }
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Package(ref unit)) => {
+            (&unit.context_clause, &unit.decl)
+        }
+        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref unit)) => {
+            (&unit.context_clause, &unit.decl)
+        }
+        AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(ref unit)) => {
+            (&unit.context_clause, &unit.decl)
+        }
+        // The use clauses of context declarations are used where they are referenced
+        _ => return Vec::new(),
+    };
+
+    let context_names = context_clause.iter().filter_map(|item| match item {
+        ContextItem::Use(use_clause) => Some(&use_clause.name_list),
+        _ => None,
+    });
+    let decl_names = decl.iter().filter_map(|decl| match decl.item {
+        Declaration::Use(ref use_clause) => Some(&use_clause.name_list),
+        _ => None,
+    });
+
+    context_names
+        .chain(decl_names)
+        .flat_map(|name_list| name_list.items.iter())
+        .filter_map(|name| UseItem::new(&unit.tokens, root, name))
+        .collect()
+}
+
+/// The references and declarations of a design unit
+#[derive(Default)]
+struct UnitUsage<'a> {
+    references: Vec<(SrcPos, EntRef<'a>)>,
+    declarations: Vec<EntRef<'a>>,
+}
+
+struct UsageSearcher<'a> {
+    root: &'a DesignRoot,
+    usage: UnitUsage<'a>,
+}
+
+impl<'a> Searcher for UsageSearcher<'a> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.usage
+                .references
+                .push((pos.clone(), self.root.get_ent(id)));
+        }
+        SearchState::NotFinished
+    }
+
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            self.usage.declarations.push(self.root.get_ent(id));
+        }
+        SearchState::NotFinished
+    }
+}
+
+fn unit_usage<'a>(root: &'a DesignRoot, unit: &LockedUnit) -> UnitUsage<'a> {
+    let mut searcher = UsageSearcher {
+        root,
+        usage: UnitUsage::default(),
+    };
+    let _ = unit
+        .unit
+        .expect_analyzed()
+        .search(&unit.tokens, &mut searcher);
+    searcher.usage
+}
+
+fn contains(outer: &SrcPos, inner: &SrcPos) -> bool {
+    outer.source == inner.source && outer.start() <= inner.start() && inner.end() <= outer.end()
+}
+
+/// Check the use clauses of one design unit given the use clauses already visible to it
+/// and the usage within the design units where its use clauses are visible
+fn check_use_items<'a>(
+    items: &[UseItem<'a>],
+    visible: &mut Vec<UseItem<'a>>,
+    usages: &[&UnitUsage<'a>],
+    use_positions: &[&SrcPos],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let references: Vec<EntRef> = usages
+        .iter()
+        .flat_map(|usage| usage.references.iter())
+        .filter(|(pos, _)| !use_positions.iter().any(|use_pos| contains(use_pos, pos)))
+        .map(|(_, ent)| *ent)
+        .collect();
+
+    for item in items.iter() {
+        if let Some(previous) = visible
+            .iter()
+            .find(|previous| previous.target.covers(&item.target))
+        {
+            diagnostics.push(
+                Diagnostic::new(
+                    &item.pos,
+                    format!(
+                        "Use clause '{}' is redundant, it is already made visible",
+                        item.name
+                    ),
+                    ErrorCode::RedundantUseClause,
+                )
+                .related(&previous.pos, "Previously made visible here"),
+            );
+            continue;
+        }
+        visible.push(item.clone());
+
+        if !references.iter().any(|ent| item.target.is_used_by(ent)) {
+            let mut hidden_by: Vec<_> = usages
+                .iter()
+                .flat_map(|usage| usage.declarations.iter())
+                .filter(|ent| !is_within(ent, item.target.ent()))
+                .filter(|ent| item.target.is_hidden_by(ent.designator()))
+                .filter_map(|ent| ent.decl_pos())
+                .collect();
+            hidden_by.sort_by_key(|pos| pos.start());
+
+            if let Some(decl_pos) = hidden_by.first() {
+                diagnostics.push(
+                    Diagnostic::new(
+                        &item.pos,
+                        format!(
+                            "Use clause '{}' is unused, it is hidden by a local declaration",
+                            item.name
+                        ),
+                        ErrorCode::ShadowedUseClause,
+                    )
+                    .related(*decl_pos, "Hidden by this declaration"),
+                );
+            } else {
+                diagnostics.push(Diagnostic::new(
+                    &item.pos,
+                    format!(
+                        "Use clause '{}' is redundant, nothing it makes visible is used",
+                        item.name
+                    ),
+                    ErrorCode::RedundantUseClause,
+                ));
+            }
+        }
+    }
+}
+
+/// Find the use clauses of the library that make nothing visible that is used
+/// or that make visible what is already visible
+pub(crate) fn find_unused_use_clauses(root: &DesignRoot, library: &Library) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for primary in library.primary_units() {
+        let primary_name = primary.unit_id().primary_name();
+        let secondaries: Vec<_> = library.secondary_units(primary_name).collect();
+
+        let primary_items = use_items(root, primary);
+        let secondary_items: Vec<_> = secondaries
+            .iter()
+            .map(|unit| use_items(root, unit))
+            .collect();
+        let primary_usage = unit_usage(root, primary);
+        let secondary_usages: Vec<_> = secondaries
+            .iter()
+            .map(|unit| unit_usage(root, unit))
+            .collect();
+
+        let use_positions: Vec<_> = primary_items
+            .iter()
+            .chain(secondary_items.iter().flatten())
+            .map(|item| &item.pos)
+            .collect();
+
+        // The use clauses of the primary unit are visible in all secondary units
+        let all_usages: Vec<_> = std::iter::once(&primary_usage)
+            .chain(secondary_usages.iter())
+            .collect();
+        let mut visible = Vec::new();
+        check_use_items(
+            &primary_items,
+            &mut visible,
+            &all_usages,
+            &use_positions,
+            &mut diagnostics,
+        );
+        for (items, usage) in secondary_items.iter().zip(secondary_usages.iter()) {
+            let mut visible = visible.clone();
+            check_use_items(
+                items,
+                &mut visible,
+                &[usage],
+                &use_positions,
+                &mut diagnostics,
+            );
+        }
+    }
+
+    diagnostics
+}
+
+/// Opt-in check for use clauses that are redundant or hidden by local declarations
+#[derive(Default)]
+pub(crate) struct UnusedUseClauseLinter;
+
+impl UnusedUseClauseLinter {
+    pub fn lint(
+        &self,
+        root: &DesignRoot,
+        config: &Config,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for library in root.libraries() {
+            if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+                if library_config.is_third_party {
+                    continue;
+                }
+            }
+            diagnostics.append(find_unused_use_clauses(root, library));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+
+    fn find_in_libname(builder: LibraryBuilder) -> Vec<Diagnostic> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        find_unused_use_clauses(&root, lib)
+    }
+
+    #[test]
+    fn use_clause_without_used_names() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+package pkg1 is
+  constant c1 : natural := 0;
+end package;
+
+package pkg2 is
+  type state_t is (idle, busy);
+end package;
+
+use std.textio.all;
+use work.pkg1.all;
+use work.pkg2.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  use work.pkg1.c1;
+  signal state : work.pkg2.state_t := idle;
+begin
+end architecture;
+",
+        );
+
+        check_diagnostics(
+            find_in_libname(builder),
+            vec![
+                Diagnostic::new(
+                    code.s1("work.pkg1.all"),
+                    "Use clause 'work.pkg1.all' is redundant, nothing it makes visible is used",
+                    ErrorCode::RedundantUseClause,
+                ),
+                Diagnostic::new(
+                    code.s1("work.pkg1.c1"),
+                    "Use clause 'work.pkg1.c1' is redundant, it is already made visible",
+                    ErrorCode::RedundantUseClause,
+                )
+                .related(code.s1("work.pkg1.all"), "Previously made visible here"),
+            ],
+        );
+    }
+
+    #[test]
+    fn use_clause_hidden_by_local_declaration() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+package pkg is
+  constant width : natural := 8;
+end package;
+
+use work.pkg.width;
+
+entity ent is
+  generic (width : natural);
+end entity;
+
+architecture a of ent is
+  signal data : bit_vector(width - 1 downto 0);
+begin
+end architecture;
+",
+        );
+
+        check_diagnostics(
+            find_in_libname(builder),
+            vec![Diagnostic::new(
+                code.s1("work.pkg.width"),
+                "Use clause 'work.pkg.width' is unused, it is hidden by a local declaration",
+                ErrorCode::ShadowedUseClause,
+            )
+            .related(code.s("width", 3), "Hidden by this declaration")],
+        );
+    }
+
+    #[test]
+    fn use_clause_of_primary_unit_used_in_secondary_unit() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+package pkg is
+  type state_t is (idle, busy);
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant state : work.pkg.state_t := busy;
+begin
+end architecture;
+",
+        );
+
+        check_no_diagnostics(&find_in_libname(builder));
+    }
+}
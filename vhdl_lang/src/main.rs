@@ -7,7 +7,10 @@
 use clap::Parser;
 use itertools::Itertools;
 use std::path::Path;
-use vhdl_lang::{Config, Diagnostic, MessagePrinter, Project, Severity, SeverityMap};
+use vhdl_lang::{
+    Config, Diagnostic, DiagnosticFormat, Message, MessageHandler, MessagePrinter, Project,
+    Severity, SeverityMap,
+};
 
 /// Run vhdl analysis
 #[derive(Parser, Debug)]
@@ -25,6 +28,32 @@ struct Args {
     /// Config file in TOML format containing libraries and settings
     #[arg(short, long)]
     config: String,
+
+    /// Print the diagnostics in a machine readable format
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Report violations of the standard that vendor tools commonly accept,
+    /// such as reading ports of mode out before VHDL-2008, as warnings
+    #[arg(long)]
+    lenient: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    /// One JSON object per line and diagnostic
+    JsonLines,
+    /// SARIF 2.1.0
+    Sarif,
+}
+
+impl From<OutputFormat> for DiagnosticFormat {
+    fn from(format: OutputFormat) -> DiagnosticFormat {
+        match format {
+            OutputFormat::JsonLines => DiagnosticFormat::JsonLines,
+            OutputFormat::Sarif => DiagnosticFormat::Sarif,
+        }
+    }
 }
 
 fn main() {
@@ -35,19 +64,32 @@ fn main() {
         .unwrap();
 
     let mut config = Config::default();
-    let mut msg_printer = MessagePrinter::default();
-    config.load_external_config(&mut msg_printer, args.libraries.clone());
+    // Keep stdout machine readable when a format is selected
+    let mut msg_printer: Box<dyn MessageHandler> = match args.format {
+        Some(_) => Box::new(StderrMessagePrinter),
+        None => Box::new(MessagePrinter::default()),
+    };
+    config.load_external_config(msg_printer.as_mut(), args.libraries.clone());
     config.append(
         &Config::read_file_path(Path::new(&args.config)).expect("Failed to read config file"),
-        &mut msg_printer,
+        msg_printer.as_mut(),
     );
 
     let severity_map = *config.severities();
-    let mut project = Project::from_config(config, &mut msg_printer);
+    let mut project = Project::from_config(config, msg_printer.as_mut());
     project.enable_unused_declaration_detection();
+    if args.lenient {
+        project.enable_lenient_analysis();
+    }
     let diagnostics = project.analyse();
 
-    show_diagnostics(&diagnostics, &severity_map);
+    match args.format {
+        Some(format) => print!(
+            "{}",
+            DiagnosticFormat::from(format).format(&diagnostics, &severity_map)
+        ),
+        None => show_diagnostics(&diagnostics, &severity_map),
+    }
 
     if diagnostics
         .iter()
@@ -59,6 +101,14 @@ fn main() {
     }
 }
 
+struct StderrMessagePrinter;
+
+impl MessageHandler for StderrMessagePrinter {
+    fn push(&mut self, message: Message) {
+        eprintln!("{message}");
+    }
+}
+
 fn show_diagnostics(diagnostics: &[Diagnostic], severity_map: &SeverityMap) {
     let diagnostics = diagnostics
         .iter()
@@ -19,7 +19,9 @@ pub use types::{BaseType, Subtype, Type, TypeEnt, TypedSelection, UniversalType}
 mod overloaded;
 pub use overloaded::{Overloaded, OverloadedEnt, Signature, SignatureKey, SubprogramKey};
 mod object;
-pub use object::{InterfaceMode, Object, ObjectEnt, ObjectInterface, ViewEnt};
+pub use object::{
+    InterfaceMode, Object, ObjectEnt, ObjectInterface, StaticRange, StaticValue, ViewEnt,
+};
 mod design;
 pub use design::{Design, DesignEnt};
 mod attribute;
@@ -428,6 +430,7 @@ impl<'a> AnyEnt<'a> {
         match self.kind() {
             AnyEntKind::Overloaded(Overloaded::Alias(ref ent)) => ent.as_actual(),
             AnyEntKind::Type(Type::Alias(ref ent)) => ent.as_actual(),
+            AnyEntKind::Design(Design::Alias(ref ent)) => ent.as_actual(),
             AnyEntKind::ObjectAlias { base_object, .. } => base_object.as_actual(),
             _ => self,
         }
@@ -631,6 +634,8 @@ impl HasEntityId for Declaration {
             Declaration::SubprogramBody(body) => body.ent_id(),
             Declaration::SubprogramInstantiation(decl) => decl.ent_id(),
             Declaration::Package(pkg) => pkg.ent_id(),
+            Declaration::PackageDeclaration(pkg) => pkg.ident.decl.get(),
+            Declaration::PackageBody(body) => body.ident.decl.get(),
             Declaration::Use(_) => None,
             Declaration::Configuration(_) => None,
             Declaration::View(decl) => decl.ent_id(),
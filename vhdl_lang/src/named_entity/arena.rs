@@ -23,6 +23,8 @@ use super::AnyEntKind;
 use super::AttributeEnt;
 use super::EntRef;
 use super::Related;
+use super::StaticRange;
+use super::StaticValue;
 use super::TypeEnt;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -46,6 +48,10 @@ pub struct LocalId(u32);
 struct LocalArena {
     pub id: ArenaId,
     items: PinnedVec<AnyEnt<'static>>,
+    // Values of constants, generic defaults and physical units known during analysis
+    static_values: FnvHashMap<u32, StaticValue>,
+    // Ranges of integer type declarations whose bounds are static
+    static_ranges: FnvHashMap<u32, StaticRange>,
 }
 
 impl LocalArena {
@@ -53,6 +59,8 @@ impl LocalArena {
         Self {
             id,
             items: PinnedVec::new(),
+            static_values: FnvHashMap::default(),
+            static_ranges: FnvHashMap::default(),
         }
     }
 
@@ -82,6 +90,14 @@ impl LocalArena {
         std::mem::transmute(std::pin::Pin::into_inner(item) as *mut AnyEnt)
     }
 
+    fn static_value(&self, id: LocalId) -> Option<StaticValue> {
+        self.static_values.get(&id.0).copied()
+    }
+
+    fn static_range(&self, id: LocalId) -> Option<StaticRange> {
+        self.static_ranges.get(&id.0).copied()
+    }
+
     pub fn contains(&self, id: LocalId) -> bool {
         (id.0 as usize) < self.items.len()
     }
@@ -119,6 +135,14 @@ impl<'a> FinalArena {
         }
     }
 
+    pub(crate) fn static_value(&self, id: EntityId) -> Option<StaticValue> {
+        self.refs.get(&id.arena_id().0)?.static_value(id.local_id())
+    }
+
+    pub(crate) fn static_range(&self, id: EntityId) -> Option<StaticRange> {
+        self.refs.get(&id.arena_id().0)?.static_range(id.local_id())
+    }
+
     pub fn is_valid_id(&self, id: EntityId) -> bool {
         self.refs
             .get(&id.arena_id().0)
@@ -277,6 +301,36 @@ impl Arena {
         }
     }
 
+    pub(crate) fn set_static_value(&self, id: EntityId, value: StaticValue) {
+        let mut local = self.local.borrow_mut();
+        assert_eq!(id.arena_id(), local.id);
+        local.static_values.insert(id.local_id().0, value);
+    }
+
+    pub(crate) fn static_value(&self, id: EntityId) -> Option<StaticValue> {
+        let local = self.local.borrow();
+        if local.id == id.arena_id() {
+            local.static_value(id.local_id())
+        } else {
+            self.refs.borrow().static_value(id)
+        }
+    }
+
+    pub(crate) fn set_static_range(&self, id: EntityId, range: StaticRange) {
+        let mut local = self.local.borrow_mut();
+        assert_eq!(id.arena_id(), local.id);
+        local.static_ranges.insert(id.local_id().0, range);
+    }
+
+    pub(crate) fn static_range(&self, id: EntityId) -> Option<StaticRange> {
+        let local = self.local.borrow();
+        if local.id == id.arena_id() {
+            local.static_range(id.local_id())
+        } else {
+            self.refs.borrow().static_range(id)
+        }
+    }
+
     pub fn get_type(&self, id: EntityId) -> TypeEnt {
         TypeEnt::from_any(self.get(id)).unwrap()
     }
@@ -35,6 +35,11 @@ pub enum Design<'a> {
     /// ```
     InterfacePackageInstance(Region<'a>),
     Context(Region<'a>),
+    /// An alias of a package, i.e.,
+    /// ```vhdl
+    /// alias foo is work.bar;
+    /// ```
+    Alias(DesignEnt<'a>),
 }
 
 impl<'a> Design<'a> {
@@ -49,6 +54,21 @@ impl<'a> Design<'a> {
             UninstPackage(..) => "uninstantiated package",
             PackageInstance(_) | InterfacePackageInstance(_) => "package instance",
             Context(..) => "context",
+            Alias(..) => "alias",
+        }
+    }
+
+    /// The region of declarations of a primary unit
+    pub fn region(&self) -> Option<&Region<'a>> {
+        use Design::*;
+        match self {
+            Entity(_, region)
+            | Package(_, region)
+            | UninstPackage(_, region)
+            | PackageInstance(region)
+            | InterfacePackageInstance(region)
+            | Context(region) => Some(region),
+            Architecture(..) | Configuration | PackageBody | Alias(..) => None,
         }
     }
 }
@@ -94,6 +114,7 @@ impl<'a> DesignEnt<'a> {
                     ))
                 }
             }
+            Design::Alias(ref design) => design.selected(ctx, prefix_pos, suffix),
             _ => Err(Diagnostic::invalid_selected_name_prefix(
                 self,
                 &prefix_pos.pos(ctx),
@@ -76,19 +76,24 @@ impl<'a> InterfaceEnt<'a> {
         self.interface_class() == InterfaceClass::Signal
     }
 
-    pub fn is_out_or_inout_signal(&self) -> bool {
+    pub fn mode(&self) -> Option<&'a InterfaceMode<'a>> {
         match self.ent.kind() {
-            AnyEntKind::Object(obj) => {
-                obj.class == ObjectClass::Signal
-                    && matches!(
-                        obj.mode(),
-                        Some(InterfaceMode::Simple(Mode::Out | Mode::InOut))
-                    )
-            }
-            _ => false,
+            AnyEntKind::Object(obj) => obj.mode(),
+            _ => None,
         }
     }
 
+    pub fn is_out_or_inout(&self) -> bool {
+        matches!(
+            self.mode(),
+            Some(InterfaceMode::Simple(Mode::Out | Mode::InOut))
+        )
+    }
+
+    pub fn is_out_or_inout_signal(&self) -> bool {
+        self.is_signal() && self.is_out_or_inout()
+    }
+
     pub fn type_mark(&self) -> TypeEnt<'a> {
         match self.ent.kind() {
             AnyEntKind::Object(obj) => obj.subtype.type_mark(),
@@ -287,8 +292,12 @@ impl<'a> RecordElement<'a> {
     }
 
     pub fn type_mark(&self) -> TypeEnt<'a> {
+        self.subtype().type_mark()
+    }
+
+    pub fn subtype(&self) -> &'a Subtype<'a> {
         match self.ent.kind() {
-            AnyEntKind::ElementDeclaration(subtype) => subtype.type_mark(),
+            AnyEntKind::ElementDeclaration(subtype) => subtype,
             _ => {
                 unreachable!();
             }
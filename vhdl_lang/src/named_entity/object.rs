@@ -8,6 +8,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::ops::Deref;
 
 use super::*;
+use crate::ast::Direction;
 use crate::ast::InterfaceType;
 use crate::ast::Mode;
 use crate::ast::ObjectClass;
@@ -69,6 +70,22 @@ impl<'a> ObjectEnt<'a> {
         format!("{} '{}'", self.class(), self.designator())
     }
 
+    /// The kind of interface or else the class of the object together with its designator,
+    /// such as `port 'clk'`
+    pub fn describe_class(&self) -> String {
+        match self.object().iface {
+            Some(ObjectInterface::Generic) => format!("generic '{}'", self.designator()),
+            Some(ObjectInterface::Port(_)) => format!("port '{}'", self.designator()),
+            Some(ObjectInterface::Parameter(_)) if self.class() == ObjectClass::Constant => {
+                format!("parameter '{}'", self.designator())
+            }
+            Some(ObjectInterface::Parameter(_)) => {
+                format!("{} parameter '{}'", self.class(), self.designator())
+            }
+            None => self.describe_name(),
+        }
+    }
+
     pub fn object(&self) -> &'a Object<'a> {
         if let AnyEntKind::Object(object) = self.ent.actual_kind() {
             object
@@ -137,6 +154,76 @@ pub struct Object<'a> {
     pub has_default: bool,
 }
 
+/// A scalar value that is known during analysis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaticValue {
+    Integer(i64),
+    /// The position of an enumeration literal within its type
+    Enum(usize),
+    /// A physical value as a multiple of the primary unit
+    Physical(i64),
+}
+
+/// The range of a scalar subtype whose bounds are known during analysis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaticRange {
+    pub left: StaticValue,
+    pub direction: Direction,
+    pub right: StaticValue,
+}
+
+impl StaticRange {
+    /// Values of a different kind than the bounds are considered to be contained
+    pub fn contains(&self, value: StaticValue) -> bool {
+        let (low, high) = match self.direction {
+            Direction::Ascending => (self.left, self.right),
+            Direction::Descending => (self.right, self.left),
+        };
+        use StaticValue::*;
+        match (low, value, high) {
+            (Integer(low), Integer(value), Integer(high))
+            | (Physical(low), Physical(value), Physical(high)) => low <= value && value <= high,
+            (Enum(low), Enum(value), Enum(high)) => low <= value && value <= high,
+            _ => true,
+        }
+    }
+
+    /// A null range has no values, such as `1 to 0`
+    pub fn is_null(&self) -> bool {
+        !self.contains(self.left)
+    }
+
+    /// The number of values within the range, which is zero for a null range
+    pub fn length(&self) -> Option<i64> {
+        let (low, high) = match self.direction {
+            Direction::Ascending => (self.left, self.right),
+            Direction::Descending => (self.right, self.left),
+        };
+        use StaticValue::*;
+        let (low, high) = match (low, high) {
+            (Integer(low), Integer(high)) => (low, high),
+            (Enum(low), Enum(high)) => (i64::try_from(low).ok()?, i64::try_from(high).ok()?),
+            _ => return None,
+        };
+        Some(high.checked_sub(low)?.checked_add(1)?.max(0))
+    }
+
+    /// The value at `offset` positions from the left bound in the direction of the range
+    pub fn nth(&self, offset: i64) -> Option<StaticValue> {
+        let offset = match self.direction {
+            Direction::Ascending => offset,
+            Direction::Descending => offset.checked_neg()?,
+        };
+        match self.left {
+            StaticValue::Integer(left) => Some(StaticValue::Integer(left.checked_add(offset)?)),
+            StaticValue::Enum(left) => Some(StaticValue::Enum(
+                usize::try_from(i64::try_from(left).ok()?.checked_add(offset)?).ok()?,
+            )),
+            StaticValue::Physical(_) => None,
+        }
+    }
+}
+
 impl<'a> Object<'a> {
     pub(crate) fn const_param(subtype: Subtype<'a>) -> Object<'a> {
         Object {
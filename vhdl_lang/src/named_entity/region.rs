@@ -131,9 +131,35 @@ impl<'a> Region<'a> {
         }
     }
 
+    fn check_incomplete_types_are_completed(&self, diagnostics: &mut dyn DiagnosticHandler) {
+        for ent in self.entities.values() {
+            if let AnyEntKind::Type(Type::Incomplete(decl_pos)) = ent.first_kind() {
+                // A full declaration that could not be analyzed has already been reported
+                if ent.first().decl_pos() != Some(decl_pos) {
+                    continue;
+                }
+                diagnostics.push(
+                    Diagnostic::new(
+                        decl_pos,
+                        format!(
+                            "Missing full type declaration of incomplete type '{}'",
+                            ent.designator()
+                        ),
+                        ErrorCode::MissingFullTypeDeclaration,
+                    )
+                    .related(
+                        decl_pos,
+                        "The full type declaration shall occur immediately within the same declarative part",
+                    ),
+                );
+            }
+        }
+    }
+
     pub(crate) fn close(&self, diagnostics: &mut dyn DiagnosticHandler) {
         self.check_deferred_constant_pairs(diagnostics);
         self.check_protected_types_have_body(diagnostics);
+        self.check_incomplete_types_are_completed(diagnostics);
     }
 
     pub fn add(&mut self, ent: EntRef<'a>, diagnostics: &mut dyn DiagnosticHandler) {
@@ -218,33 +244,134 @@ impl<'a> Region<'a> {
             })
             .filter(|ent| ent.is_explicit())
     }
+
+    /// A stable textual representation of the explicit declarations of the region.
+    /// Each declaration is on its own line with its kind, designator and type or signature,
+    /// sorted so that the output can be compared against golden files
+    pub fn dump(&self) -> String {
+        let mut lines: Vec<_> = self.immediates().map(dump_entity).collect();
+        lines.sort();
+        lines.join("\n")
+    }
+}
+
+fn dump_entity(ent: EntRef) -> String {
+    let type_mark = |typ: TypeEnt| EntRef::from(typ).designator().to_string();
+
+    let suffix = match ent.kind() {
+        AnyEntKind::Object(object) => match object.iface {
+            Some(ObjectInterface::Port(ref mode) | ObjectInterface::Parameter(ref mode)) => {
+                format!(" : {mode} {}", type_mark(object.subtype.type_mark()))
+            }
+            Some(ObjectInterface::Generic) | None => {
+                format!(" : {}", type_mark(object.subtype.type_mark()))
+            }
+        },
+        AnyEntKind::Overloaded(overloaded) => format!(" {}", overloaded.signature().describe()),
+        AnyEntKind::DeferredConstant(subtype)
+        | AnyEntKind::File(subtype)
+        | AnyEntKind::ElementDeclaration(subtype)
+        | AnyEntKind::View(subtype)
+        | AnyEntKind::Type(Type::Subtype(subtype))
+        | AnyEntKind::Type(Type::Access(subtype)) => {
+            format!(" : {}", type_mark(subtype.type_mark()))
+        }
+        AnyEntKind::ObjectAlias { type_mark: typ, .. }
+        | AnyEntKind::ExternalAlias { type_mark: typ, .. }
+        | AnyEntKind::InterfaceFile(typ)
+        | AnyEntKind::Attribute(typ)
+        | AnyEntKind::PhysicalLiteral(typ)
+        | AnyEntKind::Type(Type::Alias(typ)) => format!(" : {}", type_mark(*typ)),
+        _ => String::new(),
+    };
+
+    let kind = match ent.kind() {
+        AnyEntKind::Object(Object {
+            iface: Some(ObjectInterface::Generic),
+            class,
+            ..
+        }) => format!("generic {class}"),
+        AnyEntKind::Object(Object {
+            iface: Some(ObjectInterface::Port(_)),
+            ..
+        }) => "port".to_owned(),
+        kind => kind.describe().to_owned(),
+    };
+
+    format!("{kind} {}{suffix}", ent.designator())
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// A non-empty collection of overloaded entities
 pub struct OverloadedName<'a> {
+    // One of the entities, which represents the name such as for its designator
+    first: OverloadedEnt<'a>,
     entities: FnvHashMap<SubprogramKey<'a>, OverloadedEnt<'a>>,
+    // Explicit homographs of the entities above that were made visible by different
+    // use clauses. They are kept so that calls with their signature are ambiguous
+    homographs: Vec<OverloadedEnt<'a>>,
 }
 
 impl<'a> OverloadedName<'a> {
-    pub fn new(entities: Vec<OverloadedEnt>) -> OverloadedName {
-        debug_assert!(!entities.is_empty());
+    /// `None` if there are no entities
+    pub fn new(entities: Vec<OverloadedEnt>) -> Option<OverloadedName> {
         let mut map = FnvHashMap::default();
         for ent in entities.into_iter() {
             map.insert(ent.subprogram_key(), ent);
         }
-        OverloadedName { entities: map }
+        Some(OverloadedName {
+            first: *map.values().next()?,
+            entities: map,
+            homographs: Vec::new(),
+        })
+    }
+
+    /// Overloaded entities that were made potentially visible by use clauses.
+    ///
+    /// LRM 12.4: An explicit declaration hides an implicit homograph,
+    /// other homographs are not directly visible and any call to them is ambiguous.
+    /// `None` if there are no entities
+    pub fn new_visible(entities: Vec<OverloadedEnt<'a>>) -> Option<OverloadedName<'a>> {
+        let mut entities = entities.into_iter();
+        let mut name = OverloadedName::single(entities.next()?);
+        for ent in entities {
+            match name.entities.entry(ent.subprogram_key()) {
+                Entry::Occupied(mut entry) => {
+                    let old_ent = *entry.get();
+                    if old_ent.is_implicit() && ent.is_explicit() {
+                        if name.first.subprogram_key() == ent.subprogram_key() {
+                            name.first = ent;
+                        }
+                        entry.insert(ent);
+                    } else if old_ent.is_explicit()
+                        && ent.is_explicit()
+                        && old_ent.as_actual().id() != ent.as_actual().id()
+                        && !ent.is_declared_by(&old_ent)
+                        && !old_ent.is_declared_by(&ent)
+                    {
+                        name.homographs.push(ent);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(ent);
+                }
+            }
+        }
+        Some(name)
     }
 
     pub fn single(ent: OverloadedEnt) -> OverloadedName {
         let mut map = FnvHashMap::default();
         map.insert(ent.subprogram_key(), ent);
-        OverloadedName { entities: map }
+        OverloadedName {
+            first: ent,
+            entities: map,
+            homographs: Vec::new(),
+        }
     }
 
     pub fn first(&self) -> OverloadedEnt<'a> {
-        let first_key = self.entities.keys().next().unwrap();
-        *self.entities.get(first_key).unwrap()
+        self.first
     }
 
     pub fn designator(&self) -> &Designator {
@@ -252,15 +379,18 @@ impl<'a> OverloadedName<'a> {
     }
 
     pub fn len(&self) -> usize {
-        self.entities.len()
+        self.entities.len() + self.homographs.len()
     }
 
     pub fn entities(&self) -> impl Iterator<Item = OverloadedEnt<'a>> + '_ {
-        self.entities.values().cloned()
+        self.entities
+            .values()
+            .cloned()
+            .chain(self.homographs.iter().cloned())
     }
 
     pub fn sorted_entities(&self) -> Vec<OverloadedEnt<'a>> {
-        let mut res: Vec<_> = self.entities.values().cloned().collect();
+        let mut res: Vec<_> = self.entities().collect();
         res.sort_by(|x, y| x.decl_pos().cmp(&y.decl_pos()));
         res
     }
@@ -273,6 +403,21 @@ impl<'a> OverloadedName<'a> {
         self.entities.get(key).cloned()
     }
 
+    /// All entities with the signature, there is more than one when homographs
+    /// were made visible from different regions
+    pub fn with_signature(&self, key: &SubprogramKey) -> Vec<OverloadedEnt<'a>> {
+        self.entities
+            .get(key)
+            .into_iter()
+            .chain(
+                self.homographs
+                    .iter()
+                    .filter(|ent| ent.subprogram_key() == *key),
+            )
+            .cloned()
+            .collect()
+    }
+
     #[allow(clippy::if_same_then_else)]
     fn insert(&mut self, ent: OverloadedEnt<'a>) -> Result<(), Diagnostic> {
         match self.entities.entry(ent.subprogram_key()) {
@@ -280,6 +425,9 @@ impl<'a> OverloadedName<'a> {
                 let old_ent = entry.get();
 
                 if (old_ent.is_implicit() && ent.is_explicit()) || (ent.is_declared_by(old_ent)) {
+                    if self.first.subprogram_key() == ent.subprogram_key() {
+                        self.first = ent;
+                    }
                     entry.insert(ent);
                     return Ok(());
                 } else if old_ent.is_implicit()
@@ -319,8 +467,13 @@ impl<'a> OverloadedName<'a> {
     // Merge overloaded names where self is overloaded names from an
     // immediate/enclosing region and visible are overloaded names that have been made visible
     pub(crate) fn with_visible(mut self, visible: Self) -> Self {
+        // Ignore visible entites that conflict with those in the enclosing region
+        for homograph in visible.homographs.into_iter() {
+            if !self.entities.contains_key(&homograph.subprogram_key()) {
+                self.homographs.push(homograph);
+            }
+        }
         for (signature, visible_entity) in visible.entities.into_iter() {
-            // Ignore visible entites that conflict with those in the enclosing region
             if let Entry::Vacant(entry) = self.entities.entry(signature) {
                 entry.insert(visible_entity);
             }
@@ -339,13 +492,14 @@ pub enum NamedEntities<'a> {
 impl<'a> NamedEntities<'a> {
     pub fn new(ent: EntRef<'a>) -> NamedEntities<'a> {
         match OverloadedEnt::from_any(ent) {
-            Some(ent) => Self::Overloaded(OverloadedName::new(vec![ent])),
+            Some(ent) => Self::Overloaded(OverloadedName::single(ent)),
             None => Self::Single(ent),
         }
     }
 
-    pub fn new_overloaded(named_entities: Vec<OverloadedEnt<'a>>) -> NamedEntities<'a> {
-        Self::Overloaded(OverloadedName::new(named_entities))
+    /// `None` if there are no entities
+    pub fn new_overloaded(named_entities: Vec<OverloadedEnt<'a>>) -> Option<NamedEntities<'a>> {
+        OverloadedName::new(named_entities).map(Self::Overloaded)
     }
 
     pub fn into_non_overloaded(self) -> Result<EntRef<'a>, OverloadedName<'a>> {
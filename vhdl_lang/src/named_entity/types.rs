@@ -28,8 +28,9 @@ pub enum Type<'a> {
     Physical,
     Access(Subtype<'a>),
     Record(RecordRegion<'a>),
-    // Incomplete type will be overwritten when full type is found
-    Incomplete,
+    // Incomplete type will be overwritten when full type is found,
+    // the position is that of the incomplete type declaration
+    Incomplete(SrcPos),
     Subtype(Subtype<'a>),
     // The region of the protected type which needs to be extendend by the body
     Protected(Region<'a>, bool),
@@ -57,7 +58,7 @@ impl<'a> Type<'a> {
             Type::Physical => "physical type",
             Type::Access(..) => "access type",
             Type::Subtype(..) => "subtype",
-            Type::Incomplete => "type",
+            Type::Incomplete(_) => "type",
             Type::Interface => "type",
             Type::File => "file type",
             Type::Protected(..) => "protected type",
@@ -143,6 +144,34 @@ impl<'a> TypeEnt<'a> {
         }
     }
 
+    /// The static length of a constrained one-dimensional array subtype
+    pub fn static_length(&self) -> Option<i64> {
+        match self.kind() {
+            Type::Alias(alias) => alias.static_length(),
+            Type::Subtype(subtype) => subtype.static_length(),
+            _ => None,
+        }
+    }
+
+    /// The static index range of a constrained one-dimensional array subtype
+    pub fn static_index_range(&self) -> Option<StaticRange> {
+        match self.kind() {
+            Type::Alias(alias) => alias.static_index_range(),
+            Type::Subtype(subtype) => subtype.static_index_range(),
+            _ => None,
+        }
+    }
+
+    /// True if the type is a resolved subtype or an array of resolved elements
+    pub fn is_resolved(&self) -> bool {
+        match self.kind() {
+            Type::Alias(alias) => alias.is_resolved(),
+            Type::Subtype(subtype) => subtype.is_resolved(),
+            Type::Array { elem_type, .. } => elem_type.is_resolved(),
+            _ => false,
+        }
+    }
+
     pub fn base_type(&self) -> TypeEnt<'a> {
         match self.kind() {
             Type::Alias(alias) => alias.base_type(),
@@ -196,6 +225,19 @@ impl<'a> TypeEnt<'a> {
         prefix_pos: TokenSpan,
         suffix: &WithToken<WithRef<Designator>>,
     ) -> Result<TypedSelection<'a>, Diagnostic> {
+        // Operators are only declared within packages, so they cannot be selected from a value
+        if let Designator::OperatorSymbol(_) = suffix.item.item {
+            return Err(Diagnostic::new(
+                suffix.pos(ctx),
+                format!(
+                    "Operator symbol {} can only be selected from a package or library, not from a value of {}",
+                    suffix.item.item,
+                    self.describe()
+                ),
+                ErrorCode::MismatchedKinds,
+            ));
+        }
+
         match self.kind() {
             Type::Record(ref region) => {
                 if let Some(decl) = region.lookup(suffix.designator()) {
@@ -224,14 +266,18 @@ impl<'a> TypeEnt<'a> {
                         }
                     }
                 } else {
-                    Err(Diagnostic::no_declaration_within(
-                        &self,
+                    Err(Diagnostic::new(
                         suffix.pos(ctx),
-                        &suffix.item.item,
+                        format!(
+                            "No method '{}' in protected type '{}'",
+                            suffix.item.item,
+                            self.designator()
+                        ),
+                        ErrorCode::Unresolved,
                     ))
                 }
             }
-            Type::Incomplete => Err(Diagnostic::new(
+            Type::Incomplete(_) => Err(Diagnostic::new(
                 prefix_pos.pos(ctx),
                 "Cannot select incomplete type before full type definition",
                 ErrorCode::MismatchedKinds,
@@ -458,11 +504,70 @@ impl<'a> Deref for BaseType<'a> {
 #[derive(Clone, Copy)]
 pub struct Subtype<'a> {
     pub(crate) type_mark: TypeEnt<'a>,
+    /// The static range of a range constraint of a scalar subtype
+    pub(crate) range: Option<StaticRange>,
+    /// The subtype indication has a range constraint, which need not be static
+    pub(crate) range_constrained: bool,
+    /// The static length of an index constraint of a one-dimensional array subtype
+    pub(crate) length: Option<i64>,
+    /// The static index range of an index constraint of a one-dimensional array subtype
+    pub(crate) index_range: Option<StaticRange>,
+    /// The subtype indication has a resolution function
+    pub(crate) resolved: bool,
 }
 
 impl<'a> Subtype<'a> {
     pub fn new(type_mark: TypeEnt<'a>) -> Subtype<'a> {
-        Subtype { type_mark }
+        Subtype {
+            type_mark,
+            range: None,
+            range_constrained: false,
+            length: None,
+            index_range: None,
+            resolved: false,
+        }
+    }
+
+    pub fn with_range(type_mark: TypeEnt<'a>, range: Option<StaticRange>) -> Subtype<'a> {
+        Subtype {
+            type_mark,
+            range,
+            range_constrained: true,
+            length: None,
+            index_range: None,
+            resolved: false,
+        }
+    }
+
+    pub fn with_length(type_mark: TypeEnt<'a>, length: Option<i64>) -> Subtype<'a> {
+        Subtype {
+            type_mark,
+            range: None,
+            range_constrained: false,
+            length,
+            index_range: None,
+            resolved: false,
+        }
+    }
+
+    pub fn with_resolution(self, resolved: bool) -> Subtype<'a> {
+        Subtype { resolved, ..self }
+    }
+
+    /// True if the subtype or the type mark it refers to is resolved
+    pub fn is_resolved(&self) -> bool {
+        self.resolved || self.type_mark.is_resolved()
+    }
+
+    /// The length of the index constraint or else the length of the type mark
+    pub fn static_length(&self) -> Option<i64> {
+        self.length.or_else(|| self.type_mark.static_length())
+    }
+
+    /// The index range of the index constraint or else the index range of the type mark
+    pub fn static_index_range(&self) -> Option<StaticRange> {
+        self.index_range
+            .or_else(|| self.type_mark.static_index_range())
     }
 
     pub fn type_mark(&self) -> TypeEnt<'a> {
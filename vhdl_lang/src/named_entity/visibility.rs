@@ -63,6 +63,14 @@ impl<'a> Visibility<'a> {
         self.visible.values().flatten().map(|entry| entry.1.entity)
     }
 
+    /// The designators of all potentially visible named entities
+    pub fn designators(&self) -> impl Iterator<Item = &Designator> + '_ {
+        self.all_in_regions
+            .iter()
+            .flat_map(|visible| visible.region.entities.keys())
+            .chain(self.visible.keys())
+    }
+
     pub fn add_context_visibility(
         &mut self,
         visible_pos: Option<&SrcPos>,
@@ -190,12 +198,13 @@ impl<'a> Visible<'a> {
         if named_entities.is_empty() {
             Ok(None)
         } else if named_entities.iter().all(|ent| ent.is_overloaded()) {
-            Ok(Some(NamedEntities::new_overloaded(
+            Ok(OverloadedName::new_visible(
                 named_entities
                     .into_iter()
-                    .map(|ent| OverloadedEnt::from_any(ent).unwrap())
+                    .filter_map(OverloadedEnt::from_any)
                     .collect(),
-            )))
+            )
+            .map(NamedEntities::Overloaded))
         } else if named_entities.len() == 1 {
             Ok(Some(NamedEntities::new(named_entities.pop().unwrap())))
         } else {
@@ -4,15 +4,22 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
+use crate::analysis::{DesignInterface, DesignRoot, InlayHint, InstanceTree, SemanticToken};
 use crate::ast::search::Searcher;
-use crate::ast::DesignFile;
+use crate::ast::{DesignFile, Designator, UnitId};
 use crate::completion::{list_completion_options, CompletionItem};
 use crate::config::Config;
+use crate::lint::component_entity::ComponentEntityLinter;
 use crate::lint::dead_code::UnusedDeclarationsLinter;
+use crate::lint::infinite_recursion::InfiniteRecursionLinter;
+use crate::lint::multiple_drivers::MultipleDriversLinter;
+use crate::lint::read_after_assignment::ReadAfterAssignmentLinter;
+use crate::lint::sensitivity_list::IncompleteSensitivityLinter;
+use crate::lint::unused_port::UnusedPortLinter;
+use crate::lint::unused_use_clause::UnusedUseClauseLinter;
 use crate::named_entity::{AnyEnt, EntRef};
 use crate::standard::VHDLStandard;
-use crate::syntax::VHDLParser;
+use crate::syntax::{Suppressions, VHDLParser};
 use crate::{data::*, EntHierarchy, EntityId};
 use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
@@ -26,23 +33,125 @@ pub struct Project {
     files: FnvHashMap<FilePath, SourceFile>,
     empty_libraries: FnvHashSet<Symbol>,
     lint: Option<UnusedDeclarationsLinter>,
+    component_entity_lint: Option<ComponentEntityLinter>,
+    unused_port_lint: Option<UnusedPortLinter>,
+    infinite_recursion_lint: Option<InfiniteRecursionLinter>,
+    multiple_drivers_lint: Option<MultipleDriversLinter>,
+    sensitivity_list_lint: Option<IncompleteSensitivityLinter>,
+    read_after_assignment_lint: Option<ReadAfterAssignmentLinter>,
+    unused_use_clause_lint: Option<UnusedUseClauseLinter>,
 }
 
 impl Project {
     pub fn new(vhdl_standard: VHDLStandard) -> Project {
         let parser = VHDLParser::new(vhdl_standard);
         Project {
-            root: DesignRoot::new(parser.symbols.clone()),
+            root: DesignRoot::with_standard(parser.symbols.clone(), vhdl_standard),
             files: FnvHashMap::default(),
             empty_libraries: FnvHashSet::default(),
             parser,
             lint: None,
+            component_entity_lint: None,
+            unused_port_lint: None,
+            infinite_recursion_lint: None,
+            multiple_drivers_lint: None,
+            sensitivity_list_lint: None,
+            read_after_assignment_lint: None,
+            unused_use_clause_lint: None,
             config: Config::default(),
         }
     }
 
     pub fn enable_unused_declaration_detection(&mut self) {
-        self.lint = Some(UnusedDeclarationsLinter::default());
+        let mut lint = UnusedDeclarationsLinter::default();
+        lint.ignore_entity_interfaces = self.unused_port_lint.is_some();
+        self.lint = Some(lint);
+    }
+
+    /// Report ports and generics of entities that are not used by any of their architectures.
+    /// These are reported instead of as unused declarations.
+    pub fn enable_unused_port_detection(&mut self) {
+        self.unused_port_lint = Some(UnusedPortLinter::default());
+        if let Some(ref mut lint) = self.lint {
+            lint.ignore_entity_interfaces = true;
+        }
+    }
+
+    /// Report components whose generics or ports do not match the entity they are bound to by default
+    pub fn enable_component_entity_check(&mut self) {
+        self.component_entity_lint = Some(ComponentEntityLinter);
+    }
+
+    /// Report subprograms that call themselves before any statement that could end the recursion
+    pub fn enable_infinite_recursion_detection(&mut self) {
+        self.infinite_recursion_lint = Some(InfiniteRecursionLinter);
+    }
+
+    /// Report signals that are driven by more than one concurrent statement of a design unit.
+    /// Signals of a resolved subtype such as std_logic are only reported if `warn_resolved` is set
+    pub fn enable_multiple_driver_detection(&mut self, warn_resolved: bool) {
+        self.multiple_drivers_lint = Some(MultipleDriversLinter { warn_resolved });
+    }
+
+    /// Report signals that are read in a combinational process but are not in its sensitivity list.
+    /// Processes with wait statements and processes that check for a clock edge are not reported
+    pub fn enable_incomplete_sensitivity_detection(&mut self) {
+        self.sensitivity_list_lint = Some(IncompleteSensitivityLinter);
+    }
+
+    /// Report signals that are read after being assigned in the same process without the
+    /// process suspending in between, which does not return the assigned value
+    pub fn enable_read_after_assignment_detection(&mut self) {
+        self.read_after_assignment_lint = Some(ReadAfterAssignmentLinter);
+    }
+
+    /// Report use clauses that make nothing visible that is used
+    /// and use clauses that are hidden by local declarations
+    pub fn enable_unused_use_clause_detection(&mut self) {
+        self.unused_use_clause_lint = Some(UnusedUseClauseLinter);
+    }
+
+    /// Report violations of the standard that vendor tools commonly accept as warnings
+    /// instead of errors, see [DesignRoot::set_lenient]
+    pub fn enable_lenient_analysis(&mut self) {
+        self.root.set_lenient(true);
+    }
+
+    /// Enable the opt-in lints whose error code is given a severity in the `lint` table
+    /// of the configuration, see [Config::is_configured].
+    /// Lints that are already enabled stay enabled.
+    fn enable_configured_lints(&mut self) {
+        if self.config.is_configured(ErrorCode::UnusedPort) && self.unused_port_lint.is_none() {
+            self.enable_unused_port_detection();
+        }
+        if self
+            .config
+            .is_configured(ErrorCode::ComponentEntityMismatch)
+        {
+            self.enable_component_entity_check();
+        }
+        if self.config.is_configured(ErrorCode::InfiniteRecursion) {
+            self.enable_infinite_recursion_detection();
+        }
+        if self.config.is_configured(ErrorCode::MultipleDrivers)
+            && self.multiple_drivers_lint.is_none()
+        {
+            self.enable_multiple_driver_detection(false);
+        }
+        if self.config.is_configured(ErrorCode::IncompleteSensitivity) {
+            self.enable_incomplete_sensitivity_detection();
+        }
+        if self
+            .config
+            .is_configured(ErrorCode::ReadAfterSignalAssignment)
+        {
+            self.enable_read_after_assignment_detection();
+        }
+        if self.config.is_configured(ErrorCode::RedundantUseClause)
+            || self.config.is_configured(ErrorCode::ShadowedUseClause)
+        {
+            self.enable_unused_use_clause_detection();
+        }
     }
 
     /// Create instance from given configuration.
@@ -52,6 +161,7 @@ impl Project {
         let files = project.load_files_from_config(&config, messages);
         project.parse_and_add_files(files, messages);
         project.config = config;
+        project.enable_configured_lints();
         project
     }
 
@@ -60,7 +170,9 @@ impl Project {
     /// kept and parsed from in-memory source (required for incremental document updates).
     pub fn update_config(&mut self, config: Config, messages: &mut dyn MessageHandler) {
         self.parser = VHDLParser::new(config.standard());
-        self.root = DesignRoot::new(self.parser.symbols.clone());
+        let lenient = self.root.is_lenient();
+        self.root = DesignRoot::with_standard(self.parser.symbols.clone(), self.parser.standard);
+        self.root.set_lenient(lenient);
 
         // Reset library associations for known files,
         // all project files are added to the corresponding libraries later on.
@@ -86,6 +198,7 @@ impl Project {
         }
 
         self.config = config;
+        self.enable_configured_lints();
         self.parse_and_add_files(new_files, messages);
     }
 
@@ -101,6 +214,9 @@ impl Project {
             let library_name =
                 Latin1String::from_utf8(library.name()).expect("Library name not latin-1 encoded");
             let library_name = self.parser.symbol(&library_name);
+            if library.is_third_party {
+                self.root.set_third_party(library_name.clone());
+            }
 
             let mut empty_library = true;
             for file_name in library.file_names(messages) {
@@ -159,6 +275,7 @@ impl Project {
                     source,
                     library_names,
                     parser_diagnostics,
+                    suppressions: Suppressions::from_design_file(&design_file),
                     design_file,
                 },
             );
@@ -176,6 +293,14 @@ impl Project {
         libs
     }
 
+    /// Returns the design units that must be re-analyzed when the given sources change.
+    ///
+    /// This is informational only: [`Project::update_source`] followed by [`Project::analyse`]
+    /// already limits re-analysis to these units and reuses the cached result of all others.
+    pub fn affected_by_sources(&self, sources: &[Source]) -> FnvHashSet<UnitId> {
+        self.root.affected_by_sources(sources)
+    }
+
     pub fn get_source(&self, file_name: &Path) -> Option<Source> {
         self.files
             .get(&FilePath::new(file_name))
@@ -205,6 +330,7 @@ impl Project {
                     library_names,
                     parser_diagnostics: vec![],
                     design_file: DesignFile::default(),
+                    suppressions: Suppressions::default(),
                 }
             }
         };
@@ -212,13 +338,25 @@ impl Project {
         source_file.design_file = self
             .parser
             .parse_design_source(source, &mut source_file.parser_diagnostics);
+        source_file.suppressions = Suppressions::from_design_file(&source_file.design_file);
         self.files
             .insert(source.file_path().to_owned(), source_file);
     }
 
     pub fn analyse(&mut self) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
+        self.analyse_to(&mut diagnostics, false);
+        diagnostics
+    }
+
+    /// Analyse the project and push the diagnostics to the handler as soon as they are known.
+    /// Parser diagnostics come first, followed by the diagnostics of each design unit as soon
+    /// as the unit is analyzed and finally the diagnostics of the lints.
+    pub fn analyse_streaming(&mut self, diagnostics: &mut dyn DiagnosticHandler) {
+        self.analyse_to(diagnostics, true)
+    }
 
+    fn analyse_to(&mut self, diagnostics: &mut dyn DiagnosticHandler, streaming: bool) {
         for source_file in self.files.values_mut() {
             let design_file = source_file.take_design_file();
             // Avoid cloning design files for single library
@@ -228,7 +366,20 @@ impl Project {
                 let design_file = design_files.pop().unwrap();
                 self.root.add_design_file(library_name.clone(), design_file);
             }
+        }
 
+        // Diagnostics disabled by comments in the source file are never emitted
+        let mut diagnostics = SuppressedDiagnostics {
+            suppressions: self
+                .files
+                .values()
+                .filter(|source_file| !source_file.suppressions.is_empty())
+                .map(|source_file| (source_file.source.file_name(), &source_file.suppressions))
+                .collect(),
+            inner: diagnostics,
+        };
+
+        for source_file in self.files.values() {
             for diagnostic in source_file.parser_diagnostics.iter().cloned() {
                 diagnostics.push(diagnostic);
             }
@@ -238,13 +389,50 @@ impl Project {
             self.root.ensure_library(library_name.clone());
         }
 
-        let analyzed_units = self.root.analyze(&mut diagnostics);
+        let analyzed_units = if streaming {
+            self.root.analyze_streaming(&mut diagnostics)
+        } else {
+            self.root.analyze(&mut diagnostics)
+        };
 
         if let Some(ref mut lint) = self.lint {
             lint.lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
         }
 
-        diagnostics
+        if let Some(ref lint) = self.component_entity_lint {
+            lint.lint(&self.root, &self.config, &mut diagnostics);
+        }
+
+        if let Some(ref lint) = self.infinite_recursion_lint {
+            lint.lint(&self.root, &self.config, &mut diagnostics);
+        }
+
+        if let Some(ref lint) = self.multiple_drivers_lint {
+            lint.lint(&self.root, &self.config, &mut diagnostics);
+        }
+
+        if let Some(ref lint) = self.sensitivity_list_lint {
+            lint.lint(&self.root, &self.config, &mut diagnostics);
+        }
+
+        if let Some(ref lint) = self.read_after_assignment_lint {
+            lint.lint(&self.root, &self.config, &mut diagnostics);
+        }
+
+        if let Some(ref lint) = self.unused_use_clause_lint {
+            lint.lint(&self.root, &self.config, &mut diagnostics);
+        }
+
+        if let Some(ref mut lint) = self.unused_port_lint {
+            lint.lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
+        }
+    }
+
+    /// Analyse the project and collect all diagnostics grouped by file, using the
+    /// severities of the configuration
+    pub fn diagnostic_report(&mut self) -> DiagnosticReport {
+        let diagnostics = self.analyse();
+        DiagnosticReport::new(&diagnostics, self.config.severities())
     }
 
     /// Search for reference at position
@@ -277,6 +465,32 @@ impl Project {
         self.root.public_symbols()
     }
 
+    /// The public symbols of all libraries whose name contains `query`, ignoring case.
+    /// Symbols whose name starts with `query` come first, each group sorted by name.
+    pub fn workspace_symbols<'a>(&'a self, query: &str) -> Vec<EntRef<'a>> {
+        let query = query.to_lowercase();
+        let mut symbols: Vec<_> = self
+            .public_symbols()
+            .filter(|ent| ent.decl_pos().is_some())
+            .filter_map(|ent| {
+                let name = match ent.designator() {
+                    Designator::Identifier(_) | Designator::Character(_) => {
+                        ent.designator().to_string()
+                    }
+                    Designator::OperatorSymbol(op) => op.to_string(),
+                    Designator::Anonymous(_) => return None,
+                }
+                .to_lowercase();
+                let idx = name.find(&query)?;
+                Some((idx != 0, name, ent))
+            })
+            .collect();
+        symbols.sort_by(|(other1, name1, _), (other2, name2, _)| {
+            (other1, name1).cmp(&(other2, name2))
+        });
+        symbols.into_iter().map(|(_, _, ent)| ent).collect()
+    }
+
     // Find symbols that are public such as primary design units and their interfaces
     pub fn document_symbols<'a>(
         &'a self,
@@ -309,12 +523,59 @@ impl Project {
         self.root.find_all_references(ent)
     }
 
+    /// The fully qualified name of a named entity, see [DesignRoot::full_name]
+    pub fn full_name(&self, ent: &AnyEnt) -> String {
+        self.root.full_name(ent)
+    }
+
+    /// Find all processes and concurrent signal assignments that drive a signal
+    pub fn drivers_of(&self, ent: &AnyEnt) -> Vec<SrcPos> {
+        self.root.drivers_of(ent)
+    }
+
+    /// Find the signals that are implicitly read by each `process(all)` within the source
+    pub fn implied_sensitivity(&self, source: &Source) -> Vec<(SrcPos, Vec<EntRef<'_>>)> {
+        self.root.implied_sensitivity(source)
+    }
+
+    /// Hints for the formals of positional actuals and the return types of function calls
+    pub fn inlay_hints(&self, source: &Source) -> Vec<InlayHint<'_>> {
+        self.root.inlay_hints(source)
+    }
+
+    /// Classify the names within the source by the kind of named entity they denote
+    pub fn semantic_tokens(&self, source: &Source) -> Vec<SemanticToken<'_>> {
+        self.root.semantic_tokens(source)
+    }
+
     /// Get source positions that are not resolved to a declaration
     /// This is used for development to test where the language server is blind
     pub fn find_all_unresolved(&self) -> (usize, Vec<SrcPos>) {
         self.root.find_all_unresolved()
     }
 
+    /// Elaborate the design hierarchy below a top level entity or architecture
+    pub fn elaborate<'a>(&'a self, top: EntRef<'a>) -> Option<InstanceTree<'a>> {
+        self.root.elaborate(top)
+    }
+
+    /// The generics and ports of an entity or a component
+    pub fn interface_of(&self, ent: EntRef) -> Option<DesignInterface> {
+        self.root.interface_of(ent)
+    }
+
+    /// The instantiation statement at the cursor with every generic and port associated,
+    /// laid out using the formatting options of the config,
+    /// see [DesignRoot::instantiation_template_at]
+    pub fn instantiation_template_at(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<(SrcPos, String)> {
+        self.root
+            .instantiation_template_at(source, cursor, self.config.formatting())
+    }
+
     pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
         self.files.values()
     }
@@ -349,11 +610,29 @@ fn multiply<T: Clone>(value: T, n: usize) -> Vec<T> {
     }
 }
 
+struct SuppressedDiagnostics<'a> {
+    suppressions: FnvHashMap<&'a Path, &'a Suppressions>,
+    inner: &'a mut dyn DiagnosticHandler,
+}
+
+impl DiagnosticHandler for SuppressedDiagnostics<'_> {
+    fn push(&mut self, diagnostic: Diagnostic) {
+        let is_suppressed = self
+            .suppressions
+            .get(diagnostic.pos.file_name())
+            .is_some_and(|suppressions| suppressions.is_suppressed(&diagnostic));
+        if !is_suppressed {
+            self.inner.push(diagnostic);
+        }
+    }
+}
+
 pub struct SourceFile {
     library_names: FnvHashSet<Symbol>,
     source: Source,
     design_file: DesignFile,
     parser_diagnostics: Vec<Diagnostic>,
+    suppressions: Suppressions,
 }
 
 impl SourceFile {
@@ -437,6 +716,349 @@ end architecture;
         assert_eq!(diag.message, "Duplicate architecture 'rtl' of entity 'ent'")
     }
 
+    #[test]
+    fn diagnostic_report_uses_configured_severities() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            &vhdl_file_path,
+            "
+entity ent is
+end ent;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+architecture rtl of ent is
+begin
+end architecture;
+",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+
+[lint]
+duplicate = 'warning'
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let report = project.diagnostic_report();
+        assert_eq!(report.files.len(), 1);
+        let file = report.files.first().unwrap();
+        assert_eq!(file.path, vhdl_file_path);
+        assert_eq!(file.diagnostics.len(), 1);
+        let diag = file.diagnostics.first().unwrap();
+        assert_eq!(diag.code, ErrorCode::Duplicate);
+        assert_eq!(diag.severity, Severity::Warning);
+        assert_eq!(diag.related.len(), 1);
+        assert_eq!(diag.related[0].path, vhdl_file_path);
+    }
+
+    #[test]
+    fn opt_in_lints_are_enabled_by_configured_severity() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  signal s : bit;
+begin
+  s <= '0';
+  s <= '1';
+end architecture;
+",
+        )
+        .unwrap();
+
+        let config_str = format!(
+            "
+[libraries]
+std.files = ['{}/../vhdl_libraries/std/*.vhd']
+lib.files = ['file.vhd']
+        ",
+            std::env::var("CARGO_MANIFEST_DIR").unwrap()
+        );
+        let config = Config::from_str(&config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        assert_eq!(project.analyse(), vec![]);
+
+        let config_str = format!("{config_str}\n[lint]\nmultiple_drivers = 'error'");
+        let config = Config::from_str(&config_str, root.path()).unwrap();
+        project.update_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let codes: Vec<_> = project
+            .analyse()
+            .into_iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect();
+        assert_eq!(codes, vec![ErrorCode::MultipleDrivers]);
+    }
+
+    #[test]
+    fn third_party_libraries_are_not_checked_for_impure_calls_and_unreachable_code() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            "
+package pkg is
+  impure function counter return natural;
+  function get return natural;
+  procedure proc;
+end package;
+
+package body pkg is
+  impure function counter return natural is
+  begin
+    return 0;
+  end function;
+
+  function get return natural is
+  begin
+    return counter;
+  end function;
+
+  procedure proc is
+  begin
+    return;
+    report \"unreachable\";
+  end procedure;
+end package body;
+",
+        )
+        .unwrap();
+
+        let config_str = |is_third_party: bool| {
+            format!(
+                "
+[libraries]
+std.files = ['{}/../vhdl_libraries/std/*.vhd']
+lib.files = ['file.vhd']
+lib.is_third_party = {is_third_party}
+        ",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            )
+        };
+        let config = Config::from_str(&config_str(false), root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let codes: Vec<_> = project
+            .analyse()
+            .into_iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![ErrorCode::ImpureCall, ErrorCode::UnreachableCode]
+        );
+
+        let config = Config::from_str(&config_str(true), root.path()).unwrap();
+        project.update_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        assert_eq!(project.analyse(), vec![]);
+    }
+
+    #[test]
+    fn comments_suppress_diagnostics() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        let contents = "
+entity ent is
+end ent;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+architecture rtl of ent is -- vhdl_lang: disable=duplicate
+begin
+end architecture;
+";
+        std::fs::write(&vhdl_file_path, contents).unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+
+        let source = project.get_source(&vhdl_file_path).unwrap();
+        source.change(
+            None,
+            &contents.replace("-- vhdl_lang: disable=duplicate", ""),
+        );
+        project.update_source(&source);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ErrorCode::Duplicate);
+    }
+
+    #[test]
+    fn comments_suppress_unused_ports() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            "
+entity ent is
+  port (
+    spare : out bit; -- vhdl_lang: disable=unused_port
+    valid : out bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+        )
+        .unwrap();
+
+        let config_str = format!(
+            "
+[libraries]
+std.files = ['{}/../vhdl_libraries/std/*.vhd']
+lib.files = ['file.vhd']
+
+[lint]
+unused_port = 'warning'
+        ",
+            std::env::var("CARGO_MANIFEST_DIR").unwrap()
+        );
+
+        let config = Config::from_str(&config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ErrorCode::UnusedPort);
+        assert_eq!(
+            diagnostics[0].message,
+            "Port 'valid' is not used by any architecture of entity 'ent'"
+        );
+    }
+
+    /// Hands each diagnostic to another thread and waits until it is received
+    struct RendezvousHandler(std::sync::mpsc::SyncSender<Diagnostic>);
+
+    impl DiagnosticHandler for RendezvousHandler {
+        fn push(&mut self, diagnostic: Diagnostic) {
+            self.0.send(diagnostic).unwrap();
+        }
+    }
+
+    #[test]
+    fn diagnostics_are_streamed_during_analysis() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("pkg.vhd"),
+            "
+package pkg is
+  constant c0 : natural := missing;
+end package;
+",
+        )
+        .unwrap();
+        std::fs::write(
+            root.path().join("blocked.vhd"),
+            "
+package blocked is
+  constant c1 : natural := also_missing;
+end package;
+",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['pkg.vhd', 'blocked.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        // The analysis of the second unit waits until it is released, the analysis of the
+        // other units continues on the remaining threads or after the blocked unit
+        let started = std::sync::atomic::AtomicUsize::new(0);
+        let (blocked_sender, blocked_receiver) = std::sync::mpsc::channel();
+        let blocked_sender = std::sync::Mutex::new(blocked_sender);
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let released = std::sync::Mutex::new(released);
+        project.root.before_streamed_analysis = Some(Box::new(move |id: &crate::ast::UnitId| {
+            if started.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 1 {
+                let _ = blocked_sender
+                    .lock()
+                    .unwrap()
+                    .send(id.primary_name().name_utf8());
+                let _ = released.lock().unwrap().recv();
+            }
+        }));
+
+        let timeout = std::time::Duration::from_secs(30);
+        let (sender, receiver) = std::sync::mpsc::sync_channel(0);
+        let mut received = std::thread::scope(|scope| {
+            scope.spawn(|| project.analyse_streaming(&mut RendezvousHandler(sender)));
+
+            let first = receiver.recv_timeout(timeout);
+            let blocked = blocked_receiver.recv_timeout(timeout);
+            // Release before asserting, otherwise a failure would never end the scope
+            release.send(()).unwrap();
+
+            // The diagnostic of one unit arrives while the analysis of another is blocked
+            let first = first.unwrap();
+            let blocked = blocked.unwrap();
+            assert_eq!(first.code, ErrorCode::Unresolved);
+            assert_ne!(
+                first.pos.file_name().file_stem(),
+                Some(std::ffi::OsStr::new(&blocked))
+            );
+
+            let mut received = vec![first];
+            received.extend(receiver.iter());
+            received
+        });
+        project.root.before_streamed_analysis = None;
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut expected = Project::from_config(config, &mut messages).analyse();
+        let key = |diagnostic: &Diagnostic| {
+            (
+                diagnostic.pos.file_name().to_owned(),
+                diagnostic.pos.start(),
+                diagnostic.message.clone(),
+            )
+        };
+        received.sort_by_key(key);
+        expected.sort_by_key(key);
+        assert_eq!(received, expected);
+
+        // Diagnostics of units which are already analyzed are emitted as well
+        let mut again = Vec::new();
+        project.analyse_streaming(&mut again);
+        again.sort_by_key(key);
+        assert_eq!(again, expected);
+    }
+
     /// Test that the same file can be added to several libraries
     #[test]
     fn test_same_file_in_multiple_libraries() {
@@ -26,6 +26,7 @@ mod separated_list;
 mod sequential_statement;
 mod subprogram;
 mod subtype_indication;
+mod suppression;
 mod type_declaration;
 mod waveform;
 
@@ -34,4 +35,5 @@ pub mod test;
 mod view;
 
 pub use parser::{ParserResult, VHDLParser};
+pub(crate) use suppression::Suppressions;
 pub use tokens::*;
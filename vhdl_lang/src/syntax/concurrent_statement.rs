@@ -26,6 +26,7 @@ use crate::syntax::{Kind, TokenAccess};
 use crate::TokenId;
 use vhdl_lang::syntax::parser::ParsingContext;
 use vhdl_lang::TokenSpan;
+use vhdl_lang::VHDLStandard::VHDL2008;
 
 /// LRM 11.2 Block statement
 pub fn parse_block_statement(
@@ -182,6 +183,11 @@ pub fn parse_process_statement(
         peek_token!(ctx.stream, token,
         All => {
             ctx.stream.skip();
+            if ctx.standard < VHDL2008 {
+                ctx.diagnostics.push(
+                    Diagnostic::syntax_error(token, "Sensitivity list 'all' is only allowed in VHDL-2008 and later")
+                );
+            }
             ctx.stream.expect_kind(RightPar)?;
             Some(SensitivityList::All)
         },
@@ -309,12 +315,18 @@ fn parse_selected_signal_assignment(
     ctx.stream.expect_kind(With)?;
     let expression = parse_expression(ctx)?;
     ctx.stream.expect_kind(Select)?;
+    let is_matching = ctx.stream.pop_if_kind(Que).is_some();
     let target = parse_target(ctx)?;
     ctx.stream.expect_kind(LTE)?;
     // @TODO guarded
     let guarded = false;
     let delay_mechanism = parse_delay_mechanism(ctx)?;
-    let rhs = AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_waveform)?);
+    let rhs = AssignmentRightHand::Selected(parse_selection(
+        ctx,
+        is_matching,
+        expression,
+        parse_waveform,
+    )?);
     Ok(ConcurrentSignalAssignment {
         postponed,
         guarded,
@@ -563,6 +575,7 @@ fn parse_case_generate_statement(
 
     Ok(CaseGenerateStatement {
         sels: Selection {
+            is_matching: false,
             expression,
             alternatives,
         },
@@ -730,6 +743,7 @@ mod tests {
     use crate::ast::{Alternative, AssertStatement, DelayMechanism, Selection};
     use crate::syntax::design_unit::parse_architecture_body;
     use crate::syntax::test::Code;
+    use crate::VHDLStandard;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -1188,6 +1202,25 @@ end process;",
         );
     }
 
+    #[test]
+    fn test_process_all_requires_vhdl2008() {
+        let code = Code::with_standard(
+            "\
+process (all) is
+begin
+end process;",
+            VHDLStandard::VHDL1993,
+        );
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_labeled_concurrent_statement);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::syntax_error(
+                code.s1("all"),
+                "Sensitivity list 'all' is only allowed in VHDL-2008 and later"
+            )]
+        );
+    }
+
     #[test]
     fn test_concurrent_assert() {
         let code = Code::new("assert cond = true;");
@@ -1274,6 +1307,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![Alternative {
                 choices: code.s1("0|1").choices(),
@@ -1296,6 +1330,42 @@ with x(0) + 1 select
         assert_eq!(stmt.statement.span, code.token_span());
     }
 
+    #[test]
+    fn parse_matching_selected_signal_assignment() {
+        let code = Code::new(
+            "\
+with x select?
+   foo <= bar when \"1-\", baz when others;",
+        );
+
+        let selection = Selection {
+            is_matching: true,
+            expression: code.s1("x").expr(),
+            alternatives: vec![
+                Alternative {
+                    choices: code.s1("\"1-\"").choices(),
+                    item: code.s1("bar").waveform(),
+                },
+                Alternative {
+                    choices: code.s1("others").choices(),
+                    item: code.s1("baz").waveform(),
+                },
+            ],
+        };
+
+        let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
+        assert_eq!(
+            stmt.statement.item,
+            ConcurrentStatement::Assignment(ConcurrentSignalAssignment {
+                postponed: false,
+                guarded: false,
+                target: code.s1("foo").name().map_into(Target::Name),
+                delay_mechanism: None,
+                rhs: AssignmentRightHand::Selected(selection)
+            })
+        );
+    }
+
     #[test]
     fn test_component_instantiation() {
         let code = Code::new("inst: component lib.foo.bar;");
@@ -1965,6 +2035,7 @@ end generate;",
         );
         let gen = CaseGenerateStatement {
             sels: Selection {
+                is_matching: false,
                 expression: code.s1("expr(0) + 2").expr(),
                 alternatives: vec![
                     Alternative {
@@ -2014,6 +2085,7 @@ end generate gen1;",
         );
         let gen = CaseGenerateStatement {
             sels: Selection {
+                is_matching: false,
                 expression: code.s1("expr(0) + 2").expr(),
                 alternatives: vec![
                     Alternative {
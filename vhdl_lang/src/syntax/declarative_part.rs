@@ -10,6 +10,7 @@ use super::common::ParseResult;
 use super::component_declaration::parse_component_declaration;
 use super::configuration::parse_configuration_specification;
 use super::context::parse_use_clause;
+use super::design_unit::{parse_package_body, parse_package_declaration};
 use super::names::parse_selected_name;
 use super::object_declaration::{parse_file_declaration, parse_object_declaration};
 use super::subprogram::parse_subprogram;
@@ -41,6 +42,17 @@ pub fn parse_package_instantiation(
     })
 }
 
+/// A package instantiation, or a nested package declaration or body (LRM 4.7, 4.8)
+fn parse_package_declarative_item(ctx: &mut ParsingContext<'_>) -> ParseResult<Declaration> {
+    if ctx.stream.nth_kind_is(1, Body) {
+        parse_package_body(ctx).map(Declaration::PackageBody)
+    } else if ctx.stream.nth_kind_is(3, New) {
+        parse_package_instantiation(ctx).map(Declaration::Package)
+    } else {
+        parse_package_declaration(ctx).map(Declaration::PackageDeclaration)
+    }
+}
+
 pub fn is_declarative_part(ctx: &mut ParsingContext) -> ParseResult<bool> {
     Ok(matches!(
         ctx.stream.peek_expect()?.kind,
@@ -104,7 +116,7 @@ pub fn parse_declarative_part(
                     Type | Subtype => parse_type_declaration(ctx).map(Declaration::Type)?,
                     Component => parse_component_declaration(ctx).map(Declaration::Component)?,
                     Impure | Pure | Function | Procedure => parse_subprogram(ctx)?,
-                    Package => parse_package_instantiation(ctx).map(Declaration::Package)?,
+                    Package => parse_package_declarative_item(ctx)?,
                     For => {
                         parse_configuration_specification(ctx).map(Declaration::Configuration)?
                     }
@@ -253,6 +265,32 @@ package ident is new lib.foo.bar
         );
     }
 
+    #[test]
+    fn nested_package_declaration_and_body() {
+        let code = Code::new(
+            "\
+package inner is
+  constant c0 : natural;
+end package;
+package body inner is
+  constant c0 : natural := 0;
+end package body;
+package inst is new work.inner;
+",
+        );
+        let decls = code.declarative_part();
+        assert_eq!(decls.len(), 3);
+        assert!(matches!(
+            decls[0].item,
+            Declaration::PackageDeclaration(ref package) if package.decl.len() == 1
+        ));
+        assert!(matches!(
+            decls[1].item,
+            Declaration::PackageBody(ref body) if body.decl.len() == 1
+        ));
+        assert!(matches!(decls[2].item, Declaration::Package(_)));
+    }
+
     #[test]
     fn parse_declarative_part_recover() {
         let code = Code::new(
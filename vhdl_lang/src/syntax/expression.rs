@@ -12,7 +12,7 @@ use crate::ast::token_range::{WithToken, WithTokenSpan};
 use crate::ast::{Literal, *};
 use crate::data::Diagnostic;
 use crate::syntax::TokenAccess;
-use crate::{ast, TokenSpan};
+use crate::{ast, TokenSpan, VHDLStandard};
 use vhdl_lang::syntax::parser::ParsingContext;
 
 impl WithTokenSpan<Name> {
@@ -331,9 +331,79 @@ fn name_to_selected_name(name: Name) -> Option<Name> {
     }
 }
 
+/// Parse the remainder of a conditional expression within parenthesis
+/// after the initial value, i.e. `when cond else value [when cond else value]* )`
+fn parse_conditional_expression(
+    ctx: &mut ParsingContext<'_>,
+    initial_item: WithTokenSpan<Expression>,
+) -> ParseResult<Conditionals<WithTokenSpan<Expression>>> {
+    let mut conditionals = Vec::new();
+    let mut item = initial_item;
+
+    loop {
+        ctx.stream.expect_kind(When)?;
+        let condition = parse_expression(ctx)?;
+        conditionals.push(Conditional { condition, item });
+        ctx.stream.expect_kind(Else)?;
+        item = parse_expression(ctx)?;
+
+        if !ctx.stream.next_kind_is(When) {
+            break;
+        }
+    }
+
+    Ok(Conditionals {
+        conditionals,
+        else_item: Some(item),
+    })
+}
+
+/// Parse the remainder of a selected expression within parenthesis
+/// after the `with` keyword, i.e. `expression select[?] value when choices [, value when choices]* )`
+fn parse_selected_expression(
+    ctx: &mut ParsingContext<'_>,
+) -> ParseResult<Selection<WithTokenSpan<Expression>>> {
+    let expression = parse_expression(ctx)?;
+    ctx.stream.expect_kind(Select)?;
+    let is_matching = ctx.stream.pop_if_kind(Que).is_some();
+
+    let mut alternatives = Vec::with_capacity(2);
+    loop {
+        let item = parse_expression(ctx)?;
+        ctx.stream.expect_kind(When)?;
+        let choices = parse_choices(ctx)?;
+        alternatives.push(Alternative { choices, item });
+
+        if !ctx.stream.skip_if_kind(Comma) {
+            break;
+        }
+    }
+
+    Ok(Selection {
+        is_matching,
+        expression,
+        alternatives,
+    })
+}
+
 fn parse_expression_or_aggregate(
     ctx: &mut ParsingContext<'_>,
 ) -> ParseResult<WithTokenSpan<Expression>> {
+    if let Some(with_token) = ctx.stream.pop_if_kind(With) {
+        if ctx.standard < VHDLStandard::VHDL2019 {
+            ctx.diagnostics.push(Diagnostic::syntax_error(
+                ctx.stream.get_pos(with_token),
+                "Selected expressions are only allowed in VHDL-2019 and later",
+            ));
+        }
+        let selection = parse_selected_expression(ctx)?;
+        let rpar = ctx.stream.expect_kind(RightPar)?;
+        return Ok(WithTokenSpan::from(
+            Expression::Selected(Box::new(selection)),
+            rpar,
+        ));
+    }
+
     let mut choices = parse_choices(ctx)?;
 
     if choices.len() == 1
@@ -373,6 +443,25 @@ fn parse_expression_or_aggregate(
                     token_id
                 );
                 Ok(expr)
+            },
+
+            // Was conditional expression
+            When => {
+                if ctx.standard < VHDLStandard::VHDL2019 {
+                    ctx.diagnostics.push(Diagnostic::syntax_error(
+                        token,
+                        "Conditional expressions are only allowed in VHDL-2019 and later",
+                    ));
+                }
+                let conditionals = parse_conditional_expression(
+                    ctx,
+                    WithTokenSpan::new(expr, span),
+                )?;
+                let rpar = ctx.stream.expect_kind(RightPar)?;
+                Ok(WithTokenSpan::from(
+                    Expression::Conditional(Box::new(conditionals)),
+                    rpar,
+                ))
             }
         )
     } else {
@@ -419,8 +508,12 @@ fn parse_primary(ctx: &mut ParsingContext<'_>) -> ParseResult<WithTokenSpan<Expr
                 .map_into_span(|chr| Expression::Literal(Literal::Character(chr))))
         }
         StringLiteral => {
-            if ctx.stream.next_kinds_are(&[StringLiteral, LeftPar]) {
+            if ctx.stream.next_kinds_are(&[StringLiteral, LeftPar])
+                || ctx.stream.next_kinds_are(&[StringLiteral, LeftSquare])
+                || ctx.stream.next_kinds_are(&[StringLiteral, Tick])
+            {
                 // Probably a function call via operator symbol "foo"()
+                // or an attribute of an operator symbol such as "foo"'simple_name
                 parse_name(ctx).map(|name| name.map_into(|name| Expression::Name(Box::new(name))))
             } else {
                 ctx.stream.skip();
@@ -624,6 +717,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_attribute_of_operator_symbol() {
+        for attr in [
+            "\"+\"'simple_name",
+            "\"+\"[integer return integer]'path_name",
+        ] {
+            let code = Code::new(attr);
+            assert_eq!(
+                code.with_stream(parse_expression),
+                code.s1(attr)
+                    .name()
+                    .map_into(|name| Expression::Name(Box::new(name)))
+            );
+        }
+    }
+
     #[test]
     fn parses_exteral_name() {
         let code = Code::new("<< signal dut.foo : boolean >>");
@@ -1256,6 +1365,108 @@ mod tests {
         assert_eq!(code.with_stream(parse_expression), expr_add1);
     }
 
+    #[test]
+    fn parses_conditional_expression() {
+        let code = Code::with_standard(
+            "(val0 when cond0 else val1 when cond1 else val2)",
+            VHDLStandard::VHDL2019,
+        );
+        let expr = WithTokenSpan {
+            item: Expression::Conditional(Box::new(Conditionals {
+                conditionals: vec![
+                    Conditional {
+                        condition: code.s1("cond0").expr(),
+                        item: code.s1("val0").expr(),
+                    },
+                    Conditional {
+                        condition: code.s1("cond1").expr(),
+                        item: code.s1("val1").expr(),
+                    },
+                ],
+                else_item: Some(code.s1("val2").expr()),
+            })),
+            span: code.token_span(),
+        };
+
+        assert_eq!(code.with_stream(parse_expression), expr);
+    }
+
+    #[test]
+    fn conditional_expression_requires_else() {
+        let code = Code::with_standard("(val0 when cond0)", VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_partial_stream(parse_expression),
+            Err(Diagnostic::syntax_error(
+                code.s1(")").pos(),
+                "Expected 'else'"
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_selected_expression() {
+        let code = Code::with_standard(
+            "(with sel select val0 when '0' | '1', val1 when others)",
+            VHDLStandard::VHDL2019,
+        );
+        let expr = WithTokenSpan {
+            item: Expression::Selected(Box::new(Selection {
+                is_matching: false,
+                expression: code.s1("sel").expr(),
+                alternatives: vec![
+                    Alternative {
+                        choices: code.s1("'0' | '1'").choices(),
+                        item: code.s1("val0").expr(),
+                    },
+                    Alternative {
+                        choices: code.s1("others").choices(),
+                        item: code.s1("val1").expr(),
+                    },
+                ],
+            })),
+            span: code.token_span(),
+        };
+
+        assert_eq!(code.with_stream(parse_expression), expr);
+    }
+
+    #[test]
+    fn parses_qualified_conditional_expression() {
+        let code = Code::with_standard("foo'(val0 when cond0 else val1)", VHDLStandard::VHDL2019);
+        let type_mark = code.s1("foo").type_mark();
+        let expr = code.s1("(val0 when cond0 else val1)").expr();
+
+        let qexpr = WithTokenSpan {
+            item: Expression::Qualified(Box::new(QualifiedExpression { type_mark, expr })),
+            span: code.token_span(),
+        };
+
+        assert_eq!(code.with_stream(parse_expression), qexpr);
+    }
+
+    #[test]
+    fn conditional_and_selected_expressions_require_vhdl2019() {
+        let code = Code::new("(val0 when cond0 else val1)");
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_expression);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::syntax_error(
+                code.s1("when"),
+                "Conditional expressions are only allowed in VHDL-2019 and later"
+            )]
+        );
+
+        let code = Code::new("(with sel select val0 when '0', val1 when others)");
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_expression);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::syntax_error(
+                code.s1("with"),
+                "Selected expressions are only allowed in VHDL-2019 and later"
+            )]
+        );
+    }
+
     /// Format expression as a string to simplify testing of precedence.
     fn fmt(ctx: &dyn TokenAccess, expr: &WithTokenSpan<Expression>) -> String {
         match expr.item {
@@ -369,6 +369,7 @@ where
 
 pub fn parse_selection<T, F>(
     ctx: &mut ParsingContext<'_>,
+    is_matching: bool,
     expression: WithTokenSpan<Expression>,
     parse_item: F,
 ) -> ParseResult<Selection<T>>
@@ -392,6 +393,7 @@ where
     }
 
     Ok(Selection {
+        is_matching,
         expression,
         alternatives,
     })
@@ -491,12 +493,13 @@ pub fn parse_target(ctx: &mut ParsingContext<'_>) -> ParseResult<WithTokenSpan<T
 fn parse_selected_assignment(ctx: &mut ParsingContext<'_>) -> ParseResult<SequentialStatement> {
     let expression = parse_expression(ctx)?;
     ctx.stream.expect_kind(Select)?;
+    let is_matching = ctx.stream.pop_if_kind(Que).is_some();
     let target = parse_target(ctx)?;
     expect_token!(
         ctx.stream,
         token,
         ColonEq => {
-            let rhs = AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_expression)?);
+            let rhs = AssignmentRightHand::Selected(parse_selection(ctx, is_matching, expression, parse_expression)?);
             Ok(SequentialStatement::VariableAssignment(VariableAssignment {
                 target,
                 rhs,
@@ -507,13 +510,13 @@ fn parse_selected_assignment(ctx: &mut ParsingContext<'_>) -> ParseResult<Sequen
                 Ok(SequentialStatement::SignalForceAssignment(SignalForceAssignment {
                     target,
                     force_mode: parse_optional_force_mode(ctx)?,
-                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_expression)?)
+                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, is_matching, expression, parse_expression)?)
                 }))
             } else {
                 Ok(SequentialStatement::SignalAssignment(SignalAssignment {
                     target,
                     delay_mechanism: parse_delay_mechanism(ctx)?,
-                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_waveform)?)
+                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, is_matching, expression, parse_waveform)?)
             }))
         }
         }
@@ -1063,6 +1066,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![
                 Alternative {
@@ -1210,6 +1214,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![
                 Alternative {
@@ -1239,6 +1244,44 @@ with x(0) + 1 select
         );
     }
 
+    #[test]
+    fn parse_matching_selected_variable_assignment() {
+        let (code, statement) = parse(
+            "\
+with x select?
+   foo := bar when \"1-\", baz when others;",
+        );
+
+        let selection = Selection {
+            is_matching: true,
+            expression: code.s1("x").expr(),
+            alternatives: vec![
+                Alternative {
+                    choices: code.s1("\"1-\"").choices(),
+                    item: code.s1("bar").expr(),
+                },
+                Alternative {
+                    choices: code.s1("others").choices(),
+                    item: code.s1("baz").expr(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            statement,
+            with_label(
+                None,
+                WithTokenSpan::new(
+                    SequentialStatement::VariableAssignment(VariableAssignment {
+                        target: code.s1("foo").name().map_into(Target::Name),
+                        rhs: AssignmentRightHand::Selected(selection),
+                    }),
+                    code.token_span()
+                )
+            )
+        );
+    }
+
     #[test]
     fn parse_selected_signal_force_assignment() {
         let (code, statement) = parse(
@@ -1249,6 +1292,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![
                 Alternative {
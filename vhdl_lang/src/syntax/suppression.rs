@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::tokens::Comment;
+use crate::ast::DesignFile;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Diagnostic;
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// Suppresses diagnostics on the line of the comment,
+/// or on the next line when the comment is on a line of its own
+const DISABLE: &str = "vhdl_lang: disable=";
+/// Suppresses diagnostics in the whole file when placed at the top of the file
+const DISABLE_FILE: &str = "vhdl_lang: disable-file=";
+
+/// Error codes that are disabled using comments within a single file, e.g.
+/// ```vhdl
+/// -- vhdl_lang: disable-file=unnecessary_work_library
+///
+/// signal foo : bit; -- vhdl_lang: disable=unused
+/// -- vhdl_lang: disable=unused,duplicate
+/// signal bar : bit;
+/// ```
+/// Error codes can be written like in the `[lint]` section of the configuration
+/// or in the same case as the `ErrorCode` variant, e.g. `Unused`.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub(crate) struct Suppressions {
+    file: FnvHashSet<ErrorCode>,
+    // zero-based line number to the codes disabled on that line
+    lines: FnvHashMap<u32, FnvHashSet<ErrorCode>>,
+}
+
+impl Suppressions {
+    pub fn from_design_file(design_file: &DesignFile) -> Suppressions {
+        let mut suppressions = Suppressions::default();
+
+        let tokens = design_file
+            .design_units
+            .iter()
+            .flat_map(|(tokens, _)| tokens.iter());
+
+        for (idx, token) in tokens.enumerate() {
+            let Some(ref comments) = token.comments else {
+                continue;
+            };
+
+            for comment in comments.leading.iter() {
+                // Only comments before the first token are at the top of the file
+                if idx == 0 {
+                    suppressions.file.extend(codes_after(comment, DISABLE_FILE));
+                }
+                suppressions.disable(token.pos.start().line, codes_after(comment, DISABLE));
+            }
+
+            if let Some(ref comment) = comments.trailing {
+                suppressions.disable(comment.range.start.line, codes_after(comment, DISABLE));
+            }
+        }
+
+        suppressions
+    }
+
+    fn disable(&mut self, line: u32, codes: impl Iterator<Item = ErrorCode>) {
+        let mut codes = codes.peekable();
+        if codes.peek().is_some() {
+            self.lines.entry(line).or_default().extend(codes);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.file.is_empty() && self.lines.is_empty()
+    }
+
+    pub fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+        self.file.contains(&diagnostic.code)
+            || self
+                .lines
+                .get(&diagnostic.pos.start().line)
+                .is_some_and(|codes| codes.contains(&diagnostic.code))
+    }
+}
+
+/// The comma separated error codes following the directive.
+/// Unknown error codes are ignored.
+fn codes_after<'a>(comment: &'a Comment, directive: &str) -> impl Iterator<Item = ErrorCode> + 'a {
+    let codes = comment
+        .value
+        .find(directive)
+        .and_then(|idx| {
+            comment.value[idx + directive.len()..]
+                .split_whitespace()
+                .next()
+        })
+        .unwrap_or_default();
+
+    codes.split(',').filter_map(parse_error_code)
+}
+
+fn parse_error_code(name: &str) -> Option<ErrorCode> {
+    if let Ok(code) = ErrorCode::try_from(name) {
+        return Some(code);
+    }
+
+    // Convert from the case of the enum variant, e.g. OutOfRange -> out_of_range
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    for (idx, chr) in name.chars().enumerate() {
+        if chr.is_ascii_uppercase() && idx > 0 {
+            snake_case.push('_');
+        }
+        snake_case.push(chr.to_ascii_lowercase());
+    }
+    ErrorCode::try_from(snake_case.as_str()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    fn suppressions(code: &Code) -> Suppressions {
+        Suppressions::from_design_file(&code.design_file())
+    }
+
+    #[test]
+    fn parses_error_codes() {
+        assert_eq!(parse_error_code("unused"), Some(ErrorCode::Unused));
+        assert_eq!(parse_error_code("Unused"), Some(ErrorCode::Unused));
+        assert_eq!(parse_error_code("OutOfRange"), Some(ErrorCode::OutOfRange));
+        assert_eq!(parse_error_code("not_a_code"), None);
+    }
+
+    #[test]
+    fn suppresses_on_same_and_next_line() {
+        let code = Code::new(
+            "
+package pkg is
+  constant c0 : natural := 0; -- vhdl_lang: disable=unused
+  -- vhdl_lang: disable=Unused,duplicate because of reasons
+  constant c1 : natural := 0;
+  constant c2 : natural := 0;
+end package;
+",
+        );
+        let suppressions = suppressions(&code);
+
+        for (name, error_code, suppressed) in [
+            ("c0", ErrorCode::Unused, true),
+            ("c0", ErrorCode::Duplicate, false),
+            ("c1", ErrorCode::Unused, true),
+            ("c1", ErrorCode::Duplicate, true),
+            ("c2", ErrorCode::Unused, false),
+        ] {
+            assert_eq!(
+                suppressions.is_suppressed(&Diagnostic::new(code.s1(name), "", error_code)),
+                suppressed,
+                "{name} {error_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn suppresses_within_file() {
+        let code = Code::new(
+            "\
+-- vhdl_lang: disable-file=unused
+library ieee;
+
+package pkg is
+  -- vhdl_lang: disable-file=duplicate
+  constant c0 : natural := 0;
+end package;
+",
+        );
+        let suppressions = suppressions(&code);
+
+        assert!(suppressions.is_suppressed(&Diagnostic::new(code.s1("c0"), "", ErrorCode::Unused)));
+        assert!(!suppressions.is_suppressed(&Diagnostic::new(
+            code.s1("c0"),
+            "",
+            ErrorCode::Duplicate
+        )));
+    }
+}
@@ -432,7 +432,7 @@ pub struct Token {
 /// A TokenId represents a unique value that is used to access a token.
 /// A token ID cannot be created directly by the user. Instead, the value must be taken
 /// from the AST.
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Ord, PartialOrd)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Ord, PartialOrd, Hash)]
 pub struct TokenId(usize);
 
 /// The TokenId represents an index into an array of tokens.
@@ -444,6 +444,11 @@ impl TokenId {
         TokenId(idx)
     }
 
+    /// The index of the token within the tokens of its design unit
+    pub(crate) fn idx(&self) -> usize {
+        self.0
+    }
+
     pub fn pos<'a>(&'a self, ctx: &'a dyn TokenAccess) -> &SrcPos {
         ctx.get_pos(*self)
     }
@@ -523,7 +528,7 @@ pub trait HasTokenSpan {
 /// Holds token information about an AST element.
 /// Since the different pieces may be gathered in different locations,
 /// the fields are gated behind accessor functions which also check some invariants every time they are called.
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
 pub struct TokenSpan {
     pub start_token: TokenId,
     pub end_token: TokenId,
@@ -4,7 +4,10 @@ use predicates::prelude::*;
 use std::error::Error;
 use std::path::PathBuf;
 use std::process::Command;
-use vhdl_lang::{Config, MessagePrinter, Project, Severity};
+use vhdl_lang::{
+    AnyEntKind, Config, Design, LanguageServerFacade, MessagePrinter, Position, Project, Severity,
+    Source,
+};
 
 #[test]
 pub fn parses_example_project_without_errors() {
@@ -58,3 +61,240 @@ fn unused_function_gets_detected() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn diagnostics_can_be_printed_as_sarif() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("vhdl_lang")?;
+
+    cmd.arg("--config")
+        .arg("tests/unused_declarations/vhdl_ls.toml")
+        .arg("--libraries")
+        .arg("../vhdl_libraries/vhdl_ls.toml")
+        .arg("--format")
+        .arg("sarif");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::starts_with("{\"version\":\"2.1.0\""))
+        .stdout(predicate::str::contains("\"ruleId\":\"unused\""));
+
+    Ok(())
+}
+
+fn facade_with_standard_libraries() -> LanguageServerFacade {
+    let mut config = Config::default();
+    let mut msg_printer = MessagePrinter::default();
+
+    let mut vhdl_libraries_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    vhdl_libraries_path.push("../vhdl_libraries/vhdl_ls.toml");
+    config.append(
+        &Config::read_file_path(&vhdl_libraries_path).expect("Failed to read config file"),
+        &mut msg_printer,
+    );
+    LanguageServerFacade::new(Project::from_config(config, &mut msg_printer))
+}
+
+/// Position of the n:th occurrence of substr within the contents
+fn position_of(contents: &str, substr: &str, occurrence: usize) -> Position {
+    let (offset, _) = contents.match_indices(substr).nth(occurrence - 1).unwrap();
+    let line = contents[..offset].matches('\n').count();
+    let character = offset - contents[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+    Position::new(line as u32, character as u32)
+}
+
+#[test]
+fn language_server_facade_end_to_end() {
+    let mut facade = facade_with_standard_libraries();
+
+    let pkg_contents = "\
+package pkg is
+  constant width : natural := 8;
+  function double(arg : natural) return natural;
+end package;
+
+package body pkg is
+  function double(arg : natural) return natural is
+  begin
+    return 2 * arg;
+  end function;
+end package body;
+";
+    let ent_contents = "\
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : natural := double(width);
+begin
+  sig <= width + undefined;
+end architecture;
+";
+    let pkg = Source::inline(&PathBuf::from("pkg.vhd"), pkg_contents);
+    let ent = Source::inline(&PathBuf::from("ent.vhd"), ent_contents);
+    facade.update_source(&pkg);
+    facade.update_source(&ent);
+    facade.analyse();
+
+    assert!(facade.diagnostics(&pkg).is_empty());
+    let diagnostics = facade.diagnostics(&ent);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "No declaration of 'undefined'");
+
+    let width_decl = position_of(pkg_contents, "width", 1);
+    let width_use = position_of(ent_contents, "width", 2);
+    let decl_pos = facade.definition_at(&ent, width_use).unwrap();
+    assert_eq!(decl_pos.source, pkg);
+    assert_eq!(decl_pos.start(), width_decl);
+
+    // The definition of a subprogram is its body
+    let double_use = position_of(ent_contents, "double", 1);
+    let double_def = facade.definition_at(&ent, double_use).unwrap();
+    assert_eq!(double_def.start(), position_of(pkg_contents, "double", 2));
+    assert_eq!(
+        facade.declaration_at(&ent, double_use).unwrap().start(),
+        position_of(pkg_contents, "double", 1)
+    );
+
+    let references = facade.references(&ent, width_use);
+    assert_eq!(references.len(), 3);
+    assert!(references.contains(&decl_pos));
+
+    assert_eq!(
+        facade.hover(&ent, width_use).as_deref(),
+        Some("constant width : natural := 8;")
+    );
+
+    let edits = facade.rename(&pkg, width_decl, "bits").unwrap();
+    assert_eq!(edits.len(), 3);
+    assert!(edits.iter().all(|edit| edit.new_text == "bits"));
+
+    let completions = facade.completions(&ent, position_of(ent_contents, "sig <=", 1));
+    assert!(!completions.is_empty());
+
+    let symbols = facade.document_symbols(&ent);
+    let names = symbols
+        .iter()
+        .map(|symbol| symbol.ent.designator().to_string())
+        .collect_vec();
+    assert_eq!(names, vec!["ent", "a"]);
+    let architecture = &symbols[1];
+    assert_eq!(
+        architecture.pos.start(),
+        position_of(ent_contents, "a of", 1)
+    );
+    assert_eq!(architecture.children.len(), 1);
+    assert_eq!(architecture.children[0].ent.designator().to_string(), "sig");
+}
+
+#[test]
+fn document_symbols_are_nested_by_declarative_region() {
+    let mut facade = facade_with_standard_libraries();
+
+    let contents = "\
+package pkg is
+  type state_t is (idle, busy);
+  procedure proc(arg : natural);
+end package;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal state : work.pkg.state_t;
+begin
+  main: process
+    variable count : natural;
+  begin
+    wait;
+  end process;
+end architecture;
+";
+    let source = Source::inline(&PathBuf::from("nested.vhd"), contents);
+    facade.update_source(&source);
+    facade.analyse();
+
+    fn names(symbols: &[vhdl_lang::DocumentSymbol]) -> Vec<String> {
+        symbols.iter().map(|symbol| symbol.name()).collect()
+    }
+
+    let symbols = facade.document_symbols(&source);
+    assert_eq!(names(&symbols), vec!["pkg", "ent", "a"]);
+
+    let pkg = &symbols[0];
+    assert!(matches!(
+        pkg.kind(),
+        AnyEntKind::Design(Design::Package(..))
+    ));
+    assert_eq!(names(&pkg.children), vec!["state_t", "proc"]);
+    assert_eq!(names(&pkg.children[0].children), vec!["idle", "busy"]);
+
+    let architecture = &symbols[2];
+    assert_eq!(names(&architecture.children), vec!["state", "main"]);
+    let process = &architecture.children[1];
+    assert_eq!(names(&process.children), vec!["count"]);
+    assert_eq!(process.pos.start(), position_of(contents, "main", 1));
+    assert_eq!(process.span.start(), position_of(contents, "main", 1));
+    assert_eq!(
+        process.span.end().line,
+        position_of(contents, "end process;", 1).line
+    );
+}
+
+#[test]
+fn workspace_symbols_match_substrings_ignoring_case() {
+    let mut facade = facade_with_standard_libraries();
+
+    let contents = "\
+package fifo_pkg is
+  constant FIFO_DEPTH : natural := 16;
+  type fifo_state_t is (empty, full);
+end package;
+
+entity my_fifo is
+  port (fifo_clk : in bit);
+end entity;
+";
+    let source = Source::inline(&PathBuf::from("fifo.vhd"), contents);
+    facade.update_source(&source);
+    facade.analyse();
+
+    fn names(symbols: &[vhdl_lang::WorkspaceSymbol]) -> Vec<String> {
+        symbols.iter().map(|symbol| symbol.name()).collect()
+    }
+
+    let symbols = facade.workspace_symbols("Fifo");
+    assert_eq!(
+        names(&symbols),
+        vec![
+            "fifo_clk",
+            "FIFO_DEPTH",
+            "fifo_pkg",
+            "fifo_state_t",
+            "my_fifo"
+        ]
+    );
+    assert_eq!(symbols[1].container().as_deref(), Some("work.fifo_pkg"));
+    assert_eq!(
+        symbols[1].pos.start(),
+        position_of(contents, "FIFO_DEPTH", 1)
+    );
+    assert!(matches!(
+        symbols[4].kind(),
+        AnyEntKind::Design(Design::Entity(..))
+    ));
+
+    // The symbols follow changes of the sources
+    let contents = contents.replace("FIFO_DEPTH", "FIFO_SIZE");
+    let source = Source::inline(&PathBuf::from("fifo.vhd"), &contents);
+    facade.update_source(&source);
+    facade.analyse();
+    assert_eq!(
+        names(&facade.workspace_symbols("depth")),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        names(&facade.workspace_symbols("fifo_s")),
+        vec!["FIFO_SIZE", "fifo_state_t"]
+    );
+}
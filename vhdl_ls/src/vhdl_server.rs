@@ -17,9 +17,9 @@ use std::io;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use vhdl_lang::{
-    kind_str, AnyEntKind, Concurrent, Config, Design, Diagnostic, EntHierarchy, EntRef,
-    InterfaceEnt, Message, MessageHandler, Object, Overloaded, Project, Severity, SeverityMap,
-    Source, SrcPos, Token, Type, VHDLStandard,
+    kind_str, AnyEntKind, Concurrent, Config, Design, Diagnostic, EntRef, InterfaceEnt, Message,
+    MessageHandler, Object, Overloaded, Project, Severity, SeverityMap, Source, SrcPos, Token,
+    Type, VHDLStandard,
 };
 
 /// Defines how the language server handles files
@@ -181,6 +181,7 @@ impl VHDLServer {
                 work_done_progress_options: Default::default(),
             })),
             workspace_symbol_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             document_symbol_provider: Some(OneOf::Left(true)),
             completion_provider: Some(CompletionOptions {
                 resolve_provider: Some(true),
@@ -554,9 +555,10 @@ impl VHDLServer {
 
         let mut files_with_notifications = std::mem::take(&mut self.files_with_notifications);
         for (file_uri, diagnostics) in diagnostics_by_uri(diagnostics).into_iter() {
-            let lsp_diagnostics = diagnostics
-                .into_iter()
-                .filter_map(|diag| to_lsp_diagnostic(diag, &self.severity_map))
+            let lsp_diagnostics = self
+                .severity_map
+                .apply(diagnostics)
+                .map(|(diag, severity)| to_lsp_diagnostic(diag, severity))
                 .collect();
 
             let publish_diagnostics = PublishDiagnosticsParams {
@@ -681,43 +683,87 @@ impl VHDLServer {
         })
     }
 
+    /// Quick fixes for the diagnostics that carry suggested replacements and the completion
+    /// of an instantiation statement at the cursor with all generics and ports
+    pub fn text_document_code_action(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let mut actions = Vec::new();
+        for diagnostic in params.context.diagnostics.iter() {
+            let Some(edits) = diagnostic
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<Vec<TextEdit>>(data).ok())
+            else {
+                continue;
+            };
+            for (i, edit) in edits.into_iter().enumerate() {
+                let title = if edit.range.start == edit.range.end {
+                    format!("Insert '{}'", edit.new_text.trim())
+                } else {
+                    format!("Replace with '{}'", edit.new_text)
+                };
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(
+                            params.text_document.uri.clone(),
+                            vec![edit],
+                        )])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(i == 0),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some((pos, text)) = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))
+            .and_then(|source| {
+                self.project
+                    .instantiation_template_at(&source, from_lsp_pos(params.range.start))
+            })
+        {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Associate all generics and ports".to_owned(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        params.text_document.uri.clone(),
+                        vec![TextEdit {
+                            range: to_lsp_range(pos.range()),
+                            new_text: text,
+                        }],
+                    )])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+        }
+        actions
+    }
+
     pub fn workspace_symbol(
         &self,
         params: &WorkspaceSymbolParams,
     ) -> Option<WorkspaceSymbolResponse> {
         let trunc_limit = 200;
-        let query = params.query.to_ascii_lowercase();
-        let mut symbols: Vec<_> = self
-            .project
-            .public_symbols()
-            .filter_map(|ent| match ent.designator() {
-                Designator::Identifier(_) | Designator::Character(_) => {
-                    Some((ent, ent.designator().to_string().to_ascii_lowercase()))
-                }
-                Designator::OperatorSymbol(op) => Some((ent, op.to_string().to_ascii_lowercase())),
-                Designator::Anonymous(_) => None,
-            })
-            .collect();
-        symbols.sort_by(|(_, n1), (_, n2)| n1.cmp(n2));
         Some(WorkspaceSymbolResponse::Nested(
-            symbols
+            self.project
+                .workspace_symbols(&params.query)
                 .into_iter()
-                .filter_map(|(ent, name)| {
-                    let decl_pos = ent.decl_pos()?;
-                    if name.starts_with(&query) {
-                        Some(WorkspaceSymbol {
-                            name: ent.describe(),
-                            kind: to_symbol_kind(ent.kind()),
-                            tags: None,
-                            container_name: ent.parent.map(|ent| ent.path_name()),
-                            location: OneOf::Left(srcpos_to_location(decl_pos)),
-                            data: None,
-                        })
-                    } else {
-                        None
-                    }
-                })
+                .filter_map(vhdl_lang::WorkspaceSymbol::new)
                 .take(trunc_limit)
+                .map(|symbol| WorkspaceSymbol {
+                    name: symbol.ent.describe(),
+                    kind: to_symbol_kind(symbol.kind()),
+                    tags: None,
+                    container_name: symbol.container(),
+                    location: OneOf::Left(srcpos_to_location(&symbol.pos)),
+                    data: None,
+                })
                 .collect(),
         ))
     }
@@ -735,28 +781,21 @@ impl VHDLServer {
             .next()?;
 
         if self.client_has_hierarchical_document_symbol_support() {
-            fn to_document_symbol(
-                EntHierarchy { ent, children }: EntHierarchy,
-                ctx: &Vec<Token>,
-            ) -> DocumentSymbol {
-                // Use the declaration position, if it exists,
-                // else the position of the first source range token.
-                // The latter is applicable for unnamed elements, e.g., processes or loops.
-                let selection_pos = ent.decl_pos().unwrap_or(ent.src_span.start_token.pos(ctx));
-                let src_range = ent.src_span.pos(ctx).range();
+            fn to_document_symbol(symbol: vhdl_lang::DocumentSymbol) -> DocumentSymbol {
                 #[allow(deprecated)]
                 DocumentSymbol {
-                    name: ent.describe(),
-                    kind: to_symbol_kind(ent.kind()),
+                    name: symbol.ent.describe(),
+                    kind: to_symbol_kind(symbol.kind()),
                     tags: None,
                     detail: None,
-                    selection_range: to_lsp_range(selection_pos.range),
-                    range: to_lsp_range(src_range),
-                    children: if !children.is_empty() {
+                    selection_range: to_lsp_range(symbol.pos.range),
+                    range: to_lsp_range(symbol.span.range),
+                    children: if !symbol.children.is_empty() {
                         Some(
-                            children
+                            symbol
+                                .children
                                 .into_iter()
-                                .map(|hierarchy| to_document_symbol(hierarchy, ctx))
+                                .map(to_document_symbol)
                                 .collect(),
                         )
                     } else {
@@ -770,7 +809,9 @@ impl VHDLServer {
                 self.project
                     .document_symbols(&library_name, &source)
                     .into_iter()
-                    .map(|(hierarchy, tokens)| to_document_symbol(hierarchy, tokens))
+                    .map(|(hierarchy, tokens)| {
+                        to_document_symbol(vhdl_lang::DocumentSymbol::new(hierarchy, tokens))
+                    })
                     .collect(),
             ))
         } else {
@@ -1016,11 +1057,8 @@ fn uri_to_file_name(uri: &Url) -> PathBuf {
     uri.to_file_path().unwrap()
 }
 
-fn to_lsp_diagnostic(
-    diagnostic: Diagnostic,
-    severity_map: &SeverityMap,
-) -> Option<lsp_types::Diagnostic> {
-    let severity = match severity_map[diagnostic.code]? {
+fn to_lsp_diagnostic(diagnostic: Diagnostic, severity: Severity) -> lsp_types::Diagnostic {
+    let severity = match severity {
         Severity::Error => DiagnosticSeverity::ERROR,
         Severity::Warning => DiagnosticSeverity::WARNING,
         Severity::Info => DiagnosticSeverity::INFORMATION,
@@ -1044,15 +1082,31 @@ fn to_lsp_diagnostic(
         None
     };
 
-    Some(lsp_types::Diagnostic {
+    // The suggested fixes are returned by the client when requesting code actions
+    let data = if diagnostic.fixes.is_empty() {
+        None
+    } else {
+        let edits: Vec<_> = diagnostic
+            .fixes
+            .iter()
+            .map(|fix| TextEdit {
+                range: to_lsp_range(fix.pos.range()),
+                new_text: fix.replacement.clone(),
+            })
+            .collect();
+        serde_json::to_value(edits).ok()
+    };
+
+    lsp_types::Diagnostic {
         range: to_lsp_range(diagnostic.pos.range()),
         severity: Some(severity),
         code: Some(NumberOrString::String(format!("{}", diagnostic.code))),
         source: Some("vhdl ls".to_owned()),
         message: diagnostic.message,
         related_information,
+        data,
         ..Default::default()
-    })
+    }
 }
 
 fn overloaded_kind(overloaded: &Overloaded) -> SymbolKind {
@@ -1095,7 +1149,7 @@ fn type_kind(t: &Type) -> SymbolKind {
         vhdl_lang::Type::Physical => SymbolKind::NUMBER,
         vhdl_lang::Type::Access(_) => SymbolKind::ENUM,
         vhdl_lang::Type::Record(_) => SymbolKind::STRUCT,
-        vhdl_lang::Type::Incomplete => SymbolKind::NULL,
+        vhdl_lang::Type::Incomplete(_) => SymbolKind::NULL,
         vhdl_lang::Type::Subtype(t) => type_kind(t.type_mark().kind()),
         vhdl_lang::Type::Protected(_, _) => SymbolKind::CLASS,
         vhdl_lang::Type::File => SymbolKind::FILE,
@@ -1135,6 +1189,7 @@ fn to_symbol_kind(kind: &AnyEntKind) -> SymbolKind {
             vhdl_lang::Design::PackageInstance(_) => SymbolKind::PACKAGE,
             vhdl_lang::Design::InterfacePackageInstance(_) => SymbolKind::PACKAGE,
             vhdl_lang::Design::Context(_) => SymbolKind::NAMESPACE,
+            vhdl_lang::Design::Alias(design) => to_symbol_kind(design.0.kind()),
         },
     }
 }
@@ -1318,6 +1373,250 @@ end entity ent;
         server.text_document_did_change_notification(&did_change);
     }
 
+    #[test]
+    fn code_action_applies_suggested_fix() {
+        let (_mock, server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_url = root_uri.join("pkg.vhd").unwrap();
+
+        let source = Source::inline(
+            &uri_to_file_name(&file_url),
+            "constant c : natural := countr;",
+        );
+        let pos = SrcPos::new(
+            source,
+            vhdl_lang::Range::new(
+                vhdl_lang::Position::new(0, 24),
+                vhdl_lang::Position::new(0, 30),
+            ),
+        );
+        let diagnostic = to_lsp_diagnostic(
+            Diagnostic::syntax_error(&pos, "No declaration of 'countr'").fix(&pos, "counter"),
+            Severity::Error,
+        );
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: file_url.clone(),
+            },
+            range: diagnostic.range,
+            context: CodeActionContext {
+                diagnostics: vec![diagnostic.clone()],
+                only: None,
+                trigger_kind: None,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        assert_eq!(
+            server.text_document_code_action(&params),
+            vec![CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Replace with 'counter'".to_owned(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        file_url,
+                        vec![TextEdit {
+                            range: diagnostic.range,
+                            new_text: "counter".to_owned(),
+                        }],
+                    )])),
+                    ..Default::default()
+                }),
+                is_preferred: Some(true),
+                ..Default::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn code_action_associates_generics_and_ports_of_instance() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let code = "\
+entity fifo is
+  generic (width : natural := 8);
+  port (clk : in bit := '0');
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  u_fifo: entity work.fifo;
+end architecture;
+
+architecture rtl of fifo is
+begin
+  assert width > 0 or clk = '1';
+end architecture;
+"
+        .to_owned();
+        let file_url = write_file(&root_uri, "top.vhd", &code);
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let range = Range {
+            start: lsp_types::Position {
+                line: 10,
+                character: "  u_fifo: entity work.f".len() as u32,
+            },
+            end: lsp_types::Position {
+                line: 10,
+                character: "  u_fifo: entity work.f".len() as u32,
+            },
+        };
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: file_url.clone(),
+            },
+            range,
+            context: CodeActionContext::default(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        assert_eq!(
+            server.text_document_code_action(&params),
+            vec![CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Associate all generics and ports".to_owned(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        file_url,
+                        vec![TextEdit {
+                            range: Range {
+                                start: lsp_types::Position {
+                                    line: 10,
+                                    character: 2,
+                                },
+                                end: lsp_types::Position {
+                                    line: 10,
+                                    character: "  u_fifo: entity work.fifo;".len() as u32,
+                                },
+                            },
+                            new_text: "\
+u_fifo: entity work.fifo
+    generic map (
+      width => width
+    )
+    port map (
+      clk => clk
+    );"
+                            .to_owned(),
+                        }],
+                    )])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn code_action_associates_generics_and_ports_using_configured_formatting() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let code = "\
+entity fifo is
+  generic (width : natural := 8);
+  port (clk : in bit := '0');
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  u_fifo: entity work.fifo;
+end architecture;
+
+architecture rtl of fifo is
+begin
+  assert width > 0 or clk = '1';
+end architecture;
+"
+        .to_owned();
+        let file_url = write_file(&root_uri, "top.vhd", &code);
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+
+[format]
+indent_width = 4
+keyword_case = 'upper'
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let cursor = lsp_types::Position {
+            line: 10,
+            character: "  u_fifo: entity work.f".len() as u32,
+        };
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: file_url.clone(),
+            },
+            range: Range {
+                start: cursor,
+                end: cursor,
+            },
+            context: CodeActionContext::default(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let actions = server.text_document_code_action(&params);
+        let [CodeActionOrCommand::CodeAction(action)] = actions.as_slice() else {
+            panic!("Expected a single code action, got {actions:?}");
+        };
+        let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&file_url];
+        assert_eq!(
+            edits[0].new_text,
+            "\
+u_fifo: ENTITY work.fifo
+      GENERIC MAP (
+          width => width
+      )
+      PORT MAP (
+          clk => clk
+      );"
+        );
+    }
+
     fn write_file(root_uri: &Url, file_name: impl AsRef<str>, contents: impl AsRef<str>) -> Url {
         let path = root_uri.to_file_path().unwrap().join(file_name.as_ref());
         std::fs::write(&path, contents.as_ref()).unwrap();